@@ -0,0 +1,180 @@
+# Deutsche Meldungen von git-pr.
+#
+# Fehlt hier eine Meldung, wird die englische verwendet (locales/en/git-pr.ftl).
+
+## Fehler
+
+error-artifacts = ❌ Fehler bei den Artefakten:
+error-assign-next = ❌ Fehler beim Zuweisen eines Reviewers:
+error-audit = ❌ Fehler beim Lesen des Audit-Logs:
+error-autopilot = ❌ Autopilot-Fehler:
+error-bisect = ❌ Fehler beim Bisect des PR:
+error-bots = ❌ Fehler beim Auflisten der Bot-PRs:
+error-checks = ❌ Fehler beim Anzeigen der Checks:
+error-close = ❌ PR konnte nicht geschlossen werden:
+error-comment = ❌ Fehler beim Kommentieren des PR:
+error-comments = ❌ Fehler beim Anzeigen der Kommentare:
+error-config-set = ❌ Einstellung konnte nicht gespeichert werden:
+error-coverage = ❌ Fehler beim Auswerten der Testabdeckung:
+error-create = ❌ Fehler beim Erstellen des PR:
+error-describe = ❌ Fehler beim Beschreiben des PR:
+error-detached-head = ❌ Keine PR-Nummer angegeben, und HEAD steht auf keinem Branch.
+error-diff = ❌ Diff konnte nicht angezeigt werden:
+error-diff-stats = ❌ Fehler beim Zusammenfassen der Änderungen:
+error-draft = ❌ Fehler beim Umwandeln in einen Entwurf:
+error-files = ❌ Fehler beim Auflisten der Dateien:
+error-freeze = ❌ Fehler beim Merge-Freeze:
+error-lint = ❌ Fehler beim Prüfen des PR:
+error-lint-failed = ❌ Prüfung fehlgeschlagen:
+error-list = ❌ Fehler beim Auflisten der PRs:
+error-logs = ❌ Fehler beim Anzeigen der Logs:
+error-man = ❌ Man-Pages konnten nicht erzeugt werden:
+error-mark-viewed = ❌ Fehler beim Markieren der Dateien:
+error-merge = ❌ Fehler beim Mergen des PR:
+error-move = ❌ Fehler beim Verschieben des PR:
+error-no-branch-pr = ❌ Keine PR-Nummer angegeben, und für den Branch { $branch } gibt es keinen offenen PR.
+error-pending = ❌ Fehler beim Bearbeiten des ausstehenden Reviews:
+error-provider = ❌ Fehler des Anbieters:
+error-queue = ❌ Fehler beim Auflisten der Review-Warteschlange:
+error-ready = ❌ Fehler beim Freigeben zum Review:
+error-remote-url = ❌ URL des Remotes { $remote } konnte nicht ermittelt werden.
+error-review-commit = ❌ Fehler beim Review des Commits:
+error-rereview = ❌ Fehler beim erneuten Anfordern des Reviews:
+error-run = ❌ Fehler beim Ausführen des Befehls auf dem PR:
+error-resolve-branch-pr = ❌ PR des aktuellen Branches konnte nicht ermittelt werden:
+error-save-draft = ❌ Fehler beim Speichern des Review-Entwurfs:
+error-scope = ❌ Fehler beim Review nach Bereichen:
+error-search = ❌ Fehler beim Durchsuchen der PRs:
+error-selftest = ❌ Selbsttest konnte nicht gestartet werden:
+error-serve = ❌ Der JSON-RPC-Server wurde beendet:
+error-show-details = ❌ Fehler beim Anzeigen der PR-Details:
+error-submit-review = ❌ Fehler beim Absenden des Reviews:
+error-suggest-reviewer = ❌ Fehler beim Vorschlagen eines Reviewers:
+error-sync-fork = ❌ Fehler beim Synchronisieren des Forks:
+error-triage = ❌ Fehler bei der Triage:
+error-unknown-command = ❌ Unbekannter Befehl '{ $command }'. Siehe `git pr help`.
+error-unresolved = ❌ Fehler beim Auflisten der Review-Threads:
+error-verify-review = ❌ Fehler beim Prüfen des Reviews:
+error-version = ❌ Version konnte nicht ausgegeben werden:
+error-welcome = ❌ Fehler beim Begrüßen des Beitragenden:
+
+## Hilfe
+
+help-git-pr = Ein Git-Plugin für Pull Requests
+help-arg-timestamps = Zeitangaben: relative (3d), absolute (Ortszeit) oder iso (RFC 3339)
+help-arg-quiet = Nur die wesentlichen Werte ausgeben (z. B. die URL des neuen PR), ohne Dekoration
+help-arg-timing = Nach dem Befehl Anzahl und Dauer der API-Aufrufe sowie die langsamsten Endpunkte auf stderr ausgeben
+help-arg-max-requests = Höchstens N API-Anfragen senden; danach das Bisherige ausgeben und mit Fehler beenden
+help-arg-progress = Fortschritt langer Vorgänge melden: text (Standard) oder json (NDJSON-Ereignisse auf stderr)
+help-arg-no-hyperlinks = PR-Nummern, SHAs, Dateien und Checks in Tabellen nicht verlinken
+help-arg-plain = Ausgabe für Screenreader: beschriftete Zeilen statt Tabellen, keine Emojis oder Farben
+
+help-pull = Den Branch eines PR lokal holen und auschecken
+help-show-details = Details eines PR anzeigen (standardmäßig der PR des aktuellen Branches)
+help-show-diff = Den Diff eines PR anzeigen (standardmäßig der PR des aktuellen Branches)
+help-submit-review = Ein Review für einen PR absenden
+help-approve = Einen PR genehmigen (Kurzform für `submit-review --approve`)
+help-request-changes = Änderungen an einem PR anfordern (ohne ihn zu schließen)
+help-review-commit = Einen Commit eines PR anzeigen oder mit Kommentaren zu genau diesem Commit reviewen
+help-verify-review = Die GPG-signierte Quittung eines mit `--sign` abgesendeten Reviews prüfen
+help-scope = Urteile pro Verzeichnis zu einem PR festhalten und als ein strukturiertes Review absenden
+help-list = Die offenen Pull Requests des Repositorys auflisten
+help-checks = CI-Checks und Commit-Status eines PR anzeigen (standardmäßig der PR des aktuellen Branches)
+help-lint = Richtlinien für einen PR prüfen und bei Verstößen mit Fehler beenden
+help-logs = Job-Logs von GitHub Actions für einen PR anzeigen
+help-artifacts = Artefakte der Workflow-Läufe eines PR auflisten (und herunterladen)
+help-search = PRs des Repositorys nach Titel und Beschreibung durchsuchen
+help-draft = Einen PR wieder in einen Entwurf umwandeln
+help-ready = Einen Entwurf zum Review freigeben
+help-merge = Einen PR mergen, nachdem er gegen den Branch-Schutz geprüft wurde
+help-unresolved = Die ungelösten Review-Threads eines PR auflisten
+help-create = Einen PR für den aktuellen Branch öffnen, vorausgefüllt aus Commits und PR-Vorlage
+help-files = Die geänderten Dateien eines PR mit ihrem „Viewed“-Status auflisten
+help-diff-stats = Zeigen, wo die Änderungen eines PR liegen, nach Verzeichnis und Dateityp, als Balken
+help-coverage = Zeigen, wie viel des vom PR hinzugefügten Codes die Tests abdecken, aus dem Coverage-Bericht der CI
+help-run = Einen Befehl auf dem Code eines PR in einem Wegwerf-Worktree ausführen (z. B. `-- cargo test`)
+help-bisect = Den ersten Commit eines PR finden, auf dem ein Befehl fehlschlägt (z. B. `-- cargo test`)
+help-serve = JSON-RPC über stdin/stdout anbieten, als PR-Backend eines Editor-Plugins
+help-comments = Konversation und Inline-Kommentare eines PR anzeigen, veraltete markiert
+help-freeze = Merge-Freeze eines Branches anzeigen, ausrufen oder beenden
+help-autopilot = Reine Abhängigkeits-PRs vertrauenswürdiger Bots freigeben und mergen (standardmäßig nur auflisten)
+help-bots = Offene PRs von Abhängigkeits-Bots nach Ökosystem und Abhängigkeit gruppiert anzeigen
+help-viewed = Dateien eines PR als gesehen markieren
+help-describe = Eine PR-Beschreibung aus Commits, geänderten Modulen und verknüpften Issues erzeugen
+help-move = Einen PR auf einen anderen Basis-Branch umstellen und/oder umbenennen
+help-sync-fork = Den Standard-Branch des eigenen Forks vom Upstream vorspulen
+help-reopen-review = Den aktuellen Branch pushen, kommentieren und erneut ein Review anfordern
+help-comment = Die Unterhaltung eines PRs ohne Review kommentieren, optional mit angehängten Dateien
+help-suggest-reviewer = Den am wenigsten ausgelasteten Reviewer aus CODEOWNERS und Autoren der geänderten Zeilen vorschlagen
+help-assign-next = Ein Review von der nächsten Person der `review-rotation` anfordern
+help-welcome = Neue Beitragende mit einem Kommentar auf ihrem PR begrüßen
+help-triage = Unbeschriftete, nicht zugewiesene PRs durchgehen: beschriften, zuweisen, Reviews anfordern, kommentieren
+help-queue = Die offenen PRs auflisten, die auf ein Review eines Teams warten, die ältesten zuerst
+help-pending = Ausstehende Reviews auflisten (lokale Entwürfe und nicht abgesendete Reviews) oder bearbeiten
+help-audit = Das Audit-Log von Reviews, Merges, Schließungen, Label-Änderungen und anderen Schreibzugriffen anzeigen
+help-stats = Die mit `--spent` erfasste Review-Zeit anzeigen
+help-config = Einstellungen von git-pr lesen oder schreiben (pro Repository oder mit `--global`)
+help-doctor = Git, Einstellungen, Remote, Token, API-Zugriff und Rate-Limit prüfen, mit Lösungsvorschlägen
+help-selftest = Die wichtigsten Abläufe gegen eine eingebaute Test-API ausführen (ohne Netzwerk)
+help-help = Hilfe zu git-pr oder einem seiner Befehle ausgeben
+help-version = Version, Commit, Build-Datum und enthaltene Anbieter anzeigen
+help-man = Man-Pages erzeugen (auf stdout oder mit --out-dir eine Seite pro Befehl)
+
+## Tabellenköpfe
+
+header-action = Aktion
+header-after = Nachher
+header-artifact = Artefakt
+header-author = Autor
+header-authors = Autoren
+header-base = Basis
+header-base-branch = Basis-Branch
+header-before = Vorher
+header-blamed-lines = Zeilen (blame)
+header-change = Änderung
+header-changed-files = Geänderte Dateien
+header-changes = Änderungen
+header-comment = Kommentar
+header-comments = Kommentare
+header-covered = Abgedeckt
+header-commit-sha = Commit-SHA
+header-commits = Commits
+header-created = Erstellt
+header-dependency = Abhängigkeit
+header-description = Beschreibung
+header-endpoint = Endpunkt
+header-environment = Umgebung
+header-event = Ereignis
+header-file = Datei
+header-kind = Art
+header-labels = Labels
+header-last = Zuletzt
+header-line = Zeile
+header-lines = Zeilen
+header-milestone = Meilenstein
+header-name = Name
+header-number = Nummer
+header-number-of-changed-files = Anzahl geänderter Dateien
+header-open-prs = Offene PRs
+header-open-requests = Offene Anfragen
+header-participant = Beteiligte
+header-pr-number = PR-Nummer
+header-project = Projekt
+header-requested = Angefordert
+header-result = Ergebnis
+header-reviewer = Reviewer
+header-sessions = Sitzungen
+header-signature = Signatur
+header-size = Größe
+header-source = Quelle
+header-state = Zustand
+header-status = Status
+header-summary = Zusammenfassung
+header-time = Zeit
+header-title = Titel
+header-total-commits = Commits gesamt
+header-uncovered-lines = Nicht abgedeckte Zeilen
+header-updated = Aktualisiert
+header-user = Benutzer
+header-viewed = Gesehen
+header-waiting-since = Wartet seit
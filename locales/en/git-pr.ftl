@@ -0,0 +1,61 @@
+# English messages of git-pr (the fallback for every other language).
+#
+# Help texts and table headers aren't listed here: their English text is the CLI and table
+# definition in the code. Other catalogs translate them with `help-*` and `header-*`
+# messages (see locales/de/git-pr.ftl).
+
+## Errors
+
+error-artifacts = ❌ Error with artifacts:
+error-assign-next = ❌ Error assigning a reviewer:
+error-audit = ❌ Error reading the audit log:
+error-autopilot = ❌ Autopilot error:
+error-bisect = ❌ Error bisecting the PR:
+error-bots = ❌ Error listing bot PRs:
+error-checks = ❌ Error showing checks:
+error-close = ❌ Failed to close PR:
+error-comment = ❌ Error commenting on PR:
+error-comments = ❌ Error showing comments:
+error-config-set = ❌ Failed to update config:
+error-coverage = ❌ Error reporting coverage:
+error-create = ❌ Error creating PR:
+error-describe = ❌ Error describing PR:
+error-detached-head = ❌ No PR number given and HEAD is not on a branch.
+error-diff = ❌ Failed to show diff:
+error-diff-stats = ❌ Error summarizing the changes:
+error-draft = ❌ Error converting to draft:
+error-files = ❌ Error listing files:
+error-freeze = ❌ Error with the merge freeze:
+error-lint = ❌ Error linting PR:
+error-lint-failed = ❌ Lint failed:
+error-list = ❌ Error listing PRs:
+error-logs = ❌ Error showing logs:
+error-man = ❌ Failed to generate man pages:
+error-mark-viewed = ❌ Error marking files:
+error-merge = ❌ Error merging PR:
+error-move = ❌ Error moving PR:
+error-no-branch-pr = ❌ No PR number given and no open PR found for branch { $branch }.
+error-pending = ❌ Error handling pending review:
+error-provider = ❌ Provider error:
+error-queue = ❌ Error listing the review queue:
+error-ready = ❌ Error marking ready for review:
+error-remote-url = ❌ Could not determine remote { $remote } URL.
+error-review-commit = ❌ Error reviewing commit:
+error-rereview = ❌ Error re-opening review:
+error-run = ❌ Error running the command on the PR:
+error-resolve-branch-pr = ❌ Could not resolve PR for current branch:
+error-save-draft = ❌ Error saving review draft:
+error-scope = ❌ Error with scoped review:
+error-search = ❌ Error searching PRs:
+error-selftest = ❌ Self test could not start:
+error-serve = ❌ The JSON-RPC server stopped:
+error-show-details = ❌ Error showing PR details:
+error-submit-review = ❌ Error submitting review:
+error-suggest-reviewer = ❌ Error suggesting a reviewer:
+error-sync-fork = ❌ Error syncing fork:
+error-triage = ❌ Error triaging PRs:
+error-unknown-command = ❌ Unknown command '{ $command }'. See `git pr help`.
+error-unresolved = ❌ Error listing review threads:
+error-verify-review = ❌ Error verifying the review:
+error-version = ❌ Failed to print version:
+error-welcome = ❌ Error welcoming contributor:
@@ -0,0 +1,341 @@
+// Append-only audit log of every write to the provider (`git pr audit`).
+//
+// Each API request that changes something (anything but GET/HEAD, and GraphQL mutations) is
+// recorded as one JSON line in `audit.jsonl` next to the global config file
+// (`~/.config/git-pr/`), whether it succeeded or not:
+//
+//   {"time":"2024-05-01T12:03:00Z","user":"Jane Doe <jane@example.com>","repo":"acme/api",
+//    "action":"merge","method":"PUT","endpoint":"/repos/acme/api/pulls/42/merge",
+//    "payload_sha256":"9f86d0…","status":200}
+//
+// `user` is the local git identity (or the OS user), `action` a coarse classification for
+// filtering (review, merge, close, label, comment, ...; GraphQL mutations by their name),
+// and the payload is only kept as a hash so the log never holds review text or tokens.
+// Failed requests carry `error` instead of `status`. Entries are only ever appended; the
+// file is never rewritten, so it can be shipped to a log collector as is.
+//
+// `git pr audit --since 7d` reads it back, as a table or (`--json`) as the raw lines.
+use crate::config;
+use crate::debug_log;
+use crate::table::{self, Shrink};
+use crate::ui_eprintln;
+use crate::ui_println;
+use crate::utils::{parse_duration, run_git};
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tabled::Tabled;
+
+/// Name of the log file in the config directory.
+const AUDIT_FILE: &str = "audit.jsonl";
+
+/// Set once a write to the log failed, so the warning is printed only once per run.
+static WARNED: AtomicBool = AtomicBool::new(false);
+
+/// One line of the audit log.
+///
+/// Fields:
+/// - `time`: When the request was sent
+/// - `user`: Local identity of whoever ran the command
+/// - `repo`: `owner/repo` the command worked on, when known
+/// - `action`: What kind of change it was (see [`classify`])
+/// - `method`: HTTP method
+/// - `endpoint`: URL path of the request
+/// - `payload_sha256`: SHA-256 of the request body, if it had one
+/// - `status`: HTTP status of the response
+/// - `error`: Why no response arrived (network failure, ...)
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    time: DateTime<Utc>,
+    user: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    repo: Option<String>,
+    action: String,
+    method: String,
+    endpoint: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    payload_sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// One row of the `audit` table.
+#[derive(Tabled)]
+struct AuditRow {
+    #[tabled(rename = "Time")]
+    time: String,
+    #[tabled(rename = "User")]
+    user: String,
+    #[tabled(rename = "Action")]
+    action: String,
+    #[tabled(rename = "Endpoint")]
+    endpoint: String,
+    #[tabled(rename = "Result")]
+    result: String,
+}
+
+/// A request about to be sent, as far as the audit log is concerned.
+///
+/// Fields:
+/// - `action`: Classification of the change (see [`classify`])
+/// - `method`: HTTP method
+/// - `endpoint`: URL path
+/// - `payload_sha256`: Hash of the body, if any
+pub struct PendingWrite {
+    action: String,
+    method: String,
+    endpoint: String,
+    payload_sha256: Option<String>,
+}
+
+impl PendingWrite {
+    /// What the request changes (`merge`, `review`, `comment`, ...).
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+}
+
+/// Location of the audit log, next to the global config file.
+fn audit_path() -> Option<PathBuf> {
+    Some(config::global_config_path()?.parent()?.join(AUDIT_FILE))
+}
+
+/// Who is running the command: the git identity, or the OS user outside of git.
+fn local_user() -> String {
+    let name = run_git(&["config", "user.name"]);
+    let email = run_git(&["config", "user.email"]);
+    match (name, email) {
+        (Some(name), Some(email)) => format!("{} <{}>", name, email),
+        (None, Some(email)) => email,
+        (Some(name), None) => name,
+        (None, None) => env::var("USER")
+            .or_else(|_| env::var("USERNAME"))
+            .unwrap_or_else(|_| "unknown".to_string()),
+    }
+}
+
+/// Name of the first field of a GraphQL mutation, e.g. `resolveReviewThread`.
+///
+/// # Returns
+/// - `None` if the document isn't a mutation (queries don't change anything)
+fn mutation_name(body: &[u8]) -> Option<String> {
+    let payload: serde_json::Value = serde_json::from_slice(body).ok()?;
+    let query = payload["query"].as_str()?.trim_start();
+    if !query.starts_with("mutation") {
+        return None;
+    }
+    let selection = &query[query.find('{')? + 1..];
+    let name: String = selection
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect();
+    Some(if name.is_empty() {
+        "mutation".to_string()
+    } else {
+        name
+    })
+}
+
+/// Classifies a REST write by its endpoint, e.g. `PUT .../pulls/42/merge` as `merge`.
+fn classify(method: &str, path: &str, body: &[u8]) -> String {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let has = |name: &str| segments.contains(&name);
+    let action = if has("merge") || has("merges") {
+        "merge"
+    } else if has("reviews") {
+        "review"
+    } else if has("requested_reviewers") {
+        "reviewers"
+    } else if has("labels") {
+        "label"
+    } else if has("assignees") {
+        "assign"
+    } else if has("comments") {
+        "comment"
+    } else if method == "PATCH" && (has("pulls") || has("issues")) {
+        let state = serde_json::from_slice::<serde_json::Value>(body)
+            .ok()
+            .and_then(|b| b["state"].as_str().map(str::to_string));
+        match state.as_deref() {
+            Some("closed") => "close",
+            Some("open") => "reopen",
+            _ => "edit",
+        }
+    } else if has("pulls") && method == "POST" {
+        "create"
+    } else if has("git") {
+        "git-data"
+    } else if has("contents") {
+        "file"
+    } else if has("forks") {
+        "fork"
+    } else {
+        "other"
+    };
+    action.to_string()
+}
+
+/// Decides whether a request is recorded.
+///
+/// # Parameters
+/// - `method`: HTTP method
+/// - `path`: URL path
+/// - `body`: Request body (empty if none)
+///
+/// # Returns
+/// - `Some` for writes: non-GET/HEAD requests except GraphQL queries
+pub fn pending_write(method: &str, path: &str, body: &[u8]) -> Option<PendingWrite> {
+    if method == "GET" || method == "HEAD" {
+        return None;
+    }
+    let action = if path.ends_with("/graphql") {
+        mutation_name(body)?
+    } else {
+        classify(method, path, body)
+    };
+    Some(PendingWrite {
+        action,
+        method: method.to_string(),
+        endpoint: path.to_string(),
+        payload_sha256: (!body.is_empty()).then(|| format!("{:x}", Sha256::digest(body))),
+    })
+}
+
+/// Appends a write and its outcome to the log.
+///
+/// A log that can't be written is reported once on stderr; the command itself goes on,
+/// since the change has already been made.
+///
+/// # Parameters
+/// - `write`: The request, from [`pending_write`]
+/// - `repo`: `owner/repo`, when known
+/// - `outcome`: The HTTP status, or why the request failed
+pub fn record(write: PendingWrite, repo: Option<String>, outcome: Result<u16, String>) {
+    let (status, error) = match outcome {
+        Ok(status) => (Some(status), None),
+        Err(error) => (None, Some(error)),
+    };
+    let entry = Entry {
+        time: Utc::now(),
+        user: local_user(),
+        repo,
+        action: write.action,
+        method: write.method,
+        endpoint: write.endpoint,
+        payload_sha256: write.payload_sha256,
+        status,
+        error,
+    };
+    if let Err(e) = append(&entry) {
+        if !WARNED.swap(true, Ordering::Relaxed) {
+            ui_eprintln!("⚠️  Could not write the audit log: {}", e);
+        }
+    }
+}
+
+/// Writes one entry as a single line, so concurrent runs never interleave within a line.
+fn append(entry: &Entry) -> Result<(), Box<dyn Error>> {
+    let path = audit_path().ok_or("no home directory for the config dir")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    debug_log!("[DEBUG] Audit: {}", line.trim_end());
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Prints the audit log (`git pr audit`).
+///
+/// # Parameters
+/// - `since`: Only entries newer than this duration (e.g. `7d`); all of them if `None`
+/// - `json`: Print the matching lines as stored instead of a table
+/// - `config`: For the time format of the table
+///
+/// # Returns
+/// - `Err` if `since` isn't a duration or the log can't be read
+pub fn print_log(
+    since: Option<&str>,
+    json: bool,
+    config: &config::Config,
+) -> Result<(), Box<dyn Error>> {
+    let cutoff = match since {
+        Some(text) => Some(Utc::now() - Duration::seconds(parse_duration(text)? as i64)),
+        None => None,
+    };
+    let path = audit_path().ok_or("no home directory for the config dir")?;
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(format!("Can't read {}: {}", path.display(), e).into()),
+    };
+
+    let mut lines = Vec::new();
+    for line in text.lines().filter(|l| !l.trim().is_empty()) {
+        let Ok(entry) = serde_json::from_str::<Entry>(line) else {
+            debug_log!("[DEBUG] Skipping malformed audit line: {}", line);
+            continue;
+        };
+        if cutoff.is_some_and(|cutoff| entry.time < cutoff) {
+            continue;
+        }
+        lines.push((line, entry));
+    }
+
+    if json {
+        for (line, _) in &lines {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+    if lines.is_empty() {
+        ui_println!(
+            "📜 No audited changes{}.",
+            since
+                .map(|s| format!(" in the last {}", s))
+                .unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    let rows: Vec<AuditRow> = lines
+        .iter()
+        .map(|(_, entry)| AuditRow {
+            time: config.format_time(entry.time),
+            user: entry.user.clone(),
+            action: entry.action.clone(),
+            endpoint: match &entry.repo {
+                Some(repo) if !entry.endpoint.contains(repo.as_str()) => {
+                    format!("{} {} ({})", entry.method, entry.endpoint, repo)
+                }
+                _ => format!("{} {}", entry.method, entry.endpoint),
+            },
+            result: match (entry.status, &entry.error) {
+                (Some(status), _) if (200..300).contains(&status) => {
+                    status.to_string().green().to_string()
+                }
+                (Some(status), _) => status.to_string().red().to_string(),
+                (None, Some(error)) => error.red().to_string(),
+                (None, None) => "-".to_string(),
+            },
+        })
+        .collect();
+    ui_println!("{}", format!("📜 Audit log ({})", path.display()).bold());
+    println!(
+        "{}",
+        table::render(rows, &[(3, Shrink::Truncate), (1, Shrink::Truncate)])
+    );
+    Ok(())
+}
@@ -0,0 +1,142 @@
+// API request budget (`--max-requests`) and the rate limit left on the token.
+//
+// Every API request asks for a slot before it is sent. With `--max-requests N` the
+// request after the Nth is refused, so a command on a shared token (bots, CI jobs) never
+// spends more than it was given: what was fetched so far is still printed, with a note
+// that the results are partial, and the command exits non-zero.
+//
+// GitHub reports the remaining rate limit on every response. The last value seen per
+// bucket (`core`, `graphql`, ...) is kept and shown after commands that made many
+// requests, or when a budget ran out, so users see what the command cost.
+use crate::ui_eprintln;
+use crate::utils::is_quiet;
+use chrono::{Local, TimeZone};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// Requests after which a command counts as expensive and the rate limit is shown.
+const EXPENSIVE_REQUESTS: usize = 25;
+
+// Requests can be sent from worker threads (see `providers::blocking`), hence the atomics
+static MAX_REQUESTS: OnceLock<usize> = OnceLock::new();
+static SENT: AtomicUsize = AtomicUsize::new(0);
+static EXHAUSTED: AtomicBool = AtomicBool::new(false);
+static RATE_LIMITS: Mutex<BTreeMap<String, RateLimit>> = Mutex::new(BTreeMap::new());
+
+/// A rate limit bucket, as last reported by GitHub.
+///
+/// Fields:
+/// - `remaining`: Requests left in the current window
+/// - `limit`: Requests allowed per window
+/// - `reset`: When the window resets (Unix time)
+#[derive(Clone, Copy)]
+struct RateLimit {
+    remaining: u64,
+    limit: u64,
+    reset: i64,
+}
+
+/// Caps the number of API requests of this invocation (`--max-requests`).
+pub fn set_max_requests(max: usize) {
+    let _ = MAX_REQUESTS.set(max);
+}
+
+/// Takes a slot for one API request.
+///
+/// # Returns
+/// - `Ok(())` if the request may be sent
+/// - `Err(message)` once the `--max-requests` budget is spent; the command should wrap up
+///   with what it has
+pub fn take_request() -> Result<(), String> {
+    let sent = SENT.fetch_add(1, Ordering::Relaxed);
+    match MAX_REQUESTS.get() {
+        Some(&max) if sent >= max => {
+            EXHAUSTED.store(true, Ordering::Relaxed);
+            Err(format!(
+                "request budget of {} spent (--max-requests); results are partial",
+                max
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Whether a request was refused because the budget was spent.
+pub fn exhausted() -> bool {
+    EXHAUSTED.load(Ordering::Relaxed)
+}
+
+/// Records the `x-ratelimit-*` headers of a response.
+///
+/// # Parameters
+/// - `header`: Looks up a response header by name
+pub fn record_rate_limit(header: impl Fn(&str) -> Option<String>) {
+    let number = |name: &str| header(name).and_then(|v| v.parse::<i64>().ok());
+    let (Some(remaining), Some(limit), Some(reset)) = (
+        number("x-ratelimit-remaining"),
+        number("x-ratelimit-limit"),
+        number("x-ratelimit-reset"),
+    ) else {
+        return;
+    };
+    let resource = header("x-ratelimit-resource").unwrap_or_else(|| "core".to_string());
+    if let Ok(mut limits) = RATE_LIMITS.lock() {
+        limits.insert(
+            resource,
+            RateLimit {
+                remaining: remaining.max(0) as u64,
+                limit: limit.max(0) as u64,
+                reset,
+            },
+        );
+    }
+}
+
+/// Prints the requests used and the rate limit left to stderr, after commands that made
+/// many requests or ran out of budget. Nothing is printed for cheap commands, or under
+/// `--quiet` unless the budget ran out.
+pub fn print_summary() {
+    let sent = SENT.load(Ordering::Relaxed);
+    let exhausted = exhausted();
+    if !exhausted && (sent < EXPENSIVE_REQUESTS || is_quiet()) {
+        return;
+    }
+
+    if exhausted {
+        if let Some(max) = MAX_REQUESTS.get() {
+            ui_eprintln!(
+                "⚠️  Stopped at the budget of {} API request{} (--max-requests); the output above is partial.",
+                max,
+                if *max == 1 { "" } else { "s" }
+            );
+        }
+    }
+
+    let limits = RATE_LIMITS.lock().map(|l| l.clone()).unwrap_or_default();
+    let used = sent.min(MAX_REQUESTS.get().copied().unwrap_or(usize::MAX));
+    let used = format!(
+        "{} API request{} used",
+        used,
+        if used == 1 { "" } else { "s" }
+    );
+    if limits.is_empty() {
+        ui_eprintln!("📉 {}", used);
+        return;
+    }
+    let buckets: Vec<String> = limits
+        .iter()
+        .map(|(resource, limit)| {
+            let reset = Local
+                .timestamp_opt(limit.reset, 0)
+                .single()
+                .map(|time| time.format("%H:%M").to_string())
+                .unwrap_or_else(|| "?".to_string());
+            format!(
+                "{}/{} {} left (resets {})",
+                limit.remaining, limit.limit, resource, reset
+            )
+        })
+        .collect();
+    ui_eprintln!("📉 {}; rate limit: {}", used, buckets.join(", "));
+}
@@ -0,0 +1,113 @@
+// Local cache of PR data (`cache-ttl`), kept fresh by webhooks (`git pr serve --webhook`).
+//
+// Looking at a PR usually takes several commands in a row (`show-details`, `show-diff`,
+// `checks`, ...), and each fetches the same PR, files and comments again. With
+// `cache-ttl` set, GET responses of PR-scoped endpoints (`/repos/<owner>/<repo>/pulls/<n>`
+// and `/issues/<n>`, with everything below them) are kept under `.git/git-pr/cache/<n>/`
+// and reused while they are younger than the TTL. Without the setting nothing is cached.
+//
+// A PR's entries are dropped as soon as they are known to be stale:
+//   - when git-pr itself writes to one of the PR's endpoints (comment, review, merge, ...);
+//     writes that can't be tied to one PR (GraphQL mutations, repository settings) drop
+//     the whole cache,
+//   - when `git pr serve --webhook` receives a signed event about the PR (see
+//     `providers/github/webhook.rs`), so other commands on the machine see the change
+//     right away instead of after the TTL.
+//
+// Each entry is one file named after the start of the SHA-256 of its URL, holding the URL
+// on the first line and the response body after it; its age is the file's modification
+// time. Entries are written to a temporary file and renamed into place, so a reader never
+// sees half an entry, and a cache that can't be read or written is simply bypassed.
+use crate::debug_log;
+use crate::utils::run_git;
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+/// The cache directory of the current repository, looked up once per process.
+fn cache_root() -> Option<&'static PathBuf> {
+    static ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+    ROOT.get_or_init(|| {
+        run_git(&["rev-parse", "--git-dir"])
+            .map(|git_dir| PathBuf::from(git_dir).join("git-pr").join("cache"))
+    })
+    .as_ref()
+}
+
+/// The PR a URL belongs to, if it is PR-scoped (`.../repos/<owner>/<repo>/pulls/42/files`).
+pub fn pr_of(url: &str) -> Option<u64> {
+    static SCOPED: OnceLock<Regex> = OnceLock::new();
+    let scoped = SCOPED.get_or_init(|| {
+        Regex::new(r"/repos/[^/]+/[^/]+/(?:pulls|issues)/(\d+)(?:/|$)").expect("valid regex")
+    });
+    let path = url.split('?').next().unwrap_or(url);
+    scoped.captures(path)?[1].parse().ok()
+}
+
+/// The file holding the entry of a PR-scoped URL.
+fn entry_path(url: &str) -> Option<PathBuf> {
+    let pr = pr_of(url)?;
+    let digest = format!("{:x}", Sha256::digest(url));
+    Some(cache_root()?.join(pr.to_string()).join(&digest[..16]))
+}
+
+/// The cached body of `url`, unless it is older than `ttl` seconds.
+pub fn lookup(url: &str, ttl: u64) -> Option<String> {
+    let path = entry_path(url)?;
+    let age = fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())?;
+    if age >= Duration::from_secs(ttl) {
+        return None;
+    }
+    let text = fs::read_to_string(&path).ok()?;
+    // The URL guards against (unlikely) collisions of the shortened hash
+    let (cached_url, body) = text.split_once('\n')?;
+    (cached_url == url).then(|| body.to_string())
+}
+
+/// Keeps the body of a PR-scoped `url`. Failures are only logged.
+pub fn store(url: &str, body: &str) {
+    let Some(path) = entry_path(url) else {
+        return;
+    };
+    let temp = path.with_extension(format!("tmp{}", process::id()));
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&temp, format!("{}\n{}", url, body)))
+        .and_then(|_| fs::rename(&temp, &path));
+    if let Err(e) = result {
+        debug_log!("[DEBUG] Couldn't cache {}: {}", url, e);
+        let _ = fs::remove_file(&temp);
+    }
+}
+
+/// Drops every entry of a PR.
+pub fn invalidate(pr: u64) {
+    if let Some(root) = cache_root() {
+        if fs::remove_dir_all(root.join(pr.to_string())).is_ok() {
+            debug_log!("[DEBUG] Dropped the cached data of PR #{}", pr);
+        }
+    }
+}
+
+/// Drops what a write to `url` makes stale: the PR's entries, or everything if the write
+/// can't be tied to one PR.
+pub fn invalidate_after_write(url: &str) {
+    match pr_of(url) {
+        Some(pr) => invalidate(pr),
+        None => {
+            if let Some(root) = cache_root() {
+                if fs::remove_dir_all(root).is_ok() {
+                    debug_log!("[DEBUG] Dropped the PR cache after a write to {}", url);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+// Parsing and matching of GitHub `CODEOWNERS` files.
+//
+// CODEOWNERS uses gitignore-style patterns, and the *last* matching rule wins.
+// This module only deals with text; fetching the file is the provider's job.
+use regex::Regex;
+
+/// Locations GitHub checks for a CODEOWNERS file, in order of precedence.
+pub const CODEOWNERS_PATHS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// A single `pattern @owner1 @owner2` rule.
+///
+/// Fields:
+/// - `owners`: Owners without the leading `@` (users, `org/team` slugs, or emails)
+/// - `regex`: The pattern compiled to a regex over repo-relative paths
+pub struct Rule {
+    pub owners: Vec<String>,
+    regex: Regex,
+}
+
+/// A parsed CODEOWNERS file.
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+}
+
+impl CodeOwners {
+    /// Parses the contents of a CODEOWNERS file.
+    ///
+    /// Blank lines and `#` comments are skipped, as are patterns that can't be compiled.
+    pub fn parse(text: &str) -> Self {
+        let rules = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let pattern = parts.next()?;
+                let owners = parts
+                    .take_while(|p| !p.starts_with('#'))
+                    .map(|p| p.trim_start_matches('@').to_string())
+                    .collect();
+                let regex = pattern_to_regex(pattern)?;
+                Some(Rule { owners, regex })
+            })
+            .collect();
+
+        CodeOwners { rules }
+    }
+
+    /// Returns the rule that owns `path`, i.e. the last matching rule.
+    ///
+    /// A matching rule with no owners means the path is explicitly unowned.
+    pub fn rule_for(&self, path: &str) -> Option<&Rule> {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.regex.is_match(path))
+    }
+
+    /// Convenience wrapper returning just the owners of `path` (empty if unowned).
+    pub fn owners_of(&self, path: &str) -> Vec<String> {
+        self.rule_for(path)
+            .map(|rule| rule.owners.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Compiles a comma-separated list of CODEOWNERS-style patterns (as used by the
+/// `ignore-paths` and `sensitive-paths` settings); invalid patterns are skipped.
+pub fn compile_pattern_list(list: &str) -> Vec<Regex> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .filter_map(pattern_to_regex)
+        .collect()
+}
+
+/// Compiles a gitignore-style CODEOWNERS pattern into a regex.
+///
+/// - A leading `/`, or a `/` anywhere but the end, anchors the pattern at the repo root
+/// - `*` matches within a path segment, `**` across segments, `?` a single character
+/// - A pattern naming a directory also matches everything beneath it: one ending in `/`
+///   (which then matches nothing else), or one whose last segment has no wildcard.
+///   `docs/*` matches only the direct children of `docs`, as on GitHub.
+fn pattern_to_regex(pattern: &str) -> Option<Regex> {
+    let directory_only = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+    let last_segment = trimmed.rsplit('/').next().unwrap_or(trimmed);
+    let names_directory = directory_only || !last_segment.contains(['*', '?']);
+    let anchored = trimmed.starts_with('/') || trimmed.contains('/');
+    let body = trimmed.trim_start_matches('/');
+
+    let mut regex = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // `**/` matches zero or more directories
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            other => regex.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    regex.push_str(if directory_only {
+        "/.*$"
+    } else if names_directory {
+        "(?:/.*)?$"
+    } else {
+        "$"
+    });
+
+    Regex::new(&regex).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Whether `pattern` matches `path`.
+    fn matches(pattern: &str, path: &str) -> bool {
+        pattern_to_regex(pattern).is_some_and(|re| re.is_match(path))
+    }
+
+    #[test]
+    fn star_matches_within_one_segment() {
+        assert!(matches("*", "README.md"));
+        assert!(matches("*", "src/main.rs"));
+        assert!(matches("*.rs", "src/main.rs"));
+        assert!(!matches("*.rs", "src/main.rs.orig"));
+        assert!(!matches("src/*.rs", "src/providers/mod.rs"));
+    }
+
+    #[test]
+    fn double_star_matches_across_segments() {
+        assert!(matches("docs/**", "docs/a/b.md"));
+        assert!(matches("**/fixtures", "tests/unit/fixtures/data.json"));
+        assert!(matches("docs/**/*.md", "docs/guide.md"));
+        assert!(matches("docs/**/*.md", "docs/a/b/guide.md"));
+        assert!(!matches("docs/**/*.md", "src/guide.md"));
+    }
+
+    #[test]
+    fn trailing_slash_matches_only_beneath_the_directory() {
+        assert!(matches("apps/", "apps/web/index.ts"));
+        assert!(matches("apps/", "services/apps/main.go"));
+        assert!(!matches("apps/", "apps"));
+        assert!(!matches("apps/", "apps.md"));
+    }
+
+    #[test]
+    fn dir_star_matches_only_direct_children() {
+        assert!(matches("docs/*", "docs/a.md"));
+        assert!(!matches("docs/*", "docs/a/b.md"));
+        assert!(!matches("docs/*", "other/docs/a.md"));
+    }
+
+    #[test]
+    fn plain_names_match_the_path_and_everything_beneath() {
+        assert!(matches("build", "build"));
+        assert!(matches("build", "tools/build/out.txt"));
+        assert!(!matches("build", "builds/out.txt"));
+    }
+
+    #[test]
+    fn leading_or_inner_slash_anchors_at_the_root() {
+        assert!(matches("/build/logs", "build/logs/today.log"));
+        assert!(!matches("/build/logs", "sub/build/logs/today.log"));
+        assert!(matches("/*.md", "README.md"));
+        assert!(!matches("/*.md", "docs/README.md"));
+        assert!(!matches("src/api", "lib/src/api/mod.rs"));
+    }
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let owners = CodeOwners::parse("* @all\ndocs/* @docs\n/docs/internal/ @internal\n");
+        assert_eq!(owners.owners_of("docs/a.md"), vec!["docs"]);
+        assert_eq!(owners.owners_of("docs/a/b.md"), vec!["all"]);
+        assert_eq!(owners.owners_of("docs/internal/x.md"), vec!["internal"]);
+    }
+}
@@ -0,0 +1,217 @@
+// Column registry of the PR table printed by `list` and `search`.
+//
+// Every column the table can show is registered here once, with the name used by
+// `--columns` and the `list-columns` setting, its header, and how it gives up space on a
+// narrow terminal. Providers only fill in cell values; which columns appear, and in which
+// order, is decided by the user.
+use crate::table::{self, Shrink};
+
+/// A column of the PR table.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Column {
+    Number,
+    Title,
+    Author,
+    Created,
+    Updated,
+    Commits,
+    Files,
+    Head,
+    Base,
+    Labels,
+    Milestone,
+    Project,
+    Description,
+    Checks,
+    Review,
+    Ticket,
+}
+
+/// Registry entry of a column.
+///
+/// Fields:
+/// - `column`: The column
+/// - `name`: Name in `--columns` / `list-columns`
+/// - `header`: Table header
+/// - `shrink`: How the column gives up space, and when: lower ranks shrink first; `None`
+///   keeps its natural width
+struct ColumnInfo {
+    column: Column,
+    name: &'static str,
+    header: &'static str,
+    shrink: Option<(u8, Shrink)>,
+}
+
+/// Every column, in the order `--columns` help lists them.
+const REGISTRY: &[ColumnInfo] = &[
+    ColumnInfo {
+        column: Column::Number,
+        name: "number",
+        header: "Number",
+        shrink: None,
+    },
+    ColumnInfo {
+        column: Column::Title,
+        name: "title",
+        header: "Title",
+        shrink: Some((1, Shrink::Wrap)),
+    },
+    ColumnInfo {
+        column: Column::Author,
+        name: "author",
+        header: "Author",
+        shrink: None,
+    },
+    ColumnInfo {
+        column: Column::Created,
+        name: "created",
+        header: "Created",
+        shrink: None,
+    },
+    ColumnInfo {
+        column: Column::Updated,
+        name: "updated",
+        header: "Updated",
+        shrink: None,
+    },
+    ColumnInfo {
+        column: Column::Commits,
+        name: "commits",
+        header: "Total Commits",
+        shrink: None,
+    },
+    ColumnInfo {
+        column: Column::Files,
+        name: "files",
+        header: "Number of Changed Files",
+        shrink: None,
+    },
+    ColumnInfo {
+        column: Column::Head,
+        name: "head",
+        header: "Head",
+        shrink: Some((3, Shrink::Truncate)),
+    },
+    ColumnInfo {
+        column: Column::Base,
+        name: "base",
+        header: "Base",
+        shrink: Some((4, Shrink::Truncate)),
+    },
+    ColumnInfo {
+        column: Column::Labels,
+        name: "labels",
+        header: "Labels",
+        shrink: Some((2, Shrink::Truncate)),
+    },
+    ColumnInfo {
+        column: Column::Milestone,
+        name: "milestone",
+        header: "Milestone",
+        shrink: Some((5, Shrink::Truncate)),
+    },
+    ColumnInfo {
+        column: Column::Project,
+        name: "project",
+        header: "Project",
+        shrink: Some((6, Shrink::Wrap)),
+    },
+    ColumnInfo {
+        column: Column::Description,
+        name: "description",
+        header: "Description",
+        shrink: Some((0, Shrink::Wrap)),
+    },
+    ColumnInfo {
+        column: Column::Checks,
+        name: "checks",
+        header: "Checks",
+        shrink: None,
+    },
+    ColumnInfo {
+        column: Column::Review,
+        name: "review",
+        header: "Review",
+        shrink: None,
+    },
+    ColumnInfo {
+        column: Column::Ticket,
+        name: "ticket",
+        header: "Ticket",
+        shrink: Some((4, Shrink::Truncate)),
+    },
+];
+
+/// Columns shown when neither `--columns` nor `list-columns` is set.
+pub const DEFAULT_COLUMNS: &str =
+    "number,title,author,created,updated,commits,files,labels,description";
+
+impl Column {
+    /// The registry entry of this column.
+    fn info(self) -> &'static ColumnInfo {
+        REGISTRY
+            .iter()
+            .find(|info| info.column == self)
+            .expect("every column is registered")
+    }
+}
+
+/// Parses a comma-separated list of column names, e.g. `number,title,checks`.
+///
+/// # Returns
+/// - The columns in the given order
+/// - `Err(message)` naming an unknown column and the available ones, or if the list is empty
+pub fn parse_columns(spec: &str) -> Result<Vec<Column>, String> {
+    let mut columns = Vec::new();
+    for name in spec.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        let info = REGISTRY
+            .iter()
+            .find(|info| info.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown column '{}'. Available: {}",
+                    name,
+                    REGISTRY
+                        .iter()
+                        .map(|info| info.name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })?;
+        if !columns.contains(&info.column) {
+            columns.push(info.column);
+        }
+    }
+    if columns.is_empty() {
+        return Err("No columns given".to_string());
+    }
+    Ok(columns)
+}
+
+/// Checks a `--columns` value, for use as a clap value parser.
+pub fn validate_columns(spec: &str) -> Result<String, String> {
+    parse_columns(spec).map(|_| spec.to_string())
+}
+
+/// Renders the PR table.
+///
+/// # Parameters
+/// - `columns`: The columns to show, in order
+/// - `rows`: One cell per column, in the same order, for every PR
+pub fn render(columns: &[Column], rows: Vec<Vec<String>>) -> String {
+    let headers = columns
+        .iter()
+        .map(|c| c.info().header.to_string())
+        .collect();
+
+    // Flexible columns give up space by rank, wherever they are in the table
+    let mut flex: Vec<(u8, usize, Shrink)> = columns
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| c.info().shrink.map(|(rank, how)| (rank, i, how)))
+        .collect();
+    flex.sort_by_key(|(rank, _, _)| *rank);
+    let flex: Vec<(usize, Shrink)> = flex.into_iter().map(|(_, i, how)| (i, how)).collect();
+
+    table::render_records(headers, rows, &flex)
+}
@@ -0,0 +1,395 @@
+// Local helpers for composing a new pull request: deriving the title and body from
+// the branch's commits, picking up the repository's PR template, and letting the
+// user finish the text in their editor. Nothing here talks to a provider.
+use crate::debug_log;
+use crate::utils::{render_template, run_git};
+use regex::Regex;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Default template for `describe --regenerate` (overridable via `describe-template`).
+///
+/// Placeholders: `{title}`, `{commits}`, `{modules}`, `{issues}`, `{test_plan}`.
+pub const DEFAULT_DESCRIBE_TEMPLATE: &str = "## Summary
+
+{commits}
+
+## Changed modules
+
+{modules}
+
+## Linked issues
+
+{issues}
+
+## Test plan
+
+{test_plan}";
+
+/// Default comment of `git pr welcome` (overridable via `welcome-template`).
+///
+/// Placeholders: `{author}`, `{number}`, `{title}`, `{repo}`.
+pub const DEFAULT_WELCOME_TEMPLATE: &str =
+    "Welcome @{author}, and thanks for your first pull request to {repo}! 🎉
+
+A maintainer will review it soon. Meanwhile, please make sure CI passes and that the description \
+explains what the change does and how you tested it. Don't hesitate to ask if anything is unclear.";
+
+/// Placeholder used when there's no test plan to carry over.
+const TEST_PLAN_PLACEHOLDER: &str = "- [ ] Describe how this change was tested";
+
+/// Where GitHub looks for a pull request template, relative to the repo root.
+const TEMPLATE_PATHS: &[&str] = &[
+    ".github/PULL_REQUEST_TEMPLATE.md",
+    ".github/pull_request_template.md",
+    "PULL_REQUEST_TEMPLATE.md",
+    "pull_request_template.md",
+    "docs/PULL_REQUEST_TEMPLATE.md",
+    "docs/pull_request_template.md",
+];
+
+/// A commit on the branch being proposed.
+///
+/// Fields:
+/// - `subject`: First line of the commit message
+/// - `body`: Remaining lines of the message (may be empty)
+pub struct BranchCommit {
+    pub subject: String,
+    pub body: String,
+}
+
+/// Lists the commits in `base..HEAD`, oldest first.
+pub fn commits_since(base: &str) -> Vec<BranchCommit> {
+    // Unit/record separators keep multi-line bodies intact
+    let Some(output) = run_git(&[
+        "log",
+        "--reverse",
+        "--format=%s%x1f%b%x1e",
+        &format!("{}..HEAD", base),
+    ]) else {
+        return Vec::new();
+    };
+
+    output
+        .split('\u{1e}')
+        .map(str::trim)
+        .filter(|record| !record.is_empty())
+        .map(|record| {
+            let (subject, body) = record.split_once('\u{1f}').unwrap_or((record, ""));
+            BranchCommit {
+                subject: subject.trim().to_string(),
+                body: body.trim().to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Derives a PR title: the commit subject for single-commit branches, otherwise
+/// a humanized branch name (`fix-login-crash` → `Fix login crash`).
+pub fn default_title(commits: &[BranchCommit], branch: &str) -> String {
+    if let [only] = commits {
+        return only.subject.clone();
+    }
+
+    let name = branch.rsplit('/').next().unwrap_or(branch);
+    let words = name.replace(['-', '_'], " ");
+    let mut chars = words.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => branch.to_string(),
+    }
+}
+
+/// Derives a PR body from the commit bodies followed by the repository's PR template.
+///
+/// For multi-commit branches each commit is listed by subject, with its body indented
+/// underneath, so reviewers get a summary of the series.
+pub fn default_body(commits: &[BranchCommit]) -> String {
+    let mut sections = Vec::new();
+
+    match commits {
+        [] => {}
+        [only] => {
+            if !only.body.is_empty() {
+                sections.push(only.body.clone());
+            }
+        }
+        many => {
+            let list: Vec<String> = many
+                .iter()
+                .map(|c| {
+                    if c.body.is_empty() {
+                        format!("- {}", c.subject)
+                    } else {
+                        let indented: Vec<String> =
+                            c.body.lines().map(|l| format!("  {}", l)).collect();
+                        format!("- {}\n{}", c.subject, indented.join("\n"))
+                    }
+                })
+                .collect();
+            sections.push(list.join("\n"));
+        }
+    }
+
+    if let Some(template) = read_pr_template() {
+        sections.push(template);
+    }
+
+    sections.join("\n\n")
+}
+
+/// Finds the issue a branch is about, from its name or its commit messages.
+///
+/// Checked in order:
+/// 1. A leading number in the branch name (`1234-fix-crash`, `feature/1234_crash`)
+/// 2. A closing keyword in a commit message (`Fixes #1234`, `closes #1234`)
+/// 3. Any other `#1234` reference in a commit subject
+pub fn referenced_issue(branch: &str, commits: &[BranchCommit]) -> Option<u64> {
+    let branch_re = Regex::new(r"(?:^|/)(\d+)(?:[-_]|$)").ok()?;
+    if let Some(caps) = branch_re.captures(branch) {
+        return caps[1].parse().ok();
+    }
+
+    let closing_re =
+        Regex::new(r"(?i)\b(?:close[sd]?|fix(?:e[sd])?|resolve[sd]?)\s*:?\s+#(\d+)").ok()?;
+    for commit in commits {
+        let message = format!("{}\n{}", commit.subject, commit.body);
+        if let Some(caps) = closing_re.captures(&message) {
+            return caps[1].parse().ok();
+        }
+    }
+
+    let any_re = Regex::new(r"#(\d+)\b").ok()?;
+    commits
+        .iter()
+        .find_map(|c| any_re.captures(&c.subject))
+        .and_then(|caps| caps[1].parse().ok())
+}
+
+/// Returns `true` if the text already contains a closing keyword for `issue`.
+pub fn mentions_closing(text: &str, issue: u64) -> bool {
+    Regex::new(&format!(
+        r"(?i)\b(?:close[sd]?|fix(?:e[sd])?|resolve[sd]?)\s*:?\s+#{}\b",
+        issue
+    ))
+    .map(|re| re.is_match(text))
+    .unwrap_or(false)
+}
+
+/// Returns `true` if two PR titles are near-identical.
+///
+/// Titles are compared as sets of lowercase words (punctuation and prefixes like
+/// `[WIP]` or `chore:` mostly wash out); 80% overlap counts as the same title.
+pub fn titles_similar(a: &str, b: &str) -> bool {
+    let words = |s: &str| -> std::collections::HashSet<String> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    };
+    let (a, b) = (words(a), words(b));
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    let shared = a.intersection(&b).count() as f64;
+    let total = a.union(&b).count() as f64;
+    shared / total >= 0.8
+}
+
+/// Resolves the `describe-template` setting: a path to a template file (absolute or
+/// relative to the repo root) or the template text itself.
+pub fn load_describe_template(setting: Option<&str>) -> String {
+    load_template(setting, DEFAULT_DESCRIBE_TEMPLATE)
+}
+
+/// Resolves the `welcome-template` setting, like [`load_describe_template`].
+pub fn load_welcome_template(setting: Option<&str>) -> String {
+    load_template(setting, DEFAULT_WELCOME_TEMPLATE)
+}
+
+/// Reads a template setting: a file path (relative paths start at the repo root) or,
+/// if no such file exists, the template text itself with `\n` as line breaks.
+fn load_template(setting: Option<&str>, default: &str) -> String {
+    let Some(setting) = setting else {
+        return default.to_string();
+    };
+
+    let mut path = PathBuf::from(setting);
+    if path.is_relative() {
+        if let Some(root) = run_git(&["rev-parse", "--show-toplevel"]) {
+            path = PathBuf::from(root).join(path);
+        }
+    }
+    match fs::read_to_string(&path) {
+        Ok(text) => {
+            debug_log!("[DEBUG] Using template {}", path.display());
+            text
+        }
+        Err(_) => setting.replace("\\n", "\n"),
+    }
+}
+
+/// Builds a PR description from the template and what the PR contains.
+///
+/// # Parameters
+/// - `template`: Template text with `{placeholder}`s (see [`DEFAULT_DESCRIBE_TEMPLATE`])
+/// - `title`: The PR title
+/// - `commits`: `(short sha, message)` of every commit in the PR, oldest first
+/// - `files`: Paths of the changed files
+/// - `current_body`: The existing description; its test plan section is carried over
+pub fn render_description(
+    template: &str,
+    title: &str,
+    commits: &[(String, String)],
+    files: &[String],
+    current_body: &str,
+) -> String {
+    let commit_list: Vec<String> = commits
+        .iter()
+        .map(|(sha, message)| format!("- {} ({})", message.lines().next().unwrap_or_default(), sha))
+        .collect();
+
+    // Modules are the directories touched, with how many files changed in each
+    let mut modules: std::collections::BTreeMap<String, usize> = Default::default();
+    for file in files {
+        let dir = match file.rsplit_once('/') {
+            Some((dir, _)) => dir.to_string(),
+            None => "(root)".to_string(),
+        };
+        *modules.entry(dir).or_default() += 1;
+    }
+    let module_list: Vec<String> = modules
+        .iter()
+        .map(|(dir, count)| format!("- `{}` ({} file(s))", dir, count))
+        .collect();
+
+    // Issues referenced by commits or the current body; closing references stay closing
+    let mut text = current_body.to_string();
+    for (_, message) in commits {
+        text.push('\n');
+        text.push_str(message);
+    }
+    let issue_list: Vec<String> = issue_refs(&text)
+        .into_iter()
+        .map(|issue| {
+            if mentions_closing(&text, issue) {
+                format!("- Fixes #{}", issue)
+            } else {
+                format!("- Refs #{}", issue)
+            }
+        })
+        .collect();
+
+    let test_plan = markdown_section(current_body, "Test plan")
+        .unwrap_or_else(|| TEST_PLAN_PLACEHOLDER.to_string());
+
+    let or_none = |list: Vec<String>| {
+        if list.is_empty() {
+            "_None_".to_string()
+        } else {
+            list.join("\n")
+        }
+    };
+    render_template(
+        template,
+        &[
+            ("title", title),
+            ("commits", &or_none(commit_list)),
+            ("modules", &or_none(module_list)),
+            ("issues", &or_none(issue_list)),
+            ("test_plan", &test_plan),
+        ],
+    )
+    .trim()
+    .to_string()
+}
+
+/// Lists every distinct `#123` reference in the text, in order of appearance.
+fn issue_refs(text: &str) -> Vec<u64> {
+    let Ok(re) = Regex::new(r"(?:^|[^\w&])#(\d+)\b") else {
+        return Vec::new();
+    };
+    let mut issues = Vec::new();
+    for caps in re.captures_iter(text) {
+        if let Ok(issue) = caps[1].parse() {
+            if !issues.contains(&issue) {
+                issues.push(issue);
+            }
+        }
+    }
+    issues
+}
+
+/// Returns the body of a `## <heading>` section of a Markdown document, if present.
+fn markdown_section(text: &str, heading: &str) -> Option<String> {
+    let mut lines = text.lines();
+    lines.find(|line| {
+        line.trim_start_matches('#')
+            .trim()
+            .eq_ignore_ascii_case(heading)
+            && line.starts_with('#')
+    })?;
+    let section: Vec<&str> = lines.take_while(|line| !line.starts_with("## ")).collect();
+    let section = section.join("\n").trim().to_string();
+    (!section.is_empty()).then_some(section)
+}
+
+/// Reads the repository's pull request template from the working tree, if any.
+pub fn read_pr_template() -> Option<String> {
+    let root = PathBuf::from(run_git(&["rev-parse", "--show-toplevel"])?);
+    TEMPLATE_PATHS.iter().find_map(|path| {
+        let text = fs::read_to_string(root.join(path)).ok()?;
+        debug_log!("[DEBUG] Using PR template {}", path);
+        Some(text.trim().to_string())
+    })
+}
+
+/// Opens the user's editor on a draft title/body and returns the edited pair.
+///
+/// The file uses the same layout as a commit message: the first line is the title,
+/// everything after the first blank line is the body.
+pub fn edit_in_editor(title: &str, body: &str) -> Result<(String, String), Box<dyn Error>> {
+    let edited = edit_text("PR_EDITMSG", &format!("{}\n\n{}\n", title, body))?;
+
+    let (title, body) = edited.split_once('\n').unwrap_or((edited.as_str(), ""));
+    let title = title.trim().to_string();
+    if title.is_empty() {
+        return Err("Empty PR title; aborting".into());
+    }
+
+    Ok((title, body.trim().to_string()))
+}
+
+/// Opens the user's editor on `text` (stored as `.git/<file_name>`) and returns the result.
+///
+/// The editor is resolved the way git does it (`GIT_EDITOR`, `core.editor`, `VISUAL`,
+/// `EDITOR`, then `vi`).
+pub fn edit_text(file_name: &str, text: &str) -> Result<String, Box<dyn Error>> {
+    let git_dir = run_git(&["rev-parse", "--git-dir"]).unwrap_or_else(|| ".".to_string());
+    let path = PathBuf::from(git_dir).join(file_name);
+    fs::write(&path, text)?;
+
+    let editor = run_git(&["var", "GIT_EDITOR"])
+        .or_else(|| env::var("VISUAL").ok())
+        .or_else(|| env::var("EDITOR").ok())
+        .unwrap_or_else(|| "vi".to_string());
+    debug_log!("[DEBUG] Opening editor: {}", editor);
+
+    // Run through the shell so editors configured with arguments (`code --wait`) work
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", editor))
+        .arg("sh")
+        .arg(&path)
+        .status()?;
+    if !status.success() {
+        return Err("Editor exited with an error; aborting".into());
+    }
+
+    let edited = fs::read_to_string(&path)?;
+    let _ = fs::remove_file(&path);
+    Ok(edited)
+}
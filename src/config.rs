@@ -0,0 +1,800 @@
+// Layered configuration for `git-pr`.
+//
+// Settings are resolved from (lowest to highest precedence):
+//   1. Built-in defaults
+//   2. The global config file (`~/.config/git-pr/config.toml`)
+//   3. The repository's `.git/config`, under the `git-pr` section
+//   4. `GIT_PR_<KEY>` environment variables (`GIT_PR_REMOTE`, `GIT_PR_PAGE_SIZE`, ...; the
+//      dots of views, merge policies and default flags become `__`, as in
+//      `GIT_PR_DEFAULTS__LIST__LIMIT`)
+//
+// Command-line flags such as `--timestamps` beat all of them. The environment layer lets CI
+// pipelines configure git-pr without writing any file.
+//
+// The per-repo layer means repo-specific behavior (e.g. "this clone talks to
+// `upstream`, not `origin`") travels with the clone and never leaks into other repos.
+use crate::columns;
+use crate::debug_log;
+use crate::i18n;
+use crate::links::HYPERLINK_MODES;
+use crate::policy::MergePolicy;
+use crate::providers::methods::MERGE_METHODS;
+use crate::tracker::TRACKERS;
+use crate::ui_eprintln;
+use crate::utils::{
+    format_timestamp, is_valid_date_format, parse_duration, parse_size, run_git, split_args,
+    DEFAULT_DATE_FORMAT, TIMESTAMP_STYLES,
+};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Every configuration key understood by `git-pr`.
+///
+/// Keys are spelled identically in the global TOML file and in `git config`
+/// (e.g. `git config git-pr.branch-template "pr/{number}"`).
+pub const KNOWN_KEYS: &[&str] = &[
+    "remote",
+    "base-url",
+    "merge-strategy",
+    "branch-template",
+    "describe-template",
+    "timestamps",
+    "date-format",
+    "ignore-paths",
+    "max-file-size",
+    "sensitive-paths",
+    "owned-paths",
+    "welcome-template",
+    "list-columns",
+    "review-sla",
+    "review-sla-warn",
+    "review-rotation",
+    "review-unavailable",
+    "review-rotation-last",
+    "hyperlinks",
+    "attachment-command",
+    "locale",
+    "submodule-fetch",
+    "page-size",
+    "read-only",
+    "coverage-artifact",
+    "coverage-url",
+    "tracker-url",
+    "tracker",
+    "high-risk-repos",
+    "cache-ttl",
+];
+
+/// Largest page GitHub serves on its list endpoints (`per_page`).
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Prefix of named `list` views (`view.<name>`), which aren't part of [`KNOWN_KEYS`].
+pub const VIEW_PREFIX: &str = "view.";
+
+/// Prefix of merge policies (`merge-policy.<name>`, see `policy.rs`), which aren't part of
+/// [`KNOWN_KEYS`] either.
+pub const POLICY_PREFIX: &str = "merge-policy.";
+
+/// Prefix of per-command default flags (`defaults.<command>.<flag>`), which aren't part of
+/// [`KNOWN_KEYS`] either.
+pub const DEFAULTS_PREFIX: &str = "defaults.";
+
+/// The section name used in `.git/config`.
+const GIT_SECTION: &str = "git-pr";
+
+/// Prefix of the environment variables overriding config keys (`GIT_PR_<KEY>`).
+const ENV_PREFIX: &str = "GIT_PR_";
+
+/// What the dots of prefixed keys become in environment variable names.
+const ENV_DOT: &str = "__";
+
+/// Environment variable overriding `base-url` (mock servers, proxies, `git pr selftest`).
+pub const API_BASE_ENV: &str = "GIT_PR_API_BASE";
+
+/// Resolved configuration values.
+///
+/// Every field is optional; accessors such as [`Config::remote`] apply the built-in default.
+///
+/// Fields:
+/// - `remote`: Name of the git remote that points at the provider (defaults to `origin`)
+/// - `base_url`: API base URL, for GitHub Enterprise (defaults to `https://api.github.com`)
+/// - `merge_strategy`: Preferred merge method (`merge`, `squash` or `rebase`)
+/// - `branch_template`: Template for local branch names created by `git pr pull`
+/// - `describe_template`: Template file (or inline text) used by `git pr describe`
+/// - `timestamps`: How times are displayed (`relative`, `absolute` or `iso`)
+/// - `date_format`: strftime-style format for absolute times (e.g. `%d.%m.%Y %H:%M`)
+/// - `ignore_paths`: Comma-separated patterns of generated files hidden from diffs and
+///   size metrics (replaces the built-in list of lockfiles and vendored directories)
+/// - `max_file_size`: Size above which `git pr checks` flags changed files (e.g. `5MB`)
+/// - `sensitive_paths`: Comma-separated patterns of security-sensitive paths flagged by
+///   `show-details` and `list` (replaces the built-in list)
+/// - `owned_paths`: Comma-separated patterns of paths `list --owned-by-me` counts as yours,
+///   on top of what CODEOWNERS assigns to you
+/// - `welcome_template`: Template file (or inline text) of the comment posted by `git pr welcome`
+/// - `list_columns`: Comma-separated columns of the `list` and `search` tables, in order
+/// - `review_sla`: How long a PR may await review before it is past its SLA (e.g. `48h`)
+/// - `review_sla_warn`: How long a PR may await review before it is flagged as aging
+/// - `review_rotation`: Comma-separated logins `git pr assign-next` takes turns between
+/// - `review_unavailable`: Comma-separated logins skipped by the rotation (vacation, on call)
+/// - `review_rotation_last`: The login `assign-next` assigned last; written by the command
+/// - `hyperlinks`: When tables link to the web UI (`auto`, `always` or `never`)
+/// - `attachment_command`: Command uploading a `comment --attach` file (its argument) and
+///   printing its URL, instead of the repository's attachment branch
+/// - `locale`: Language of messages, help and table headers (e.g. `de`); defaults to `LANG`
+/// - `submodule_fetch`: Whether `show-diff` fetches a submodule's remote to list the commits
+///   of a bump it doesn't have locally (`true` or `false`, the default)
+/// - `page_size`: Items per request on paged endpoints (PR lists, changed files, commits),
+///   1 to 100 (the default and GitHub's maximum)
+/// - `read_only`: Whether every change on the provider is refused, as with `--read-only`
+///   (`true` or `false`, the default)
+/// - `coverage_artifact`: Name of the Actions artifact holding the coverage report
+/// - `coverage_url`: URL template of a coverage service's report for a commit, with
+///   `{owner}`, `{repo}`, `{sha}` and `{pr}`
+/// - `tracker_url`: Base URL of the issue tracker tickets link to (e.g.
+///   `https://acme.atlassian.net` or `https://linear.app/acme`)
+/// - `tracker`: Which tracker `tracker_url` is (`jira` or `linear`); guessed from the URL
+/// - `high_risk_repos`: Comma-separated `owner/repo` patterns (`*` matches any run of
+///   characters) where merging or closing a PR asks to retype its number
+/// - `cache_ttl`: How long fetched PR data is reused from the local cache (e.g. `10m`);
+///   nothing is cached when unset
+/// - `views`: Named `list` filters (`view.<name>` → `list` arguments), used by `list --view`
+/// - `merge_policies`: Merge policies by name (`merge-policy.<name>` → branch pattern and
+///   requirements), checked by `merge`
+/// - `defaults`: Default flags per command (command → flag → value), put in front of the
+///   flags given on the command line
+#[derive(Default, Clone, Debug)]
+pub struct Config {
+    pub remote: Option<String>,
+    pub base_url: Option<String>,
+    pub merge_strategy: Option<String>,
+    pub branch_template: Option<String>,
+    pub describe_template: Option<String>,
+    pub timestamps: Option<String>,
+    pub date_format: Option<String>,
+    pub ignore_paths: Option<String>,
+    pub max_file_size: Option<String>,
+    pub sensitive_paths: Option<String>,
+    pub owned_paths: Option<String>,
+    pub welcome_template: Option<String>,
+    pub list_columns: Option<String>,
+    pub review_sla: Option<String>,
+    pub review_sla_warn: Option<String>,
+    pub review_rotation: Option<String>,
+    pub review_unavailable: Option<String>,
+    pub review_rotation_last: Option<String>,
+    pub hyperlinks: Option<String>,
+    pub attachment_command: Option<String>,
+    pub locale: Option<String>,
+    pub submodule_fetch: Option<String>,
+    pub page_size: Option<String>,
+    pub read_only: Option<String>,
+    pub coverage_artifact: Option<String>,
+    pub coverage_url: Option<String>,
+    pub tracker_url: Option<String>,
+    pub tracker: Option<String>,
+    pub high_risk_repos: Option<String>,
+    pub cache_ttl: Option<String>,
+    pub views: BTreeMap<String, String>,
+    pub merge_policies: BTreeMap<String, String>,
+    pub defaults: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Config {
+    /// Loads the configuration by layering the global file, the repo's git config, the
+    /// `GIT_PR_<KEY>` environment variables and `GIT_PR_API_BASE`.
+    ///
+    /// Missing or unreadable sources are silently skipped, so `git-pr` always works
+    /// without any configuration at all. Invalid values from the environment are ignored
+    /// with a warning, since there is no `config set` to reject them.
+    pub fn load() -> Self {
+        let mut config = Config::default();
+
+        // Layer 1: global config file
+        for (key, value) in read_global_values() {
+            config.apply(&key, value);
+        }
+
+        // Layer 2: `git-pr.*` entries from the repository's git config
+        for (key, value) in read_repo_values() {
+            config.apply(&key, value);
+        }
+
+        // Layer 3: `GIT_PR_<KEY>` environment variables
+        for key in KNOWN_KEYS {
+            let name = env_name(key);
+            let Ok(value) = env::var(&name) else {
+                continue;
+            };
+            if let Err(e) = validate_value(key, &value) {
+                ui_eprintln!("⚠️  Ignoring {}: {}", name, e);
+                continue;
+            }
+            debug_log!("[DEBUG] {} overrides {}", name, key);
+            config.apply(key, value);
+        }
+
+        // ... and the prefixed keys, which can't be listed up front
+        let mut prefixed: Vec<(String, String, String)> = env::vars()
+            .filter_map(|(name, value)| Some((env_key(&name)?, name, value)))
+            .collect();
+        prefixed.sort();
+        for (key, name, value) in prefixed {
+            if let Err(e) = validate_value(&key, &value) {
+                ui_eprintln!("⚠️  Ignoring {}: {}", name, e);
+                continue;
+            }
+            debug_log!("[DEBUG] {} overrides {}", name, key);
+            config.apply(&key, value);
+        }
+
+        // The environment points every provider at another endpoint
+        if let Ok(base) = env::var(API_BASE_ENV) {
+            if !base.is_empty() {
+                config.apply("base-url", base);
+            }
+        }
+
+        debug_log!("[DEBUG] Loaded config: {:?}", config);
+        config
+    }
+
+    /// Name of the remote to talk to, falling back to `origin`.
+    pub fn remote(&self) -> &str {
+        self.remote.as_deref().unwrap_or("origin")
+    }
+
+    /// How times are displayed, falling back to `relative`.
+    pub fn timestamps(&self) -> &str {
+        self.timestamps.as_deref().unwrap_or("relative")
+    }
+
+    /// The `max-file-size` limit in bytes; unparseable values are ignored.
+    pub fn max_file_size(&self) -> Option<u64> {
+        self.max_file_size
+            .as_deref()
+            .and_then(|v| parse_size(v).ok())
+    }
+
+    /// Whether `show-diff` may fetch submodules (`submodule-fetch`), off by default.
+    pub fn submodule_fetch(&self) -> bool {
+        self.submodule_fetch.as_deref() == Some("true")
+    }
+
+    /// Items per request on paged endpoints (`page-size`), falling back to GitHub's maximum
+    /// of 100; values out of range are ignored.
+    pub fn page_size(&self) -> u32 {
+        self.page_size
+            .as_deref()
+            .and_then(|v| parse_page_size(v).ok())
+            .unwrap_or(MAX_PAGE_SIZE)
+    }
+
+    /// Which issue tracker `tracker-url` points at: the `tracker` setting, otherwise
+    /// `linear` for linear.app URLs and `jira` for everything else.
+    pub fn tracker(&self) -> &str {
+        match (&self.tracker, &self.tracker_url) {
+            (Some(tracker), _) => tracker,
+            (None, Some(url)) if url.contains("linear.app") => "linear",
+            _ => "jira",
+        }
+    }
+
+    /// Whether changes are refused (`read-only`), off by default.
+    pub fn read_only(&self) -> bool {
+        self.read_only.as_deref() == Some("true")
+    }
+
+    /// Whether `owner/repo` matches one of the `high-risk-repos` patterns, case-insensitively.
+    pub fn is_high_risk(&self, owner: &str, repo: &str) -> bool {
+        let full_name = format!("{}/{}", owner, repo).to_lowercase();
+        self.high_risk_repos
+            .as_deref()
+            .unwrap_or("")
+            .split(',')
+            .map(|pattern| pattern.trim().to_lowercase())
+            .filter(|pattern| !pattern.is_empty())
+            .any(|pattern| {
+                let pattern = pattern
+                    .split('*')
+                    .map(regex::escape)
+                    .collect::<Vec<_>>()
+                    .join(".*");
+                Regex::new(&format!("^{}$", pattern)).is_ok_and(|re| re.is_match(&full_name))
+            })
+    }
+
+    /// The `review-sla` and `review-sla-warn` thresholds in seconds, as `(warn, breach)`;
+    /// unparseable values are ignored.
+    pub fn review_sla(&self) -> (Option<u64>, Option<u64>) {
+        let seconds = |v: &Option<String>| v.as_deref().and_then(|v| parse_duration(v).ok());
+        (seconds(&self.review_sla_warn), seconds(&self.review_sla))
+    }
+
+    /// The `review-rotation` logins, in order (a leading `@` is dropped).
+    pub fn review_rotation(&self) -> Vec<String> {
+        logins(self.review_rotation.as_deref())
+    }
+
+    /// The `review-unavailable` logins (a leading `@` is dropped).
+    pub fn review_unavailable(&self) -> Vec<String> {
+        logins(self.review_unavailable.as_deref())
+    }
+
+    /// The `cache-ttl` in seconds; `None` (nothing cached) when unset or unparseable.
+    pub fn cache_ttl(&self) -> Option<u64> {
+        self.cache_ttl
+            .as_deref()
+            .and_then(|v| parse_duration(v).ok())
+    }
+
+    /// Formats an API timestamp using the `timestamps` style and `date-format`.
+    pub fn format_time(&self, time: DateTime<Utc>) -> String {
+        format_timestamp(
+            time,
+            self.timestamps(),
+            self.date_format.as_deref().unwrap_or(DEFAULT_DATE_FORMAT),
+        )
+    }
+
+    /// Returns the resolved value of a configuration key, if set.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        match key {
+            "remote" => self.remote.as_deref(),
+            "base-url" => self.base_url.as_deref(),
+            "merge-strategy" => self.merge_strategy.as_deref(),
+            "branch-template" => self.branch_template.as_deref(),
+            "describe-template" => self.describe_template.as_deref(),
+            "timestamps" => self.timestamps.as_deref(),
+            "date-format" => self.date_format.as_deref(),
+            "ignore-paths" => self.ignore_paths.as_deref(),
+            "max-file-size" => self.max_file_size.as_deref(),
+            "sensitive-paths" => self.sensitive_paths.as_deref(),
+            "owned-paths" => self.owned_paths.as_deref(),
+            "welcome-template" => self.welcome_template.as_deref(),
+            "list-columns" => self.list_columns.as_deref(),
+            "review-sla" => self.review_sla.as_deref(),
+            "review-sla-warn" => self.review_sla_warn.as_deref(),
+            "review-rotation" => self.review_rotation.as_deref(),
+            "review-unavailable" => self.review_unavailable.as_deref(),
+            "review-rotation-last" => self.review_rotation_last.as_deref(),
+            "hyperlinks" => self.hyperlinks.as_deref(),
+            "attachment-command" => self.attachment_command.as_deref(),
+            "locale" => self.locale.as_deref(),
+            "submodule-fetch" => self.submodule_fetch.as_deref(),
+            "page-size" => self.page_size.as_deref(),
+            "read-only" => self.read_only.as_deref(),
+            "coverage-artifact" => self.coverage_artifact.as_deref(),
+            "coverage-url" => self.coverage_url.as_deref(),
+            "tracker-url" => self.tracker_url.as_deref(),
+            "tracker" => self.tracker.as_deref(),
+            "high-risk-repos" => self.high_risk_repos.as_deref(),
+            _ if is_defaults_key(key) => {
+                let (command, flag) = split_defaults_key(key);
+                self.defaults
+                    .get(command)
+                    .and_then(|flags| flags.get(flag))
+                    .map(String::as_str)
+            }
+            _ if is_policy_key(key) => self
+                .merge_policies
+                .get(&key[POLICY_PREFIX.len()..])
+                .map(String::as_str),
+            "cache-ttl" => self.cache_ttl.as_deref(),
+            _ => key
+                .strip_prefix(VIEW_PREFIX)
+                .and_then(|name| self.views.get(name))
+                .map(String::as_str),
+        }
+    }
+
+    /// Stores a value for the given key. Unknown keys are ignored (with a debug note)
+    /// so that newer config files don't break older binaries.
+    fn apply(&mut self, key: &str, value: String) {
+        match key {
+            "remote" => self.remote = Some(value),
+            "base-url" => self.base_url = Some(value),
+            "merge-strategy" => self.merge_strategy = Some(value),
+            "branch-template" => self.branch_template = Some(value),
+            "describe-template" => self.describe_template = Some(value),
+            "timestamps" => self.timestamps = Some(value),
+            "date-format" => self.date_format = Some(value),
+            "ignore-paths" => self.ignore_paths = Some(value),
+            "max-file-size" => self.max_file_size = Some(value),
+            "sensitive-paths" => self.sensitive_paths = Some(value),
+            "owned-paths" => self.owned_paths = Some(value),
+            "welcome-template" => self.welcome_template = Some(value),
+            "list-columns" => self.list_columns = Some(value),
+            "review-sla" => self.review_sla = Some(value),
+            "review-sla-warn" => self.review_sla_warn = Some(value),
+            "review-rotation" => self.review_rotation = Some(value),
+            "review-unavailable" => self.review_unavailable = Some(value),
+            "review-rotation-last" => self.review_rotation_last = Some(value),
+            "hyperlinks" => self.hyperlinks = Some(value),
+            "attachment-command" => self.attachment_command = Some(value),
+            "locale" => self.locale = Some(value),
+            "submodule-fetch" => self.submodule_fetch = Some(value),
+            "page-size" => self.page_size = Some(value),
+            "read-only" => self.read_only = Some(value),
+            "coverage-artifact" => self.coverage_artifact = Some(value),
+            "coverage-url" => self.coverage_url = Some(value),
+            "tracker-url" => self.tracker_url = Some(value),
+            "tracker" => self.tracker = Some(value),
+            "high-risk-repos" => self.high_risk_repos = Some(value),
+            "cache-ttl" => self.cache_ttl = Some(value),
+            _ if is_view_key(key) => {
+                self.views
+                    .insert(key[VIEW_PREFIX.len()..].to_string(), value);
+            }
+            _ if is_policy_key(key) => {
+                self.merge_policies
+                    .insert(key[POLICY_PREFIX.len()..].to_string(), value);
+            }
+            _ if is_defaults_key(key) => {
+                let (command, flag) = split_defaults_key(key);
+                self.defaults
+                    .entry(command.to_string())
+                    .or_default()
+                    .insert(flag.to_string(), value);
+            }
+            _ => debug_log!("[DEBUG] Ignoring unknown config key: {}", key),
+        }
+    }
+}
+
+/// The environment variable overriding a config key: `page-size` is `GIT_PR_PAGE_SIZE`,
+/// `defaults.submit-review.approve` is `GIT_PR_DEFAULTS__SUBMIT_REVIEW__APPROVE`.
+pub fn env_name(key: &str) -> String {
+    format!(
+        "{}{}",
+        ENV_PREFIX,
+        key.to_uppercase().replace('-', "_").replace('.', ENV_DOT)
+    )
+}
+
+/// The view, merge policy or default flag an environment variable overrides, if any (the
+/// reverse of [`env_name`]; names come back with `-` for `_`).
+pub fn env_key(name: &str) -> Option<String> {
+    let rest = name.strip_prefix(ENV_PREFIX)?;
+    if !rest.contains(ENV_DOT) {
+        return None;
+    }
+    let key = rest
+        .split(ENV_DOT)
+        .map(|part| part.to_lowercase().replace('_', "-"))
+        .collect::<Vec<_>>()
+        .join(".");
+    (is_view_key(&key) || is_policy_key(&key) || is_defaults_key(&key)).then_some(key)
+}
+
+/// Splits a comma-separated list of logins.
+fn logins(list: Option<&str>) -> Vec<String> {
+    list.unwrap_or("")
+        .split(',')
+        .map(|login| login.trim().trim_start_matches('@').to_string())
+        .filter(|login| !login.is_empty())
+        .collect()
+}
+
+/// Location of the global config file.
+///
+/// Honors `XDG_CONFIG_HOME`, otherwise falls back to `~/.config/git-pr/config.toml`.
+pub fn global_config_path() -> Option<PathBuf> {
+    let base = match env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var("HOME").ok()?).join(".config"),
+    };
+    Some(base.join("git-pr").join("config.toml"))
+}
+
+/// Reads and parses the global TOML file into a table, if present.
+pub fn read_global_table() -> toml::Table {
+    let Some(path) = global_config_path() else {
+        return toml::Table::new();
+    };
+
+    match fs::read_to_string(&path) {
+        Ok(text) => text.parse::<toml::Table>().unwrap_or_else(|e| {
+            ui_eprintln!(
+                "⚠️  Ignoring malformed config file {}: {}",
+                path.display(),
+                e
+            );
+            toml::Table::new()
+        }),
+        Err(_) => toml::Table::new(),
+    }
+}
+
+/// Checks that the global config file, if present, is valid TOML.
+///
+/// # Returns
+/// - `Ok(Some(path))` if the file exists and parses
+/// - `Ok(None)` if there is no global config file
+/// - `Err(message)` naming the file and the parse error otherwise
+pub fn check_global_file() -> Result<Option<PathBuf>, String> {
+    let Some(path) = global_config_path() else {
+        return Ok(None);
+    };
+    let Ok(text) = fs::read_to_string(&path) else {
+        return Ok(None);
+    };
+    match text.parse::<toml::Table>() {
+        Ok(_) => Ok(Some(path)),
+        Err(e) => Err(format!(
+            "{}: {}",
+            path.display(),
+            e.message().trim().replace('\n', ", ")
+        )),
+    }
+}
+
+/// Whether `key` names a `list` view (`view.<name>`).
+pub fn is_view_key(key: &str) -> bool {
+    key.strip_prefix(VIEW_PREFIX)
+        .is_some_and(|name| !name.is_empty() && !name.contains('.'))
+}
+
+/// Whether `key` names a merge policy (`merge-policy.<name>`).
+pub fn is_policy_key(key: &str) -> bool {
+    key.strip_prefix(POLICY_PREFIX)
+        .is_some_and(|name| !name.is_empty() && !name.contains('.'))
+}
+
+/// Whether `key` names a default flag of a command (`defaults.<command>.<flag>`).
+pub fn is_defaults_key(key: &str) -> bool {
+    key.strip_prefix(DEFAULTS_PREFIX)
+        .and_then(|rest| rest.split_once('.'))
+        .is_some_and(|(command, flag)| {
+            !command.is_empty() && !flag.is_empty() && !flag.contains('.')
+        })
+}
+
+/// Splits a `defaults.<command>.<flag>` key into the command and the flag.
+fn split_defaults_key(key: &str) -> (&str, &str) {
+    key[DEFAULTS_PREFIX.len()..]
+        .split_once('.')
+        .expect("checked by is_defaults_key")
+}
+
+/// A default flag's value as text; TOML numbers and booleans are taken as they're written
+/// (`limit = 100`, `draft = true`).
+fn default_value(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(v) => Some(v.clone()),
+        toml::Value::Integer(v) => Some(v.to_string()),
+        toml::Value::Float(v) => Some(v.to_string()),
+        toml::Value::Boolean(v) => Some(v.to_string()),
+        _ => None,
+    }
+}
+
+/// Extracts the string values from the global config file.
+///
+/// Top-level strings map to their key; strings in a table map to `table.key`, so a
+/// `[view]` table holds the `view.<name>` settings (and `[merge-policy]` the
+/// `merge-policy.<name>` ones). `[defaults.<command>]` tables map to
+/// `defaults.<command>.<flag>`.
+fn read_global_values() -> BTreeMap<String, String> {
+    let mut values = BTreeMap::new();
+    for (key, value) in read_global_table() {
+        match value {
+            toml::Value::String(v) => {
+                values.insert(key, v);
+            }
+            toml::Value::Table(commands) if key == "defaults" => {
+                for (command, flags) in commands {
+                    let Some(flags) = flags.as_table() else {
+                        continue;
+                    };
+                    for (flag, v) in flags {
+                        if let Some(v) = default_value(v) {
+                            values.insert(format!("{}{}.{}", DEFAULTS_PREFIX, command, flag), v);
+                        }
+                    }
+                }
+            }
+            toml::Value::Table(table) => {
+                for (sub, v) in table {
+                    if let Some(v) = v.as_str() {
+                        values.insert(format!("{}.{}", key, sub), v.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    values
+}
+
+/// Reads every `git-pr.*` entry from the repository's git config.
+///
+/// Uses `git config --local` so that only the clone-specific values are returned;
+/// user-wide settings belong in the global TOML file.
+fn read_repo_values() -> BTreeMap<String, String> {
+    let Some(output) = run_git(&["config", "--local", "--get-regexp", r"^git-pr\."]) else {
+        return BTreeMap::new();
+    };
+
+    output
+        .lines()
+        .filter_map(|line| {
+            // Each line looks like: `git-pr.branch-template pr/{number}`
+            let (name, value) = line.split_once(' ')?;
+            let key = name.strip_prefix(&format!("{}.", GIT_SECTION))?;
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Parses a `page-size` value, which GitHub caps at [`MAX_PAGE_SIZE`].
+fn parse_page_size(value: &str) -> Result<u32, String> {
+    match value.trim().parse::<u32>() {
+        Ok(size) if (1..=MAX_PAGE_SIZE).contains(&size) => Ok(size),
+        _ => Err(format!(
+            "Invalid page-size '{}'. Expected a number from 1 to {}",
+            value, MAX_PAGE_SIZE
+        )),
+    }
+}
+
+/// Rejects values that would only fail later, when the setting is used.
+fn validate_value(key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+    match key {
+        "timestamps" if !TIMESTAMP_STYLES.contains(&value) => Err(format!(
+            "Invalid timestamps style '{}'. Expected one of: {}",
+            value,
+            TIMESTAMP_STYLES.join(", ")
+        )
+        .into()),
+        "date-format" if !is_valid_date_format(value) => Err(format!(
+            "Invalid date-format '{}' (expected strftime, e.g. %Y-%m-%d)",
+            value
+        )
+        .into()),
+        "merge-strategy" if !MERGE_METHODS.contains(&value) => Err(format!(
+            "Invalid merge-strategy '{}'. Expected one of: {}",
+            value,
+            MERGE_METHODS.join(", ")
+        )
+        .into()),
+        "tracker" if !TRACKERS.contains(&value) => Err(format!(
+            "Invalid tracker '{}'. Expected one of: {}",
+            value,
+            TRACKERS.join(", ")
+        )
+        .into()),
+        "tracker-url" if !value.starts_with("http://") && !value.starts_with("https://") => {
+            Err(format!("Invalid tracker-url '{}' (expected an http(s) URL)", value).into())
+        }
+        "hyperlinks" if !HYPERLINK_MODES.contains(&value) => Err(format!(
+            "Invalid hyperlinks mode '{}'. Expected one of: {}",
+            value,
+            HYPERLINK_MODES.join(", ")
+        )
+        .into()),
+        "locale" => {
+            let id = i18n::parse_locale(value)?;
+            let languages = i18n::languages();
+            if languages.contains(&id.language.as_str()) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "No messages in '{}' yet. Available languages: {}",
+                    value,
+                    languages.join(", ")
+                )
+                .into())
+            }
+        }
+        "submodule-fetch" | "read-only" if value != "true" && value != "false" => {
+            Err(format!("Invalid {} '{}'. Expected true or false", key, value).into())
+        }
+        "page-size" => parse_page_size(value).map(|_| ()).map_err(|e| e.into()),
+        "max-file-size" => parse_size(value).map(|_| ()).map_err(|e| e.into()),
+        "list-columns" => columns::parse_columns(value)
+            .map(|_| ())
+            .map_err(|e| e.into()),
+        "review-sla" | "review-sla-warn" | "cache-ttl" => {
+            parse_duration(value).map(|_| ()).map_err(|e| e.into())
+        }
+        _ if is_view_key(key) => split_args(value).map(|_| ()).map_err(|e| e.into()),
+        _ if is_policy_key(key) => MergePolicy::parse(&key[POLICY_PREFIX.len()..], value)
+            .map(|_| ())
+            .map_err(|e| e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Persists a configuration value.
+///
+/// - With `global = false` the value is written to `.git/config` (`git config git-pr.<key>`).
+/// - With `global = true` the value is written to the global TOML file.
+pub fn set_value(key: &str, value: &str, global: bool) -> Result<(), Box<dyn Error>> {
+    if !KNOWN_KEYS.contains(&key)
+        && !is_view_key(key)
+        && !is_policy_key(key)
+        && !is_defaults_key(key)
+    {
+        return Err(format!(
+            "Unknown config key '{}'. Known keys: {}, view.<name>, merge-policy.<name>, defaults.<command>.<flag>",
+            key,
+            KNOWN_KEYS.join(", ")
+        )
+        .into());
+    }
+    validate_value(key, value)?;
+
+    if global {
+        let path = global_config_path().ok_or("Could not determine config directory")?;
+        let mut table = read_global_table();
+        let named = match key.strip_prefix(VIEW_PREFIX) {
+            Some(name) => Some(("view", name)),
+            None if is_policy_key(key) => Some(("merge-policy", &key[POLICY_PREFIX.len()..])),
+            None => None,
+        };
+        match named {
+            // Default flags live in `[defaults.<command>]` tables
+            None if is_defaults_key(key) => {
+                let (command, flag) = split_defaults_key(key);
+                let mut current = &mut table;
+                for name in ["defaults", command] {
+                    let entry = current
+                        .entry(name)
+                        .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+                    if !entry.is_table() {
+                        *entry = toml::Value::Table(toml::Table::new());
+                    }
+                    current = entry.as_table_mut().expect("made a table above");
+                }
+                current.insert(flag.to_string(), toml::Value::String(value.to_string()));
+            }
+            // Views and merge policies live in `[view]` and `[merge-policy]` tables
+            Some((section, name)) => {
+                let entries = table
+                    .entry(section)
+                    .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+                if !entries.is_table() {
+                    *entries = toml::Value::Table(toml::Table::new());
+                }
+                if let Some(entries) = entries.as_table_mut() {
+                    entries.insert(name.to_string(), toml::Value::String(value.to_string()));
+                }
+            }
+            None => {
+                table.insert(key.to_string(), toml::Value::String(value.to_string()));
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, toml::to_string_pretty(&table)?)?;
+        debug_log!("[DEBUG] Wrote {}={} to {}", key, value, path.display());
+    } else {
+        let status = Command::new("git")
+            .args([
+                "config",
+                "--local",
+                &format!("{}.{}", GIT_SECTION, key),
+                value,
+            ])
+            .status()?;
+        if !status.success() {
+            return Err("Failed to write git config (are you inside a git repository?)".into());
+        }
+        debug_log!(
+            "[DEBUG] Wrote {}.{}={} to .git/config",
+            GIT_SECTION,
+            key,
+            value
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,331 @@
+// Changed-lines coverage for `git pr coverage`.
+//
+// CI uploads a coverage report (LCOV `.info` or Cobertura XML); what a reviewer wants to
+// know is whether the lines this PR adds are run by the tests. The report is reduced to
+// line → hit count per file, the PR's patches to the added line numbers per file, and the
+// two are matched up. Lines the report doesn't list (comments, blank lines, declarations)
+// aren't coverable and don't count either way.
+//
+// Report paths are often absolute paths of the CI machine
+// (`/home/runner/work/api/api/src/lib.rs`) or relative to a source root, so a changed file
+// matches a report path equal to it or ending in `/<path>`. When several end that way (a
+// vendored copy of the file, say), the shortest one wins, so the choice doesn't depend on
+// the report's order.
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::io::{Cursor, Read};
+
+/// Line number → hit count, per file of a coverage report.
+pub struct Coverage {
+    files: HashMap<String, BTreeMap<u32, u64>>,
+}
+
+/// How well the lines a PR adds to one file are covered.
+///
+/// Fields:
+/// - `path`: Repo-relative path of the file
+/// - `coverable`: Added lines the report knows about
+/// - `covered`: Of those, the lines hit at least once
+/// - `uncovered`: The added lines never hit, ascending
+pub struct FileCoverage {
+    pub path: String,
+    pub coverable: usize,
+    pub covered: usize,
+    pub uncovered: Vec<u32>,
+}
+
+impl Coverage {
+    /// Parses an LCOV or Cobertura report, telling them apart by content.
+    ///
+    /// # Returns
+    /// - `Err` if the text is neither, or lists no lines at all
+    pub fn parse(text: &str) -> Result<Coverage, Box<dyn Error>> {
+        let files = if text.trim_start().starts_with('<') {
+            parse_cobertura(text)
+        } else {
+            parse_lcov(text)
+        };
+        if files.values().all(BTreeMap::is_empty) {
+            return Err("Not an LCOV or Cobertura coverage report (no covered lines found)".into());
+        }
+        Ok(Coverage { files })
+    }
+
+    /// The line hits recorded for a repo-relative path (see the module comment).
+    fn lines_of(&self, path: &str) -> Option<&BTreeMap<u32, u64>> {
+        self.files.get(path).or_else(|| {
+            let suffix = format!("/{}", path);
+            self.files
+                .iter()
+                .filter(|(reported, _)| reported.ends_with(&suffix))
+                .min_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+                .map(|(_, lines)| lines)
+        })
+    }
+
+    /// Matches the lines a PR adds to a file against the report.
+    ///
+    /// # Returns
+    /// - `None` if the report doesn't cover the file at all
+    pub fn of_changes(&self, path: &str, added: &[u32]) -> Option<FileCoverage> {
+        let lines = self.lines_of(path)?;
+        let mut result = FileCoverage {
+            path: path.to_string(),
+            coverable: 0,
+            covered: 0,
+            uncovered: Vec::new(),
+        };
+        for line in added {
+            match lines.get(line) {
+                Some(0) => {
+                    result.coverable += 1;
+                    result.uncovered.push(*line);
+                }
+                Some(_) => {
+                    result.coverable += 1;
+                    result.covered += 1;
+                }
+                None => {}
+            }
+        }
+        Some(result)
+    }
+}
+
+/// Reads LCOV: `SF:<path>` opens a file, `DA:<line>,<hits>` records a line.
+fn parse_lcov(text: &str) -> HashMap<String, BTreeMap<u32, u64>> {
+    let mut files: HashMap<String, BTreeMap<u32, u64>> = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(path) = line.strip_prefix("SF:") {
+            let path = path.replace('\\', "/");
+            files.entry(path.clone()).or_default();
+            current = Some(path);
+        } else if let Some(data) = line.strip_prefix("DA:") {
+            let mut fields = data.split(',');
+            let (Some(number), Some(hits)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let (Ok(number), Ok(hits)) = (number.parse::<u32>(), hits.parse::<f64>()) else {
+                continue;
+            };
+            if let Some(path) = &current {
+                *files
+                    .entry(path.clone())
+                    .or_default()
+                    .entry(number)
+                    .or_default() += hits as u64;
+            }
+        } else if line == "end_of_record" {
+            current = None;
+        }
+    }
+    files
+}
+
+/// Reads Cobertura XML: `<class filename="...">` elements with `<line number hits>` in them.
+///
+/// Only those two elements matter, so they're picked out with patterns rather than a full
+/// XML parser. Filenames are relative to a `<source>`, which the suffix match makes moot.
+fn parse_cobertura(text: &str) -> HashMap<String, BTreeMap<u32, u64>> {
+    let class = Regex::new(r#"<class\b[^>]*\bfilename="([^"]*)""#).expect("valid regex");
+    let line = Regex::new(r#"<line\b[^>]*>"#).expect("valid regex");
+    let attr = |tag: &str, name: &str| {
+        Regex::new(&format!(r#"\b{}="([^"]*)""#, name))
+            .expect("valid regex")
+            .captures(tag)
+            .map(|c| c[1].to_string())
+    };
+
+    let mut files: HashMap<String, BTreeMap<u32, u64>> = HashMap::new();
+    let starts: Vec<(usize, String)> = class
+        .captures_iter(text)
+        .map(|c| {
+            (
+                c.get(0).expect("whole match").start(),
+                c[1].replace('\\', "/"),
+            )
+        })
+        .collect();
+    for (i, (start, path)) in starts.iter().enumerate() {
+        let end = starts.get(i + 1).map_or(text.len(), |(next, _)| *next);
+        let lines = files.entry(path.clone()).or_default();
+        for tag in line.find_iter(&text[*start..end]) {
+            let number = attr(tag.as_str(), "number").and_then(|n| n.parse::<u32>().ok());
+            let hits = attr(tag.as_str(), "hits").and_then(|h| h.parse::<u64>().ok());
+            if let (Some(number), Some(hits)) = (number, hits) {
+                *lines.entry(number).or_default() += hits;
+            }
+        }
+    }
+    files
+}
+
+/// Whether a file name looks like a coverage report (`lcov.info`, `coverage.xml`, ...).
+pub fn is_report_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.ends_with(".info")
+        || name.ends_with(".lcov")
+        || (name.ends_with(".xml") && (name.contains("cov") || name.contains("cobertura")))
+}
+
+/// Finds and parses the first coverage report inside a zip archive (a CI artifact).
+///
+/// # Returns
+/// - `Err` if the archive can't be read or holds no parseable report
+pub fn from_zip(bytes: &[u8]) -> Result<Coverage, Box<dyn Error>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+    let mut names: Vec<String> = archive
+        .file_names()
+        .filter(|name| is_report_name(name))
+        .map(str::to_string)
+        .collect();
+    names.sort();
+    for name in &names {
+        let mut text = String::new();
+        if archive.by_name(name)?.read_to_string(&mut text).is_err() {
+            continue;
+        }
+        if let Ok(coverage) = Coverage::parse(&text) {
+            return Ok(coverage);
+        }
+    }
+    Err(format!(
+        "No LCOV or Cobertura report in the artifact (looked at: {})",
+        if names.is_empty() {
+            "no .info, .lcov or coverage .xml files".to_string()
+        } else {
+            names.join(", ")
+        }
+    )
+    .into())
+}
+
+/// The line numbers a unified-diff patch adds, in the new version of the file.
+pub fn added_lines(patch: &str) -> Vec<u32> {
+    let mut added = Vec::new();
+    let mut line = 0u32;
+    for text in patch.lines() {
+        if let Some(header) = text.strip_prefix("@@ ") {
+            // `@@ -12,7 +14,9 @@`: the new side starts at 14
+            line = header
+                .split_whitespace()
+                .find_map(|part| part.strip_prefix('+'))
+                .and_then(|range| range.split(',').next())
+                .and_then(|start| start.parse().ok())
+                .unwrap_or(0);
+        } else if text.starts_with('+') {
+            added.push(line);
+            line += 1;
+        } else if !text.starts_with('-') && !text.starts_with('\\') {
+            line += 1;
+        }
+    }
+    added
+}
+
+/// Collapses ascending line numbers into ranges: `3-5, 9, 12-13`.
+pub fn ranges(lines: &[u32]) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let start = lines[i];
+        let mut end = start;
+        while i + 1 < lines.len() && lines[i + 1] == end + 1 {
+            i += 1;
+            end = lines[i];
+        }
+        parts.push(if start == end {
+            start.to_string()
+        } else {
+            format!("{}-{}", start, end)
+        });
+        i += 1;
+    }
+    parts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LCOV: &str = "TN:
+SF:/home/runner/work/api/api/src/lib.rs
+DA:1,4
+DA:2,0
+DA:3,1
+end_of_record
+SF:/home/runner/work/api/api/vendor/fork/src/lib.rs
+DA:1,0
+DA:2,0
+end_of_record
+";
+
+    const COBERTURA: &str = r#"<?xml version="1.0" ?>
+<coverage line-rate="0.5">
+  <sources><source>/build/app</source></sources>
+  <packages><package name="app"><classes>
+    <class name="util" filename="app/util.py" line-rate="0.5">
+      <lines>
+        <line number="3" hits="2"/>
+        <line number="4" hits="0" branch="false"/>
+      </lines>
+    </class>
+    <class name="main" filename="app/main.py" line-rate="1">
+      <lines><line number="1" hits="1"/></lines>
+    </class>
+  </classes></package></packages>
+</coverage>"#;
+
+    #[test]
+    fn lcov_lines_are_matched_by_path_suffix() {
+        let coverage = Coverage::parse(LCOV).unwrap();
+        let file = coverage.of_changes("src/lib.rs", &[1, 2, 3, 9]).unwrap();
+        assert_eq!((file.coverable, file.covered), (3, 2));
+        assert_eq!(file.uncovered, [2]);
+        assert!(coverage.of_changes("lib.rs", &[1]).is_some());
+        assert!(coverage.of_changes("src/main.rs", &[1]).is_none());
+    }
+
+    #[test]
+    fn the_shortest_matching_report_path_wins() {
+        let coverage = Coverage::parse(LCOV).unwrap();
+        // Both report paths end in `/src/lib.rs`; the vendored copy is never hit
+        let file = coverage.of_changes("src/lib.rs", &[1]).unwrap();
+        assert_eq!(file.covered, 1);
+    }
+
+    #[test]
+    fn cobertura_lines_are_read_per_class() {
+        let coverage = Coverage::parse(COBERTURA).unwrap();
+        let util = coverage.of_changes("app/util.py", &[3, 4]).unwrap();
+        assert_eq!((util.coverable, util.covered), (2, 1));
+        assert_eq!(util.uncovered, [4]);
+        let main = coverage.of_changes("app/main.py", &[1]).unwrap();
+        assert_eq!((main.coverable, main.covered), (1, 1));
+    }
+
+    #[test]
+    fn other_text_is_not_a_report() {
+        assert!(Coverage::parse("just some text").is_err());
+        assert!(Coverage::parse("<html></html>").is_err());
+    }
+
+    #[test]
+    fn added_lines_follow_the_new_side_of_each_hunk() {
+        let patch = "@@ -1,3 +1,4 @@
+ fn a() {}
++fn b() {}
+ fn c() {}
+-fn d() {}
++fn e() {}
+\\ No newline at end of file
+@@ -20,2 +21,3 @@ impl X {
+ x
++y
++z";
+        assert_eq!(added_lines(patch), [2, 4, 22, 23]);
+    }
+}
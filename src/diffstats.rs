@@ -0,0 +1,164 @@
+// Change totals of a PR by directory and by file type (`git pr diff-stats`).
+//
+// Before reading hunks it helps to know where a PR's weight is: which directories carry
+// most of the change and whether it's code, tests, config or docs. The changed files are
+// summed per bucket and drawn as bars scaled to the biggest bucket, additions in green and
+// deletions in red (`+` and `-` under `--plain`):
+//
+//   src/providers  ████████████████████▆▆▆▆   +620   -98  12 files
+//   docs           ███                          +90    -2   3 files
+//
+// Directories are cut at a depth (`src/providers/github/x.rs` is `src/providers` at depth
+// 2), files in the repository root count as `.`. The file type is the extension, or the
+// whole name for files without one (`Makefile`, `.gitignore`).
+use crate::utils::is_plain;
+use colored::Colorize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Width of the longest bar, in characters.
+const BAR_WIDTH: u64 = 30;
+
+/// Buckets shown per grouping; the rest are summed up in one line.
+const MAX_ROWS: usize = 15;
+
+/// The change of one file, as far as the totals are concerned.
+///
+/// Fields:
+/// - `path`: Repo-relative path
+/// - `additions` / `deletions`: Changed line counts (0 for binary files)
+pub struct FileChange {
+    pub path: String,
+    pub additions: u64,
+    pub deletions: u64,
+}
+
+/// Summed changes of the files in one directory or of one type.
+///
+/// Fields:
+/// - `name`: The directory or file type
+/// - `additions` / `deletions`: Line counts over all its files
+/// - `files`: How many files fall into it
+pub struct Bucket {
+    pub name: String,
+    pub additions: u64,
+    pub deletions: u64,
+    pub files: usize,
+}
+
+impl Bucket {
+    /// Lines changed in either direction, by which buckets are ranked.
+    fn total(&self) -> u64 {
+        self.additions + self.deletions
+    }
+}
+
+/// The directory of `path`, cut after `depth` components (`.` for the repository root).
+pub fn directory_of(path: &str, depth: usize) -> String {
+    let parts: Vec<&str> = path.split('/').collect();
+    let dirs = &parts[..parts.len() - 1];
+    if dirs.is_empty() {
+        ".".to_string()
+    } else {
+        dirs[..dirs.len().min(depth)].join("/")
+    }
+}
+
+/// The file type of `path`: `.rs`, `.md`, ... or the file name if it has no extension.
+pub fn type_of(path: &str) -> String {
+    let file = Path::new(path);
+    match file.extension() {
+        Some(ext) => format!(".{}", ext.to_string_lossy().to_lowercase()),
+        None => file
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string()),
+    }
+}
+
+/// Sums the changes per key, biggest bucket first (ties by name).
+pub fn group(files: &[FileChange], key: impl Fn(&str) -> String) -> Vec<Bucket> {
+    let mut buckets: HashMap<String, Bucket> = HashMap::new();
+    for file in files {
+        let name = key(&file.path);
+        let bucket = buckets.entry(name.clone()).or_insert_with(|| Bucket {
+            name,
+            additions: 0,
+            deletions: 0,
+            files: 0,
+        });
+        bucket.additions += file.additions;
+        bucket.deletions += file.deletions;
+        bucket.files += 1;
+    }
+    let mut buckets: Vec<Bucket> = buckets.into_values().collect();
+    buckets.sort_by(|a, b| b.total().cmp(&a.total()).then_with(|| a.name.cmp(&b.name)));
+    buckets
+}
+
+/// Length of the bar for `lines`, out of [`BAR_WIDTH`] for `max`; never 0 for a change.
+fn scaled(lines: u64, max: u64) -> usize {
+    if lines == 0 || max == 0 {
+        return 0;
+    }
+    ((lines * BAR_WIDTH + max / 2) / max).max(1) as usize
+}
+
+/// Draws one bar: additions then deletions, padded to the full width.
+fn bar(bucket: &Bucket, max: u64) -> String {
+    let added = scaled(bucket.additions, max);
+    let deleted = scaled(bucket.deletions, max);
+    let padding = " ".repeat((BAR_WIDTH as usize + 1).saturating_sub(added + deleted));
+    if is_plain() {
+        format!("{}{}{}", "+".repeat(added), "-".repeat(deleted), padding)
+    } else {
+        format!(
+            "{}{}{}",
+            "█".repeat(added).green(),
+            "▆".repeat(deleted).red(),
+            padding
+        )
+    }
+}
+
+/// Renders one grouping as a titled block of bars.
+///
+/// # Parameters
+/// - `title`: Heading of the block (e.g. "By directory")
+/// - `buckets`: The buckets, biggest first (see [`group`])
+///
+/// # Returns
+/// - The block, ending in a newline; buckets past [`MAX_ROWS`] are summed up in one line
+pub fn render(title: &str, buckets: &[Bucket]) -> String {
+    let max = buckets.iter().map(Bucket::total).max().unwrap_or(0);
+    let shown = &buckets[..buckets.len().min(MAX_ROWS)];
+    let name_width = shown
+        .iter()
+        .map(|b| b.name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = format!("{}\n", title.bold());
+    for bucket in shown {
+        out.push_str(&format!(
+            "  {:<width$}  {} {:>7} {:>7} {:>4} file(s)\n",
+            bucket.name,
+            bar(bucket, max),
+            format!("+{}", bucket.additions),
+            format!("-{}", bucket.deletions),
+            bucket.files,
+            width = name_width
+        ));
+    }
+    let rest = &buckets[shown.len()..];
+    if !rest.is_empty() {
+        out.push_str(&format!(
+            "  … {} more ({} file(s), +{} -{})\n",
+            rest.len(),
+            rest.iter().map(|b| b.files).sum::<usize>(),
+            rest.iter().map(|b| b.additions).sum::<u64>(),
+            rest.iter().map(|b| b.deletions).sum::<u64>()
+        ));
+    }
+    out
+}
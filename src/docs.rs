@@ -0,0 +1,770 @@
+// Documentation that ships inside the binary: the examples registry behind
+// `git pr help <command> --examples`, and man pages generated from the clap definitions.
+//
+// Keeping examples next to the code (rather than only in the README) means they are
+// versioned with the flags they use and are available offline, on any install.
+use clap::Command;
+use std::error::Error;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// A usage example: what it does, and the command line.
+pub struct Example {
+    pub about: &'static str,
+    pub command: &'static str,
+}
+
+/// Shorthand for building the registry below.
+const fn ex(about: &'static str, command: &'static str) -> Example {
+    Example { about, command }
+}
+
+/// Examples per subcommand, in the order they're shown.
+pub const EXAMPLES: &[(&str, &[Example])] = &[
+    (
+        "list",
+        &[
+            ex("List open PRs", "git pr list"),
+            ex(
+                "Only PRs against a release branch",
+                "git pr list --base release-2.x",
+            ),
+            ex("Count open PRs per base branch", "git pr list --bases"),
+            ex(
+                "Line-by-line output for a screen reader",
+                "git pr list --plain",
+            ),
+            ex(
+                "Labeled PRs, most recently updated first",
+                "git pr list --label backend --sort updated",
+            ),
+            ex(
+                "Save a triage view, then use it",
+                "git pr config set view.backend \"--label backend --sort updated\" && git pr list --view backend",
+            ),
+            ex(
+                "Choose the columns, with review and CI state",
+                "git pr list --columns number,title,author,checks,review",
+            ),
+            ex(
+                "What's still open for the next release, and where it sits on the board",
+                "git pr list --milestone v2.0 --columns number,title,author,project",
+            ),
+            ex(
+                "PRs that can be merged right now",
+                "git pr list --mergeable",
+            ),
+            ex(
+                "Triage only the PRs touching code you own (CODEOWNERS or owned-paths)",
+                "git pr list --owned-by-me",
+            ),
+            ex(
+                "Fail a cron job when a PR has awaited review for more than 48 hours",
+                "git pr config set review-sla 48h && git pr list --fail-on-sla",
+            ),
+            ex(
+                "Show which branch each PR targets, by default",
+                "git pr config set list-columns number,title,author,head,base,updated",
+            ),
+            ex(
+                "Just numbers and titles (scripts)",
+                "git pr list --porcelain | cut -f1,7",
+            ),
+            ex("See where the time goes", "git pr list --timing"),
+            ex(
+                "Progress as NDJSON events on stderr, for a wrapper's UI",
+                "git pr list --columns number,title,checks --progress json",
+            ),
+            ex(
+                "Dashboard refreshing every 60 seconds",
+                "git pr list --watch 60",
+            ),
+        ],
+    ),
+    (
+        "pull",
+        &[
+            ex("Check out PR #42 locally", "git pr pull 42"),
+            ex(
+                "Switch to the PR and print only the branch name",
+                "branch=$(git pr pull 42 -q)",
+            ),
+            ex(
+                "Check out the PR with its submodules",
+                "git pr pull 42 --recurse-submodules",
+            ),
+            ex(
+                "Pull a PR in a huge repository without its history or file contents",
+                "git pr pull 42 --depth 1 --filter blob:none",
+            ),
+        ],
+    ),
+    (
+        "run",
+        &[
+            ex(
+                "Run the tests on PR #42 without touching your checkout",
+                "git pr run 42 -- cargo test",
+            ),
+            ex(
+                "Keep the worktree to look around afterwards",
+                "git pr run 42 --keep -- sh -c 'make && make test'",
+            ),
+        ],
+    ),
+    (
+        "bisect",
+        &[
+            ex(
+                "Find which commit of PR #42 broke the tests",
+                "git pr bisect 42 -- cargo test",
+            ),
+            ex(
+                "Skip commits that don't build (exit 125), after checking the base passes",
+                "git pr bisect 42 --check-base -- sh -c 'cargo build || exit 125; cargo test'",
+            ),
+        ],
+    ),
+    (
+        "serve",
+        &[
+            ex(
+                "Start the JSON-RPC server for an editor plugin",
+                "git pr serve",
+            ),
+            ex(
+                "One request from the shell, one JSON object per line",
+                "echo '{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"list\"}' | git pr serve",
+            ),
+            ex(
+                "Drop cached PR data when GitHub reports a change",
+                "GIT_PR_WEBHOOK_SECRET=... git pr serve --webhook 127.0.0.1:8787 --webhook-only",
+            ),
+        ],
+    ),
+    (
+        "comments",
+        &[
+            ex("Read the whole discussion of a PR", "git pr comments 42"),
+            ex(
+                "Only the comments that still apply to the current code",
+                "git pr comments 42 --hide-outdated",
+            ),
+        ],
+    ),
+    (
+        "freeze",
+        &[
+            ex("Is the default branch frozen?", "git pr freeze"),
+            ex(
+                "Freeze merges until a release ships",
+                "git pr freeze start -m \"Release 2.0\" --until 2024-05-03",
+            ),
+            ex("Lift the freeze of a release branch", "git pr freeze end --branch release/2.0"),
+        ],
+    ),
+    (
+        "autopilot",
+        &[
+            ex(
+                "See which Dependabot PRs are safe to merge",
+                "git pr autopilot --author dependabot[bot] --only-patch",
+            ),
+            ex(
+                "Approve and squash-merge them",
+                "git pr autopilot --author dependabot[bot] --only-patch --approve --merge --method squash",
+            ),
+        ],
+    ),
+    (
+        "bots",
+        &[
+            ex("Review the pile of dependency updates", "git pr bots"),
+            ex(
+                "Ask Dependabot to rebase all its PRs",
+                "git pr bots --author dependabot[bot] --rebase",
+            ),
+        ],
+    ),
+    (
+        "show-details",
+        &[
+            ex(
+                "Details of the PR for the current branch",
+                "git pr show-details",
+            ),
+            ex(
+                "Details of PR #42 with absolute dates",
+                "git pr show-details 42 --timestamps absolute",
+            ),
+            ex(
+                "Keep the links to the web UI when paging the output",
+                "git pr show-details 42 --web-links | less -R",
+            ),
+        ],
+    ),
+    (
+        "show-diff",
+        &[
+            ex(
+                "Page through the diff (delta, less or cat)",
+                "git pr show-diff 42",
+            ),
+            ex(
+                "Raw diff, e.g. to apply elsewhere",
+                "git pr show-diff 42 --raw | git apply",
+            ),
+            ex(
+                "See who last touched each removed line, and in which PR",
+                "git pr show-diff 42 --blame",
+            ),
+            ex(
+                "Everything, including lockfiles and vendored code",
+                "git pr show-diff 42 --include-generated",
+            ),
+        ],
+    ),
+    (
+        "submit-review",
+        &[
+            ex(
+                "Approve with a message",
+                "git pr submit-review 42 --approve -m \"Nice cleanup\"",
+            ),
+            ex(
+                "Comment without approving",
+                "git pr submit-review 42 --comment-only -m \"Question inline\"",
+            ),
+            ex(
+                "Keep the review as a local draft",
+                "git pr submit-review 42 -m \"WIP notes\" --save",
+            ),
+        ],
+    ),
+    (
+        "approve",
+        &[
+            ex("Approve the PR for the current branch", "git pr approve"),
+            ex("Approve with a message", "git pr approve 42 -m \"Ship it\""),
+            ex(
+                "Record the time the review took",
+                "git pr approve 42 --spent 25m",
+            ),
+            ex(
+                "Sign the approval with your GPG key",
+                "git pr approve 42 --sign",
+            ),
+        ],
+    ),
+    (
+        "request-changes",
+        &[ex(
+            "Request changes, leaving the PR open",
+            "git pr request-changes 42 -m \"Please add a test\"",
+        )],
+    ),
+    (
+        "review-commit",
+        &[
+            ex(
+                "Read the PR's second commit on its own",
+                "git pr review-commit 42 3f2a9c1",
+            ),
+            ex(
+                "Comment on a line of that commit",
+                "git pr review-commit 42 3f2a9c1 --comment \"src/parser.rs:118:This misses the empty case\"",
+            ),
+        ],
+    ),
+    (
+        "verify-review",
+        &[ex(
+            "Check the signed receipt of a review on PR 42",
+            "git pr verify-review 1843920211 --pr 42",
+        )],
+    ),
+    (
+        "scope",
+        &[
+            ex(
+                "Approve one directory of a large PR",
+                "git pr scope 42 approve src/api -m \"Handlers look good\"",
+            ),
+            ex(
+                "Request changes on another",
+                "git pr scope 42 request-changes src/db -m \"The migration needs a rollback\"",
+            ),
+            ex("Submit all verdicts as one review", "git pr scope 42 submit"),
+        ],
+    ),
+    (
+        "checks",
+        &[
+            ex("CI status of the current branch's PR", "git pr checks"),
+            ex(
+                "Names of failing checks (scripts)",
+                "git pr checks 42 --porcelain | awk -F'\\t' '$1 == \"failure\" { print $3 }'",
+            ),
+            ex(
+                "Fail if the PR adds or grows a file beyond 5 MB",
+                "git pr checks 42 --max-file-size 5MB",
+            ),
+        ],
+    ),
+    (
+        "lint",
+        &[
+            ex(
+                "Fail unless every commit is signed off by its author (DCO)",
+                "git pr lint 42 --require-dco",
+            ),
+            ex(
+                "Fail on broken links, missing template sections or checkboxes, and TODOs",
+                "git pr lint 42 --body",
+            ),
+        ],
+    ),
+    (
+        "logs",
+        &[
+            ex("Logs of all jobs", "git pr logs 42"),
+            ex(
+                "Follow one job until it finishes",
+                "git pr logs --job test --follow",
+            ),
+        ],
+    ),
+    (
+        "artifacts",
+        &[
+            ex("List artifacts", "git pr artifacts 42"),
+            ex(
+                "Download one into a directory",
+                "git pr artifacts 42 --download coverage --out /tmp",
+            ),
+        ],
+    ),
+    (
+        "coverage",
+        &[
+            ex(
+                "Coverage of the lines PR #42 adds, from its CI artifact",
+                "git pr coverage 42",
+            ),
+            ex(
+                "Fail when less than 80% of the new code is tested",
+                "git pr coverage 42 --artifact lcov-report --fail-under 80",
+            ),
+            ex(
+                "Read reports from a coverage service instead",
+                "git pr config set coverage-url 'https://cov.example.com/{owner}/{repo}/{sha}/lcov.info'",
+            ),
+        ],
+    ),
+    (
+        "search",
+        &[
+            ex("Open PRs mentioning a word", "git pr search flaky"),
+            ex(
+                "Merged PRs by a user",
+                "git pr search \"retry\" --state merged --author alice",
+            ),
+        ],
+    ),
+    (
+        "draft",
+        &[ex(
+            "Turn the current branch's PR back into a draft",
+            "git pr draft",
+        )],
+    ),
+    (
+        "ready",
+        &[ex("Mark PR #42 ready for review", "git pr ready 42")],
+    ),
+    (
+        "merge",
+        &[
+            ex(
+                "See what blocks the merge, without merging",
+                "git pr merge 42 --dry-run",
+            ),
+            ex("Squash-merge the current branch's PR", "git pr merge --method squash"),
+            ex(
+                "Require sign-off on security-sensitive changes",
+                "git pr merge 42 --require-label security-review",
+            ),
+            ex(
+                "Require the hotfix label and two approvals for release branches",
+                "git pr config set merge-policy.release \"release/* label=hotfix approvals=2\"",
+            ),
+            ex(
+                "Merge an urgent fix while the branch is frozen",
+                "git pr merge 42 --override",
+            ),
+            ex(
+                "Merge in a high-risk repository from a script",
+                "git pr merge 42 --confirm 42",
+            ),
+        ],
+    ),
+    (
+        "unresolved",
+        &[
+            ex("List the open conversations on a PR", "git pr unresolved 42"),
+            ex(
+                "Resolve the threads you opened, then list the rest",
+                "git pr unresolved 42 --resolve-all-mine",
+            ),
+        ],
+    ),
+    (
+        "create",
+        &[
+            ex(
+                "Open a PR, editing the pre-filled text in $EDITOR",
+                "git pr create",
+            ),
+            ex(
+                "Draft PR against another base, no editor",
+                "git pr create --draft --base develop --no-edit",
+            ),
+            ex(
+                "Take title and labels from the referenced issue",
+                "git pr create --fill-issue",
+            ),
+            ex("Contribute from your fork", "git pr create --fork"),
+            ex(
+                "Capture the new PR's URL",
+                "url=$(git pr create -q --no-edit -t \"Fix parser\")",
+            ),
+        ],
+    ),
+    (
+        "files",
+        &[
+            ex("Changed files, review progress and size", "git pr files 42"),
+            ex(
+                "Include lockfiles and generated code",
+                "git pr files 42 --include-generated",
+            ),
+        ],
+    ),
+    (
+        "diff-stats",
+        &[
+            ex(
+                "Changed lines of PR #42 by directory and file type",
+                "git pr diff-stats 42",
+            ),
+            ex(
+                "Group by top-level directory, counting generated files too",
+                "git pr diff-stats 42 --depth 1 --include-generated",
+            ),
+        ],
+    ),
+    (
+        "viewed",
+        &[
+            ex(
+                "Mark two files as viewed",
+                "git pr viewed 42 --file src/main.rs --file README.md",
+            ),
+            ex("Mark everything as viewed", "git pr viewed --all"),
+            ex(
+                "Clear a file's viewed state",
+                "git pr viewed --file src/main.rs --unmark",
+            ),
+        ],
+    ),
+    (
+        "describe",
+        &[
+            ex("Preview a generated description", "git pr describe"),
+            ex("Write it to the PR", "git pr describe 42 --regenerate"),
+        ],
+    ),
+    (
+        "move",
+        &[
+            ex(
+                "Retarget onto a release branch",
+                "git pr move 42 --base release-2.x",
+            ),
+            ex("Rename the PR", "git pr move -t \"Parser: handle CRLF\""),
+        ],
+    ),
+    (
+        "sync-fork",
+        &[
+            ex(
+                "Fast-forward your fork's default branch",
+                "git pr sync-fork",
+            ),
+            ex("Sync another branch", "git pr sync-fork --branch develop"),
+        ],
+    ),
+    (
+        "reopen-review",
+        &[ex(
+            "Push fixes and ping the reviewers who requested changes",
+            "git pr reopen-review -m \"Addressed all comments\"",
+        )],
+    ),
+    (
+        "comment",
+        &[
+            ex(
+                "Leave a plain comment, without a review",
+                "git pr comment 42 -m \"Rebased on main, ready for another look\"",
+            ),
+            ex(
+                "Attach a screenshot to a comment",
+                "git pr comment 42 -m \"Looks off on mobile:\" --attach shot.png",
+            ),
+            ex(
+                "Attach several files without text",
+                "git pr comment --attach before.png --attach after.png",
+            ),
+            ex(
+                "Comment on a range of lines of the diff",
+                "git pr comment 42 --file src/parser.rs --line-range 40-45 -m \"Split this up?\"",
+            ),
+            ex(
+                "Comment on lines the PR removes",
+                "git pr comment 42 --file src/old.rs --line-range 12 --side left -m \"Still needed?\"",
+            ),
+        ],
+    ),
+    (
+        "suggest-reviewer",
+        &[
+            ex(
+                "See who owns and last touched the changed lines, by review load",
+                "git pr suggest-reviewer 42",
+            ),
+            ex(
+                "Request a review from the suggested reviewer",
+                "git pr suggest-reviewer --request",
+            ),
+        ],
+    ),
+    (
+        "assign-next",
+        &[
+            ex(
+                "Set up a rotation once",
+                "git pr config set review-rotation alice,bob,carol",
+            ),
+            ex("Request a review from whoever's turn it is", "git pr assign-next 42"),
+            ex(
+                "Skip someone while they're away",
+                "git pr config set review-unavailable bob",
+            ),
+        ],
+    ),
+    (
+        "welcome",
+        &[
+            ex("Greet a first-time contributor", "git pr welcome 42"),
+            ex(
+                "Use your own message",
+                "git pr config set welcome-template .github/welcome.md",
+            ),
+        ],
+    ),
+    (
+        "stats",
+        &[
+            ex("Review time per PR", "git pr stats"),
+            ex(
+                "Total minutes, for a spreadsheet",
+                "git pr stats --porcelain | awk -F'\\t' '{ s += $3 } END { print s / 60 }'",
+            ),
+        ],
+    ),
+    (
+        "triage",
+        &[
+            ex("Triage PRs without labels or assignees", "git pr triage"),
+            ex(
+                "Start over, including PRs skipped earlier",
+                "git pr triage --restart",
+            ),
+        ],
+    ),
+    (
+        "queue",
+        &[
+            ex(
+                "PRs waiting on your team's review",
+                "git pr queue --team platform-core",
+            ),
+            ex(
+                "A team of another organization",
+                "git pr queue --team acme/security",
+            ),
+        ],
+    ),
+    (
+        "pending",
+        &[
+            ex("List drafts and unsubmitted reviews", "git pr pending"),
+            ex(
+                "Submit a draft as an approval",
+                "git pr pending submit 42 --approve",
+            ),
+            ex("Throw a draft away", "git pr pending discard 42"),
+        ],
+    ),
+    (
+        "audit",
+        &[
+            ex("What git-pr changed in the last week", "git pr audit --since 7d"),
+            ex(
+                "Merges of the last month, as JSON",
+                "git pr audit --since 30d --json | jq 'select(.action == \"merge\")'",
+            ),
+        ],
+    ),
+    (
+        "config",
+        &[
+            ex(
+                "Talk to `upstream` in this clone",
+                "git pr config set remote upstream",
+            ),
+            ex(
+                "Use GitHub Enterprise everywhere",
+                "git pr config set base-url https://ghe.example.com/api/v3 --global",
+            ),
+            ex(
+                "Messages, help and tables in German",
+                "git pr config set locale de --global",
+            ),
+            ex(
+                "Default message for every `submit-review`",
+                "git pr config set defaults.submit-review.message LGTM --global",
+            ),
+            ex(
+                "Link ticket keys like ABC-123 to Jira",
+                "git pr config set tracker-url https://acme.atlassian.net --global",
+            ),
+            ex("Show all settings", "git pr config list"),
+            ex(
+                "Override a setting for one run, e.g. in CI",
+                "GIT_PR_REMOTE=upstream GIT_PR_READ_ONLY=true git pr list",
+            ),
+        ],
+    ),
+    (
+        "version",
+        &[
+            ex(
+                "Version, commit and compiled-in providers",
+                "git pr version",
+            ),
+            ex("Paste into a bug report", "git pr version --json"),
+        ],
+    ),
+    (
+        "doctor",
+        &[ex("Check the setup before filing a bug", "git pr doctor")],
+    ),
+    (
+        "selftest",
+        &[
+            ex("Verify a fresh build end to end", "git pr selftest"),
+            ex(
+                "Point any command at your own mock server",
+                "GIT_PR_API_BASE=http://127.0.0.1:8080 git pr list",
+            ),
+        ],
+    ),
+];
+
+/// Examples registered for a subcommand (empty if there are none).
+pub fn examples_for(command: &str) -> &'static [Example] {
+    EXAMPLES
+        .iter()
+        .find(|(name, _)| *name == command)
+        .map(|(_, examples)| *examples)
+        .unwrap_or(&[])
+}
+
+/// Examples as plain text, as printed by `git pr help <command> --examples`.
+pub fn render_examples(command: &str) -> String {
+    examples_for(command)
+        .iter()
+        .map(|e| format!("  # {}\n  $ {}\n", e.about, e.command))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Escapes text for roff: backslashes, and leading `.`/`'` that would be read as requests.
+fn roff_escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
+
+/// Writes one man page for `cmd`, followed by an EXAMPLES section if any are registered.
+fn write_man_page(
+    cmd: Command,
+    examples: &[Example],
+    out: &mut dyn Write,
+) -> Result<(), Box<dyn Error>> {
+    clap_mangen::Man::new(cmd).render(out)?;
+    if !examples.is_empty() {
+        writeln!(out, ".SH EXAMPLES")?;
+        for example in examples {
+            writeln!(out, ".TP")?;
+            writeln!(out, "{}", roff_escape(example.about))?;
+            writeln!(out, ".nf")?;
+            writeln!(out, "{}", roff_escape(example.command))?;
+            writeln!(out, ".fi")?;
+        }
+    }
+    Ok(())
+}
+
+/// Generates man pages.
+///
+/// # Parameters
+/// - `cmd`: The full CLI definition
+/// - `out_dir`: Where to write `git-pr.1` and one `git-pr-<command>.1` per subcommand;
+///   with `None` only the top-level page is printed to stdout
+///
+/// # Returns
+/// - The paths written (empty when printing to stdout)
+pub fn generate_man_pages(
+    mut cmd: Command,
+    out_dir: Option<&Path>,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    // Propagates bin names ("git-pr list") into the subcommands' synopses
+    cmd.build();
+
+    let Some(dir) = out_dir else {
+        write_man_page(cmd, &[], &mut std::io::stdout())?;
+        return Ok(Vec::new());
+    };
+
+    fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+    for sub in cmd.get_subcommands() {
+        let name = sub.get_name().to_string();
+        let path = dir.join(format!("git-pr-{}.1", name));
+        let mut file = fs::File::create(&path)?;
+        // Built subcommands carry the display name `git-pr-<command>`, used as page title
+        write_man_page(sub.clone(), examples_for(&name), &mut file)?;
+        written.push(path.display().to_string());
+    }
+    let path = dir.join("git-pr.1");
+    write_man_page(cmd, &[], &mut fs::File::create(&path)?)?;
+    written.push(path.display().to_string());
+    Ok(written)
+}
@@ -0,0 +1,111 @@
+// `git pr doctor`: environment diagnostics.
+//
+// Runs the local checks (git, config file, remote, token) itself and then hands over to
+// the provider for everything that needs the API. Every failure comes with a fix, so
+// the output can be pasted into a bug report or simply followed.
+use crate::config::{self, Config};
+use crate::providers::get_provider;
+use crate::providers::methods::Diagnostic;
+use crate::ui_println;
+use crate::utils::{get_remote_url, run_git};
+use colored::Colorize;
+use std::env;
+
+/// Runs every check and prints one line per result.
+///
+/// # Returns
+/// - `true` if all checks passed
+pub fn run(config: &Config) -> bool {
+    let mut results = local_checks(config);
+
+    // API checks need a remote URL and a token; without them they'd only repeat the failure
+    let remote_url = get_remote_url(config.remote());
+    let has_token = env::var("GITHUB_TOKEN").is_ok_and(|t| !t.is_empty());
+    if let (Some(url), true) = (remote_url, has_token) {
+        match get_provider(&url, config) {
+            Ok(provider) => results.extend(provider.diagnose()),
+            Err(e) => results.push(Diagnostic::fail(
+                "Provider",
+                e.to_string(),
+                "For GitHub Enterprise, `git pr config set base-url https://<host>/api/v3`",
+            )),
+        }
+    }
+
+    for result in &results {
+        if result.ok {
+            ui_println!("{} {}: {}", "✅".green(), result.name, result.detail);
+        } else {
+            ui_println!("{} {}: {}", "❌".red(), result.name.red(), result.detail);
+            if let Some(fix) = &result.fix {
+                println!("   ↳ {}", fix.cyan());
+            }
+        }
+    }
+
+    let failed = results.iter().filter(|r| !r.ok).count();
+    if failed == 0 {
+        println!("{}", "All checks passed.".green());
+    } else {
+        println!("{}", format!("{} check(s) failed.", failed).red());
+    }
+    failed == 0
+}
+
+/// Checks that don't need the network.
+fn local_checks(config: &Config) -> Vec<Diagnostic> {
+    let mut results = Vec::new();
+
+    match run_git(&["--version"]) {
+        Some(version) => results.push(Diagnostic::pass("git", version)),
+        None => results.push(Diagnostic::fail(
+            "git",
+            "git was not found on PATH",
+            "Install git and make sure `git --version` works",
+        )),
+    }
+
+    match config::check_global_file() {
+        Ok(Some(path)) => results.push(Diagnostic::pass("Config", path.display().to_string())),
+        Ok(None) => results.push(Diagnostic::pass("Config", "no global file (defaults)")),
+        Err(e) => results.push(Diagnostic::fail(
+            "Config",
+            e,
+            "Fix the TOML syntax, or recreate it with `git pr config set <key> <value> --global`",
+        )),
+    }
+
+    if run_git(&["rev-parse", "--git-dir"]).is_none() {
+        results.push(Diagnostic::fail(
+            "Repository",
+            "not inside a git repository",
+            "Run git pr from a clone of the repository",
+        ));
+    } else {
+        match get_remote_url(config.remote()) {
+            Some(url) => results.push(Diagnostic::pass(
+                "Remote URL",
+                format!("{} = {}", config.remote(), url),
+            )),
+            None => results.push(Diagnostic::fail(
+                "Remote URL",
+                format!("remote '{}' is not configured", config.remote()),
+                format!(
+                    "git remote add {} <url>, or `git pr config set remote <name>`",
+                    config.remote()
+                ),
+            )),
+        }
+    }
+
+    match env::var("GITHUB_TOKEN") {
+        Ok(token) if !token.is_empty() => results.push(Diagnostic::pass("GITHUB_TOKEN", "set")),
+        _ => results.push(Diagnostic::fail(
+            "GITHUB_TOKEN",
+            "not set",
+            "export GITHUB_TOKEN=<token> (scopes: repo, or public_repo)",
+        )),
+    }
+
+    results
+}
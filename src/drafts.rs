@@ -0,0 +1,77 @@
+// Locally saved review drafts (`git pr submit-review --save`).
+//
+// Drafts live under `.git/git-pr/reviews/<pr>`, one file per PR: the first line is the
+// review event (APPROVE, REQUEST_CHANGES or COMMENT), the rest is the message. Keeping
+// them inside `.git` means they follow the clone and never end up in a commit. Writes
+// hold the repository lock (see `lock`).
+use crate::debug_log;
+use crate::lock;
+use crate::providers::methods::PrNumber;
+use crate::utils::run_git;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+/// A review written but not yet submitted.
+///
+/// Fields:
+/// - `pr_number`: The PR the review is for
+/// - `event`: Review event to submit with (`APPROVE`, `REQUEST_CHANGES`, `COMMENT`)
+/// - `message`: The review body
+pub struct ReviewDraft {
+    pub pr_number: PrNumber,
+    pub event: String,
+    pub message: String,
+}
+
+/// Directory holding the drafts of the current repository.
+fn drafts_dir() -> Result<PathBuf, Box<dyn Error>> {
+    let git_dir = run_git(&["rev-parse", "--git-dir"]).ok_or("Not inside a git repository")?;
+    Ok(PathBuf::from(git_dir).join("git-pr").join("reviews"))
+}
+
+/// Saves (or replaces) the draft review for a PR.
+pub fn save_draft(draft: &ReviewDraft) -> Result<(), Box<dyn Error>> {
+    let dir = drafts_dir()?;
+    let _lock = lock::acquire()?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(draft.pr_number.to_string());
+    debug_log!("[DEBUG] Saving review draft to {}", path.display());
+    fs::write(path, format!("{}\n{}\n", draft.event, draft.message))?;
+    Ok(())
+}
+
+/// Loads the draft review for a PR, if one was saved.
+pub fn load_draft(pr_number: PrNumber) -> Option<ReviewDraft> {
+    let text = fs::read_to_string(drafts_dir().ok()?.join(pr_number.to_string())).ok()?;
+    let (event, message) = text.split_once('\n').unwrap_or((text.as_str(), ""));
+    Some(ReviewDraft {
+        pr_number,
+        event: event.trim().to_string(),
+        message: message.trim().to_string(),
+    })
+}
+
+/// Loads every saved draft, ordered by PR number.
+pub fn load_drafts() -> Vec<ReviewDraft> {
+    let Ok(entries) = drafts_dir().and_then(|dir| Ok(fs::read_dir(dir)?)) else {
+        return Vec::new();
+    };
+
+    let mut drafts: Vec<ReviewDraft> = entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|name| load_draft(name.parse().ok()?))
+        .collect();
+    drafts.sort_by_key(|d| d.pr_number);
+    drafts
+}
+
+/// Deletes the draft review for a PR. Returns `false` if there was none.
+pub fn discard_draft(pr_number: PrNumber) -> bool {
+    let Ok(_lock) = lock::acquire() else {
+        return false;
+    };
+    drafts_dir()
+        .map(|dir| fs::remove_file(dir.join(pr_number.to_string())).is_ok())
+        .unwrap_or(false)
+}
@@ -0,0 +1,154 @@
+// Detection of generated and vendored files.
+//
+// Lockfiles, generated protobuf code and vendored dependencies make diffs long and size
+// metrics meaningless, so `show-diff` and `files` leave them out unless
+// `--include-generated` is given. A file counts as generated when:
+//   1. `.gitattributes` marks it `linguist-generated` or `linguist-vendored` (the same
+//      attributes GitHub uses to collapse files in the web UI), or
+//   2. it matches the `ignore-paths` setting (comma-separated CODEOWNERS-style patterns),
+//      which defaults to `DEFAULT_IGNORE_PATHS`.
+use crate::codeowners::compile_pattern_list;
+use crate::config::Config;
+use crate::debug_log;
+use crate::utils::run_git;
+use regex::Regex;
+use std::collections::HashSet;
+
+/// Patterns treated as generated when `ignore-paths` isn't set.
+pub const DEFAULT_IGNORE_PATHS: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "go.sum",
+    "poetry.lock",
+    "Gemfile.lock",
+    "composer.lock",
+    "*.pb.go",
+    "*_pb2.py",
+    "*_pb2_grpc.py",
+    "*.min.js",
+    "vendor/",
+    "node_modules/",
+];
+
+/// Attributes that mark a file as generated.
+const ATTRIBUTES: &[&str] = &["linguist-generated", "linguist-vendored"];
+
+/// Decides which paths of a change are generated.
+///
+/// Fields:
+/// - `patterns`: The `ignore-paths` patterns, compiled
+/// - `attributed`: Paths `.gitattributes` marks as generated or vendored
+pub struct GeneratedFiles {
+    patterns: Vec<Regex>,
+    attributed: HashSet<String>,
+}
+
+impl GeneratedFiles {
+    /// Classifies `paths` using the `ignore-paths` setting and the `.gitattributes` of
+    /// the current checkout.
+    pub fn new(config: &Config, paths: &[String]) -> Self {
+        let patterns = match config.ignore_paths.as_deref() {
+            Some(list) => compile_pattern_list(list),
+            None => compile_pattern_list(&DEFAULT_IGNORE_PATHS.join(",")),
+        };
+
+        GeneratedFiles {
+            patterns,
+            attributed: attributed_paths(paths),
+        }
+    }
+
+    /// Whether `path` is generated or vendored.
+    pub fn contains(&self, path: &str) -> bool {
+        self.attributed.contains(path) || self.patterns.iter().any(|p| p.is_match(path))
+    }
+}
+
+/// Paths among `paths` that `.gitattributes` marks with one of `ATTRIBUTES`.
+///
+/// Outside a repository (or with no `.gitattributes`) nothing is marked.
+fn attributed_paths(paths: &[String]) -> HashSet<String> {
+    let mut marked = HashSet::new();
+    // Keep command lines a reasonable length on PRs touching thousands of files
+    for chunk in paths.chunks(500) {
+        let mut args: Vec<&str> = vec!["check-attr"];
+        args.extend(ATTRIBUTES);
+        args.push("--");
+        args.extend(chunk.iter().map(String::as_str));
+
+        let Some(output) = run_git(&args) else {
+            continue;
+        };
+        // Each line looks like: `path: attribute: value`
+        for line in output.lines() {
+            let mut parts = line.rsplitn(3, ": ");
+            let (Some(value), Some(_), Some(path)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            if value == "set" || value == "true" {
+                marked.insert(path.to_string());
+            }
+        }
+    }
+    debug_log!(
+        "[DEBUG] {} path(s) marked generated by .gitattributes",
+        marked.len()
+    );
+    marked
+}
+
+/// Removes the sections of generated files from a multi-file unified diff.
+///
+/// # Returns
+/// - The remaining diff and the paths that were removed, in diff order.
+pub fn strip_generated(diff: &str, config: &Config) -> (String, Vec<String>) {
+    let sections = split_diff(diff);
+    let paths: Vec<String> = sections.iter().filter_map(|(p, _)| p.clone()).collect();
+    let generated = GeneratedFiles::new(config, &paths);
+
+    let mut kept = String::new();
+    let mut removed = Vec::new();
+    for (path, section) in sections {
+        match path {
+            Some(path) if generated.contains(&path) => removed.push(path),
+            _ => kept.push_str(section),
+        }
+    }
+    (kept, removed)
+}
+
+/// Splits a multi-file diff at its `diff --git` headers.
+///
+/// # Returns
+/// - `(path, section)` pairs covering the whole input; the path is the new-side path
+///   (`b/...`) and is `None` for text before the first header.
+fn split_diff(diff: &str) -> Vec<(Option<String>, &str)> {
+    let mut starts: Vec<usize> = diff
+        .match_indices("diff --git ")
+        .filter(|(i, _)| *i == 0 || diff.as_bytes()[i - 1] == b'\n')
+        .map(|(i, _)| i)
+        .collect();
+    if starts.first() != Some(&0) {
+        starts.insert(0, 0);
+    }
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(n, &start)| {
+            let end = starts.get(n + 1).copied().unwrap_or(diff.len());
+            let section = &diff[start..end];
+            let path = section
+                .lines()
+                .next()
+                .and_then(|header| header.strip_prefix("diff --git "))
+                .and_then(|header| header.rsplit_once(" b/"))
+                .map(|(_, path)| path.to_string());
+            (path, section)
+        })
+        .filter(|(_, section)| !section.is_empty())
+        .collect()
+}
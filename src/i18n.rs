@@ -0,0 +1,225 @@
+// Message catalog and locale selection.
+//
+// User-facing text is looked up by message ID in Fluent catalogs (`locales/<lang>/git-pr.ftl`),
+// compiled into the binary so that a single file still runs anywhere. The locale comes from
+// the `locale` setting, or else from `LC_ALL`, `LC_MESSAGES` or `LANG` (`de_DE.UTF-8` → `de`);
+// a message missing from the chosen catalog falls back to English, so a translation can be
+// partial and grow over time.
+//
+// What goes through the catalog so far:
+//   - error messages printed by `main` (`error-*`)
+//   - CLI help: the English text is the clap definition itself; a catalog overrides it with
+//     `help-git-pr` (the tool), `help-<command>` (a subcommand's summary) and
+//     `help-<command>-<arg>` / `help-arg-<arg>` (a subcommand's / a global option's help)
+//   - table headers: the English header is the column's name in code; a catalog overrides it
+//     with `header-<name>`, e.g. `header-total-commits`
+//
+// Adding a language means adding its catalog to `locales/` and to `CATALOGS`.
+use crate::debug_log;
+use clap::Command;
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use std::env;
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+/// The fallback language; its catalog has every `error-*` message.
+const DEFAULT_LANGUAGE: &str = "en";
+
+/// Catalogs compiled into the binary, by language.
+const CATALOGS: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en/git-pr.ftl")),
+    ("de", include_str!("../locales/de/git-pr.ftl")),
+];
+
+/// Languages with a catalog, for validating the `locale` setting.
+pub fn languages() -> Vec<&'static str> {
+    CATALOGS.iter().map(|(language, _)| *language).collect()
+}
+
+/// Bundles in lookup order: the chosen language, then English.
+static BUNDLES: OnceLock<Vec<FluentBundle<FluentResource>>> = OnceLock::new();
+
+/// Turns a locale as found in the environment into a language tag.
+///
+/// `de_DE.UTF-8@euro` becomes `de-DE`; `C` and `POSIX` (no locale) give `None`.
+fn normalize_locale(raw: &str) -> Option<String> {
+    let tag = raw.split(['.', '@']).next().unwrap_or("").trim();
+    if tag.is_empty() || tag == "C" || tag == "POSIX" {
+        return None;
+    }
+    Some(tag.replace('_', "-"))
+}
+
+/// Parses the `locale` setting or an environment locale into a language identifier.
+///
+/// # Returns
+/// - `Err` if it isn't a valid language tag
+pub fn parse_locale(raw: &str) -> Result<LanguageIdentifier, String> {
+    let tag = normalize_locale(raw).ok_or_else(|| format!("'{}' names no language", raw))?;
+    tag.parse()
+        .map_err(|_| format!("Invalid locale '{}' (expected e.g. de or de-DE)", raw))
+}
+
+/// The locale requested by the environment, in POSIX precedence order.
+fn environment_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.is_empty())
+}
+
+/// Builds the bundle of one catalog.
+fn bundle(language: &str, source: &str) -> FluentBundle<FluentResource> {
+    let id: LanguageIdentifier = language.parse().expect("catalog language is a valid tag");
+    let mut bundle = FluentBundle::new_concurrent(vec![id]);
+    // Unicode isolation marks around arguments show up as garbage in many terminals
+    bundle.set_use_isolating(false);
+    let resource =
+        FluentResource::try_new(source.to_string()).unwrap_or_else(|(resource, errors)| {
+            debug_log!("[DEBUG] Errors in the {} catalog: {:?}", language, errors);
+            resource
+        });
+    if let Err(errors) = bundle.add_resource(resource) {
+        debug_log!(
+            "[DEBUG] Duplicate messages in the {} catalog: {:?}",
+            language,
+            errors
+        );
+    }
+    bundle
+}
+
+/// Builds the lookup chain for a requested locale.
+fn load(requested: Option<&str>) -> Vec<FluentBundle<FluentResource>> {
+    let language = requested
+        .and_then(|raw| parse_locale(raw).ok())
+        .map(|id| id.language.as_str().to_string())
+        .filter(|language| CATALOGS.iter().any(|(l, _)| l == language))
+        .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string());
+    debug_log!(
+        "[DEBUG] Locale: {} (requested: {})",
+        language,
+        requested.unwrap_or("none")
+    );
+
+    let mut chain = vec![language.as_str()];
+    if language != DEFAULT_LANGUAGE {
+        chain.push(DEFAULT_LANGUAGE);
+    }
+    chain
+        .into_iter()
+        .filter_map(|l| CATALOGS.iter().find(|(language, _)| *language == l))
+        .map(|(language, source)| bundle(language, source))
+        .collect()
+}
+
+/// Chooses the language for the rest of the process.
+///
+/// # Parameters
+/// - `configured`: The `locale` setting; without it the environment decides
+pub fn init(configured: Option<&str>) {
+    let requested = configured.map(str::to_string).or_else(environment_locale);
+    let _ = BUNDLES.set(load(requested.as_deref()));
+}
+
+/// The lookup chain; messages printed before [`init`] use the environment's locale.
+fn bundles() -> &'static [FluentBundle<FluentResource>] {
+    BUNDLES.get_or_init(|| load(environment_locale().as_deref()))
+}
+
+/// Formats a message from the first catalog in the chain that has it.
+///
+/// # Returns
+/// - `None` if no catalog has the message
+pub fn lookup(id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    bundles().iter().find_map(|bundle| {
+        let pattern = bundle.get_message(id)?.value()?;
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            debug_log!("[DEBUG] Errors formatting '{}': {:?}", id, errors);
+        }
+        Some(text.into_owned())
+    })
+}
+
+/// A message from the catalog; the ID itself if it's missing.
+pub fn tr(id: &str) -> String {
+    tr_args(id, &[])
+}
+
+/// A message from the catalog with named arguments (`{ $name }` in the catalog).
+pub fn tr_args(id: &str, args: &[(&str, &str)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.to_string());
+    }
+    lookup(id, Some(&fluent_args)).unwrap_or_else(|| {
+        debug_log!("[DEBUG] No message '{}' in any catalog", id);
+        id.to_string()
+    })
+}
+
+/// Turns a table header or argument name into the part of a message ID after its prefix.
+///
+/// `Total Commits` becomes `total-commits`.
+fn message_key(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// A table header in the chosen language (`header-<name>`), or as written in code.
+pub fn header(name: &str) -> String {
+    let key = message_key(name);
+    if key.is_empty() {
+        return name.to_string();
+    }
+    lookup(&format!("header-{}", key), None).unwrap_or_else(|| name.to_string())
+}
+
+/// Replaces the CLI's help texts with the catalog's, where it has them.
+///
+/// # Parameters
+/// - `command`: The CLI as defined with clap, in English
+pub fn localize_command(mut command: Command) -> Command {
+    if let Some(about) = lookup("help-git-pr", None) {
+        command = command.about(about);
+    }
+    let globals: Vec<String> = command
+        .get_arguments()
+        .map(|arg| arg.get_id().to_string())
+        .collect();
+    for id in globals {
+        if let Some(help) = lookup(&format!("help-arg-{}", message_key(&id)), None) {
+            command = command.mut_arg(&id, |arg| arg.help(help));
+        }
+    }
+
+    let subcommands: Vec<String> = command
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    for name in subcommands {
+        command = command.mut_subcommand(&name, |mut sub| {
+            if let Some(about) = lookup(&format!("help-{}", name), None) {
+                sub = sub.about(about);
+            }
+            let args: Vec<String> = sub
+                .get_arguments()
+                .map(|arg| arg.get_id().to_string())
+                .collect();
+            for id in args {
+                let key = format!("help-{}-{}", name, message_key(&id));
+                if let Some(help) = lookup(&key, None) {
+                    sub = sub.mut_arg(&id, |arg| arg.help(help));
+                }
+            }
+            sub
+        });
+    }
+    command
+}
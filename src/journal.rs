@@ -0,0 +1,199 @@
+// Local journal of time spent reviewing (`--spent`, `git pr stats`).
+//
+// Reviewers record how long a review took with `--spent 25m` on `submit-review`,
+// `approve` and `request-changes`; the time spent in the editor while resuming a draft
+// (`git pr pending resume`) is measured and recorded automatically. Entries are appended
+// to `.git/git-pr/journal`, one tab-separated line each:
+//
+//   <recorded-at> <pr> <seconds> <source>
+//
+// where the time is RFC 3339 in UTC and the source is `spent` (given by the reviewer) or
+// `editor` (measured). `git pr stats` sums the entries up per PR, and `--porcelain`
+// exports them unchanged for teams that track review load elsewhere. Writes hold the
+// repository lock (see `lock`).
+use crate::debug_log;
+use crate::lock;
+use crate::providers::methods::PrNumber;
+use crate::table;
+use crate::ui_println;
+use crate::utils::{format_duration, run_git};
+use chrono::{DateTime, SecondsFormat, Utc};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use tabled::Tabled;
+
+/// How the time of an entry was obtained.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Source {
+    /// Given by the reviewer with `--spent`
+    Spent,
+    /// Measured while the review was open in the editor
+    Editor,
+}
+
+impl Source {
+    fn as_str(self) -> &'static str {
+        match self {
+            Source::Spent => "spent",
+            Source::Editor => "editor",
+        }
+    }
+}
+
+/// One journal entry.
+///
+/// Fields:
+/// - `recorded_at`: When the entry was written
+/// - `pr_number`: The reviewed PR
+/// - `seconds`: Time spent on the review
+/// - `source`: Whether the time was given or measured
+struct Entry {
+    recorded_at: DateTime<Utc>,
+    pr_number: u64,
+    seconds: u64,
+    source: Source,
+}
+
+/// One row of the `stats` table.
+#[derive(Tabled)]
+struct StatsRow {
+    #[tabled(rename = "PR")]
+    pr: String,
+    #[tabled(rename = "Sessions")]
+    sessions: usize,
+    #[tabled(rename = "Time")]
+    time: String,
+    #[tabled(rename = "Last")]
+    last: String,
+}
+
+/// File holding the journal of the current repository.
+fn journal_path() -> Result<PathBuf, Box<dyn Error>> {
+    let git_dir = run_git(&["rev-parse", "--git-dir"]).ok_or("Not inside a git repository")?;
+    Ok(PathBuf::from(git_dir).join("git-pr").join("journal"))
+}
+
+/// Appends the time spent on a review to the journal.
+///
+/// # Parameters
+/// - `pr_number`: The reviewed PR
+/// - `seconds`: Time spent
+/// - `source`: Whether the time was given or measured
+pub fn record_review_time(
+    pr_number: PrNumber,
+    seconds: u64,
+    source: Source,
+) -> Result<(), Box<dyn Error>> {
+    let path = journal_path()?;
+    let _lock = lock::acquire()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    debug_log!(
+        "[DEBUG] Recording {}s ({}) on PR #{}",
+        seconds,
+        source.as_str(),
+        pr_number
+    );
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(
+        file,
+        "{}\t{}\t{}\t{}",
+        Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        pr_number,
+        seconds,
+        source.as_str()
+    )?;
+    Ok(())
+}
+
+/// Loads every entry, oldest first. Lines that don't parse are skipped; a missing
+/// journal is empty.
+fn load_entries() -> Vec<Entry> {
+    let Ok(text) = journal_path().and_then(|path| Ok(fs::read_to_string(path)?)) else {
+        return Vec::new();
+    };
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let recorded_at = DateTime::parse_from_rfc3339(fields.next()?).ok()?;
+            let pr_number = fields.next()?.parse().ok()?;
+            let seconds = fields.next()?.parse().ok()?;
+            let source = match fields.next()? {
+                "spent" => Source::Spent,
+                "editor" => Source::Editor,
+                _ => return None,
+            };
+            Some(Entry {
+                recorded_at: recorded_at.with_timezone(&Utc),
+                pr_number,
+                seconds,
+                source,
+            })
+        })
+        .collect()
+}
+
+/// Prints the review time recorded in this repository (`git pr stats`).
+///
+/// # Parameters
+/// - `porcelain`: Export the raw entries as tab-separated lines instead of a summary
+pub fn print_stats(porcelain: bool) {
+    let entries = load_entries();
+    if porcelain {
+        for entry in &entries {
+            println!(
+                "{}\t{}\t{}\t{}",
+                entry.recorded_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+                entry.pr_number,
+                entry.seconds,
+                entry.source.as_str()
+            );
+        }
+        return;
+    }
+
+    if entries.is_empty() {
+        println!("No review time recorded yet (use `--spent` when submitting a review).");
+        return;
+    }
+
+    // (sessions, seconds, last) per PR
+    let mut per_pr: BTreeMap<u64, (usize, u64, DateTime<Utc>)> = BTreeMap::new();
+    for entry in &entries {
+        let totals = per_pr
+            .entry(entry.pr_number)
+            .or_insert((0, 0, entry.recorded_at));
+        totals.0 += 1;
+        totals.1 += entry.seconds;
+        totals.2 = totals.2.max(entry.recorded_at);
+    }
+    let total: u64 = entries.iter().map(|e| e.seconds).sum();
+
+    let rows: Vec<StatsRow> = per_pr
+        .iter()
+        .map(|(number, (sessions, seconds, last))| StatsRow {
+            pr: format!("#{}", number),
+            sessions: *sessions,
+            time: format_duration(*seconds),
+            last: last
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d")
+                .to_string(),
+        })
+        .collect();
+    ui_println!("{}", "⏱️  Review time".bold());
+    println!("{}", table::render(rows, &[]));
+    println!(
+        "Total: {} over {} review{} of {} PR{}",
+        format_duration(total).bold(),
+        entries.len(),
+        if entries.len() == 1 { "" } else { "s" },
+        per_pr.len(),
+        if per_pr.len() == 1 { "" } else { "s" }
+    );
+}
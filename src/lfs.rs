@@ -0,0 +1,83 @@
+// Git LFS support for `git pr pull`.
+//
+// A PR changing LFS-tracked files (design assets, models) checks out as pointer files unless
+// their objects are fetched too, e.g. when LFS isn't set up to smudge on checkout or the
+// objects are only in the PR's branch. Which of the PR's files are tracked is answered by
+// the checked-out `.gitattributes` (`git check-attr filter`); only their objects are fetched,
+// for the PR's branch (`git lfs fetch --include`), and written into the working tree
+// (`git lfs checkout`). Without Git LFS installed the files are named with a hint instead.
+use crate::debug_log;
+use crate::status_log;
+use crate::ui_eprintln;
+use crate::utils::run_git;
+use colored::Colorize;
+use std::error::Error;
+use std::process::Command;
+
+/// The files of `paths` that `.gitattributes` routes through the LFS filter.
+pub fn tracked_files(paths: &[String]) -> Vec<String> {
+    if paths.is_empty() {
+        return Vec::new();
+    }
+    let mut args = vec!["check-attr", "filter", "--"];
+    args.extend(paths.iter().map(String::as_str));
+    run_git(&args)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.strip_suffix(": filter: lfs"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether the `git lfs` command is available.
+fn is_installed() -> bool {
+    run_git(&["lfs", "version"]).is_some()
+}
+
+/// Runs `git lfs <args>`, failing on a non-zero exit.
+fn git_lfs(args: &[&str]) -> Result<(), Box<dyn Error>> {
+    debug_log!("[DEBUG] Running: git lfs {}", args.join(" "));
+    let status = Command::new("git").arg("lfs").args(args).status()?;
+    if !status.success() {
+        return Err(format!("git lfs {} failed ({})", args[0], status).into());
+    }
+    Ok(())
+}
+
+/// Fetches and checks out the LFS objects of a freshly pulled PR.
+///
+/// Problems are reported as warnings: the branch itself is checked out already.
+///
+/// # Parameters
+/// - `remote`: Remote to fetch the objects from
+/// - `git_ref`: The local branch of the PR
+/// - `paths`: The files the PR changes
+pub fn pull_tracked(remote: &str, git_ref: &str, paths: &[String]) {
+    let tracked = tracked_files(paths);
+    if tracked.is_empty() {
+        return;
+    }
+    debug_log!("[DEBUG] LFS-tracked files in the PR: {:?}", tracked);
+    if !is_installed() {
+        ui_eprintln!(
+            "{} The PR changes {} LFS-tracked file(s) ({}), but Git LFS isn't installed; they are pointer files until you install it (https://git-lfs.com) and run `git lfs pull`.",
+            "⚠️ ".yellow(),
+            tracked.len(),
+            tracked.join(", ")
+        );
+        return;
+    }
+
+    status_log!("📦 Fetching {} LFS-tracked file(s)...", tracked.len());
+    let include = format!("--include={}", tracked.join(","));
+    let mut checkout = vec!["checkout", "--"];
+    checkout.extend(tracked.iter().map(String::as_str));
+    if let Err(e) = git_lfs(&["fetch", &include, remote, git_ref]).and_then(|_| git_lfs(&checkout))
+    {
+        ui_eprintln!(
+            "{} {}; the LFS files may still be pointer files (try `git lfs pull`).",
+            "⚠️ ".yellow(),
+            e
+        );
+    }
+}
@@ -0,0 +1,44 @@
+// Terminal hyperlinks (OSC 8) in tables.
+//
+// Modern terminals (iTerm2, kitty, WezTerm, GNOME Terminal, Windows Terminal, ...) turn
+// text wrapped in an OSC 8 escape sequence into a link that opens on click; others just
+// show the text. Tables link PR numbers, commit SHAs, file names and check runs to the
+// web UI.
+//
+// The `hyperlinks` setting decides when links are emitted: `auto` (the default) only
+// when stdout is a terminal, `always` (e.g. when piping into `less -R`) or `never`.
+// `--no-hyperlinks` turns them off for one run; `show-details --web-links` turns them on.
+use crate::debug_log;
+use std::env;
+use std::io::{self, IsTerminal};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Accepted values of the `hyperlinks` config key.
+pub const HYPERLINK_MODES: [&str; 3] = ["auto", "always", "never"];
+
+/// Set once at startup from the `hyperlinks` setting; read by [`link`].
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Decides whether links are emitted for the rest of the process.
+///
+/// # Parameters
+/// - `mode`: One of [`HYPERLINK_MODES`]; `None` means `auto`
+pub fn init(mode: Option<&str>) {
+    let enabled = match mode.unwrap_or("auto") {
+        "always" => true,
+        "never" => false,
+        // Terminals too old for OSC 8 usually announce themselves as `dumb`
+        _ => io::stdout().is_terminal() && env::var("TERM").map_or(true, |term| term != "dumb"),
+    };
+    debug_log!("[DEBUG] Terminal hyperlinks: {}", enabled);
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Wraps `text` in a hyperlink to `url`, or returns it unchanged when links are off or
+/// there is nothing to link to.
+pub fn link(text: &str, url: &str) -> String {
+    if text.is_empty() || url.is_empty() || !ENABLED.load(Ordering::Relaxed) {
+        return text.to_string();
+    }
+    format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+}
@@ -0,0 +1,262 @@
+// Per-repository operation lock.
+//
+// Commands that write local state under `.git/git-pr/` (review drafts today; watch mode,
+// bulk operations and caches later) take this lock first, so two invocations running
+// side by side can't interleave their writes. The lock is a file created exclusively at
+// `.git/git-pr/lock` holding the owner's PID, start time and host name; it is removed
+// when the guard is dropped.
+//
+// A lock is only taken over when it was left behind: its PID is no longer running on
+// this host, or the file is still empty or half-written after `INCOMPLETE_GRACE` (the
+// owner crashed while creating it). A long merge or rebase keeps its lock however long
+// it runs, and a lock taken on another host (a repository on a network share) is never
+// taken over. The takeover renames the lock aside and checks the moved file again, so
+// a lock another invocation created in the meantime is put back rather than deleted.
+use crate::debug_log;
+use crate::utils::run_git;
+use std::error::Error;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long to wait for another invocation to finish before giving up.
+const WAIT_FOR: Duration = Duration::from_secs(10);
+
+/// Pause between attempts while waiting.
+const RETRY_EVERY: Duration = Duration::from_millis(100);
+
+/// How long an empty or half-written lock file is given to be completed by its owner.
+const INCOMPLETE_GRACE: Duration = Duration::from_secs(2);
+
+// Guards held by this process; nested acquisitions share the one lock file
+static HELD: AtomicUsize = AtomicUsize::new(0);
+
+/// Holds the repository lock until dropped.
+///
+/// Fields:
+/// - `path`: The lock file, removed when the last guard of this process is dropped
+pub struct RepoLock {
+    path: PathBuf,
+}
+
+impl Drop for RepoLock {
+    fn drop(&mut self) {
+        if HELD.fetch_sub(1, Ordering::SeqCst) == 1 {
+            debug_log!("[DEBUG] Releasing {}", self.path.display());
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Path of the lock file of the current repository.
+fn lock_path() -> Result<PathBuf, Box<dyn Error>> {
+    let git_dir = run_git(&["rev-parse", "--git-dir"]).ok_or("Not inside a git repository")?;
+    Ok(PathBuf::from(git_dir).join("git-pr").join("lock"))
+}
+
+/// Takes the repository lock, waiting up to `WAIT_FOR` for another invocation.
+///
+/// Re-entrant within a process: a nested call returns another guard for the same lock.
+///
+/// # Returns
+/// - `Ok(guard)` once the lock is held; drop the guard to release it.
+/// - `Err` if another live invocation kept it for longer than `WAIT_FOR`, or the lock
+///   file can't be created.
+pub fn acquire() -> Result<RepoLock, Box<dyn Error>> {
+    let path = lock_path()?;
+    if HELD.fetch_add(1, Ordering::SeqCst) > 0 {
+        return Ok(RepoLock { path });
+    }
+
+    if let Some(dir) = path.parent() {
+        if let Err(e) = fs::create_dir_all(dir) {
+            HELD.fetch_sub(1, Ordering::SeqCst);
+            return Err(e.into());
+        }
+    }
+
+    let mut waited = Duration::ZERO;
+    loop {
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                // The lock is ours from here on: if writing it fails, dropping the guard
+                // gives it back and removes the empty file
+                let lock = RepoLock { path };
+                writeln!(
+                    file,
+                    "{}\n{}\n{}",
+                    std::process::id(),
+                    now_secs(),
+                    host_name()
+                )?;
+                debug_log!("[DEBUG] Acquired {}", lock.path.display());
+                return Ok(lock);
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => {
+                HELD.fetch_sub(1, Ordering::SeqCst);
+                return Err(format!("Failed to create {}: {}", path.display(), e).into());
+            }
+        }
+
+        // Someone holds it: take it over if they're gone, otherwise wait our turn
+        let owner = read_owner(&path);
+        if matches!(owner, Owner::Gone) {
+            // Released between the attempt and the read
+            continue;
+        }
+        if is_stale(&owner) && take_over(&path) {
+            debug_log!("[DEBUG] Removed the stale lock of {}", owner);
+            continue;
+        }
+        if waited >= WAIT_FOR {
+            HELD.fetch_sub(1, Ordering::SeqCst);
+            return Err(format!(
+                "Another git-pr ({}) is working on this repository; if it isn't running anymore, delete {}",
+                owner,
+                path.display()
+            )
+            .into());
+        }
+        thread::sleep(RETRY_EVERY);
+        waited += RETRY_EVERY;
+    }
+}
+
+/// What a lock file says about its owner.
+enum Owner {
+    /// A complete lock: the owner's PID and host (locks written before the host was
+    /// recorded count as this host's)
+    Process { pid: u32, host: Option<String> },
+    /// An empty, half-written or unreadable lock, last modified this long ago
+    Incomplete(Duration),
+    /// The lock file is gone already
+    Gone,
+}
+
+impl std::fmt::Display for Owner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Owner::Process {
+                pid,
+                host: Some(host),
+            } => write!(f, "PID {} on {}", pid, host),
+            Owner::Process { pid, host: None } => write!(f, "PID {}", pid),
+            Owner::Incomplete(_) | Owner::Gone => write!(f, "PID ?"),
+        }
+    }
+}
+
+/// Reads the owner of a lock file (PID, start time and host, one per line).
+fn read_owner(path: &Path) -> Owner {
+    let age = match fs::metadata(path) {
+        Ok(meta) => meta
+            .modified()
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .unwrap_or_default(),
+        Err(e) if e.kind() == ErrorKind::NotFound => return Owner::Gone,
+        Err(_) => Duration::ZERO,
+    };
+    let text = fs::read_to_string(path).unwrap_or_default();
+    let mut lines = text.lines().map(str::trim);
+    let pid = lines.next().and_then(|l| l.parse().ok());
+    let since = lines.next().and_then(|l| l.parse::<u64>().ok());
+    let host = lines.next().filter(|l| !l.is_empty()).map(str::to_string);
+    match (pid, since) {
+        (Some(pid), Some(_)) => Owner::Process { pid, host },
+        _ => Owner::Incomplete(age),
+    }
+}
+
+/// Whether a lock was left behind (see the module comment).
+fn is_stale(owner: &Owner) -> bool {
+    match owner {
+        Owner::Process { pid, host } => {
+            host.as_ref().is_none_or(|host| *host == host_name()) && !process_alive(*pid)
+        }
+        Owner::Incomplete(age) => *age >= INCOMPLETE_GRACE,
+        Owner::Gone => false,
+    }
+}
+
+/// Removes a stale lock without deleting one another invocation took in the meantime.
+///
+/// The lock is renamed aside (only one contender can win that) and judged again from
+/// the moved file; if it turns out to be live it is linked back into place, keeping the
+/// owner's open file. Should that fail, the moved file is left where it is rather than
+/// deleting a live lock.
+///
+/// # Returns
+/// - `true` if the stale lock is gone and creating the lock can be retried right away
+fn take_over(path: &Path) -> bool {
+    let aside = path.with_extension(format!("stale.{}", std::process::id()));
+    if fs::rename(path, &aside).is_err() {
+        // Someone else moved or released it first
+        return false;
+    }
+    let stale = is_stale(&read_owner(&aside));
+    if !stale {
+        debug_log!("[DEBUG] The lock changed hands meanwhile; putting it back");
+        if let Err(e) = fs::hard_link(&aside, path) {
+            debug_log!(
+                "[DEBUG] Could not put the lock back ({}); leaving it at {}",
+                e,
+                aside.display()
+            );
+            return false;
+        }
+    }
+    let _ = fs::remove_file(&aside);
+    stale
+}
+
+/// Whether a process with this PID exists on this host.
+///
+/// Uses `/proc` where there is one and `kill -0` on other Unixes (a process of another
+/// user answers "not permitted", which still means it exists). Elsewhere there's no
+/// portable check, so only incomplete locks are ever taken over.
+fn process_alive(pid: u32) -> bool {
+    if !cfg!(unix) {
+        return true;
+    }
+    if Path::new("/proc/self").exists() {
+        return Path::new("/proc").join(pid.to_string()).exists();
+    }
+    Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(Stdio::piped())
+        .output()
+        .map(|output| {
+            output.status.success()
+                || String::from_utf8_lossy(&output.stderr).contains("not permitted")
+        })
+        .unwrap_or(true)
+}
+
+/// Name of this host, as recorded in lock files (empty if it can't be found out).
+fn host_name() -> String {
+    static HOST: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+    HOST.get_or_init(|| {
+        Command::new("hostname")
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .unwrap_or_default()
+    })
+    .clone()
+}
+
+/// Current time in seconds since the Unix epoch.
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,100 @@
+// Cache of the login each token belongs to.
+//
+// Several commands need to know who the token belongs to (`freeze` signs the marker with
+// it, `threads` resolves your own threads, `create --fork` finds your fork), and asking
+// `GET /user` every time costs a request on paths that are otherwise local. The answer
+// rarely changes, so it is kept in `logins.toml` in the user's cache directory
+// (`~/.cache/git-pr/`) for `LOGIN_TTL`:
+//
+//   [3f29c1d0a4b7e856]
+//   login = "alice"
+//   fetched = 1714564980
+//
+// Entries are keyed by a hash of the API base and the token, so the file never holds a
+// token, a token for GitHub Enterprise doesn't answer for github.com, and a new token
+// gets a fresh lookup. A cache that can't be read or written is ignored: the lookup
+// simply goes to the API. Commands reach it through the provider's `authenticated_login`.
+use crate::debug_log;
+use crate::timing;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// File name of the cache, in the `git-pr` cache directory.
+const LOGINS_FILE: &str = "logins.toml";
+
+/// How long a cached login is trusted, in seconds (a day).
+pub const LOGIN_TTL: u64 = 24 * 60 * 60;
+
+/// Location of the cache (`$XDG_CACHE_HOME/git-pr/`, `~/.cache/git-pr/` by default).
+fn logins_path() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("git-pr").join(LOGINS_FILE))
+}
+
+/// The cache key of a token: the start of the SHA-256 of the API base and the token.
+fn token_key(api_base: &str, token: &str) -> String {
+    let digest = format!("{:x}", Sha256::digest(format!("{}\n{}", api_base, token)));
+    digest[..16].to_string()
+}
+
+/// Seconds since the Unix epoch.
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// Reads the cache; a missing or unreadable file is an empty cache.
+fn read_cache() -> toml::Table {
+    logins_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| text.parse::<toml::Table>().ok())
+        .unwrap_or_default()
+}
+
+/// The cached login of a token, unless it is older than [`LOGIN_TTL`].
+pub fn cached_login(api_base: &str, token: &str) -> Option<String> {
+    let cache = read_cache();
+    let entry = cache.get(&token_key(api_base, token))?.as_table()?;
+    let fetched = entry.get("fetched")?.as_integer()?;
+    if now().saturating_sub(fetched.max(0) as u64) >= LOGIN_TTL {
+        debug_log!("[DEBUG] Cached login has expired");
+        return None;
+    }
+    let login = entry.get("login")?.as_str()?.to_string();
+    debug_log!("[DEBUG] Authenticated login from cache: {}", login);
+    timing::record_cache_hit("authenticated login");
+    Some(login)
+}
+
+/// Remembers the login of a token. Failures are only logged (see the module comment).
+pub fn store_login(api_base: &str, token: &str, login: &str) {
+    let Some(path) = logins_path() else {
+        return;
+    };
+    let mut cache = read_cache();
+    // Drop what has expired, so tokens that are gone don't pile up
+    cache.retain(|_, entry| {
+        entry
+            .get("fetched")
+            .and_then(|fetched| fetched.as_integer())
+            .is_some_and(|fetched| now().saturating_sub(fetched.max(0) as u64) < LOGIN_TTL)
+    });
+    let mut entry = toml::Table::new();
+    entry.insert("login".to_string(), toml::Value::String(login.to_string()));
+    entry.insert("fetched".to_string(), toml::Value::Integer(now() as i64));
+    cache.insert(token_key(api_base, token), toml::Value::Table(entry));
+
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&path, cache.to_string()));
+    if let Err(e) = result {
+        debug_log!(
+            "[DEBUG] Couldn't cache the login in {}: {}",
+            path.display(),
+            e
+        );
+    }
+}
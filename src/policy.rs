@@ -0,0 +1,132 @@
+// Local merge policies per base branch (`merge-policy.<name>`).
+//
+// Branch protection applies the same rules to every PR into a branch, and only admins can
+// change it. Teams often want more for some branches: "PRs into release/* need the hotfix
+// label and two approvals". Such rules are kept as settings, one per name:
+//
+//   merge-policy.release = "release/* label=hotfix approvals=2"
+//
+// The first word is a pattern of base branches (`*` matches any run of characters,
+// `release/*` covers `release/1.2` and `release/2024/q3`); the words after it are the
+// requirements: `label=<name>` (repeatable; the PR must carry each label) and
+// `approvals=<n>` (at least n approving reviews). `git pr merge` evaluates every policy
+// whose pattern matches the PR's base in its pre-flight, before calling the merge API,
+// and names the policies that aren't met.
+use crate::config::Config;
+use regex::Regex;
+
+/// A parsed `merge-policy.<name>` setting.
+///
+/// Fields:
+/// - `name`: The setting's name (`release` for `merge-policy.release`)
+/// - `branches`: Pattern of the base branches the policy applies to
+/// - `labels`: Labels a PR must carry
+/// - `approvals`: Approving reviews a PR needs, if the policy asks for a number
+pub struct MergePolicy {
+    pub name: String,
+    pub branches: String,
+    pub labels: Vec<String>,
+    pub approvals: Option<u32>,
+}
+
+impl MergePolicy {
+    /// Parses a policy's value (see the module comment).
+    ///
+    /// # Returns
+    /// - `Err(message)` naming the word that isn't understood
+    pub fn parse(name: &str, value: &str) -> Result<MergePolicy, String> {
+        let mut words = value.split_whitespace();
+        let branches = words
+            .next()
+            .ok_or_else(|| {
+                format!(
+                    "Empty merge policy '{}' (expected e.g. \"release/* label=hotfix approvals=2\")",
+                    name
+                )
+            })?
+            .to_string();
+        let mut policy = MergePolicy {
+            name: name.to_string(),
+            branches,
+            labels: Vec::new(),
+            approvals: None,
+        };
+        for word in words {
+            match word.split_once('=') {
+                Some(("label", label)) if !label.is_empty() => policy.labels.push(label.to_string()),
+                Some(("approvals", count)) => {
+                    policy.approvals = Some(count.parse().map_err(|_| {
+                        format!("Invalid approvals '{}' in merge policy '{}'", count, name)
+                    })?)
+                }
+                _ => {
+                    return Err(format!(
+                        "Unknown requirement '{}' in merge policy '{}' (expected label=<name> or approvals=<n>)",
+                        word, name
+                    ))
+                }
+            }
+        }
+        Ok(policy)
+    }
+
+    /// Whether the policy applies to PRs into `base`.
+    pub fn applies_to(&self, base: &str) -> bool {
+        let pattern = self
+            .branches
+            .split('*')
+            .map(regex::escape)
+            .collect::<Vec<_>>()
+            .join(".*");
+        Regex::new(&format!("^{}$", pattern)).is_ok_and(|re| re.is_match(base))
+    }
+
+    /// The requirements a PR misses.
+    ///
+    /// # Parameters
+    /// - `labels`: The PR's labels
+    /// - `approvals`: How many approving reviews it has
+    ///
+    /// # Returns
+    /// - One description per unmet requirement; empty if the policy is met
+    pub fn unmet(&self, labels: &[&str], approvals: u32) -> Vec<String> {
+        let mut unmet: Vec<String> = self
+            .labels
+            .iter()
+            .filter(|wanted| !labels.iter().any(|l| l.eq_ignore_ascii_case(wanted)))
+            .map(|wanted| format!("label '{}' missing", wanted))
+            .collect();
+        if let Some(required) = self.approvals.filter(|&required| approvals < required) {
+            unmet.push(format!("{} of {} approvals", approvals, required));
+        }
+        unmet
+    }
+
+    /// What the policy asks for, e.g. `label hotfix, 2 approvals`.
+    pub fn describe(&self) -> String {
+        let mut parts: Vec<String> = self
+            .labels
+            .iter()
+            .map(|label| format!("label '{}'", label))
+            .collect();
+        if let Some(approvals) = self.approvals {
+            parts.push(format!("{} approvals", approvals));
+        }
+        if parts.is_empty() {
+            "no requirements".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+/// The configured policies that apply to PRs into `base`, by name; settings that don't
+/// parse are skipped (they are rejected when set).
+pub fn policies_for(config: &Config, base: &str) -> Vec<MergePolicy> {
+    config
+        .merge_policies
+        .iter()
+        .filter_map(|(name, value)| MergePolicy::parse(name, value).ok())
+        .filter(|policy| policy.applies_to(base))
+        .collect()
+}
@@ -0,0 +1,49 @@
+// Machine-readable progress events behind `--progress json`.
+//
+// Wrappers and editor plugins want to show their own progress UI while git-pr pages through
+// PR lists, fetches details for every PR or bisects a series. With `--progress json` each
+// step of such an operation is written to stderr as one JSON object per line (NDJSON):
+//
+//   {"current":3,"event":"progress","message":"PR #42","phase":"details","total":12}
+//
+// `phase` names the operation (`pages`, `details`, `status`, `files`, `ownership`,
+// `bisect`), `current` counts the steps done so far and `total` is `null` when the number
+// of steps isn't known up front (paging, bisecting). stdout is left alone, so the events
+// never mix with tables, `--porcelain` or `--quiet` output. The default `text` mode keeps
+// the usual status lines only.
+use serde_json::json;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Values accepted by `--progress`.
+pub const PROGRESS_MODES: [&str; 2] = ["text", "json"];
+
+// Set by `--progress json`; events can come from worker threads (see `providers::blocking`)
+static JSON: AtomicBool = AtomicBool::new(false);
+
+/// Selects how progress is reported (one of [`PROGRESS_MODES`]).
+pub fn set_mode(mode: &str) {
+    JSON.store(mode == "json", Ordering::Relaxed);
+}
+
+/// Reports a step of a long operation; a no-op unless `--progress json` was given.
+///
+/// # Parameters
+/// - `phase`: Which operation the step belongs to (see the module comment)
+/// - `current`: Steps done so far, counting this one
+/// - `total`: Number of steps, if known
+/// - `message`: What the step was about (a PR, a commit, a page of a list)
+pub fn report(phase: &str, current: usize, total: Option<usize>, message: &str) {
+    if !JSON.load(Ordering::Relaxed) {
+        return;
+    }
+    let event = json!({
+        "event": "progress",
+        "phase": phase,
+        "current": current,
+        "total": total,
+        "message": message,
+    });
+    // One write per line, so events from several threads don't interleave
+    let _ = writeln!(std::io::stderr().lock(), "{}", event);
+}
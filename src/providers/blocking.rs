@@ -0,0 +1,68 @@
+// Blocking facade over `AsyncSourceControlProvider`.
+//
+// The CLI is synchronous and stays that way: code that wants concurrency wraps a provider
+// in `Blocking`, calls ordinary methods, and gets the results once every request has
+// finished. The runtime is private to the facade, so nothing else needs to know about it.
+use crate::providers::methods::{AsyncSourceControlProvider, PrNumber, PullRequestStatus};
+use std::error::Error;
+use tokio::runtime::{Builder, Runtime};
+
+/// Most requests a facade keeps in flight at once.
+///
+/// GitHub asks clients not to hammer the API with concurrent requests (secondary rate
+/// limits), so a handful is enough to hide latency without tripping them.
+const MAX_IN_FLIGHT: usize = 8;
+
+/// One result of a batch fetch, tagged with the PR it belongs to.
+type Fetched<T> = (PrNumber, Result<T, Box<dyn Error>>);
+
+/// Runs an [`AsyncSourceControlProvider`] to completion on a private runtime.
+///
+/// Fields:
+/// - `provider`: The wrapped provider
+/// - `runtime`: Single-threaded runtime driving its futures; blocking work (HTTP calls)
+///   runs on at most `MAX_IN_FLIGHT` helper threads
+pub(crate) struct Blocking<P> {
+    provider: P,
+    runtime: Runtime,
+}
+
+impl<P: AsyncSourceControlProvider> Blocking<P> {
+    /// Wraps `provider` in a new runtime.
+    ///
+    /// # Returns
+    /// - `Err` if the runtime can't be started (e.g. no threads available)
+    pub fn new(provider: P) -> Result<Self, Box<dyn Error>> {
+        let runtime = Builder::new_current_thread()
+            .max_blocking_threads(MAX_IN_FLIGHT)
+            .build()?;
+        Ok(Blocking { provider, runtime })
+    }
+
+    /// Blocking version of [`AsyncSourceControlProvider::pull_requests`].
+    pub fn pull_requests(&self, numbers: &[PrNumber]) -> Vec<Fetched<P::PullRequest>> {
+        self.runtime
+            .block_on(self.provider.pull_requests(numbers))
+            .into_iter()
+            .map(|(number, result)| (number, result.map_err(|e| e as Box<dyn Error>)))
+            .collect()
+    }
+
+    /// Blocking version of [`AsyncSourceControlProvider::pull_request_statuses`].
+    pub fn pull_request_statuses(&self, numbers: &[PrNumber]) -> Vec<Fetched<PullRequestStatus>> {
+        self.runtime
+            .block_on(self.provider.pull_request_statuses(numbers))
+            .into_iter()
+            .map(|(number, result)| (number, result.map_err(|e| e as Box<dyn Error>)))
+            .collect()
+    }
+
+    /// Blocking version of [`AsyncSourceControlProvider::changed_paths_of`].
+    pub fn changed_paths_of(&self, numbers: &[PrNumber]) -> Vec<Fetched<Vec<String>>> {
+        self.runtime
+            .block_on(self.provider.changed_paths_of(numbers))
+            .into_iter()
+            .map(|(number, result)| (number, result.map_err(|e| e as Box<dyn Error>)))
+            .collect()
+    }
+}
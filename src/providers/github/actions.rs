@@ -0,0 +1,331 @@
+// GitHub Actions helpers: finding the workflow runs and jobs triggered by a PR's
+// head commit, and rendering their logs in the terminal.
+use crate::debug_log;
+use crate::providers::github::models::*;
+use crate::ui_println;
+use colored::Colorize;
+use reqwest::Method;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Seconds between polls in `--follow` mode.
+const FOLLOW_INTERVAL_SECS: u64 = 5;
+
+/// How long `--follow` waits for the commit's first workflow run to show up.
+const FOLLOW_RUNS_TIMEOUT: Duration = Duration::from_secs(300);
+
+impl GitHubProvider {
+    /// Lists the workflow runs triggered for a commit.
+    pub(crate) fn workflow_runs_for_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<GitHubWorkflowRun>, Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/actions/runs?head_sha={}&per_page=100",
+            self.api_base, owner, repo, sha
+        );
+        debug_log!("[DEBUG] Fetching workflow runs from: {}", url);
+
+        let runs: GitHubWorkflowRuns = self.get(&url, "Failed to fetch workflow runs")?;
+        Ok(runs.workflow_runs)
+    }
+
+    /// Lists the jobs of a workflow run.
+    fn workflow_jobs(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+    ) -> Result<Vec<GitHubJob>, Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/actions/runs/{}/jobs?per_page=100",
+            self.api_base, owner, repo, run_id
+        );
+        debug_log!("[DEBUG] Fetching jobs from: {}", url);
+
+        let jobs: GitHubJobs = self.get(&url, "Failed to fetch jobs")?;
+        Ok(jobs.jobs)
+    }
+
+    /// Downloads the plain-text log of a completed job.
+    ///
+    /// The API answers with a redirect to short-lived blob storage, which reqwest follows
+    /// (dropping the Authorization header on the cross-host hop).
+    fn job_log(&self, owner: &str, repo: &str, job_id: u64) -> Result<String, Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/actions/jobs/{}/logs",
+            self.api_base, owner, repo, job_id
+        );
+        debug_log!("[DEBUG] Downloading job log from: {}", url);
+
+        let resp = self.send(
+            self.authed(Method::GET, &url),
+            &format!("Failed to download log for job {}", job_id),
+        )?;
+
+        Ok(resp.text()?)
+    }
+
+    /// Collects `(workflow name, job)` pairs for a commit.
+    pub(crate) fn jobs_for_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<(String, GitHubJob)>, Box<dyn Error>> {
+        let mut jobs = Vec::new();
+
+        for run in self.workflow_runs_for_sha(owner, repo, sha)? {
+            let workflow = run.name.unwrap_or_else(|| format!("run {}", run.id));
+            for job in self.workflow_jobs(owner, repo, run.id)? {
+                jobs.push((workflow.clone(), job));
+            }
+        }
+
+        Ok(jobs)
+    }
+
+    /// Prints the logs of every (matching) job of the PR's head commit.
+    ///
+    /// Without `follow`, running jobs are reported but skipped, since GitHub only serves
+    /// logs once a job finishes. With `follow`, step progress is streamed while jobs run
+    /// and each log is printed as soon as its job completes; it gives up if no run shows
+    /// up within `FOLLOW_RUNS_TIMEOUT`.
+    ///
+    /// `job_filter` is a case-insensitive substring of the job name; once the commit has
+    /// jobs and none matches, the error lists the jobs there are.
+    pub(crate) fn stream_job_logs(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+        job_filter: Option<&str>,
+        follow: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut printed: HashSet<u64> = HashSet::new();
+        let mut step_states: HashMap<(u64, usize), String> = HashMap::new();
+        let filter = job_filter.map(str::to_lowercase);
+        let started = Instant::now();
+
+        loop {
+            let all_jobs = self.jobs_for_sha(owner, repo, sha)?;
+
+            if all_jobs.is_empty() {
+                if !follow {
+                    return Err(format!("No workflow runs found for commit {}", &sha[..7]).into());
+                }
+                if started.elapsed() >= FOLLOW_RUNS_TIMEOUT {
+                    return Err(format!(
+                        "No workflow runs showed up for commit {} within {} minutes",
+                        &sha[..7],
+                        FOLLOW_RUNS_TIMEOUT.as_secs() / 60
+                    )
+                    .into());
+                }
+            }
+
+            let jobs: Vec<&(String, GitHubJob)> = all_jobs
+                .iter()
+                .filter(|(_, job)| {
+                    filter
+                        .as_deref()
+                        .is_none_or(|f| job.name.to_lowercase().contains(f))
+                })
+                .collect();
+
+            if jobs.is_empty() && !all_jobs.is_empty() {
+                let mut names: Vec<&str> = all_jobs.iter().map(|(_, j)| j.name.as_str()).collect();
+                names.sort_unstable();
+                names.dedup();
+                return Err(format!(
+                    "No jobs matching '{}' for commit {} (jobs: {})",
+                    job_filter.unwrap_or(""),
+                    &sha[..7],
+                    names.join(", ")
+                )
+                .into());
+            }
+
+            for (workflow, job) in jobs.iter().copied() {
+                if printed.contains(&job.id) {
+                    continue;
+                }
+
+                if job.status == "completed" {
+                    let log = self.job_log(owner, repo, job.id)?;
+                    print_job_log(workflow, job, &log);
+                    printed.insert(job.id);
+                } else if follow {
+                    print_step_progress(job, &mut step_states);
+                } else {
+                    ui_println!(
+                        "{}",
+                        format!(
+                            "⏳ {} / {} is still {} (logs are available once it finishes; use --follow)",
+                            workflow, job.name, job.status
+                        )
+                        .yellow()
+                    );
+                    printed.insert(job.id);
+                }
+            }
+
+            let all_done = !jobs.is_empty() && jobs.iter().all(|(_, j)| printed.contains(&j.id));
+            if !follow || all_done {
+                return Ok(());
+            }
+
+            debug_log!("[DEBUG] Waiting {}s before next poll", FOLLOW_INTERVAL_SECS);
+            thread::sleep(Duration::from_secs(FOLLOW_INTERVAL_SECS));
+        }
+    }
+}
+
+impl GitHubProvider {
+    /// Collects `(workflow name, artifact)` pairs produced by the runs of a commit.
+    pub(crate) fn artifacts_for_sha(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<(String, GitHubArtifact)>, Box<dyn Error>> {
+        let mut artifacts = Vec::new();
+
+        for run in self.workflow_runs_for_sha(owner, repo, sha)? {
+            let workflow = run.name.unwrap_or_else(|| format!("run {}", run.id));
+            let url = format!(
+                "{}/repos/{}/{}/actions/runs/{}/artifacts?per_page=100",
+                self.api_base, owner, repo, run.id
+            );
+            debug_log!("[DEBUG] Fetching artifacts from: {}", url);
+
+            let list: GitHubArtifacts = self.get(&url, "Failed to fetch artifacts")?;
+            artifacts.extend(list.artifacts.into_iter().map(|a| (workflow.clone(), a)));
+        }
+
+        Ok(artifacts)
+    }
+
+    /// Downloads an artifact's zip archive into memory.
+    pub(crate) fn artifact_bytes(
+        &self,
+        artifact: &GitHubArtifact,
+    ) -> Result<Vec<u8>, Box<dyn Error>> {
+        if artifact.expired {
+            return Err(format!("Artifact '{}' has expired", artifact.name).into());
+        }
+
+        debug_log!(
+            "[DEBUG] Downloading artifact from: {}",
+            artifact.archive_download_url
+        );
+        let resp = self.send(
+            self.authed(Method::GET, &artifact.archive_download_url),
+            &format!("Failed to download artifact '{}'", artifact.name),
+        )?;
+        Ok(resp.bytes()?.to_vec())
+    }
+
+    /// Downloads an artifact's zip archive into `out_dir` as `<name>-<id>.zip`.
+    ///
+    /// The ID keeps a re-run's or another workflow's artifact of the same name from
+    /// overwriting this one.
+    ///
+    /// # Returns
+    /// The path of the written file.
+    pub(crate) fn download_artifact(
+        &self,
+        artifact: &GitHubArtifact,
+        out_dir: &Path,
+    ) -> Result<PathBuf, Box<dyn Error>> {
+        let bytes = self.artifact_bytes(artifact)?;
+        fs::create_dir_all(out_dir)?;
+        let path = out_dir.join(format!("{}-{}.zip", artifact.name, artifact.id));
+        fs::write(&path, bytes)?;
+        Ok(path)
+    }
+}
+
+/// Formats a byte count as B / KB / MB / GB.
+pub(crate) fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Prints step transitions (queued → in_progress → completed) not yet reported.
+fn print_step_progress(job: &GitHubJob, seen: &mut HashMap<(u64, usize), String>) {
+    for (index, step) in job.steps.iter().enumerate() {
+        let state = match step.conclusion.as_deref() {
+            Some(conclusion) => conclusion.to_string(),
+            None => step.status.clone(),
+        };
+        if seen.get(&(job.id, index)) == Some(&state) {
+            continue;
+        }
+
+        let label = match state.as_str() {
+            "success" => state.green().to_string(),
+            "failure" | "cancelled" | "timed_out" => state.red().to_string(),
+            "skipped" => state.dimmed().to_string(),
+            _ => state.yellow().to_string(),
+        };
+        println!("  [{}] {} → {}", job.name.cyan(), step.name, label);
+        seen.insert((job.id, index), state);
+    }
+}
+
+/// Renders a job log with per-step sections.
+///
+/// Actions logs prefix every line with an RFC 3339 timestamp and mark each step with
+/// `##[group]` / `##[endgroup]`; timestamps are stripped, groups become section
+/// headers, and `##[error]` / `##[warning]` lines are colorized.
+fn print_job_log(workflow: &str, job: &GitHubJob, log: &str) {
+    let conclusion = job.conclusion.as_deref().unwrap_or("unknown");
+    let header = format!("═══ {} / {} ({}) ═══", workflow, job.name, conclusion);
+    match conclusion {
+        "success" => println!("{}", header.green().bold()),
+        "failure" | "cancelled" | "timed_out" => println!("{}", header.red().bold()),
+        _ => println!("{}", header.bold()),
+    }
+
+    for raw_line in log.lines() {
+        let line = strip_timestamp(raw_line);
+        if let Some(section) = line.strip_prefix("##[group]") {
+            println!("{}", format!("▶ {}", section).cyan().bold());
+        } else if line.starts_with("##[endgroup]") {
+            continue;
+        } else if let Some(msg) = line.strip_prefix("##[error]") {
+            println!("{}", msg.red());
+        } else if let Some(msg) = line.strip_prefix("##[warning]") {
+            println!("{}", msg.yellow());
+        } else {
+            println!("{}", line);
+        }
+    }
+    println!();
+}
+
+/// Removes the leading `2024-01-01T00:00:00.0000000Z ` timestamp from a log line.
+fn strip_timestamp(line: &str) -> &str {
+    let line = line.trim_start_matches('\u{feff}');
+    match line.split_once(' ') {
+        Some((ts, rest)) if ts.len() >= 20 && ts.ends_with('Z') && ts.contains('T') => rest,
+        _ => line,
+    }
+}
@@ -0,0 +1,464 @@
+// HTTP plumbing shared by every GitHub endpoint: authenticated request builders,
+// status checks that turn failures into actionable errors, and JSON decoding that
+// names the offending endpoint. New endpoints should be one `get`/`request` call.
+use crate::audit;
+use crate::budget;
+use crate::cache;
+use crate::debug_log;
+use crate::progress;
+use crate::providers::github::models::*;
+use crate::timing;
+use crate::ui_eprintln;
+use crate::utils::{ensure_writable, is_quiet, open_in_browser};
+use chrono::{Local, TimeZone};
+use reqwest::blocking::{RequestBuilder, Response};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use std::error::Error;
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// REST API version every request is pinned to (`X-GitHub-Api-Version`).
+pub(crate) const GITHUB_API_VERSION: &str = "2022-11-28";
+
+impl GitHubProvider {
+    /// Starts a request with the token, the `User-Agent` GitHub requires and the
+    /// pinned API version.
+    pub(crate) fn authed(&self, method: Method, url: &str) -> RequestBuilder {
+        self.client
+            .request(method, url)
+            .bearer_auth(&self.token)
+            .header("User-Agent", "git-pr")
+            .header("X-GitHub-Api-Version", GITHUB_API_VERSION)
+    }
+
+    /// Sends a request, recording how long it took for `--timing` and the rate limit left.
+    ///
+    /// Every request goes through here; use it instead of `RequestBuilder::send` when a
+    /// caller needs the raw response (lenient status handling, 404 as "absent", ...).
+    /// Fails without sending anything once the `--max-requests` budget is spent. Writes are
+    /// recorded in the audit log (see `audit`) and drop the cached data they make stale
+    /// (see `cache`).
+    pub(crate) fn dispatch(&self, request: RequestBuilder) -> Result<Response, Box<dyn Error>> {
+        let (client, request) = request.build_split();
+        let request = request?;
+        let method = request.method().to_string();
+        let path = request.url().path().to_string();
+        let body = request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .unwrap_or_default();
+        let write = audit::pending_write(&method, &path, body);
+        if let Some(write) = &write {
+            ensure_writable(&format!("the {} ({} {})", write.action(), method, path))?;
+        }
+        budget::take_request()?;
+
+        let started = Instant::now();
+        let resp = client.execute(request);
+        timing::record(&method, &path, started.elapsed());
+        if let Some(write) = write {
+            let repo = self
+                .infer_repo_details()
+                .map(|(owner, repo)| format!("{}/{}", owner, repo));
+            let outcome = match &resp {
+                Ok(resp) => Ok(resp.status().as_u16()),
+                Err(e) => Err(e.to_string()),
+            };
+            audit::record(write, repo, outcome);
+            cache::invalidate_after_write(&path);
+        }
+        let resp = resp?;
+        budget::record_rate_limit(|name| {
+            resp.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        });
+        Ok(resp)
+    }
+
+    /// Sends a request and checks its status (see [`GitHubProvider::check_response`]).
+    ///
+    /// Use this for endpoints without a JSON body worth decoding (diffs, logs, 204s).
+    /// A request refused for SAML SSO is sent once more if the user authorizes the token
+    /// on the spot (see [`authorize_sso`]).
+    pub(crate) fn send(
+        &self,
+        request: RequestBuilder,
+        action: &str,
+    ) -> Result<Response, Box<dyn Error>> {
+        let retry = request.try_clone();
+        let resp = self.dispatch(request)?;
+        if let Some(retry) = retry {
+            let sso = resp
+                .headers()
+                .get("x-github-sso")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            if let Some(header) = sso.filter(|_| resp.status() == StatusCode::FORBIDDEN) {
+                if authorize_sso(&header) {
+                    debug_log!("[DEBUG] Retrying after SSO authorization: {}", action);
+                    let resp = self.dispatch(retry)?;
+                    return self.check_response(resp, action);
+                }
+            }
+        }
+        self.check_response(resp, action)
+    }
+
+    /// Sends a request, checks its status and deserializes the JSON body into `T`.
+    ///
+    /// Decoding failures name the endpoint, so a changed API shape points straight at
+    /// the model that needs updating.
+    pub(crate) fn request<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+        action: &str,
+    ) -> Result<T, Box<dyn Error>> {
+        let resp = self.send(request, action)?;
+        let endpoint = resp.url().path().to_string();
+        let text = resp.text()?;
+        decode(&text, action, &endpoint)
+    }
+
+    /// GETs `url` and deserializes the JSON body into `T`.
+    ///
+    /// PR-scoped endpoints are answered from the local cache when `cache-ttl` is set and
+    /// the entry is fresh enough (see `cache`).
+    pub(crate) fn get<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        action: &str,
+    ) -> Result<T, Box<dyn Error>> {
+        let Some(ttl) = self.cache_ttl_for(url) else {
+            return self.request(self.authed(Method::GET, url), action);
+        };
+        if let Some(text) = cache::lookup(url, ttl) {
+            debug_log!("[DEBUG] Answered from the cache: {}", url);
+            timing::record_cache_hit("cached PR data");
+            return decode(&text, action, url);
+        }
+        let resp = self.send(self.authed(Method::GET, url), action)?;
+        let endpoint = resp.url().path().to_string();
+        let text = resp.text()?;
+        let value = decode(&text, action, &endpoint)?;
+        cache::store(url, &text);
+        Ok(value)
+    }
+
+    /// The `cache-ttl` in seconds if `url` is a PR-scoped endpoint the cache may answer.
+    fn cache_ttl_for(&self, url: &str) -> Option<u64> {
+        self.config
+            .cache_ttl()
+            .filter(|_| cache::pr_of(url).is_some())
+    }
+
+    /// GETs every page of a list endpoint and concatenates the items.
+    ///
+    /// Pages hold `page-size` items (see [`crate::config::Config::page_size`]); the next
+    /// page is the one GitHub links as `rel="next"`. A big page size means fewer round
+    /// trips on PRs with hundreds of files or commits.
+    ///
+    /// # Parameters
+    /// - `url`: The endpoint, with or without a query string (but without `per_page`)
+    /// - `action`: What is being fetched, used as the error prefix
+    pub(crate) fn get_pages<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        action: &str,
+    ) -> Result<Vec<T>, Box<dyn Error>> {
+        self.get_pages_up_to(url, action, usize::MAX)
+    }
+
+    /// Like [`Self::get_pages`], but stops paging once `max` items are in and returns at
+    /// most that many.
+    pub(crate) fn get_pages_up_to<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        action: &str,
+        max: usize,
+    ) -> Result<Vec<T>, Box<dyn Error>> {
+        self.collect_pages(url, None, action, max)
+    }
+
+    /// Like [`Self::get_pages`], for endpoints that wrap each page's items in an object
+    /// (`{"total_count": 2, "check_runs": [...]}`); `key` names the list.
+    pub(crate) fn get_wrapped_pages<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        key: &str,
+        action: &str,
+    ) -> Result<Vec<T>, Box<dyn Error>> {
+        self.collect_pages(url, Some(key), action, usize::MAX)
+    }
+
+    /// Pages through a list endpoint (see [`Self::get_pages`]), taking each page's items
+    /// from `key` if the list is wrapped, until `max` items are in.
+    fn collect_pages<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        key: Option<&str>,
+        action: &str,
+        max: usize,
+    ) -> Result<Vec<T>, Box<dyn Error>> {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        let first = format!("{}{}per_page={}", url, separator, self.config.page_size());
+        // A cached list holds every page, concatenated
+        let ttl = self.cache_ttl_for(&first);
+        if let Some(text) = ttl.and_then(|ttl| cache::lookup(&first, ttl)) {
+            debug_log!("[DEBUG] Answered from the cache: {}", first);
+            timing::record_cache_hit("cached PR data");
+            return decode(&text, action, &first);
+        }
+        let mut next = Some(first.clone());
+        let mut items = Vec::new();
+        let mut raw: Vec<serde_json::Value> = Vec::new();
+        let mut pages = 0;
+        while let Some(url) = next {
+            debug_log!("[DEBUG] Fetching page: {}", url);
+            pages += 1;
+            progress::report("pages", pages, None, &url);
+            let resp = self.send(self.authed(Method::GET, &url), action)?;
+            next = resp
+                .headers()
+                .get("link")
+                .and_then(|v| v.to_str().ok())
+                .and_then(next_page);
+            let endpoint = resp.url().path().to_string();
+            let text = resp.text()?;
+            let mut page: serde_json::Value = decode(&text, action, &endpoint)?;
+            if let Some(key) = key {
+                page = page[key].take();
+            }
+            if ttl.is_some() {
+                raw.extend(decode_value::<Vec<serde_json::Value>>(
+                    page.clone(),
+                    action,
+                    &endpoint,
+                )?);
+            }
+            items.extend(decode_value::<Vec<T>>(page, action, &endpoint)?);
+            if items.len() >= max {
+                items.truncate(max);
+                // A partial list would answer later calls wanting all of it
+                return Ok(items);
+            }
+        }
+        if ttl.is_some() {
+            cache::store(&first, &serde_json::Value::Array(raw).to_string());
+        }
+        Ok(items)
+    }
+
+    /// Passes successful responses through and converts failures into errors with a
+    /// remediation hint.
+    ///
+    /// # Parameters
+    /// - `resp`: The response to check
+    /// - `action`: What was being attempted, used as the error prefix (e.g. "Failed to fetch PR #42")
+    ///
+    /// # Returns
+    /// - `Ok(resp)` for 2xx responses.
+    /// - `Err` for everything else; 401, 403 (SSO, rate limit, permissions) and 404 get
+    ///   a specific explanation, other statuses carry GitHub's error message.
+    pub(crate) fn check_response(
+        &self,
+        resp: Response,
+        action: &str,
+    ) -> Result<Response, Box<dyn Error>> {
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(resp);
+        }
+        debug_log!("[DEBUG] {} → HTTP {}", action, status);
+
+        let header = |name: &str| {
+            resp.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let sso = header("x-github-sso");
+        // Fine-grained tokens: the permissions the endpoint would have accepted
+        let accepted = header("x-accepted-github-permissions");
+        let rate_limit_reset = match header("x-ratelimit-remaining").as_deref() {
+            Some("0") => header("x-ratelimit-reset"),
+            _ => None,
+        };
+
+        let hint = match status {
+            StatusCode::UNAUTHORIZED => {
+                "GitHub token is invalid or expired; create a new token and export it as GITHUB_TOKEN"
+                    .to_string()
+            }
+            StatusCode::FORBIDDEN if sso.is_some() => sso_hint(sso.as_deref().unwrap_or_default()),
+            StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS if rate_limit_reset.is_some() => {
+                let reset = rate_limit_reset
+                    .and_then(|epoch| epoch.parse::<i64>().ok())
+                    .and_then(|epoch| Local.timestamp_opt(epoch, 0).single())
+                    .map(|time| time.format("%H:%M:%S").to_string())
+                    .unwrap_or_else(|| "soon".to_string());
+                format!("API rate limit exceeded; it resets at {}", reset)
+            }
+            StatusCode::FORBIDDEN if accepted.is_some() => format!(
+                "permission denied ({}); your token lacks the permission this needs ({})",
+                api_message(resp),
+                accepted.unwrap_or_default().replace('=', ": ")
+            ),
+            StatusCode::FORBIDDEN => format!(
+                "permission denied ({}); check that the token has the `repo` scope",
+                api_message(resp)
+            ),
+            StatusCode::NOT_FOUND => {
+                let slug = self
+                    .infer_repo_details()
+                    .map(|(owner, repo)| format!("{}/{}", owner, repo))
+                    .unwrap_or_else(|| self.remote_url.clone());
+                format!(
+                    "not found in {} (is the remote '{}' correct, and can the token see the repository?)",
+                    slug, self.remote
+                )
+            }
+            _ => format!("{} ({})", api_message(resp), status),
+        };
+
+        Err(format!("{}: {}", action, hint).into())
+    }
+}
+
+/// Deserializes a JSON body, naming the endpoint if it doesn't have the expected shape.
+fn decode<T: DeserializeOwned>(
+    text: &str,
+    action: &str,
+    endpoint: &str,
+) -> Result<T, Box<dyn Error>> {
+    serde_json::from_str(text)
+        .map_err(|e| format!("{}: unexpected response from {}: {}", action, endpoint, e).into())
+}
+
+/// Percent-encodes a branch name for use in a URL path. Each `/`-separated part is
+/// encoded on its own, so `release/2.0` stays readable while `#`, `?` and `%` can't end the
+/// path early.
+pub(crate) fn encode_branch(branch: &str) -> String {
+    let mut url = reqwest::Url::parse("https://localhost/").expect("valid URL");
+    if let Ok(mut segments) = url.path_segments_mut() {
+        segments.extend(branch.split('/'));
+    }
+    url.path().trim_start_matches('/').to_string()
+}
+
+/// Like [`decode`], for JSON that is already parsed.
+fn decode_value<T: DeserializeOwned>(
+    value: serde_json::Value,
+    action: &str,
+    endpoint: &str,
+) -> Result<T, Box<dyn Error>> {
+    serde_json::from_value(value)
+        .map_err(|e| format!("{}: unexpected response from {}: {}", action, endpoint, e).into())
+}
+
+/// Finds the `rel="next"` URL in a `Link` header
+/// (`<https://...&page=2>; rel="next", <https://...&page=9>; rel="last"`).
+fn next_page(link: &str) -> Option<String> {
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|p| p.trim() == "rel=\"next\"")
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
+/// Splits an `X-GitHub-SSO` header into the organization and the authorization URL.
+///
+/// The header looks like
+/// `required; url=https://github.com/orgs/<org>/sso?authorization_request=...`.
+fn sso_target(header: &str) -> (&str, &str) {
+    let url = header
+        .split(';')
+        .find_map(|part| part.trim().strip_prefix("url="))
+        .unwrap_or_default();
+    let org = url
+        .split("/orgs/")
+        .nth(1)
+        .and_then(|rest| rest.split('/').next())
+        .unwrap_or("the organization");
+    (org, url)
+}
+
+/// Offers to open the SSO authorization page and waits until the user is done.
+///
+/// Only asked once per run, and only when someone is there to answer: stdin and stderr
+/// are terminals and `--quiet` is off. The prompts go to stderr, leaving stdout to the
+/// command's output.
+///
+/// # Returns
+/// - `true` if the user authorized the token and the request should be retried
+fn authorize_sso(header: &str) -> bool {
+    static ASKED: AtomicBool = AtomicBool::new(false);
+    let (org, url) = sso_target(header);
+    if url.is_empty()
+        || is_quiet()
+        || !io::stdin().is_terminal()
+        || !io::stderr().is_terminal()
+        || ASKED.swap(true, Ordering::Relaxed)
+    {
+        return false;
+    }
+
+    let ask = |question: &str| {
+        eprint!("{}", question);
+        let _ = io::stderr().flush();
+        let mut answer = String::new();
+        io::stdin()
+            .read_line(&mut answer)
+            .ok()
+            .filter(|&n| n > 0)
+            .map(|_| answer.trim().to_lowercase())
+    };
+    ui_eprintln!(
+        "🔐 {} enforces SAML single sign-on and the token isn't authorized for it yet.",
+        org
+    );
+    match ask("   Open the authorization page in your browser? [Y/n] ").as_deref() {
+        Some("") | Some("y") | Some("yes") => {}
+        _ => return false,
+    }
+    if let Err(e) = open_in_browser(url) {
+        ui_eprintln!("⚠️  {}; open {} yourself", e, url);
+    }
+    ask("   Press Enter once the token is authorized (Ctrl-D to give up) ").is_some()
+}
+
+/// Builds the hint for a 403 caused by SAML single sign-on enforcement.
+fn sso_hint(header: &str) -> String {
+    let (org, url) = sso_target(header);
+
+    if url.is_empty() {
+        format!(
+            "the token must be authorized for SAML SSO in {}; authorize it in your token settings",
+            org
+        )
+    } else {
+        format!(
+            "the token must be authorized for SAML SSO in {}; authorize it at {}",
+            org, url
+        )
+    }
+}
+
+/// Extracts GitHub's `message` field from an error response, falling back to the raw body.
+fn api_message(resp: Response) -> String {
+    let text = resp.text().unwrap_or_default();
+    serde_json::from_str::<serde_json::Value>(&text)
+        .ok()
+        .and_then(|body| body["message"].as_str().map(str::to_string))
+        .unwrap_or(text)
+}
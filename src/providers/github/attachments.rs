@@ -0,0 +1,269 @@
+// `git pr comment --attach`: screenshots and other files in PR comments.
+//
+// The web UI uploads attachments through a session-only endpoint that API tokens can't
+// use. Instead, each file is committed to an orphan branch of the repository
+// (`git-pr-attachments`, created on first use) through the Git Data API, and the comment
+// links to it at that commit, so the link keeps working when later files are added.
+// Everybody who can read the repository can see the attachment; nothing lands on the
+// branches people work on.
+//
+// Teams that keep screenshots elsewhere set `attachment-command` to a command that
+// uploads the file given as its argument and prints the URL (e.g. to an internal image
+// host); the branch isn't used then.
+//
+// Images are embedded (`![name](url)`), any other file is linked.
+use crate::debug_log;
+use crate::providers::github::models::*;
+use crate::providers::methods::{CommentOptions, PrNumber};
+use crate::status_log;
+use base64::Engine;
+use chrono::Utc;
+use reqwest::Method;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Branch the attachments are committed to.
+const ATTACHMENT_BRANCH: &str = "git-pr-attachments";
+
+/// Largest file accepted, as for attachments in the web UI.
+const MAX_ATTACHMENT_SIZE: u64 = 25 * 1024 * 1024;
+
+/// Extensions of files shown inline rather than linked.
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "svg", "webp"];
+
+/// Markdown for an attachment: an image for pictures, a link otherwise.
+fn attachment_markdown(name: &str, url: &str) -> String {
+    let is_image = Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+    if is_image {
+        format!("![{}]({})", name, url)
+    } else {
+        format!("[{}]({})", name, url)
+    }
+}
+
+/// Path of an attachment on the branch, e.g. `pr-42/20240501-140300-screenshot.png`.
+///
+/// Characters that would need escaping in a URL are replaced with `-`.
+fn attachment_path(pr_number: PrNumber, name: &str) -> String {
+    let safe: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!(
+        "pr-{}/{}-{}",
+        pr_number,
+        Utc::now().format("%Y%m%d-%H%M%S"),
+        safe
+    )
+}
+
+/// Uploads a file with the configured `attachment-command`.
+///
+/// # Returns
+/// - The URL printed by the command (its last non-empty line of output)
+fn upload_with_command(command: &str, file: &Path) -> Result<String, Box<dyn Error>> {
+    debug_log!("[DEBUG] Uploading {} with: {}", file.display(), command);
+    // Run through the shell so commands with arguments work, as for the editor
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{} \"$1\"", command))
+        .arg("sh")
+        .arg(file)
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(format!("attachment-command failed ({})", output.status).into());
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| "attachment-command printed no URL".into())
+}
+
+impl GitHubProvider {
+    /// Posts a comment on a PR's conversation, uploading the attachments first.
+    ///
+    /// # Parameters
+    /// - `options`: The message and the files to attach
+    ///
+    /// # Returns
+    /// - `Err` if there is nothing to post, an attachment can't be uploaded, or the
+    ///   comment can't be posted
+    pub(crate) fn comment_on_pull_request(
+        &self,
+        pr_number: PrNumber,
+        options: &CommentOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        let message = options.message.as_deref().unwrap_or("").trim();
+        if message.is_empty() && options.attachments.is_empty() {
+            return Err("Nothing to post; pass a message with -m or files with --attach".into());
+        }
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+
+        let mut body = message.to_string();
+        for file in &options.attachments {
+            let name = file
+                .file_name()
+                .and_then(|n| n.to_str())
+                .ok_or_else(|| format!("'{}' is not a file name", file.display()))?;
+            let size = fs::metadata(file)
+                .map_err(|e| format!("Can't read {}: {}", file.display(), e))?
+                .len();
+            if size > MAX_ATTACHMENT_SIZE {
+                return Err(format!("{} is larger than 25 MB", file.display()).into());
+            }
+
+            status_log!("📎 Uploading {}...", name);
+            let url = match self.config.attachment_command.as_deref() {
+                Some(command) => upload_with_command(command, file)?,
+                None => self.upload_to_attachment_branch(&owner, &repo, pr_number, file, name)?,
+            };
+            debug_log!("[DEBUG] {} uploaded to {}", name, url);
+            if !body.is_empty() {
+                body.push_str("\n\n");
+            }
+            body.push_str(&attachment_markdown(name, &url));
+        }
+
+        match (&options.path, options.lines) {
+            (Some(path), Some(lines)) => {
+                let side = options.side.to_uppercase();
+                let start_side = options
+                    .start_side
+                    .as_deref()
+                    .map_or_else(|| side.clone(), str::to_uppercase);
+                let url = self.post_review_comment(
+                    &owner,
+                    &repo,
+                    pr_number,
+                    path,
+                    lines,
+                    &side,
+                    &start_side,
+                    &body,
+                )?;
+                status_log!(
+                    "💬 Commented on {} line{} {} of PR #{}: {}",
+                    path,
+                    if lines.is_single() { "" } else { "s" },
+                    lines,
+                    pr_number,
+                    url
+                );
+            }
+            _ => {
+                self.post_comment(&owner, &repo, &pr_number.to_string(), &body)?;
+                status_log!("💬 Commented on PR #{}", pr_number);
+            }
+        }
+        Ok(())
+    }
+
+    /// Commits a file to the attachment branch, creating the branch if needed.
+    ///
+    /// # Returns
+    /// - The file's raw URL at the new commit
+    fn upload_to_attachment_branch(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+        file: &Path,
+        name: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        let git = format!("{}/repos/{}/{}/git", self.api_base, owner, repo);
+        let content = base64::engine::general_purpose::STANDARD.encode(fs::read(file)?);
+        let path = attachment_path(pr_number, name);
+
+        // The branch's current commit and tree, if it exists yet
+        let ref_url = format!("{}/ref/heads/{}", git, ATTACHMENT_BRANCH);
+        let resp = self.dispatch(self.authed(Method::GET, &ref_url))?;
+        let parent = if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            debug_log!("[DEBUG] No {} branch yet; creating it", ATTACHMENT_BRANCH);
+            None
+        } else {
+            let resp = self.check_response(resp, "Failed to read the attachment branch")?;
+            let head: Value = resp.json()?;
+            let sha = head["object"]["sha"]
+                .as_str()
+                .ok_or("Malformed branch reference")?
+                .to_string();
+            let commit: Value = self.get(
+                &format!("{}/commits/{}", git, sha),
+                "Failed to read the attachment branch",
+            )?;
+            let tree = commit["tree"]["sha"].as_str().map(str::to_string);
+            Some((sha, tree))
+        };
+
+        let blob: Value = self.request(
+            self.authed(Method::POST, &format!("{}/blobs", git))
+                .json(&json!({ "content": content, "encoding": "base64" })),
+            "Failed to upload the attachment",
+        )?;
+        let mut tree_request = json!({
+            "tree": [{ "path": path, "mode": "100644", "type": "blob", "sha": blob["sha"] }]
+        });
+        if let Some((_, Some(base_tree))) = &parent {
+            tree_request["base_tree"] = json!(base_tree);
+        }
+        let tree: Value = self.request(
+            self.authed(Method::POST, &format!("{}/trees", git))
+                .json(&tree_request),
+            "Failed to upload the attachment",
+        )?;
+        let parents: Vec<&str> = parent.iter().map(|(sha, _)| sha.as_str()).collect();
+        let commit: Value = self.request(
+            self.authed(Method::POST, &format!("{}/commits", git))
+                .json(&json!({
+                    "message": format!("Attach {} to PR #{}", name, pr_number),
+                    "tree": tree["sha"],
+                    "parents": parents,
+                })),
+            "Failed to upload the attachment",
+        )?;
+        let sha = commit["sha"]
+            .as_str()
+            .ok_or("Malformed commit response")?
+            .to_string();
+
+        if parent.is_some() {
+            self.send(
+                self.authed(
+                    Method::PATCH,
+                    &format!("{}/refs/heads/{}", git, ATTACHMENT_BRANCH),
+                )
+                .json(&json!({ "sha": sha })),
+                "Failed to update the attachment branch",
+            )?;
+        } else {
+            self.send(
+                self.authed(Method::POST, &format!("{}/refs", git))
+                    .json(&json!({
+                        "ref": format!("refs/heads/{}", ATTACHMENT_BRANCH),
+                        "sha": sha,
+                    })),
+                "Failed to create the attachment branch",
+            )?;
+        }
+
+        Ok(self.web_url(owner, repo, &format!("raw/{}/{}", sha, path)))
+    }
+}
@@ -0,0 +1,137 @@
+// Authorship breakdown of a PR, for `git pr show-details`.
+//
+// Counts commits and changed lines per person, crediting both the commit author and every
+// `Co-authored-by:` trailer, so pairs and mob sessions show up as they happened and release
+// notes can thank everyone involved. A co-authored commit counts in full for each of its
+// authors; there's no way to tell who typed which line.
+use crate::providers::github::models::*;
+use crate::table::{self, Shrink};
+use crate::ui_println;
+use colored::Colorize;
+use std::collections::BTreeMap;
+use tabled::Tabled;
+
+/// What one person contributed to a PR.
+///
+/// Fields:
+/// - `name`: GitHub login if the commit author is linked to an account, else the git name
+/// - `authored`: Commits they authored
+/// - `co_authored`: Commits crediting them in a `Co-authored-by:` trailer
+/// - `additions` / `deletions`: Lines changed by those commits
+#[derive(Default)]
+struct Contribution {
+    name: String,
+    authored: usize,
+    co_authored: usize,
+    additions: u64,
+    deletions: u64,
+}
+
+/// One row of the authorship table.
+#[derive(Tabled)]
+struct AttributionRow {
+    #[tabled(rename = "Author")]
+    name: String,
+    #[tabled(rename = "Commits")]
+    authored: String,
+    #[tabled(rename = "Co-authored")]
+    co_authored: String,
+    #[tabled(rename = "Lines")]
+    lines: String,
+}
+
+/// Prints commits and lines per author, co-authors included.
+///
+/// Nothing is printed for single-author PRs without co-authors: the details table already
+/// says who wrote them.
+///
+/// # Parameters
+/// - `commits`: The PR's commits
+/// - `stats`: Lines added and deleted per commit SHA (commits without stats count no lines)
+pub(crate) fn print_attribution(
+    commits: &[GitHubPRCommit],
+    stats: &BTreeMap<String, GitHubCommitStats>,
+) {
+    // People are told apart by email, the one identifier trailers and commits share
+    let mut people: BTreeMap<String, Contribution> = BTreeMap::new();
+    for commit in commits {
+        let lines = stats.get(&commit.sha).copied().unwrap_or_default();
+        let author = &commit.commit.author;
+        let entry = people.entry(key(&author.email, &author.name)).or_default();
+        entry.name = match &commit.author {
+            Some(user) => user.login.clone(),
+            None if entry.name.is_empty() => author.name.clone(),
+            None => entry.name.clone(),
+        };
+        entry.authored += 1;
+        entry.additions += lines.additions;
+        entry.deletions += lines.deletions;
+
+        for (name, email) in co_authors(&commit.commit.message) {
+            if email.eq_ignore_ascii_case(&author.email) {
+                continue;
+            }
+            let entry = people.entry(key(&email, &name)).or_default();
+            if entry.name.is_empty() {
+                entry.name = name;
+            }
+            entry.co_authored += 1;
+            entry.additions += lines.additions;
+            entry.deletions += lines.deletions;
+        }
+    }
+    if people.len() < 2 {
+        return;
+    }
+
+    let mut contributions: Vec<Contribution> = people.into_values().collect();
+    contributions.sort_by(|a, b| {
+        (b.authored + b.co_authored)
+            .cmp(&(a.authored + a.co_authored))
+            .then((b.additions + b.deletions).cmp(&(a.additions + a.deletions)))
+    });
+    let rows: Vec<AttributionRow> = contributions
+        .iter()
+        .map(|c| AttributionRow {
+            name: c.name.clone(),
+            authored: c.authored.to_string(),
+            co_authored: c.co_authored.to_string(),
+            lines: format!(
+                "{} {}",
+                format!("+{}", c.additions).green(),
+                format!("-{}", c.deletions).red()
+            ),
+        })
+        .collect();
+
+    ui_println!(
+        "{}",
+        format!("👥 Authorship ({} people):", contributions.len()).bold()
+    );
+    println!("{}", table::render(rows, &[(0, Shrink::Truncate)]));
+}
+
+/// Map key for a person: their email, or their name if git recorded no email.
+fn key(email: &str, name: &str) -> String {
+    if email.is_empty() {
+        name.to_string()
+    } else {
+        email.to_lowercase()
+    }
+}
+
+/// `(name, email)` of the `Co-authored-by: Name <email>` trailers of a commit message.
+fn co_authors(message: &str) -> Vec<(String, String)> {
+    message
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            if !key.trim().eq_ignore_ascii_case("Co-authored-by") {
+                return None;
+            }
+            let (name, rest) = value.split_once('<')?;
+            let email = rest.split('>').next()?.trim();
+            Some((name.trim().to_string(), email.to_string()))
+        })
+        .collect()
+}
@@ -0,0 +1,349 @@
+// `git pr autopilot`: approving and merging routine PRs from trusted automation.
+//
+// Dependabot, Renovate and similar bots open a steady stream of PRs that mostly need the
+// same three checks before someone clicks approve and merge. Autopilot does those checks
+// on every open PR by the given `--author`s (`dependabot` also matches the
+// `dependabot[bot]` account):
+//   1. the diff touches only dependency manifests and lockfiles (see
+//      `manifests::is_dependency_file`),
+//   2. with `--only-patch`, every changed file is a manifest read here (`Cargo.toml`,
+//      `Cargo.lock`, `package.json`, `go.mod`), nothing in them but dependency versions
+//      changed, and every dependency change is a patch upgrade,
+//   3. the checks on the head commit are green (and there are some).
+// Without `--approve` or `--merge` it only lists which PRs qualify and why the others
+// don't. `--approve` submits an approving review and `--merge` merges through the usual
+// pre-flight (`merge.rs`), so branch protection, merge policies and freezes still apply.
+// Both are pinned to the head commit that was checked: the review is given on that
+// commit, and the merge is refused if the PR has moved on since.
+// A PR that fails to merge doesn't stop the others; the command fails at the end.
+use crate::debug_log;
+use crate::manifests::{self, DependencyChange};
+use crate::providers::github::checks::{overall_state, CheckState};
+use crate::providers::github::dependencies::ManifestChanges;
+use crate::providers::github::models::*;
+use crate::providers::methods::{AutopilotOptions, MergeOptions, PrNumber};
+use crate::status_log;
+use crate::ui_eprintln;
+use crate::ui_println;
+use colored::Colorize;
+use std::error::Error;
+
+/// Body of the approving review.
+const APPROVAL: &str =
+    "Approved by `git pr autopilot`: dependency manifests and lockfiles only, checks green.";
+
+/// Whether `login` is one of the `authors`, case-insensitively; a bot's `[bot]` suffix is
+/// optional.
+pub(crate) fn is_author(login: &str, authors: &[String]) -> bool {
+    let bare = |name: &str| name.trim_end_matches("[bot]").to_lowercase();
+    authors.iter().any(|author| bare(author) == bare(login))
+}
+
+/// Checks whether autopilot may handle a PR.
+///
+/// # Parameters
+/// - `draft`: Whether the PR is still a draft
+/// - `files`: The PR's changed files
+/// - `manifests`: What changed in the manifests among them (from `dependency_changes`)
+/// - `checks`: The overall state of the checks on the head commit, `None` if there are none
+/// - `only_patch`: Whether `--only-patch` was given
+///
+/// # Returns
+/// - `Ok(summary)` if it qualifies, with what it changes
+/// - `Err(reason)` if it doesn't
+pub(crate) fn autopilot_verdict(
+    draft: bool,
+    files: &[GitHubPRFile],
+    manifests: &[ManifestChanges],
+    checks: Option<CheckState>,
+    only_patch: bool,
+) -> Result<String, String> {
+    if draft {
+        return Err("draft".to_string());
+    }
+    if let Some(other) = files
+        .iter()
+        .find(|f| !manifests::is_dependency_file(&f.filename))
+    {
+        return Err(format!("touches {}", other.filename));
+    }
+
+    let changes: Vec<&DependencyChange> = manifests.iter().flat_map(|m| &m.changes).collect();
+    if only_patch {
+        // A lockfile that isn't read here could hide any change
+        if let Some(unread) = files.iter().find(|f| !manifests::is_manifest(&f.filename)) {
+            return Err(format!("touches {}, which isn't compared", unread.filename));
+        }
+        if let Some(manifest) = manifests.iter().find(|m| m.other_changes) {
+            return Err(format!("{} changes more than versions", manifest.path));
+        }
+        if changes.is_empty() {
+            return Err("no version change found in the manifests".to_string());
+        }
+        for change in &changes {
+            let jump = match change {
+                DependencyChange::Changed { from, to, .. } => {
+                    manifests::version_jump(from, to).unwrap_or("unversioned change")
+                }
+                DependencyChange::Added { .. } => "addition",
+                DependencyChange::Removed { .. } => "removal",
+            };
+            if jump != "patch" {
+                return Err(format!("{} of {}", jump, change.name()));
+            }
+        }
+    }
+
+    match checks {
+        None => return Err("no checks reported".to_string()),
+        Some(CheckState::Pending) => return Err("checks pending".to_string()),
+        Some(CheckState::Failure) => return Err("checks failing".to_string()),
+        Some(CheckState::Success | CheckState::Neutral) => {}
+    }
+
+    let summary = changes
+        .iter()
+        .map(|change| match change {
+            DependencyChange::Changed { name, from, to } => {
+                format!("{} {} → {}", name, from, to)
+            }
+            DependencyChange::Added { name, version } => format!("+{} {}", name, version),
+            DependencyChange::Removed { name, .. } => format!("-{}", name),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    Ok(if summary.is_empty() {
+        format!("{} file(s), checks green", files.len())
+    } else {
+        summary
+    })
+}
+
+impl GitHubProvider {
+    /// Fetches what autopilot needs to know about a PR and checks it (see
+    /// [`autopilot_verdict`]).
+    ///
+    /// # Returns
+    /// - `Err` if its files or checks can't be fetched
+    fn check_for_autopilot(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr: &BasicGitHubPR,
+        only_patch: bool,
+    ) -> Result<Result<String, String>, Box<dyn Error>> {
+        let files = self.pull_request_files(owner, repo, PrNumber(pr.number.into()))?;
+        let manifests = self.dependency_changes(owner, repo, &pr.base.sha, &pr.head.sha, &files);
+        let checks = self.combined_checks(owner, repo, &pr.head.sha)?;
+        debug_log!(
+            "[DEBUG] PR #{}: {} file(s), {} changed manifest(s)",
+            pr.number,
+            files.len(),
+            manifests.len()
+        );
+        Ok(autopilot_verdict(
+            pr.draft,
+            &files,
+            &manifests,
+            overall_state(&checks),
+            only_patch,
+        ))
+    }
+
+    /// Lists, approves and merges qualifying PRs (see the module comment).
+    pub(crate) fn run_autopilot(&self, options: &AutopilotOptions) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let candidates: Vec<BasicGitHubPR> = self
+            .open_pull_requests(&owner, &repo, None)?
+            .into_iter()
+            .filter(|pr| is_author(&pr.user.login, &options.authors))
+            .collect();
+        if candidates.is_empty() {
+            status_log!("ℹ️  No open PRs by {}.", options.authors.join(", ").cyan());
+            return Ok(());
+        }
+
+        let mut eligible = Vec::new();
+        for pr in &candidates {
+            match self.check_for_autopilot(&owner, &repo, pr, options.only_patch)? {
+                Ok(summary) => {
+                    ui_println!(
+                        "✅ #{} {} {}",
+                        pr.number,
+                        pr.title,
+                        format!("({})", summary).dimmed()
+                    );
+                    eligible.push((pr.number, pr.head.sha.as_str()));
+                }
+                Err(reason) => ui_println!(
+                    "⏭️  #{} {} {}",
+                    pr.number,
+                    pr.title,
+                    format!("(skipped: {})", reason).yellow()
+                ),
+            }
+        }
+        if !options.approve && !options.merge {
+            status_log!(
+                "{} of {} PR(s) qualify; nothing done (pass --approve and/or --merge to act on them).",
+                eligible.len(),
+                candidates.len()
+            );
+            return Ok(());
+        }
+
+        let mut failed = Vec::new();
+        for (number, head_sha) in eligible {
+            let pr_number = PrNumber(number.into());
+            let result = (|| -> Result<(), Box<dyn Error>> {
+                if options.approve {
+                    self.submit_review_on(pr_number, APPROVAL, "APPROVE", Some(head_sha))?;
+                }
+                if options.merge {
+                    self.merge_pull_request_checked(
+                        pr_number,
+                        &MergeOptions {
+                            method: options.method.clone(),
+                            expected_head: Some(head_sha.to_string()),
+                            ..Default::default()
+                        },
+                    )?;
+                }
+                Ok(())
+            })();
+            if let Err(e) = result {
+                ui_eprintln!("❌ PR #{}: {}", number, e);
+                failed.push(format!("#{}", number));
+            }
+        }
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Autopilot failed on {}", failed.join(", ")).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(names: &[&str]) -> Vec<GitHubPRFile> {
+        names
+            .iter()
+            .map(|name| {
+                serde_json::from_value(serde_json::json!({ "filename": name, "patch": null }))
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn manifest(path: &str, from: &str, to: &str) -> ManifestChanges {
+        ManifestChanges {
+            path: path.to_string(),
+            changes: vec![DependencyChange::Changed {
+                name: "serde".to_string(),
+                from: from.to_string(),
+                to: to.to_string(),
+            }],
+            other_changes: false,
+        }
+    }
+
+    #[test]
+    fn authors_match_with_or_without_the_bot_suffix() {
+        let authors = vec!["dependabot".to_string(), "Renovate[bot]".to_string()];
+        assert!(is_author("dependabot[bot]", &authors));
+        assert!(is_author("Dependabot", &authors));
+        assert!(is_author("renovate", &authors));
+        assert!(!is_author("dependabot-fork[bot]", &authors));
+        assert!(!is_author("octocat", &[]));
+    }
+
+    #[test]
+    fn green_dependency_only_prs_qualify() {
+        let files = files(&["Cargo.toml", "Cargo.lock"]);
+        let manifests = [manifest("Cargo.toml", "1.0.1", "1.0.2")];
+        let success = Some(CheckState::Success);
+        assert_eq!(
+            autopilot_verdict(false, &files, &manifests, success, true),
+            Ok("serde 1.0.1 → 1.0.2".to_string())
+        );
+        assert_eq!(
+            autopilot_verdict(false, &files, &[], success, false),
+            Ok("2 file(s), checks green".to_string())
+        );
+    }
+
+    #[test]
+    fn drafts_other_files_and_red_checks_disqualify() {
+        let manifests = [manifest("Cargo.toml", "1.0.1", "1.0.2")];
+        let success = Some(CheckState::Success);
+        let cargo = files(&["Cargo.toml"]);
+        assert_eq!(
+            autopilot_verdict(true, &cargo, &manifests, success, false),
+            Err("draft".to_string())
+        );
+        assert_eq!(
+            autopilot_verdict(
+                false,
+                &files(&["Cargo.toml", "src/main.rs"]),
+                &manifests,
+                success,
+                false
+            ),
+            Err("touches src/main.rs".to_string())
+        );
+        assert_eq!(
+            autopilot_verdict(false, &cargo, &manifests, None, false),
+            Err("no checks reported".to_string())
+        );
+        assert_eq!(
+            autopilot_verdict(false, &cargo, &manifests, Some(CheckState::Pending), false),
+            Err("checks pending".to_string())
+        );
+        assert_eq!(
+            autopilot_verdict(false, &cargo, &manifests, Some(CheckState::Failure), false),
+            Err("checks failing".to_string())
+        );
+    }
+
+    #[test]
+    fn only_patch_needs_explained_patch_upgrades() {
+        let success = Some(CheckState::Success);
+        let cargo = files(&["Cargo.toml"]);
+        assert_eq!(
+            autopilot_verdict(
+                false,
+                &cargo,
+                &[manifest("Cargo.toml", "1.0.1", "1.1.0")],
+                success,
+                true
+            ),
+            Err("minor of serde".to_string())
+        );
+        assert_eq!(
+            autopilot_verdict(
+                false,
+                &files(&["package.json", "package-lock.json"]),
+                &[manifest("package.json", "1.0.1", "1.0.2")],
+                success,
+                true
+            ),
+            Err("touches package-lock.json, which isn't compared".to_string())
+        );
+
+        let mut patched = manifest("Cargo.toml", "1.0.1", "1.0.2");
+        patched.other_changes = true;
+        assert_eq!(
+            autopilot_verdict(false, &cargo, &[patched], success, true),
+            Err("Cargo.toml changes more than versions".to_string())
+        );
+        assert_eq!(
+            autopilot_verdict(false, &cargo, &[], success, true),
+            Err("no version change found in the manifests".to_string())
+        );
+    }
+}
@@ -0,0 +1,327 @@
+// Binary, Git LFS and oversized files in a PR.
+//
+// GitHub's diff has no content for binary files ("Binary files ... differ") and the files
+// API omits the patch for them and for very large text files, so without help they show up
+// as changes of nothing. This module reports what actually changed instead: the blob size
+// before and after (from the git trees API), and for Git LFS pointers the size of the real
+// object as recorded in the pointer. The same sizes back the `checks --max-file-size` gate.
+use crate::debug_log;
+use crate::providers::github::actions::human_size;
+use crate::providers::github::models::*;
+use crate::providers::methods::PrNumber;
+use crate::status_log;
+use crate::table::{self, Shrink};
+use crate::ui_println;
+use colored::Colorize;
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use tabled::Tabled;
+
+/// First line of every Git LFS pointer file.
+const LFS_POINTER: &str = "version https://git-lfs.github.com/spec/v1";
+
+/// A binary or LFS file changed by a PR.
+///
+/// Fields:
+/// - `path`: Repo-relative path
+/// - `status`: `added`, `removed`, `modified`, ... (from the files API)
+/// - `lfs`: Whether the file is a Git LFS pointer (sizes are then those of the real object)
+/// - `before`: Size on the base commit (`None` if added, or unknown)
+/// - `after`: Size on the head commit (`None` if removed, or unknown)
+pub(crate) struct BinaryChange {
+    pub path: String,
+    pub status: String,
+    pub lfs: bool,
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+}
+
+/// One row of the binary files report.
+#[derive(Tabled)]
+struct BinaryRow {
+    #[tabled(rename = "File")]
+    path: String,
+    #[tabled(rename = "Kind")]
+    kind: String,
+    #[tabled(rename = "Before")]
+    before: String,
+    #[tabled(rename = "After")]
+    after: String,
+    #[tabled(rename = "Change")]
+    change: String,
+}
+
+impl GitHubProvider {
+    /// Finds the binary and LFS files among a PR's changed files and sizes them.
+    ///
+    /// Files without a patch are treated as binary (GitHub also omits the patch of huge
+    /// text diffs, which are worth reporting just the same).
+    ///
+    /// # Parameters
+    /// - `base_sha` / `head_sha`: The PR's base and head commits
+    /// - `files`: The PR's changed files (from `pull_request_files`)
+    pub(crate) fn binary_changes(
+        &self,
+        owner: &str,
+        repo: &str,
+        base_sha: &str,
+        head_sha: &str,
+        files: &[GitHubPRFile],
+    ) -> Vec<BinaryChange> {
+        let mut changes = Vec::new();
+        let mut unsized_files: Vec<&GitHubPRFile> = Vec::new();
+        for file in files {
+            match file.patch.as_deref() {
+                Some(patch) if patch.contains(LFS_POINTER) => {
+                    let (before, after) = lfs_sizes(patch, &file.status);
+                    changes.push(BinaryChange {
+                        path: file.filename.clone(),
+                        status: file.status.clone(),
+                        lfs: true,
+                        before,
+                        after,
+                    });
+                }
+                Some(_) => {}
+                None => unsized_files.push(file),
+            }
+        }
+        if unsized_files.is_empty() {
+            return changes;
+        }
+
+        // Blob sizes on both sides, skipping the side where the file doesn't exist
+        let old_paths: Vec<&str> = unsized_files
+            .iter()
+            .filter(|f| f.status != "added")
+            .map(|f| f.filename.as_str())
+            .collect();
+        let new_paths: Vec<&str> = unsized_files
+            .iter()
+            .filter(|f| f.status != "removed")
+            .map(|f| f.filename.as_str())
+            .collect();
+        let before = self.blob_sizes(owner, repo, base_sha, &old_paths);
+        let after = self.blob_sizes(owner, repo, head_sha, &new_paths);
+
+        for file in unsized_files {
+            changes.push(BinaryChange {
+                path: file.filename.clone(),
+                status: file.status.clone(),
+                lfs: false,
+                before: before.get(&file.filename).copied(),
+                after: after.get(&file.filename).copied(),
+            });
+        }
+        changes.sort_by(|a, b| a.path.cmp(&b.path));
+        changes
+    }
+
+    /// Changed files that are larger than `threshold` bytes on the head commit.
+    ///
+    /// LFS files count with the size of the real object, not of the pointer.
+    fn oversized_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        base_sha: &str,
+        head_sha: &str,
+        files: &[GitHubPRFile],
+        threshold: u64,
+    ) -> Vec<(String, u64)> {
+        let paths: Vec<&str> = files
+            .iter()
+            .filter(|f| f.status != "removed")
+            .map(|f| f.filename.as_str())
+            .collect();
+        let mut sizes = self.blob_sizes(owner, repo, head_sha, &paths);
+        for lfs in self
+            .binary_changes(owner, repo, base_sha, head_sha, files)
+            .into_iter()
+            .filter(|c| c.lfs)
+        {
+            if let Some(size) = lfs.after {
+                sizes.insert(lfs.path, size);
+            }
+        }
+
+        let mut oversized: Vec<(String, u64)> = sizes
+            .into_iter()
+            .filter(|(_, size)| *size > threshold)
+            .collect();
+        oversized.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+        oversized
+    }
+
+    /// Flags the changed files of a PR that are larger than `limit` bytes.
+    ///
+    /// # Returns
+    /// - `Err` naming the oversized files, so `checks` fails like a failed CI check.
+    pub(crate) fn check_file_sizes(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+        limit: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let pr = self.fetch_pull_request(owner, repo, pr_number)?;
+        let files = self.pull_request_files(owner, repo, pr_number)?;
+        let oversized =
+            self.oversized_files(owner, repo, &pr.base.sha, &pr.head.sha, &files, limit);
+        if oversized.is_empty() {
+            status_log!(
+                "{}",
+                format!("✅ No changed file is larger than {}", human_size(limit)).green()
+            );
+            return Ok(());
+        }
+
+        ui_println!(
+            "{}",
+            format!(
+                "⚠️  {} file(s) larger than {}:",
+                oversized.len(),
+                human_size(limit)
+            )
+            .yellow()
+        );
+        for (path, size) in &oversized {
+            println!("   {} ({})", path, human_size(*size));
+        }
+        Err(format!(
+            "{} file(s) exceed the {} limit",
+            oversized.len(),
+            human_size(limit)
+        )
+        .into())
+    }
+
+    /// Sizes of `paths` on commit `sha`.
+    ///
+    /// One recursive tree listing covers every path; if GitHub truncates it (very large
+    /// repositories), the missing paths are looked up one by one. Paths that can't be
+    /// sized are left out.
+    fn blob_sizes(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+        paths: &[&str],
+    ) -> BTreeMap<String, u64> {
+        let mut sizes = BTreeMap::new();
+        if paths.is_empty() {
+            return sizes;
+        }
+        let wanted: HashSet<&str> = paths.iter().copied().collect();
+
+        let url = format!(
+            "{}/repos/{}/{}/git/trees/{}?recursive=1",
+            self.api_base, owner, repo, sha
+        );
+        let mut truncated = true;
+        match self.get::<GitHubTree>(&url, "Failed to list the tree") {
+            Ok(tree) => {
+                truncated = tree.truncated;
+                for entry in tree.tree {
+                    if let Some(size) = entry.size {
+                        if wanted.contains(entry.path.as_str()) {
+                            sizes.insert(entry.path, size);
+                        }
+                    }
+                }
+            }
+            Err(e) => debug_log!("[DEBUG] Can't list tree {}: {}", sha, e),
+        }
+
+        if truncated {
+            let missing: Vec<&str> = wanted
+                .into_iter()
+                .filter(|p| !sizes.contains_key(*p))
+                .collect();
+            for path in missing {
+                let url = format!(
+                    "{}/repos/{}/{}/contents/{}?ref={}",
+                    self.api_base, owner, repo, path, sha
+                );
+                match self.get::<GitHubContentMeta>(&url, "Failed to fetch file metadata") {
+                    Ok(meta) => {
+                        sizes.insert(path.to_string(), meta.size);
+                    }
+                    Err(e) => debug_log!("[DEBUG] Can't size {}: {}", path, e),
+                }
+            }
+        }
+        sizes
+    }
+}
+
+/// Whether a unified diff has binary files or LFS pointers worth a size report.
+pub(crate) fn has_binary_changes(diff: &str) -> bool {
+    diff.contains("\nBinary files ") || diff.contains(LFS_POINTER)
+}
+
+/// Real object sizes recorded in the old and new side of an LFS pointer diff.
+///
+/// A pointer line `size 1234` is prefixed with `-`, `+` or (unchanged) a space.
+fn lfs_sizes(patch: &str, status: &str) -> (Option<u64>, Option<u64>) {
+    let (mut before, mut after) = (None, None);
+    for line in patch.lines() {
+        let mut chars = line.chars();
+        let side = chars.next();
+        let Some(size) = chars
+            .as_str()
+            .strip_prefix("size ")
+            .and_then(|s| s.trim().parse().ok())
+        else {
+            continue;
+        };
+        match side {
+            Some('-') => before = Some(size),
+            Some('+') => after = Some(size),
+            _ => {
+                before = Some(size);
+                after = Some(size);
+            }
+        }
+    }
+    match status {
+        "added" => (None, after),
+        "removed" => (before, None),
+        _ => (before, after),
+    }
+}
+
+/// Renders the binary files report, e.g. for the top of `show-diff`.
+///
+/// # Returns
+/// - An empty string if there are no binary or LFS changes.
+pub(crate) fn render_binary_changes(changes: &[BinaryChange]) -> String {
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    let size = |s: Option<u64>| s.map_or_else(|| "-".to_string(), human_size);
+    let rows: Vec<BinaryRow> = changes
+        .iter()
+        .map(|c| BinaryRow {
+            path: c.path.clone(),
+            kind: if c.lfs { "LFS" } else { "binary" }.to_string(),
+            before: size(c.before),
+            after: size(c.after),
+            change: match (c.status.as_str(), c.before, c.after) {
+                ("added" | "removed", _, _) => c.status.clone(),
+                (_, Some(before), Some(after)) if after >= before => {
+                    format!("+{}", human_size(after - before))
+                }
+                (_, Some(before), Some(after)) => format!("-{}", human_size(before - after)),
+                _ => "?".to_string(),
+            },
+        })
+        .collect();
+
+    format!(
+        "📦 Binary and LFS files ({}):\n{}\n",
+        changes.len(),
+        table::render(rows, &[(0, Shrink::Truncate)])
+    )
+}
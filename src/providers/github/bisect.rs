@@ -0,0 +1,227 @@
+// `git pr bisect <n> -- <command>`: which commit of the PR broke it?
+//
+// A PR of a dozen commits that fails its tests at the head leaves the question of where the
+// failure came in. The PR's commits (oldest first, as the API lists them) are tested in a
+// throwaway worktree (see `crate::worktree` and `git pr run`) with a binary search: the base
+// is taken as good, the head must be bad, and every step halves the range in between. The
+// command's exit status is read the way `git bisect run` reads it:
+//
+//   0          good
+//   125        skip (the commit can't be tested, e.g. it doesn't build)
+//   anything   bad
+//
+// A skipped commit is replaced by its nearest untested neighbour; if everything left is
+// skipped, the answer is the range the failure lies in. `--check-base` also runs the
+// command on the base first, since a failure that is already there isn't the PR's doing.
+use crate::progress;
+use crate::providers::github::models::*;
+use crate::providers::methods::{BisectOptions, PrNumber};
+use crate::status_log;
+use crate::ui_println;
+use crate::worktree::{self, Worktree};
+use colored::Colorize;
+use std::error::Error;
+use std::path::Path;
+use std::process::Command;
+
+/// Exit code by which the command says "can't test this commit" (as in `git bisect run`).
+const SKIP_CODE: i32 = 125;
+
+/// What running the command on one commit said.
+#[derive(Clone, Copy, PartialEq)]
+enum Verdict {
+    Good,
+    Bad,
+    Skip,
+}
+
+impl Verdict {
+    /// Reads an exit code (see the module comment).
+    fn of(code: i32) -> Verdict {
+        match code {
+            0 => Verdict::Good,
+            SKIP_CODE => Verdict::Skip,
+            _ => Verdict::Bad,
+        }
+    }
+
+    /// How a step is reported.
+    fn label(self) -> String {
+        match self {
+            Verdict::Good => "good".green().to_string(),
+            Verdict::Bad => "bad".red().to_string(),
+            Verdict::Skip => "skip".yellow().to_string(),
+        }
+    }
+}
+
+/// First line of a commit message.
+fn subject(commit: &GitHubPRCommit) -> &str {
+    commit.commit.message.lines().next().unwrap_or("")
+}
+
+/// Abbreviated commit id.
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+/// Checks out `commit` in the worktree and runs the command there.
+///
+/// # Returns
+/// - The verdict on the commit
+/// - `Err` if the checkout fails, the command can't be started or Ctrl-C stopped it
+fn test_commit(dir: &Path, commit: &str, command: &[String]) -> Result<Verdict, Box<dyn Error>> {
+    let (program, args) = command
+        .split_first()
+        .ok_or("No command given; put it after `--`")?;
+    worktree::checkout(dir, commit)?;
+    let status = worktree::run(Command::new(program).args(args).current_dir(dir))
+        .map_err(|e| format!("Could not run '{}': {}", program, e))?;
+    if worktree::interrupted() {
+        return Err("Bisect interrupted".into());
+    }
+    Ok(Verdict::of(status.code().unwrap_or(1)))
+}
+
+/// The untested index between `lo` and `hi` (both exclusive) closest to their middle.
+fn next_probe(lo: isize, hi: isize, skipped: &[bool]) -> Option<isize> {
+    let mid = lo + (hi - lo) / 2;
+    (0..hi - lo)
+        .flat_map(|distance| [mid - distance, mid + distance])
+        .find(|&i| i > lo && i < hi && !skipped[i as usize])
+}
+
+impl GitHubProvider {
+    /// Finds the first commit of a PR for which the command fails (see the module comment).
+    ///
+    /// # Returns
+    /// - `Ok(())` after reporting the culprit, or that the head passes
+    /// - `Err` if the PR can't be fetched, the command can't be run, or the base already fails
+    pub(crate) fn bisect_pull_request(
+        &self,
+        pr_number: PrNumber,
+        options: &BisectOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        if options.command.is_empty() {
+            return Err("No command given; put it after `--`".into());
+        }
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let commits = self.pull_request_commits(&owner, &repo, pr_number)?;
+        let head = commits
+            .last()
+            .ok_or_else(|| format!("PR #{} has no commits", pr_number))?;
+        self.ensure_pull_request_head(pr_number, &head.sha)?;
+
+        let worktree = Worktree::add(&format!("bisect-{}", pr_number), &head.sha, options.keep)?;
+        self.bisect_in(&worktree.dir, pr_number, &commits, options)
+    }
+
+    /// The search itself, in an existing worktree.
+    fn bisect_in(
+        &self,
+        dir: &Path,
+        pr_number: PrNumber,
+        commits: &[GitHubPRCommit],
+        options: &BisectOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        let total = commits.len();
+        let shown = options.command.join(" ");
+        status_log!(
+            "🔎 Bisecting {} commit(s) of PR #{} with `{}`",
+            total,
+            pr_number,
+            shown
+        );
+
+        let steps = std::cell::Cell::new(0);
+        let step = |index: usize| -> Result<Verdict, Box<dyn Error>> {
+            let commit = &commits[index];
+            steps.set(steps.get() + 1);
+            progress::report(
+                "bisect",
+                steps.get(),
+                None,
+                &format!("{} {}", short(&commit.sha), subject(commit)),
+            );
+            status_log!(
+                "🔎 [{}/{}] {} {}",
+                index + 1,
+                total,
+                short(&commit.sha),
+                subject(commit)
+            );
+            let verdict = test_commit(dir, &commit.sha, &options.command)?;
+            status_log!("   → {}", verdict.label());
+            Ok(verdict)
+        };
+
+        match step(total - 1)? {
+            Verdict::Good => {
+                ui_println!(
+                    "✅ `{}` passes on the head of PR #{}; nothing to bisect.",
+                    shown,
+                    pr_number
+                );
+                return Ok(());
+            }
+            Verdict::Skip => {
+                return Err(
+                    "The command skipped (exit 125) the PR's head, so there is no failure to trace"
+                        .into(),
+                );
+            }
+            Verdict::Bad => {}
+        }
+
+        if options.check_base {
+            let base = format!("{}^", commits[0].sha);
+            status_log!("🔎 [base] {}^", short(&commits[0].sha));
+            let verdict = test_commit(dir, &base, &options.command)?;
+            status_log!("   → {}", verdict.label());
+            if verdict == Verdict::Bad {
+                return Err(format!(
+                    "`{}` already fails on the PR's base, so the failure isn't introduced by the PR",
+                    shown
+                )
+                .into());
+            }
+        }
+
+        // `lo` is the last commit known good (-1: the base), `hi` the first known bad
+        let mut lo: isize = -1;
+        let mut hi: isize = total as isize - 1;
+        let mut skipped = vec![false; total];
+        while let Some(probe) = next_probe(lo, hi, &skipped) {
+            match step(probe as usize)? {
+                Verdict::Good => lo = probe,
+                Verdict::Bad => hi = probe,
+                Verdict::Skip => skipped[probe as usize] = true,
+            }
+        }
+
+        let culprit = &commits[hi as usize];
+        let candidates = &commits[(lo + 1) as usize..=hi as usize];
+        if candidates.len() == 1 {
+            ui_println!(
+                "🎯 First failing commit of PR #{}: {} {}",
+                pr_number,
+                short(&culprit.sha).yellow(),
+                subject(culprit)
+            );
+            if !culprit.html_url.is_empty() {
+                ui_println!("   {}", culprit.html_url);
+            }
+        } else {
+            ui_println!(
+                "🎯 The failure came in with one of these {} commits (the skipped ones couldn't be told apart):",
+                candidates.len()
+            );
+            for commit in candidates {
+                ui_println!("   {} {}", short(&commit.sha).yellow(), subject(commit));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,317 @@
+// `git blame` on a PR's base commit, and `git pr show-diff --blame`.
+//
+// The lines a PR removes or changes exist on its base commit, so blaming that commit tells
+// who last touched them. The base is usually present locally already; if not, it is
+// fetched from the remote by SHA. `show-diff --blame` then maps each blamed commit to the
+// PR that introduced it (GitHub's commit → PR association) and annotates the removed lines
+// with the author, date and PR, so reviewers know who to loop in about a risky change.
+use crate::debug_log;
+use crate::providers::github::models::*;
+use crate::utils::run_git;
+use chrono::DateTime;
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::error::Error;
+
+/// How many distinct commits are mapped to the PR that introduced them (one call each).
+const MAX_ASSOCIATED_COMMITS: usize = 30;
+
+/// Who last touched a blamed commit's lines.
+///
+/// Fields:
+/// - `author`: Author name as recorded in git
+/// - `time`: Author time, seconds since the Unix epoch
+#[derive(Clone, Default)]
+pub(crate) struct BlameCommit {
+    pub author: String,
+    pub time: i64,
+}
+
+/// `git blame` of a line range.
+///
+/// Fields:
+/// - `lines`: Commit SHA per line number (of the blamed revision)
+/// - `commits`: Author data per commit SHA
+#[derive(Default)]
+pub(crate) struct Blame {
+    pub lines: BTreeMap<usize, String>,
+    pub commits: BTreeMap<String, BlameCommit>,
+}
+
+impl GitHubProvider {
+    /// Makes sure `sha` is available locally, fetching it from the remote if needed.
+    ///
+    /// # Returns
+    /// - `false` if the commit is missing and can't be fetched (e.g. the server refuses
+    ///   fetching by SHA, or there is no network).
+    pub(crate) fn ensure_commit(&self, sha: &str) -> bool {
+        let commit = format!("{}^{{commit}}", sha);
+        if run_git(&["cat-file", "-e", &commit]).is_some() {
+            return true;
+        }
+        debug_log!("[DEBUG] Fetching {} from {}", sha, self.remote);
+        run_git(&["fetch", "--quiet", "--no-tags", &self.remote, sha]).is_some()
+    }
+
+    /// Annotates the removed lines of a PR diff with who last touched them, and in which PR.
+    ///
+    /// # Parameters
+    /// - `owner`, `repo`: The repository of the PR
+    /// - `base_sha`: The PR's base commit, which holds the removed lines
+    /// - `diff`: The unified diff of the PR
+    ///
+    /// # Returns
+    /// - The diff, colored, with `⟵ author, date (sha, #pr)` after the first removed line
+    ///   of every run of lines from the same commit.
+    /// - `Err` if the base commit isn't available locally and can't be fetched.
+    pub(crate) fn annotate_diff(
+        &self,
+        owner: &str,
+        repo: &str,
+        base_sha: &str,
+        diff: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        if !self.ensure_commit(base_sha) {
+            return Err(format!(
+                "Base commit {} isn't available locally; run `git fetch {}` and retry",
+                short(base_sha),
+                self.remote
+            )
+            .into());
+        }
+
+        // Blame the old side of every hunk, per file
+        let mut blames: BTreeMap<String, Blame> = BTreeMap::new();
+        for (path, ranges) in old_hunks(diff) {
+            let mut file_blame = Blame::default();
+            for (start, len) in ranges {
+                if let Some(b) = blame(base_sha, &path, start, len) {
+                    file_blame.lines.extend(b.lines);
+                    file_blame.commits.extend(b.commits);
+                }
+            }
+            blames.insert(path, file_blame);
+        }
+
+        // Commit → PR that introduced it, for the commits covering the most removed lines
+        let mut line_counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for (path, lines) in removed_lines(diff) {
+            if let Some(b) = blames.get(&path) {
+                for line in lines {
+                    if let Some(sha) = b.lines.get(&line) {
+                        *line_counts.entry(sha.as_str()).or_default() += 1;
+                    }
+                }
+            }
+        }
+        let mut commits: Vec<(&str, usize)> = line_counts.into_iter().collect();
+        commits.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+        let prs: BTreeMap<String, u32> = commits
+            .into_iter()
+            .take(MAX_ASSOCIATED_COMMITS)
+            .filter_map(|(sha, _)| {
+                self.commit_pull_request(owner, repo, sha)
+                    .map(|n| (sha.to_string(), n))
+            })
+            .collect();
+
+        Ok(render(diff, &blames, &prs))
+    }
+
+    /// The PR that introduced a commit, if GitHub associates one with it.
+    fn commit_pull_request(&self, owner: &str, repo: &str, sha: &str) -> Option<u32> {
+        let url = format!(
+            "{}/repos/{}/{}/commits/{}/pulls",
+            self.api_base, owner, repo, sha
+        );
+        match self.get::<Vec<GitHubCommitPullRequest>>(&url, "Failed to fetch PRs of commit") {
+            Ok(prs) => prs.first().map(|pr| pr.number),
+            Err(e) => {
+                debug_log!("[DEBUG] No PR for commit {}: {}", sha, e);
+                None
+            }
+        }
+    }
+}
+
+/// Runs `git blame --porcelain` on `len` lines of `path` at `commit`, from line `start`.
+///
+/// # Returns
+/// - `None` if git fails (e.g. the file doesn't exist at `commit`).
+pub(crate) fn blame(commit: &str, path: &str, start: usize, len: usize) -> Option<Blame> {
+    let range = format!("{},+{}", start, len);
+    let porcelain = run_git(&["blame", "--porcelain", "-L", &range, commit, "--", path])?;
+
+    // Every line starts with `<sha> <orig line> <final line> [<group size>]`; the
+    // commit's metadata (author, author-time, ...) follows only its first appearance
+    let mut blame = Blame::default();
+    let mut current = String::new();
+    for line in porcelain.lines() {
+        let parts: Vec<&str> = line.split(' ').collect();
+        if (parts.len() == 3 || parts.len() == 4)
+            && parts[0].len() == 40
+            && parts[0].chars().all(|c| c.is_ascii_hexdigit())
+        {
+            current = parts[0].to_string();
+            if let Ok(number) = parts[2].parse() {
+                blame.lines.insert(number, current.clone());
+            }
+            blame.commits.entry(current.clone()).or_default();
+        } else if let Some(author) = line.strip_prefix("author ") {
+            if let Some(c) = blame.commits.get_mut(&current) {
+                c.author = author.to_string();
+            }
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            if let Some(c) = blame.commits.get_mut(&current) {
+                c.time = time.parse().unwrap_or_default();
+            }
+        }
+    }
+    Some(blame)
+}
+
+/// Old-side line ranges (`start`, `length`) of the hunks in a unified diff patch.
+///
+/// Pure additions (`@@ -12,0 ...`) have no old lines and are skipped.
+pub(crate) fn old_ranges(patch: &str) -> Vec<(usize, usize)> {
+    patch
+        .lines()
+        .filter_map(|line| line.strip_prefix("@@ -"))
+        .filter_map(|rest| {
+            let old = rest.split_whitespace().next()?;
+            let (start, len) = match old.split_once(',') {
+                Some((start, len)) => (start.parse().ok()?, len.parse().ok()?),
+                None => (old.parse().ok()?, 1),
+            };
+            (len > 0 && start > 0).then_some((start, len))
+        })
+        .collect()
+}
+
+/// Splits a multi-file diff into `(old path, section)` pairs; new files are left out.
+fn old_files(diff: &str) -> Vec<(String, String)> {
+    let mut files: Vec<(String, String)> = Vec::new();
+    let mut current: Option<(String, String)> = None;
+    for line in diff.lines() {
+        if line.starts_with("diff --git ") {
+            files.extend(current.take());
+        } else if let Some(path) = line.strip_prefix("--- a/") {
+            current = Some((path.to_string(), String::new()));
+        } else if let Some((_, section)) = current.as_mut() {
+            section.push_str(line);
+            section.push('\n');
+        }
+    }
+    files.extend(current);
+    files
+}
+
+/// Old-side hunk ranges per file of a multi-file diff.
+fn old_hunks(diff: &str) -> Vec<(String, Vec<(usize, usize)>)> {
+    old_files(diff)
+        .into_iter()
+        .map(|(path, section)| {
+            let ranges = old_ranges(&section);
+            (path, ranges)
+        })
+        .collect()
+}
+
+/// Old-side line numbers of the removed lines, per file of a multi-file diff.
+fn removed_lines(diff: &str) -> Vec<(String, Vec<usize>)> {
+    old_files(diff)
+        .into_iter()
+        .map(|(path, section)| {
+            let mut removed = Vec::new();
+            let mut old_line = 0;
+            for line in section.lines() {
+                if let Some(start) = hunk_old_start(line) {
+                    old_line = start;
+                } else if line.starts_with('-') {
+                    removed.push(old_line);
+                    old_line += 1;
+                } else if line.starts_with(' ') {
+                    old_line += 1;
+                }
+            }
+            (path, removed)
+        })
+        .collect()
+}
+
+/// First old-side line number of a hunk header (`@@ -12,3 +12,4 @@`).
+fn hunk_old_start(line: &str) -> Option<usize> {
+    let old = line.strip_prefix("@@ -")?.split_whitespace().next()?;
+    old.split(',').next()?.parse().ok()
+}
+
+/// Colors the diff and appends the blame annotations.
+fn render(diff: &str, blames: &BTreeMap<String, Blame>, prs: &BTreeMap<String, u32>) -> String {
+    let mut out = String::new();
+    let mut file: Option<&Blame> = None;
+    let mut old_line = 0;
+    // Commit of the previous removed line; only the first line of a run is annotated
+    let mut previous: Option<&str> = None;
+
+    for line in diff.lines() {
+        let mut annotation = None;
+        if line.starts_with("diff --git ") {
+            file = None;
+            previous = None;
+        } else if let Some(path) = line.strip_prefix("--- a/") {
+            file = blames.get(path);
+        }
+
+        let colored_line = if line.starts_with("diff --git ")
+            || line.starts_with("--- ")
+            || line.starts_with("+++ ")
+        {
+            line.bold().to_string()
+        } else if let Some(start) = hunk_old_start(line) {
+            old_line = start;
+            previous = None;
+            line.cyan().to_string()
+        } else if line.starts_with('-') {
+            let sha = file.and_then(|b| b.lines.get(&old_line));
+            if let (Some(sha), Some(b)) = (sha, file) {
+                if previous != Some(sha.as_str()) {
+                    annotation = Some(annotate(sha, b.commits.get(sha), prs.get(sha)));
+                }
+                previous = Some(sha.as_str());
+            }
+            old_line += 1;
+            line.red().to_string()
+        } else if line.starts_with('+') {
+            line.green().to_string()
+        } else {
+            if line.starts_with(' ') {
+                old_line += 1;
+            }
+            previous = None;
+            line.to_string()
+        };
+
+        out.push_str(&colored_line);
+        if let Some(annotation) = annotation {
+            out.push_str("   ");
+            out.push_str(&annotation.yellow().dimmed().to_string());
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// `⟵ author, 2024-05-01 (abc1234, #12)` for one blamed commit.
+fn annotate(sha: &str, commit: Option<&BlameCommit>, pr: Option<&u32>) -> String {
+    let commit = commit.cloned().unwrap_or_default();
+    let date = DateTime::from_timestamp(commit.time, 0)
+        .map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let pr = pr.map(|n| format!(", #{}", n)).unwrap_or_default();
+    format!("⟵ {}, {} ({}{})", commit.author, date, short(sha), pr)
+}
+
+/// Abbreviated commit SHA.
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
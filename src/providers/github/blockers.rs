@@ -0,0 +1,176 @@
+// Dependencies between pull requests, declared in PR descriptions.
+//
+// Teams sequence merges by writing "Depends on #123" or "Blocked by #120, #121" in a PR
+// body. Those references are picked up here: `show-details` lists them with their state,
+// and `list` marks PRs still waiting on an open dependency with ⛔. A dependency may be a
+// PR or an issue; it stops blocking once it is merged or closed.
+use crate::debug_log;
+use crate::providers::github::models::*;
+use crate::ui_println;
+use colored::Colorize;
+use regex::Regex;
+use std::collections::{BTreeMap, HashSet};
+
+/// Where a dependency stands.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DependencyState {
+    Open,
+    Merged,
+    Closed,
+}
+
+/// A PR or issue another PR depends on.
+///
+/// Fields:
+/// - `number`: The PR or issue number
+/// - `title`: Its title
+/// - `state`: Whether it is still open
+/// - `is_pr`: Whether it is a pull request (rather than an issue)
+pub(crate) struct Dependency {
+    pub number: u32,
+    pub title: String,
+    pub state: DependencyState,
+    pub is_pr: bool,
+}
+
+/// Lists the `#123` references following "depends on" or "blocked by" in a PR body.
+///
+/// Several references may follow one phrase ("Depends on #1, #2 and #3"); each number
+/// is returned once, in order of appearance.
+pub(crate) fn dependency_refs(body: &str) -> Vec<u32> {
+    let Ok(phrase) = Regex::new(
+        r"(?i)\b(?:depends\s+on|blocked\s+by)\s*:?\s*((?:#\d+\b(?:\s*(?:,|&|and)?\s*)?)+)",
+    ) else {
+        return Vec::new();
+    };
+    let Ok(reference) = Regex::new(r"#(\d+)") else {
+        return Vec::new();
+    };
+
+    let mut numbers = Vec::new();
+    for caps in phrase.captures_iter(body) {
+        for number in reference.captures_iter(&caps[1]) {
+            if let Ok(number) = number[1].parse() {
+                if !numbers.contains(&number) {
+                    numbers.push(number);
+                }
+            }
+        }
+    }
+    numbers
+}
+
+impl GitHubProvider {
+    /// Looks up the PRs and issues a PR depends on.
+    ///
+    /// # Parameters
+    /// - `numbers`: The referenced numbers, from [`dependency_refs`]
+    ///
+    /// # Returns
+    /// - The dependencies, in the given order; references that can't be fetched (typos,
+    ///   deleted issues) are skipped
+    pub(crate) fn pull_request_dependencies(
+        &self,
+        owner: &str,
+        repo: &str,
+        numbers: &[u32],
+    ) -> Vec<Dependency> {
+        numbers
+            .iter()
+            .filter_map(|&number| {
+                let url = format!(
+                    "{}/repos/{}/{}/issues/{}",
+                    self.api_base, owner, repo, number
+                );
+                let issue: GitHubIssue = self
+                    .get(&url, &format!("Failed to fetch #{}", number))
+                    .map_err(|e| debug_log!("[DEBUG] Skipping dependency #{}: {}", number, e))
+                    .ok()?;
+
+                let merged = issue
+                    .pull_request
+                    .as_ref()
+                    .is_some_and(|pr| !pr["merged_at"].is_null());
+                let state = match issue.state.as_str() {
+                    "open" => DependencyState::Open,
+                    _ if merged => DependencyState::Merged,
+                    _ => DependencyState::Closed,
+                };
+                Some(Dependency {
+                    number,
+                    title: issue.title,
+                    state,
+                    is_pr: issue.pull_request.is_some(),
+                })
+            })
+            .collect()
+    }
+
+    /// PRs among `prs` that depend on a PR or issue which is still open.
+    ///
+    /// # Parameters
+    /// - `prs`: `(number, body)` of every PR to check
+    ///
+    /// # Returns
+    /// - The numbers of the blocked PRs; every referenced number is fetched only once
+    pub(crate) fn blocked_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        prs: &[(u32, &str)],
+    ) -> HashSet<u32> {
+        let refs: BTreeMap<u32, Vec<u32>> = prs
+            .iter()
+            .map(|&(number, body)| (number, dependency_refs(body)))
+            .filter(|(_, refs)| !refs.is_empty())
+            .collect();
+        let mut wanted: Vec<u32> = refs.values().flatten().copied().collect();
+        wanted.sort_unstable();
+        wanted.dedup();
+
+        let open: HashSet<u32> = self
+            .pull_request_dependencies(owner, repo, &wanted)
+            .into_iter()
+            .filter(|d| d.state == DependencyState::Open)
+            .map(|d| d.number)
+            .collect();
+        refs.into_iter()
+            .filter(|(_, refs)| refs.iter().any(|n| open.contains(n)))
+            .map(|(number, _)| number)
+            .collect()
+    }
+}
+
+/// Prints the "Dependencies" section of `show-details`; nothing when there are none.
+pub(crate) fn print_dependencies(dependencies: &[Dependency]) {
+    if dependencies.is_empty() {
+        return;
+    }
+
+    ui_println!(
+        "{}",
+        format!("🔗 Dependencies ({}):", dependencies.len()).bold()
+    );
+    for dependency in dependencies {
+        let kind = if dependency.is_pr { "PR" } else { "issue" };
+        let state = match dependency.state {
+            DependencyState::Open => format!("⏳ open {}", kind).yellow().to_string(),
+            DependencyState::Merged => "✅ merged".green().to_string(),
+            DependencyState::Closed => format!("✔️  closed {}", kind).dimmed().to_string(),
+        };
+        println!("   #{} {} — {}", dependency.number, dependency.title, state);
+    }
+
+    let waiting: Vec<String> = dependencies
+        .iter()
+        .filter(|d| d.state == DependencyState::Open)
+        .map(|d| format!("#{}", d.number))
+        .collect();
+    if !waiting.is_empty() {
+        ui_println!(
+            "{} waiting on {}",
+            "⛔ Blocked:".red().bold(),
+            waiting.join(", ")
+        );
+    }
+}
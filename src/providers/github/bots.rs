@@ -0,0 +1,296 @@
+// `git pr bots`: the open PRs of dependency bots, grouped by ecosystem and dependency.
+//
+// Dependabot and Renovate open one PR per dependency update, and a busy repository
+// quickly collects dozens of them. This view reads what each PR updates from its title
+// ("Bump serde from 1.0.200 to 1.0.201", "Update Rust crate serde to 1.0.201"), falling
+// back to the version table Renovate puts in the description, and which ecosystem it
+// belongs to from the branch name (`dependabot/cargo/...`) or Renovate's title wording.
+// PRs updating the same dependency (several directories, superseded updates) share a row,
+// with the size of the version jump and a link to the changelog found in the description.
+//
+// PRs count as bot PRs when their author is an app (`...[bot]`); `--author` names the
+// accounts instead, e.g. a self-hosted Renovate user. `--rebase` asks the bot to rebase
+// every listed PR: a `@dependabot rebase` comment, or ticking Renovate's rebase checkbox.
+use crate::debug_log;
+use crate::links::link;
+use crate::manifests;
+use crate::providers::github::autopilot::is_author;
+use crate::providers::github::dependencies::jump_label;
+use crate::providers::github::models::*;
+use crate::providers::methods::BotsOptions;
+use crate::status_log;
+use crate::table::{self, Shrink};
+use crate::ui_eprintln;
+use crate::ui_println;
+use colored::Colorize;
+use regex::Regex;
+use reqwest::Method;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::error::Error;
+use tabled::Tabled;
+
+/// Marks the rebase checkbox in the description of Renovate's PRs.
+const RENOVATE_REBASE: &str = "<!-- rebase-check -->";
+
+/// One row of a bots table: one dependency.
+#[derive(Tabled)]
+struct BotRow {
+    #[tabled(rename = "Dependency")]
+    dependency: String,
+    #[tabled(rename = "PRs")]
+    prs: String,
+    #[tabled(rename = "From")]
+    from: String,
+    #[tabled(rename = "To")]
+    to: String,
+    #[tabled(rename = "Jump")]
+    jump: String,
+    #[tabled(rename = "Changelog")]
+    changelog: String,
+}
+
+/// What a bot PR updates.
+///
+/// Fields:
+/// - `ecosystem`: Package ecosystem (`cargo`, `npm`, `github-actions`, ...), `other` if unknown
+/// - `dependency`: The dependency, or the group for grouped updates
+/// - `from` / `to`: The versions, when the title or description names them
+/// - `changelog`: Release notes or changelog linked from the description
+struct BotUpdate {
+    ecosystem: String,
+    dependency: String,
+    from: Option<String>,
+    to: Option<String>,
+    changelog: Option<String>,
+}
+
+/// Bot PRs by ecosystem, then by dependency (lowercased), with what each updates.
+type Groups<'a> = BTreeMap<String, BTreeMap<String, Vec<(&'a BasicGitHubPR, BotUpdate)>>>;
+
+/// Reads what a bot PR updates from its title, branch and description (see the module
+/// comment).
+fn parse_update(title: &str, head_ref: &str, body: &str) -> BotUpdate {
+    // Conventional-commit and security prefixes: "chore(deps): ", "[Security] "
+    let prefix =
+        Regex::new(r"^(?:\[[^\]]*\]\s*)?(?:[a-z]+(?:\([^)]*\))?!?:\s*)?(?:\[[^\]]*\]\s*)?")
+            .expect("valid regex");
+    let title = prefix.replace(title.trim(), "").to_string();
+
+    let dependabot = Regex::new(r"(?i)^bump (\S+) from v?(\S+) to v?(\S+)").expect("valid regex");
+    let group = Regex::new(r"(?i)^bump the (\S+) group").expect("valid regex");
+    let renovate = Regex::new(
+        r"(?i)^update (?:(dependency|module|rust crate|\S+ crate|docker tag) )?(\S+?)(?: (action|digest))? to v?(\S+)",
+    )
+    .expect("valid regex");
+
+    let mut update = BotUpdate {
+        ecosystem: String::new(),
+        dependency: title.clone(),
+        from: None,
+        to: None,
+        changelog: None,
+    };
+    let mut kind = String::new();
+    if let Some(caps) = dependabot.captures(&title) {
+        update.dependency = caps[1].to_string();
+        update.from = Some(caps[2].to_string());
+        update.to = Some(caps[3].to_string());
+    } else if let Some(caps) = group.captures(&title) {
+        update.dependency = format!("{} group", &caps[1]);
+    } else if let Some(caps) = renovate.captures(&title) {
+        update.dependency = caps[2].to_string();
+        update.to = Some(caps[4].to_string());
+        kind = caps
+            .get(1)
+            .or(caps.get(3))
+            .map_or(String::new(), |m| m.as_str().to_lowercase());
+    }
+
+    // Renovate's description lists `from` -> `to` for each update
+    if update.from.is_none() {
+        let versions = Regex::new(r"`v?([^`\s]+)` -> `v?([^`\s]+)`").expect("valid regex");
+        if let Some(caps) = versions.captures(body) {
+            update.from = Some(caps[1].to_string());
+            update.to.get_or_insert_with(|| caps[2].to_string());
+        }
+    }
+
+    update.ecosystem = match head_ref.strip_prefix("dependabot/") {
+        Some(rest) => rest
+            .split('/')
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches("_and_yarn")
+            .trim_end_matches("_modules")
+            .replace('_', "-"),
+        None => match kind.as_str() {
+            "rust crate" => "cargo".to_string(),
+            "module" => "go".to_string(),
+            "action" => "github-actions".to_string(),
+            "docker tag" | "digest" => "docker".to_string(),
+            _ => "other".to_string(),
+        },
+    };
+
+    let url = Regex::new(r#"https?://[^\s)"'<>\]]+"#).expect("valid regex");
+    update.changelog = url
+        .find_iter(body)
+        .map(|m| m.as_str())
+        .find(|u| {
+            let lower = u.to_lowercase();
+            lower.contains("/releases")
+                || lower.contains("changelog")
+                || lower.contains("/compare/")
+        })
+        .map(str::to_string);
+    update
+}
+
+impl GitHubProvider {
+    /// Prints the open bot PRs grouped by ecosystem and dependency and, with `rebase`,
+    /// asks the bots to rebase them (see the module comment).
+    pub(crate) fn print_bot_pull_requests(
+        &self,
+        options: &BotsOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let prs: Vec<BasicGitHubPR> = self
+            .open_pull_requests(&owner, &repo, None)?
+            .into_iter()
+            .filter(|pr| {
+                if options.authors.is_empty() {
+                    pr.user.login.ends_with("[bot]")
+                } else {
+                    is_author(&pr.user.login, &options.authors)
+                }
+            })
+            .collect();
+        if prs.is_empty() {
+            status_log!("✅ No open PRs by dependency bots.");
+            return Ok(());
+        }
+
+        let mut groups: Groups = BTreeMap::new();
+        for pr in &prs {
+            let update = parse_update(
+                &pr.title,
+                &pr.head.ref_name,
+                pr.body.as_deref().unwrap_or(""),
+            );
+            debug_log!(
+                "[DEBUG] PR #{}: {} / {} {:?} → {:?}",
+                pr.number,
+                update.ecosystem,
+                update.dependency,
+                update.from,
+                update.to
+            );
+            groups
+                .entry(update.ecosystem.clone())
+                .or_default()
+                .entry(update.dependency.to_lowercase())
+                .or_default()
+                .push((pr, update));
+        }
+
+        ui_println!("{}", format!("🤖 {} open bot PR(s):", prs.len()).bold());
+        for (ecosystem, dependencies) in &mut groups {
+            let count: usize = dependencies.values().map(Vec::len).sum();
+            ui_println!("\n{}", format!("📦 {} ({})", ecosystem, count).underline());
+            let rows: Vec<BotRow> = dependencies
+                .values_mut()
+                .map(|updates| {
+                    updates.sort_by_key(|(pr, _)| pr.number);
+                    // The oldest PR's starting point and the newest PR's target
+                    let from = updates.iter().find_map(|(_, u)| u.from.clone());
+                    let to = updates.iter().rev().find_map(|(_, u)| u.to.clone());
+                    let jump = match (&from, &to) {
+                        (Some(from), Some(to)) if manifests::version_jump(from, to).is_some() => {
+                            jump_label(from, to)
+                        }
+                        _ => "-".to_string(),
+                    };
+                    let changelog = updates
+                        .iter()
+                        .rev()
+                        .find_map(|(_, u)| u.changelog.as_deref())
+                        .map_or("-".to_string(), |url| link("changelog", url));
+                    BotRow {
+                        dependency: updates[0].1.dependency.clone(),
+                        prs: updates
+                            .iter()
+                            .map(|(pr, _)| link(&format!("#{}", pr.number), &pr.html_url))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        from: from.unwrap_or_else(|| "-".to_string()),
+                        to: to.unwrap_or_else(|| "-".to_string()),
+                        jump,
+                        changelog,
+                    }
+                })
+                .collect();
+            println!("{}", table::render(rows, &[(0, Shrink::Wrap)]));
+        }
+
+        if options.rebase {
+            self.rebase_bot_pull_requests(&owner, &repo, &prs)?;
+        }
+        Ok(())
+    }
+
+    /// Asks the bot behind each PR to rebase it.
+    ///
+    /// # Returns
+    /// - `Err` naming the PRs that couldn't be asked; PRs whose bot isn't known are skipped
+    fn rebase_bot_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        prs: &[BasicGitHubPR],
+    ) -> Result<(), Box<dyn Error>> {
+        let unticked = format!("- [ ] {}", RENOVATE_REBASE);
+        let mut asked = 0;
+        let mut failed = Vec::new();
+        for pr in prs {
+            let body = pr.body.as_deref().unwrap_or("");
+            let result = if pr.user.login.starts_with("dependabot") {
+                self.post_comment(owner, repo, &pr.number.to_string(), "@dependabot rebase")
+            } else if body.contains(&unticked) {
+                let url = format!(
+                    "{}/repos/{}/{}/pulls/{}",
+                    self.api_base, owner, repo, pr.number
+                );
+                let ticked = body.replacen(&unticked, &format!("- [x] {}", RENOVATE_REBASE), 1);
+                self.send(
+                    self.authed(Method::PATCH, &url)
+                        .json(&json!({ "body": ticked })),
+                    "Failed to tick the rebase checkbox",
+                )
+                .map(|_| ())
+            } else {
+                ui_eprintln!(
+                    "⏭️  #{}: don't know how to ask {} to rebase",
+                    pr.number,
+                    pr.user.login
+                );
+                continue;
+            };
+            match result {
+                Ok(()) => asked += 1,
+                Err(e) => {
+                    ui_eprintln!("❌ #{}: {}", pr.number, e);
+                    failed.push(format!("#{}", pr.number));
+                }
+            }
+        }
+        status_log!("🔁 Asked for a rebase of {} PR(s).", asked);
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Couldn't ask for a rebase of {}", failed.join(", ")).into())
+        }
+    }
+}
@@ -0,0 +1,185 @@
+// Unified CI status for a commit.
+//
+// GitHub has two independent CI reporting APIs: check runs (GitHub Actions and most
+// modern apps) and legacy commit statuses (Jenkins, older integrations). Reviewers
+// don't care which API a job used, so both are merged into one view keyed by name.
+use crate::debug_log;
+use crate::links::link;
+use crate::providers::github::models::*;
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::error::Error;
+
+/// Normalized state of a single CI signal, ordered from best to worst.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub(crate) enum CheckState {
+    Success,
+    Neutral,
+    Pending,
+    Failure,
+}
+
+impl CheckState {
+    /// Maps a check run's `status`/`conclusion` pair onto a normalized state.
+    fn from_check_run(status: &str, conclusion: Option<&str>) -> Self {
+        if status != "completed" {
+            return CheckState::Pending;
+        }
+        match conclusion {
+            Some("success") => CheckState::Success,
+            Some("neutral") | Some("skipped") | Some("stale") => CheckState::Neutral,
+            _ => CheckState::Failure,
+        }
+    }
+
+    /// Maps a commit status `state` onto a normalized state.
+    fn from_commit_status(state: &str) -> Self {
+        match state {
+            "success" => CheckState::Success,
+            "pending" => CheckState::Pending,
+            _ => CheckState::Failure,
+        }
+    }
+
+    /// Plain lowercase name, as used by `--porcelain`.
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            CheckState::Success => "success",
+            CheckState::Neutral => "neutral",
+            CheckState::Pending => "pending",
+            CheckState::Failure => "failure",
+        }
+    }
+
+    /// Colorized label used in tables.
+    pub(crate) fn label(&self) -> String {
+        match self {
+            CheckState::Success => "✅ success".green().to_string(),
+            CheckState::Neutral => "➖ neutral".dimmed().to_string(),
+            CheckState::Pending => "⏳ pending".yellow().to_string(),
+            CheckState::Failure => "❌ failure".red().to_string(),
+        }
+    }
+}
+
+/// A single CI signal from either API.
+///
+/// Fields:
+/// - `name`: Check run name or status context
+/// - `source`: `"check"` or `"status"`
+/// - `state`: Normalized state
+/// - `details`: Description or URL for follow-up
+#[derive(Clone, Debug)]
+pub(crate) struct CheckEntry {
+    pub name: String,
+    pub source: &'static str,
+    pub state: CheckState,
+    pub details: String,
+}
+
+impl GitHubProvider {
+    /// Collects check runs and commit statuses for `sha` into one list keyed by name.
+    ///
+    /// When both APIs report the same name, the worse state wins so a failing
+    /// legacy status can never be hidden behind a green check run (or vice versa).
+    pub(crate) fn combined_checks(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<CheckEntry>, Box<dyn Error>> {
+        let mut entries: BTreeMap<String, CheckEntry> = BTreeMap::new();
+
+        // Checks API: a commit can have hundreds of runs (matrix builds), so every page
+        let runs_url = format!(
+            "{}/repos/{}/{}/commits/{}/check-runs",
+            self.api_base, owner, repo, sha
+        );
+        debug_log!("[DEBUG] Fetching check runs from: {}", runs_url);
+
+        let runs: Vec<GitHubCheckRun> =
+            self.get_wrapped_pages(&runs_url, "check_runs", "Failed to fetch check runs")?;
+        for run in runs {
+            let entry = CheckEntry {
+                state: CheckState::from_check_run(&run.status, run.conclusion.as_deref()),
+                details: run.html_url.unwrap_or_default(),
+                name: run.name,
+                source: "check",
+            };
+            merge_entry(&mut entries, entry);
+        }
+
+        // Statuses API (combined endpoint already keeps only the latest per context),
+        // every page of it
+        let status_url = format!(
+            "{}/repos/{}/{}/commits/{}/status",
+            self.api_base, owner, repo, sha
+        );
+        debug_log!("[DEBUG] Fetching commit statuses from: {}", status_url);
+
+        let statuses: Vec<GitHubCommitStatus> =
+            self.get_wrapped_pages(&status_url, "statuses", "Failed to fetch commit statuses")?;
+        for status in statuses {
+            let details = status
+                .description
+                .filter(|d| !d.is_empty())
+                .or(status.target_url)
+                .unwrap_or_default();
+            let entry = CheckEntry {
+                state: CheckState::from_commit_status(&status.state),
+                details,
+                name: status.context,
+                source: "status",
+            };
+            merge_entry(&mut entries, entry);
+        }
+
+        debug_log!("[DEBUG] {} unified checks for {}", entries.len(), sha);
+        Ok(entries.into_values().collect())
+    }
+}
+
+/// Inserts `entry`, keeping the worse of two entries that share a name.
+fn merge_entry(entries: &mut BTreeMap<String, CheckEntry>, entry: CheckEntry) {
+    match entries.get(&entry.name) {
+        Some(existing) if existing.state >= entry.state => {}
+        _ => {
+            entries.insert(entry.name.clone(), entry);
+        }
+    }
+}
+
+/// Returns the overall state of a set of checks (the worst individual state),
+/// or `None` when no CI reported anything.
+pub(crate) fn overall_state(entries: &[CheckEntry]) -> Option<CheckState> {
+    entries.iter().map(|e| e.state).max()
+}
+
+/// Builds the table rows for the `checks` command.
+pub(crate) fn check_rows(entries: &[CheckEntry]) -> Vec<CheckRow> {
+    entries
+        .iter()
+        .map(|e| CheckRow {
+            name: link(&e.name, &e.details),
+            source: e.source.to_string(),
+            state: e.state.label(),
+            details: if e.details.is_empty() {
+                "-".to_string()
+            } else {
+                e.details.clone()
+            },
+        })
+        .collect()
+}
+
+/// One-line summary such as "3 passed, 1 failed, 2 pending".
+pub(crate) fn summarize(entries: &[CheckEntry]) -> String {
+    let count = |state: CheckState| entries.iter().filter(|e| e.state == state).count();
+    format!(
+        "{} passed, {} failed, {} pending, {} neutral",
+        count(CheckState::Success),
+        count(CheckState::Failure),
+        count(CheckState::Pending),
+        count(CheckState::Neutral)
+    )
+}
@@ -0,0 +1,205 @@
+// `git pr comments`: the conversation and the inline comments of a PR in one view.
+//
+// Inline comments are anchored to lines of the diff as of the commit they were written on.
+// Once later pushes change or remove those lines, GitHub can no longer place the comment
+// on the current head: the review comments endpoint then reports `line: null` while
+// keeping `original_line` and the `diff_hunk` the comment was written against. Like the
+// web UI, such comments are marked `outdated` and shown with the last lines of that hunk,
+// so it's clear what they were about. `--hide-outdated` leaves them out (with their
+// replies), e.g. to see only what still applies to the current code.
+//
+// Inline comments are grouped by file, with replies under the comment they answer.
+// Comments on a whole file have no line either, but aren't outdated.
+use crate::debug_log;
+use crate::providers::github::models::*;
+use crate::providers::methods::PrNumber;
+use crate::status_log;
+use crate::ui_println;
+use colored::Colorize;
+use std::collections::HashMap;
+use std::error::Error;
+
+/// How many lines of the original diff hunk are shown above an outdated comment (the
+/// web UI shows the same amount).
+const HUNK_CONTEXT: usize = 4;
+
+/// Whether an inline comment's lines are gone from the current diff (see the module comment).
+pub(crate) fn is_outdated(comment: &GitHubComment) -> bool {
+    comment.path.is_some()
+        && comment.line.is_none()
+        && comment.subject_type.as_deref() != Some("file")
+}
+
+/// The hunk header and the last `HUNK_CONTEXT` lines of a diff hunk, ending at the
+/// commented line, colored like a diff.
+fn hunk_context(hunk: &str) -> Vec<String> {
+    let lines: Vec<&str> = hunk.lines().collect();
+    let (header, body) = match lines.split_first() {
+        Some((first, rest)) if first.starts_with("@@") => (Some(*first), rest),
+        _ => (None, &lines[..]),
+    };
+    let tail = &body[body.len().saturating_sub(HUNK_CONTEXT)..];
+    header
+        .map(|h| h.cyan().to_string())
+        .into_iter()
+        .chain(tail.iter().map(|line| match line.chars().next() {
+            Some('+') => line.green().to_string(),
+            Some('-') => line.red().to_string(),
+            _ => line.dimmed().to_string(),
+        }))
+        .collect()
+}
+
+impl GitHubProvider {
+    /// Fetches the comments on a PR's conversation, oldest first.
+    pub(crate) fn conversation_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+    ) -> Result<Vec<GitHubComment>, Box<dyn Error>> {
+        self.get_pages(
+            &format!(
+                "{}/repos/{}/{}/issues/{}/comments",
+                self.api_base, owner, repo, pr_number
+            ),
+            "Failed to fetch comments",
+        )
+    }
+
+    /// Fetches the inline review comments of a PR, oldest first.
+    pub(crate) fn inline_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+    ) -> Result<Vec<GitHubComment>, Box<dyn Error>> {
+        self.get_pages(
+            &format!(
+                "{}/repos/{}/{}/pulls/{}/comments",
+                self.api_base, owner, repo, pr_number
+            ),
+            "Failed to fetch review comments",
+        )
+    }
+
+    /// Prints the conversation and the inline comments of a PR.
+    ///
+    /// # Parameters
+    /// - `hide_outdated`: Leave out inline comments whose lines are gone, and their replies
+    ///
+    /// # Returns
+    /// - `Err` if the comments can't be fetched
+    pub(crate) fn print_comments(
+        &self,
+        pr_number: PrNumber,
+        hide_outdated: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let conversation = self.conversation_comments(&owner, &repo, pr_number)?;
+        let inline = self.inline_comments(&owner, &repo, pr_number)?;
+
+        // Replies carry no anchor of their own; they follow the comment that opened the thread
+        let mut replies: HashMap<u64, Vec<&GitHubComment>> = HashMap::new();
+        let mut roots: Vec<&GitHubComment> = Vec::new();
+        for comment in &inline {
+            match comment.in_reply_to_id {
+                Some(parent) if inline.iter().any(|c| c.id == parent) => {
+                    replies.entry(parent).or_default().push(comment)
+                }
+                _ => roots.push(comment),
+            }
+        }
+        let outdated = roots.iter().filter(|c| is_outdated(c)).count();
+        if hide_outdated {
+            roots.retain(|c| !is_outdated(c));
+        }
+        // Stable, so comments on a file stay in the order they were written
+        roots.sort_by(|a, b| a.path.cmp(&b.path));
+        debug_log!(
+            "[DEBUG] {} conversation comment(s), {} inline thread(s), {} outdated",
+            conversation.len(),
+            roots.len(),
+            outdated
+        );
+
+        if conversation.is_empty() && roots.is_empty() {
+            status_log!("ℹ️  No comments on PR #{}.", pr_number);
+            if hide_outdated && outdated > 0 {
+                status_log!("   ({} outdated thread(s) hidden)", outdated);
+            }
+            return Ok(());
+        }
+
+        ui_println!(
+            "{}",
+            format!(
+                "💬 Comments on PR #{}: {} on the conversation, {} inline thread(s){}",
+                pr_number,
+                conversation.len(),
+                roots.len(),
+                match (outdated, hide_outdated) {
+                    (0, _) => String::new(),
+                    (n, true) => format!(", {} outdated hidden", n),
+                    (n, false) => format!(" ({} outdated)", n),
+                }
+            )
+            .bold()
+        );
+
+        let heading = |comment: &GitHubComment| {
+            let when = comment
+                .created_at
+                .map(|t| format!(" · {}", self.config.format_time(t)))
+                .unwrap_or_default();
+            format!("@{}{}", comment.user.login, when)
+        };
+        let print_body = |body: &str, indent: &str| {
+            for line in body.trim().lines() {
+                ui_println!("{}{}", indent, line);
+            }
+        };
+
+        if !conversation.is_empty() {
+            ui_println!("\n{}", "Conversation".underline());
+            for comment in &conversation {
+                ui_println!("{}", heading(comment).yellow());
+                print_body(&comment.body, "  ");
+            }
+        }
+
+        let mut current_path: Option<&str> = None;
+        for root in roots {
+            let path = root.path.as_deref().unwrap_or("");
+            if current_path != Some(path) {
+                ui_println!("\n{}", path.underline());
+                current_path = Some(path);
+            }
+            let anchor = if is_outdated(root) {
+                let line = root
+                    .original_line
+                    .map(|l| format!("L{} ", l))
+                    .unwrap_or_default();
+                format!("{}{}", line, "outdated".dimmed())
+            } else {
+                root.line
+                    .map(|l| format!("L{}", l))
+                    .unwrap_or_else(|| "file".to_string())
+            };
+            ui_println!("{} {}", anchor, heading(root).yellow());
+            if is_outdated(root) {
+                for line in hunk_context(root.diff_hunk.as_deref().unwrap_or("")) {
+                    ui_println!("  │ {}", line);
+                }
+            }
+            print_body(&root.body, "  ");
+            for reply in replies.get(&root.id).into_iter().flatten() {
+                ui_println!("  ↳ {}", heading(reply).yellow());
+                print_body(&reply.body, "    ");
+            }
+        }
+        Ok(())
+    }
+}
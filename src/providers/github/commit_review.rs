@@ -0,0 +1,161 @@
+// `git pr review-commit`: reviewing a PR one commit at a time.
+//
+// Some reviewers read a well-structured PR commit by commit rather than as one diff.
+// `git pr review-commit 42 <sha>` pages that commit like `git show` (header, message, diff),
+// and with `-m` and/or `--comment FILE:LINE:TEXT` submits a comment review pinned to it:
+// the review's `commit_id` is the commit instead of the PR's head, so GitHub shows the
+// comments against the code as it was in that commit.
+//
+// The commit must belong to the PR; an abbreviated SHA (at least 4 characters) is resolved
+// against the PR's commits, so a SHA from `show-details` or `git log` can be pasted as is.
+use crate::debug_log;
+use crate::providers::github::github::page_diff;
+use crate::providers::github::models::*;
+use crate::providers::methods::{CommitReviewOptions, PrNumber};
+use crate::status_log;
+use reqwest::Method;
+use serde_json::json;
+use std::error::Error;
+
+/// Shortest abbreviated SHA accepted, as for `git rev-parse`.
+const MIN_SHA_LENGTH: usize = 4;
+
+/// Formats a commit like the header of `git show`, with its position in the PR.
+fn commit_header(
+    commit: &GitHubPRCommit,
+    position: usize,
+    total: usize,
+    pr_number: PrNumber,
+) -> String {
+    let author = &commit.commit.author;
+    let mut header = format!(
+        "commit {} ({}/{} of PR #{})\nAuthor: {} <{}>\n\n",
+        commit.sha, position, total, pr_number, author.name, author.email
+    );
+    for line in commit.commit.message.lines() {
+        header.push_str(&format!("    {}\n", line));
+    }
+    header.push('\n');
+    header
+}
+
+impl GitHubProvider {
+    /// Shows or reviews one commit of a PR (see the module comment).
+    ///
+    /// # Parameters
+    /// - `sha`: Full or abbreviated SHA of a commit of the PR
+    /// - `options`: Review summary and inline comments; both empty means "show"
+    ///
+    /// # Returns
+    /// - `Err` if the SHA matches no commit of the PR (or several), or the review fails
+    pub(crate) fn review_pull_request_commit(
+        &self,
+        pr_number: PrNumber,
+        sha: &str,
+        options: &CommitReviewOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        let sha = sha.trim().to_lowercase();
+        if sha.len() < MIN_SHA_LENGTH || !sha.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!(
+                "'{}' is not a commit SHA (at least {} hex characters)",
+                sha, MIN_SHA_LENGTH
+            )
+            .into());
+        }
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+
+        let commits = self.pull_request_commits(&owner, &repo, pr_number)?;
+        let matches: Vec<(usize, &GitHubPRCommit)> = commits
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.sha.starts_with(&sha))
+            .collect();
+        let (index, commit) = match matches.as_slice() {
+            [found] => *found,
+            [] => {
+                return Err(format!(
+                    "{} is not one of the {} commit(s) of PR #{}",
+                    sha,
+                    commits.len(),
+                    pr_number
+                )
+                .into())
+            }
+            _ => {
+                return Err(format!(
+                    "{} is ambiguous in PR #{}; give more characters of the SHA",
+                    sha, pr_number
+                )
+                .into())
+            }
+        };
+        debug_log!(
+            "[DEBUG] {} is commit {}/{} of PR #{}",
+            commit.sha,
+            index + 1,
+            commits.len(),
+            pr_number
+        );
+
+        let message = options.message.as_deref().unwrap_or("").trim();
+        if message.is_empty() && options.comments.is_empty() {
+            let diff = self
+                .send(
+                    self.authed(
+                        Method::GET,
+                        &format!(
+                            "{}/repos/{}/{}/commits/{}",
+                            self.api_base, owner, repo, commit.sha
+                        ),
+                    )
+                    .header("Accept", "application/vnd.github.v3.diff"),
+                    "Failed to fetch the commit's diff",
+                )?
+                .text()?;
+            let header = commit_header(commit, index + 1, commits.len(), pr_number);
+            return page_diff(&format!("{}{}", header, diff), true);
+        }
+
+        let comments: Vec<_> = options
+            .comments
+            .iter()
+            .map(|c| {
+                let mut comment =
+                    json!({ "path": c.path, "line": c.lines.end, "side": "RIGHT", "body": c.body });
+                if !c.lines.is_single() {
+                    comment["start_line"] = json!(c.lines.start);
+                    comment["start_side"] = json!("RIGHT");
+                }
+                comment
+            })
+            .collect();
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/reviews",
+            self.api_base, owner, repo, pr_number
+        );
+        self.send(
+            self.authed(Method::POST, &url).json(&json!({
+                "commit_id": commit.sha,
+                "event": "COMMENT",
+                "body": message,
+                "comments": comments,
+            })),
+            &format!("Failed to review commit {}", &commit.sha[..7]),
+        )?;
+        status_log!(
+            "✅ Reviewed commit {} ({}/{}) of PR #{}{}",
+            &commit.sha[..7],
+            index + 1,
+            commits.len(),
+            pr_number,
+            match comments.len() {
+                0 => String::new(),
+                1 => " with 1 inline comment".to_string(),
+                n => format!(" with {} inline comments", n),
+            }
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+// `AsyncSourceControlProvider` for GitHub.
+//
+// Requests go through the same blocking helpers as every other command (same headers,
+// same error hints), each on its own blocking task, so several can be in flight at once.
+// How many actually run together is capped by the runtime driving the futures.
+use crate::debug_log;
+use crate::progress;
+use crate::providers::github::checks::overall_state;
+use crate::providers::github::models::*;
+use crate::providers::methods::{
+    AsyncError, AsyncSourceControlProvider, PrNumber, PullRequestStatus,
+};
+use async_trait::async_trait;
+use std::error::Error;
+use tokio::task::{self, JoinHandle};
+
+impl GitHubProvider {
+    /// Fetches a PR (list model) for the repository behind the remote.
+    fn fetch_list_pull_request(&self, pr_number: PrNumber) -> Result<GitHubPR, Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        debug_log!("[DEBUG] Fetching details for PR #{}", pr_number);
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}",
+            self.api_base, owner, repo, pr_number
+        );
+        self.get(&url, &format!("Failed to fetch PR #{}", pr_number))
+    }
+
+    /// Computes the current verdict and overall check state of a PR.
+    fn fetch_pull_request_status(
+        &self,
+        pr_number: PrNumber,
+    ) -> Result<PullRequestStatus, Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        debug_log!(
+            "[DEBUG] Fetching review and check state of PR #{}",
+            pr_number
+        );
+
+        let verdicts = self.latest_review_states(&owner, &repo, pr_number)?;
+        let review = if verdicts.values().any(|s| s == "CHANGES_REQUESTED") {
+            "changes requested"
+        } else if verdicts.values().any(|s| s == "APPROVED") {
+            "approved"
+        } else {
+            "pending"
+        };
+
+        let head_sha = self.pull_request_head_sha(&owner, &repo, pr_number)?;
+        let checks = overall_state(&self.combined_checks(&owner, &repo, &head_sha)?)
+            .map_or("-", |state| state.as_str());
+
+        Ok(PullRequestStatus {
+            review: review.to_string(),
+            checks: checks.to_string(),
+        })
+    }
+
+    /// Lists the paths changed by a PR of the repository behind the remote.
+    fn fetch_changed_paths(&self, pr_number: PrNumber) -> Result<Vec<String>, Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let files = self.pull_request_files(&owner, &repo, pr_number)?;
+        Ok(files.into_iter().map(|f| f.filename).collect())
+    }
+
+    /// Runs `fetch` on a blocking task.
+    ///
+    /// The task owns a clone of the provider (the HTTP client is shared, not copied) and
+    /// returns the error as a string, since `Box<dyn Error>` can't leave the thread.
+    fn spawn<T, F>(&self, fetch: F) -> JoinHandle<Result<T, String>>
+    where
+        T: Send + 'static,
+        F: FnOnce(&GitHubProvider) -> Result<T, Box<dyn Error>> + Send + 'static,
+    {
+        let provider = self.clone();
+        task::spawn_blocking(move || fetch(&provider).map_err(|e| e.to_string()))
+    }
+}
+
+/// Flattens a finished task into the trait's result type.
+fn joined<T>(result: Result<Result<T, String>, task::JoinError>) -> Result<T, AsyncError> {
+    match result {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => Err(e.into()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Awaits tasks started up front, pairing each result with its PR number.
+///
+/// Each finished task is reported as a step of `phase` (see `crate::progress`).
+async fn join_all<T>(
+    phase: &str,
+    handles: Vec<(PrNumber, JoinHandle<Result<T, String>>)>,
+) -> Vec<(PrNumber, Result<T, AsyncError>)> {
+    let total = handles.len();
+    let mut results = Vec::with_capacity(total);
+    for (number, handle) in handles {
+        results.push((number, joined(handle.await)));
+        progress::report(
+            phase,
+            results.len(),
+            Some(total),
+            &format!("PR #{}", number),
+        );
+    }
+    results
+}
+
+#[async_trait]
+impl AsyncSourceControlProvider for GitHubProvider {
+    type PullRequest = GitHubPR;
+
+    async fn pull_request(&self, pr_number: PrNumber) -> Result<GitHubPR, AsyncError> {
+        joined(
+            self.spawn(move |p| p.fetch_list_pull_request(pr_number))
+                .await,
+        )
+    }
+
+    async fn pull_requests(
+        &self,
+        numbers: &[PrNumber],
+    ) -> Vec<(PrNumber, Result<GitHubPR, AsyncError>)> {
+        // Start every request before waiting on any of them
+        let handles = numbers
+            .iter()
+            .map(|&n| (n, self.spawn(move |p| p.fetch_list_pull_request(n))))
+            .collect();
+        join_all("details", handles).await
+    }
+
+    async fn pull_request_status(
+        &self,
+        pr_number: PrNumber,
+    ) -> Result<PullRequestStatus, AsyncError> {
+        joined(
+            self.spawn(move |p| p.fetch_pull_request_status(pr_number))
+                .await,
+        )
+    }
+
+    async fn pull_request_statuses(
+        &self,
+        numbers: &[PrNumber],
+    ) -> Vec<(PrNumber, Result<PullRequestStatus, AsyncError>)> {
+        let handles = numbers
+            .iter()
+            .map(|&n| (n, self.spawn(move |p| p.fetch_pull_request_status(n))))
+            .collect();
+        join_all("status", handles).await
+    }
+
+    async fn changed_paths(&self, pr_number: PrNumber) -> Result<Vec<String>, AsyncError> {
+        joined(self.spawn(move |p| p.fetch_changed_paths(pr_number)).await)
+    }
+
+    async fn changed_paths_of(
+        &self,
+        numbers: &[PrNumber],
+    ) -> Vec<(PrNumber, Result<Vec<String>, AsyncError>)> {
+        let handles = numbers
+            .iter()
+            .map(|&n| (n, self.spawn(move |p| p.fetch_changed_paths(n))))
+            .collect();
+        join_all("files", handles).await
+    }
+}
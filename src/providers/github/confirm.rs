@@ -0,0 +1,103 @@
+// Confirming merges and closes on high-risk repositories.
+//
+// Some repositories hurt more than others when a PR is merged or closed by mistake: the
+// production deploy repo, the shared infrastructure, the public SDK. `high-risk-repos`
+// lists them as `owner/repo` patterns (`acme/infra,acme/*-prod`). Before `git pr merge`
+// or `git pr submit-review --reject` changes a PR there, its title and author are shown
+// and the PR number has to be typed again, so the command can't act on the wrong PR just
+// because a number was mistyped or came from shell history.
+//
+// Scripts pass the number along with `--confirm <n>` instead; without it a run that isn't
+// attached to a terminal fails rather than waiting for an answer. Repositories that match
+// no pattern aren't affected, and `merge --dry-run` never asks.
+use crate::providers::github::models::*;
+use crate::providers::methods::PrNumber;
+use crate::ui_eprintln;
+use crate::utils;
+use colored::Colorize;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+
+impl GitHubProvider {
+    /// Asks for the PR number before `action` changes a PR of a high-risk repository.
+    ///
+    /// # Parameters
+    /// - `title` / `author`: The PR's title and author login, shown before asking
+    /// - `action`: What is about to happen, e.g. `merge` or `close`
+    /// - `confirm`: The number given with `--confirm`, if any
+    ///
+    /// # Returns
+    /// - `Ok(())` if the repository isn't high-risk or the number was confirmed
+    /// - `Err` if the number doesn't match, or nobody is there to type it
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn confirm_high_risk_pr(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+        title: &str,
+        author: &str,
+        action: &str,
+        confirm: Option<PrNumber>,
+    ) -> Result<(), Box<dyn Error>> {
+        if !self.config.is_high_risk(owner, repo) {
+            return Ok(());
+        }
+        ui_eprintln!(
+            "⚠️  {}/{} is a high-risk repository. About to {} PR #{}:",
+            owner,
+            repo,
+            action,
+            pr_number
+        );
+        ui_eprintln!("   {} {}", title.bold(), format!("by @{}", author).dimmed());
+
+        let typed = match confirm {
+            Some(number) => number.to_string(),
+            None if io::stdin().is_terminal() => {
+                utils::prompt(&format!("Type the PR number to {} it: ", action))
+                    .ok_or("Confirmation aborted")?
+            }
+            None => {
+                return Err(format!(
+                    "{}/{} is high-risk; pass --confirm {} to {} PR #{} without a terminal",
+                    owner, repo, pr_number, action, pr_number
+                )
+                .into())
+            }
+        };
+        if typed.trim_start_matches('#') != pr_number.to_string() {
+            return Err(format!(
+                "'{}' doesn't match PR #{}; not going to {} it",
+                typed, pr_number, action
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Like [`Self::confirm_high_risk_pr`], fetching the PR's title and author first.
+    pub(crate) fn confirm_high_risk_action(
+        &self,
+        pr_number: PrNumber,
+        action: &str,
+        confirm: Option<PrNumber>,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        if !self.config.is_high_risk(&owner, &repo) {
+            return Ok(());
+        }
+        let pr = self.fetch_pull_request(&owner, &repo, pr_number)?;
+        self.confirm_high_risk_pr(
+            &owner,
+            &repo,
+            pr_number,
+            &pr.title,
+            &pr.user.login,
+            action,
+            confirm,
+        )
+    }
+}
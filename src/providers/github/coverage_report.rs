@@ -0,0 +1,190 @@
+// `git pr coverage`: how much of the code a PR adds is run by its tests.
+//
+// The report comes from the PR's CI: the `coverage-url` service when configured (a URL
+// template with `{owner}`, `{repo}`, `{sha}` and `{pr}`, fetched without the GitHub token),
+// otherwise an Actions artifact of the head commit, named with `--artifact` or the
+// `coverage-artifact` setting, or else the first whose name mentions coverage, lcov or
+// cobertura. Parsing and matching live in `crate::coverage`.
+use crate::coverage::{self, Coverage, FileCoverage};
+use crate::debug_log;
+use crate::generated::GeneratedFiles;
+use crate::providers::github::models::*;
+use crate::providers::methods::{CoverageOptions, PrNumber};
+use crate::status_log;
+use crate::table::{self, Shrink};
+use crate::ui_println;
+use crate::utils::render_template;
+use colored::Colorize;
+use std::error::Error;
+
+/// Artifact names taken for a coverage report when none is configured.
+const REPORT_HINTS: [&str; 3] = ["coverage", "lcov", "cobertura"];
+
+impl GitHubProvider {
+    /// Fetches the coverage report of a commit (see the module comment).
+    ///
+    /// # Returns
+    /// - The report and where it came from, for the summary line
+    fn coverage_report(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+        sha: &str,
+        artifact: Option<&str>,
+    ) -> Result<(Coverage, String), Box<dyn Error>> {
+        let artifact = artifact.or(self.config.coverage_artifact.as_deref());
+        if let (Some(template), None) = (&self.config.coverage_url, artifact) {
+            let url = render_template(
+                template,
+                &[
+                    ("owner", owner),
+                    ("repo", repo),
+                    ("sha", sha),
+                    ("pr", &pr_number.to_string()),
+                ],
+            );
+            debug_log!("[DEBUG] Fetching coverage report from: {}", url);
+            let resp = self.dispatch(self.client.get(&url))?;
+            if !resp.status().is_success() {
+                return Err(format!(
+                    "The coverage service answered {} for {}",
+                    resp.status(),
+                    url
+                )
+                .into());
+            }
+            let bytes = resp.bytes()?;
+            let report = if bytes.starts_with(b"PK") {
+                coverage::from_zip(&bytes)?
+            } else {
+                Coverage::parse(&String::from_utf8_lossy(&bytes))?
+            };
+            return Ok((report, url));
+        }
+
+        let artifacts = self.artifacts_for_sha(owner, repo, sha)?;
+        let found = artifacts.iter().find(|(_, a)| match artifact {
+            Some(name) => a.name == name,
+            None => {
+                let name = a.name.to_lowercase();
+                REPORT_HINTS.iter().any(|hint| name.contains(hint))
+            }
+        });
+        let Some((workflow, found)) = found else {
+            return Err(match artifact {
+                Some(name) => format!(
+                    "No artifact named '{}' for the head of PR #{}",
+                    name, pr_number
+                ),
+                None => format!(
+                    "No coverage artifact for the head of PR #{} (looked for names with {}); name it with --artifact or set coverage-artifact / coverage-url",
+                    pr_number,
+                    REPORT_HINTS.join(", ")
+                ),
+            }
+            .into());
+        };
+        status_log!(
+            "⬇️  Downloading artifact '{}' ({})...",
+            found.name,
+            workflow
+        );
+        let report = coverage::from_zip(&self.artifact_bytes(found)?)?;
+        Ok((report, format!("artifact '{}' of {}", found.name, workflow)))
+    }
+
+    /// Prints the coverage of the lines a PR adds, per file, and flags uncovered ones.
+    ///
+    /// # Returns
+    /// - `Err` if no report is found, or if coverage is below `--fail-under`
+    pub(crate) fn print_changed_lines_coverage(
+        &self,
+        pr_number: PrNumber,
+        options: &CoverageOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let sha = self.pull_request_head_sha(&owner, &repo, pr_number)?;
+        let (report, source) =
+            self.coverage_report(&owner, &repo, pr_number, &sha, options.artifact.as_deref())?;
+
+        let files = self.pull_request_files(&owner, &repo, pr_number)?;
+        let paths: Vec<String> = files.iter().map(|f| f.filename.clone()).collect();
+        let generated = GeneratedFiles::new(&self.config, &paths);
+        let mut results: Vec<FileCoverage> = files
+            .iter()
+            .filter(|f| !generated.contains(&f.filename))
+            .filter_map(|f| {
+                let added = coverage::added_lines(f.patch.as_deref()?);
+                report.of_changes(&f.filename, &added)
+            })
+            .filter(|c| c.coverable > 0)
+            .collect();
+        if results.is_empty() {
+            ui_println!(
+                "ℹ️  The report ({}) covers none of the lines PR #{} adds.",
+                source,
+                pr_number
+            );
+            return Ok(());
+        }
+
+        // Files adding uncovered code first, most uncovered lines on top
+        results.sort_by(|a, b| {
+            b.uncovered
+                .len()
+                .cmp(&a.uncovered.len())
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        let percent = |covered: usize, coverable: usize| covered as f64 * 100.0 / coverable as f64;
+        let rows: Vec<CoverageRow> = results
+            .iter()
+            .map(|c| CoverageRow {
+                path: c.path.clone(),
+                covered: format!(
+                    "{}/{} ({:.0}%)",
+                    c.covered,
+                    c.coverable,
+                    percent(c.covered, c.coverable)
+                ),
+                uncovered: if c.uncovered.is_empty() {
+                    "-".to_string()
+                } else {
+                    coverage::ranges(&c.uncovered).red().to_string()
+                },
+            })
+            .collect();
+        println!("{}", table::render(rows, &[(2, Shrink::Wrap)]));
+
+        let covered: usize = results.iter().map(|c| c.covered).sum();
+        let coverable: usize = results.iter().map(|c| c.coverable).sum();
+        let total = percent(covered, coverable);
+        ui_println!(
+            "🧪 Changed-lines coverage: {} ({} of {} coverable added lines; from {})",
+            format!("{:.1}%", total).bold(),
+            covered,
+            coverable,
+            source
+        );
+        let flagged = results.iter().filter(|c| !c.uncovered.is_empty()).count();
+        if flagged > 0 {
+            ui_println!(
+                "{} {} file(s) add code no test runs",
+                "⚠️ ".yellow(),
+                flagged
+            );
+        }
+        if let Some(minimum) = options.fail_under {
+            if total < minimum {
+                return Err(format!(
+                    "Changed-lines coverage {:.1}% is below --fail-under {}%",
+                    total, minimum
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+// Dependency changes of a PR, for `git pr show-details`.
+//
+// When a PR touches `Cargo.toml`, `Cargo.lock`, `package.json` or `go.mod` (at any depth),
+// both versions of the file are fetched (base and head commit) and compared with the
+// `manifests` module. The result is a table of added, removed and upgraded dependencies
+// with the size of each version jump, which reads far better than a raw lockfile diff.
+use crate::debug_log;
+use crate::manifests::{self, DependencyChange};
+use crate::providers::github::models::*;
+use crate::table::{self, Shrink};
+use crate::ui_println;
+use colored::Colorize;
+use reqwest::Method;
+use tabled::Tabled;
+
+/// The dependency changes of one manifest.
+///
+/// Fields:
+/// - `path`: Repo-relative path of the manifest
+/// - `changes`: What changed, sorted by dependency name
+/// - `other_changes`: Whether more than dependency versions changed (scripts, `[patch]`
+///   sections, sources, ...), or a side couldn't be fetched or parsed
+pub(crate) struct ManifestChanges {
+    pub path: String,
+    pub changes: Vec<DependencyChange>,
+    pub other_changes: bool,
+}
+
+/// One row of the dependency changes table.
+#[derive(Tabled)]
+struct DependencyRow {
+    #[tabled(rename = "Manifest")]
+    manifest: String,
+    #[tabled(rename = "Dependency")]
+    name: String,
+    #[tabled(rename = "Before")]
+    before: String,
+    #[tabled(rename = "After")]
+    after: String,
+    #[tabled(rename = "Change")]
+    change: String,
+}
+
+impl GitHubProvider {
+    /// Compares the dependency manifests a PR changes between its base and head commits.
+    ///
+    /// # Parameters
+    /// - `base_sha` / `head_sha`: The PR's base and head commits
+    /// - `files`: The PR's changed files (from `pull_request_files`)
+    ///
+    /// # Returns
+    /// - The manifests with at least one dependency change or other changes, in path
+    ///   order. Files that can't be fetched or parsed count as having no dependencies.
+    pub(crate) fn dependency_changes(
+        &self,
+        owner: &str,
+        repo: &str,
+        base_sha: &str,
+        head_sha: &str,
+        files: &[GitHubPRFile],
+    ) -> Vec<ManifestChanges> {
+        let mut manifests: Vec<ManifestChanges> = files
+            .iter()
+            .filter(|f| manifests::is_manifest(&f.filename))
+            .filter_map(|file| {
+                let before = match file.status.as_str() {
+                    "added" => None,
+                    _ => self.file_at(owner, repo, &file.filename, base_sha),
+                };
+                let after = match file.status.as_str() {
+                    "removed" => None,
+                    _ => self.file_at(owner, repo, &file.filename, head_sha),
+                };
+                let dependencies = |text: &Option<String>| {
+                    text.as_deref()
+                        .map(|text| manifests::parse(&file.filename, text))
+                        .unwrap_or_default()
+                };
+
+                let changes = manifests::compare(&dependencies(&before), &dependencies(&after));
+                let other_changes = match (&before, &after) {
+                    (Some(before), Some(after)) => {
+                        !manifests::only_versions_differ(&file.filename, before, after)
+                    }
+                    _ => true,
+                };
+                (!changes.is_empty() || other_changes).then(|| ManifestChanges {
+                    path: file.filename.clone(),
+                    changes,
+                    other_changes,
+                })
+            })
+            .collect();
+        manifests.sort_by(|a, b| a.path.cmp(&b.path));
+        manifests
+    }
+
+    /// Raw contents of `path` on commit `sha`.
+    ///
+    /// # Returns
+    /// - `None` if the file doesn't exist there or can't be fetched.
+    fn file_at(&self, owner: &str, repo: &str, path: &str, sha: &str) -> Option<String> {
+        let url = format!(
+            "{}/repos/{}/{}/contents/{}?ref={}",
+            self.api_base, owner, repo, path, sha
+        );
+        let resp = self
+            .dispatch(
+                self.authed(Method::GET, &url)
+                    .header("Accept", "application/vnd.github.raw"),
+            )
+            .ok()?;
+        if !resp.status().is_success() {
+            debug_log!("[DEBUG] Can't fetch {} at {}: {}", path, sha, resp.status());
+            return None;
+        }
+        resp.text().ok()
+    }
+}
+
+/// The size of a version change as shown in tables, majors and downgrades highlighted.
+pub(crate) fn jump_label(from: &str, to: &str) -> String {
+    match manifests::version_jump(from, to) {
+        Some("major") => "major ⚠️".yellow().bold().to_string(),
+        Some("downgrade") => "downgrade".red().to_string(),
+        Some(jump) => jump.to_string(),
+        None => "changed".to_string(),
+    }
+}
+
+/// Prints the dependency changes table of `show-details`; prints nothing without changes.
+pub(crate) fn print_dependency_changes(manifests: &[ManifestChanges]) {
+    let mut rows = Vec::new();
+    for manifest in manifests {
+        for change in &manifest.changes {
+            let (before, after, label) = match change {
+                DependencyChange::Added { version, .. } => (
+                    "-".to_string(),
+                    version.clone(),
+                    "added".green().to_string(),
+                ),
+                DependencyChange::Removed { version, .. } => (
+                    version.clone(),
+                    "-".to_string(),
+                    "removed".red().to_string(),
+                ),
+                DependencyChange::Changed { from, to, .. } => {
+                    (from.clone(), to.clone(), jump_label(from, to))
+                }
+            };
+            rows.push(DependencyRow {
+                manifest: manifest.path.clone(),
+                name: change.name().to_string(),
+                before,
+                after,
+                change: label,
+            });
+        }
+    }
+
+    if rows.is_empty() {
+        return;
+    }
+
+    ui_println!(
+        "{}",
+        format!("📦 Dependency changes ({}):", rows.len()).bold()
+    );
+    println!(
+        "{}",
+        table::render(rows, &[(1, Shrink::Truncate), (0, Shrink::Truncate)])
+    );
+}
@@ -0,0 +1,100 @@
+// Deployment status lookup for a PR's head commit, so reviewers can jump straight
+// to preview environments and staging deploys from `show-details`.
+use crate::debug_log;
+use crate::providers::github::models::*;
+use crate::status_log;
+use crate::table;
+use crate::ui_eprintln;
+use colored::Colorize;
+use reqwest::Method;
+use std::collections::BTreeSet;
+use std::error::Error;
+
+impl GitHubProvider {
+    /// Returns one row per environment deployed from `sha`, with its latest status.
+    ///
+    /// Deployments come back newest first, so only the first deployment seen for each
+    /// environment is considered; older redeploys of the same environment are skipped.
+    pub(crate) fn deployment_rows(
+        &self,
+        owner: &str,
+        repo: &str,
+        sha: &str,
+    ) -> Result<Vec<DeploymentRow>, Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/deployments?sha={}&per_page=100",
+            self.api_base, owner, repo, sha
+        );
+        debug_log!("[DEBUG] Fetching deployments from: {}", url);
+
+        let deployments: Vec<GitHubDeployment> = self.get(&url, "Failed to fetch deployments")?;
+        let mut seen = BTreeSet::new();
+        let mut rows = Vec::new();
+
+        for deployment in deployments {
+            if !seen.insert(deployment.environment.clone()) {
+                continue;
+            }
+
+            // Statuses are also newest first; one is enough
+            let status_url = format!(
+                "{}/repos/{}/{}/deployments/{}/statuses?per_page=1",
+                self.api_base, owner, repo, deployment.id
+            );
+            let status_resp = self.dispatch(self.authed(Method::GET, &status_url))?;
+
+            if !status_resp.status().is_success() {
+                ui_eprintln!(
+                    "⚠️  Failed to fetch status for deployment {}: {}",
+                    deployment.id,
+                    status_resp.text()?
+                );
+                continue;
+            }
+
+            let statuses: Vec<GitHubDeploymentStatus> = status_resp.json()?;
+            let row = match statuses.into_iter().next() {
+                Some(status) => DeploymentRow {
+                    environment: deployment.environment,
+                    state: colorize_deployment_state(&status.state),
+                    url: status
+                        .environment_url
+                        .filter(|u| !u.is_empty())
+                        .or(status.target_url.filter(|u| !u.is_empty()))
+                        .unwrap_or_else(|| "-".to_string()),
+                    updated: self.config.format_time(status.created_at),
+                },
+                None => DeploymentRow {
+                    environment: deployment.environment,
+                    state: colorize_deployment_state("pending"),
+                    url: "-".to_string(),
+                    updated: "-".to_string(),
+                },
+            };
+            rows.push(row);
+        }
+
+        Ok(rows)
+    }
+}
+
+/// Colors a deployment state the way the web UI does (green/yellow/red/dimmed).
+fn colorize_deployment_state(state: &str) -> String {
+    match state {
+        "success" => state.green().to_string(),
+        "pending" | "queued" | "in_progress" => state.yellow().to_string(),
+        "failure" | "error" => state.red().to_string(),
+        _ => state.dimmed().to_string(),
+    }
+}
+
+/// Prints the "Deployments" section of `show-details`; silent when nothing was deployed.
+pub(crate) fn print_deployments(rows: Vec<DeploymentRow>) {
+    if rows.is_empty() {
+        return;
+    }
+
+    println!();
+    status_log!("🚀 Deployments");
+    println!("{}", table::render(rows, &[]));
+}
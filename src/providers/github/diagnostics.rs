@@ -0,0 +1,159 @@
+// Provider-side checks for `git pr doctor`.
+//
+// Each check talks to the API the same way real commands do, so a pass here means the
+// commands will work too. Checks that depend on an earlier failure are skipped rather
+// than reported as a cascade of errors with the same root cause.
+use crate::debug_log;
+use crate::providers::github::models::*;
+use crate::providers::methods::Diagnostic;
+use chrono::{DateTime, Local};
+use reqwest::{Method, StatusCode};
+
+/// Scopes of classic tokens that give access to pull requests.
+const PR_SCOPES: [&str; 2] = ["repo", "public_repo"];
+
+impl GitHubProvider {
+    /// Runs the provider checks in dependency order (see [`Diagnostic`]).
+    pub(crate) fn diagnostics(&self) -> Vec<Diagnostic> {
+        let mut results = Vec::new();
+
+        // The remote URL must name an owner/repo before anything else can be checked
+        let repo_details = self.infer_repo_details();
+        match &repo_details {
+            Some((owner, repo)) => results.push(Diagnostic::pass(
+                "Remote",
+                format!("{} → {}/{}", self.remote, owner, repo),
+            )),
+            None => results.push(Diagnostic::fail(
+                "Remote",
+                format!("cannot parse owner/repo from {}", self.remote_url),
+                format!(
+                    "git remote set-url {} git@github.com:<owner>/<repo>.git",
+                    self.remote
+                ),
+            )),
+        }
+
+        // Reachability and token validity in one request
+        let user_url = format!("{}/user", self.api_base);
+        debug_log!("[DEBUG] Doctor: probing {}", user_url);
+        let resp = match self.dispatch(self.authed(Method::GET, &user_url)) {
+            Ok(resp) => resp,
+            Err(e) => {
+                results.push(Diagnostic::fail(
+                    "API",
+                    format!("{} is unreachable: {}", self.api_base, e),
+                    "Check your network/proxy, or `git pr config set base-url <url>` for GitHub Enterprise",
+                ));
+                return results;
+            }
+        };
+        results.push(Diagnostic::pass(
+            "API",
+            format!("{} reachable", self.api_base),
+        ));
+
+        if resp.status() == StatusCode::UNAUTHORIZED {
+            results.push(Diagnostic::fail(
+                "Token",
+                "GITHUB_TOKEN was rejected (invalid or expired)",
+                "Create a new token and `export GITHUB_TOKEN=<token>`",
+            ));
+            return results;
+        }
+        if !resp.status().is_success() {
+            // SSO enforcement, permissions, ...: reuse the hints real commands would show
+            let detail = match self.check_response(resp, "Token check") {
+                Err(e) => e.to_string(),
+                Ok(_) => "unexpected response".to_string(),
+            };
+            results.push(Diagnostic::fail(
+                "Token",
+                detail,
+                "Follow the hint above, then re-run `git pr doctor`",
+            ));
+            return results;
+        }
+
+        // Classic tokens report their scopes; fine-grained tokens don't
+        let scopes = resp
+            .headers()
+            .get("x-oauth-scopes")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| {
+                v.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect::<Vec<_>>()
+            });
+        let login = match resp.json::<GitHubUser>() {
+            Ok(user) => {
+                // The fresh answer also refreshes the cached login (see `logins.rs`)
+                self.remember_login(&user.login);
+                user.login
+            }
+            Err(_) => "?".to_string(),
+        };
+
+        match scopes {
+            Some(scopes) if !scopes.iter().any(|s| PR_SCOPES.contains(&s.as_str())) => {
+                results.push(Diagnostic::fail(
+                    "Token",
+                    format!(
+                        "valid for {}, but scopes [{}] don't include repo or public_repo",
+                        login,
+                        scopes.join(", ")
+                    ),
+                    "Regenerate the token with the `repo` scope (or `public_repo` for public repos)",
+                ))
+            }
+            Some(scopes) => results.push(Diagnostic::pass(
+                "Token",
+                format!("valid for {} (scopes: {})", login, scopes.join(", ")),
+            )),
+            None => results.push(Diagnostic::pass(
+                "Token",
+                format!("valid for {} (fine-grained, scopes not reported)", login),
+            )),
+        }
+
+        // Remaining REST budget
+        let rate_url = format!("{}/rate_limit", self.api_base);
+        match self.get::<GitHubRateLimit>(&rate_url, "Failed to fetch rate limit") {
+            Ok(rate) => {
+                let core = rate.resources.core;
+                if core.remaining == 0 {
+                    let reset = DateTime::from_timestamp(core.reset, 0)
+                        .map(|t| t.with_timezone(&Local).format("%H:%M:%S").to_string())
+                        .unwrap_or_else(|| "-".to_string());
+                    results.push(Diagnostic::fail(
+                        "Rate limit",
+                        format!("0 of {} requests left", core.limit),
+                        format!("Wait until {} for the limit to reset", reset),
+                    ));
+                } else {
+                    results.push(Diagnostic::pass(
+                        "Rate limit",
+                        format!("{} of {} requests left", core.remaining, core.limit),
+                    ));
+                }
+            }
+            // GitHub Enterprise can disable rate limiting, in which case the endpoint 404s
+            Err(e) => results.push(Diagnostic::pass("Rate limit", format!("unknown ({})", e))),
+        }
+
+        // Default branch detection (used by `create`, `sync-fork`, ...)
+        if let Some((owner, repo)) = repo_details {
+            match self.default_branch(&owner, &repo) {
+                Ok(branch) => results.push(Diagnostic::pass("Default branch", branch)),
+                Err(e) => results.push(Diagnostic::fail(
+                    "Default branch",
+                    e.to_string(),
+                    "Make sure the token can read the repository",
+                )),
+            }
+        }
+
+        results
+    }
+}
@@ -0,0 +1,71 @@
+// `git pr diff-stats`: where a PR's changes are, by directory and by file type.
+//
+// The totals and bars are computed in `crate::diffstats`; this side fetches the changed
+// files and drops the generated ones (lockfiles, vendored code), which would otherwise
+// dwarf the change that needs reviewing.
+use crate::diffstats::{self, FileChange};
+use crate::generated::GeneratedFiles;
+use crate::providers::github::models::*;
+use crate::providers::methods::{DiffStatsOptions, PrNumber};
+use crate::ui_println;
+use colored::Colorize;
+use std::error::Error;
+
+impl GitHubProvider {
+    /// Prints the change totals of a PR by directory and by file type.
+    ///
+    /// # Returns
+    /// - `Err` if the changed files can't be fetched
+    pub(crate) fn print_diff_stats(
+        &self,
+        pr_number: PrNumber,
+        options: &DiffStatsOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let files = self.pull_request_files(&owner, &repo, pr_number)?;
+        if files.is_empty() {
+            ui_println!("No files changed in PR #{}", pr_number);
+            return Ok(());
+        }
+
+        let paths: Vec<String> = files.iter().map(|f| f.filename.clone()).collect();
+        let generated = GeneratedFiles::new(&self.config, &paths);
+        let (changes, hidden): (Vec<_>, Vec<_>) = files
+            .into_iter()
+            .map(|f| FileChange {
+                path: f.filename,
+                additions: f.additions,
+                deletions: f.deletions,
+            })
+            .partition(|f| options.include_generated || !generated.contains(&f.path));
+
+        let mut summary = format!(
+            "📊 PR #{}: {} {} in {} file(s)",
+            pr_number,
+            format!("+{}", changes.iter().map(|f| f.additions).sum::<u64>()).green(),
+            format!("-{}", changes.iter().map(|f| f.deletions).sum::<u64>()).red(),
+            changes.len()
+        );
+        if !hidden.is_empty() {
+            summary.push_str(&format!(
+                " ({} generated file(s) left out; --include-generated to count them)",
+                hidden.len()
+            ));
+        }
+        ui_println!("{}\n", summary);
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let by_directory = diffstats::group(&changes, |path| {
+            diffstats::directory_of(path, options.depth)
+        });
+        print!("{}", diffstats::render("By directory", &by_directory));
+        println!();
+        let by_type = diffstats::group(&changes, diffstats::type_of);
+        print!("{}", diffstats::render("By file type", &by_type));
+        Ok(())
+    }
+}
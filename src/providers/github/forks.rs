@@ -0,0 +1,181 @@
+// Fork helpers for contributors without write access to the upstream repository:
+// discovering (or creating) the user's fork and wiring up a local remote for it.
+use crate::debug_log;
+use crate::logins;
+use crate::providers::github::models::*;
+use crate::providers::methods::SourceControlProvider;
+use crate::status_log;
+use crate::utils::run_git;
+use reqwest::Method;
+use serde_json::json;
+use std::error::Error;
+use std::thread;
+use std::time::Duration;
+
+/// How many times to poll for a freshly created fork before giving up.
+const FORK_POLL_ATTEMPTS: u32 = 10;
+
+impl GitHubProvider {
+    /// Returns the login of the user the token belongs to, from the cache when it was
+    /// looked up recently (see `logins.rs`).
+    pub(crate) fn lookup_login(&self) -> Result<String, Box<dyn Error>> {
+        if let Some(login) = logins::cached_login(&self.api_base, &self.token) {
+            return Ok(login);
+        }
+        let url = format!("{}/user", self.api_base);
+        debug_log!("[DEBUG] Fetching authenticated user from: {}", url);
+
+        let user: GitHubUser = self.get(&url, "Failed to fetch authenticated user")?;
+        self.remember_login(&user.login);
+        Ok(user.login)
+    }
+
+    /// Caches the token's login after a `GET /user` made for another reason (`doctor`).
+    pub(crate) fn remember_login(&self, login: &str) {
+        logins::store_login(&self.api_base, &self.token, login);
+    }
+
+    /// Checks whether `owner/repo` exists and is visible to the token.
+    fn repo_exists(&self, owner: &str, repo: &str) -> Result<bool, Box<dyn Error>> {
+        let url = format!("{}/repos/{}/{}", self.api_base, owner, repo);
+        let resp = self.dispatch(self.authed(Method::GET, &url))?;
+        Ok(resp.status().is_success())
+    }
+
+    /// Makes sure `login/repo` exists as a fork of `owner/repo`, creating it if needed.
+    ///
+    /// Fork creation is asynchronous on GitHub's side, so the new repository is polled
+    /// until it becomes visible.
+    pub(crate) fn ensure_fork(
+        &self,
+        owner: &str,
+        repo: &str,
+        login: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        if self.repo_exists(login, repo)? {
+            debug_log!("[DEBUG] Fork {}/{} already exists", login, repo);
+            return Ok(());
+        }
+
+        status_log!("🍴 Creating fork {}/{}...", login, repo);
+        let url = format!("{}/repos/{}/{}/forks", self.api_base, owner, repo);
+        self.send(
+            self.authed(Method::POST, &url).json(&json!({})),
+            "Failed to create fork",
+        )?;
+
+        for attempt in 1..=FORK_POLL_ATTEMPTS {
+            if self.repo_exists(login, repo)? {
+                return Ok(());
+            }
+            debug_log!("[DEBUG] Waiting for fork (attempt {})", attempt);
+            thread::sleep(Duration::from_secs(3));
+        }
+
+        Err(format!("Fork {}/{} did not become available in time", login, repo).into())
+    }
+
+    /// Returns the name of a local remote pointing at `login/repo`, adding one if missing.
+    ///
+    /// A new remote is named after the login and reuses the URL style (SSH or HTTPS)
+    /// of the upstream remote.
+    pub(crate) fn fork_remote(
+        &self,
+        upstream_owner: &str,
+        login: &str,
+        repo: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        let needle = format!("{}/{}", login, repo).to_lowercase();
+
+        // Reuse an existing remote if one already points at the fork
+        let remotes = run_git(&["remote"]).unwrap_or_default();
+        for name in remotes.lines() {
+            if let Some(url) = run_git(&["remote", "get-url", name]) {
+                let normalized = url.trim_end_matches(".git").to_lowercase();
+                if normalized.ends_with(&needle) {
+                    debug_log!("[DEBUG] Using existing fork remote {}", name);
+                    return Ok(name.to_string());
+                }
+            }
+        }
+
+        // Derive the fork URL by swapping the owner in the upstream URL
+        let fork_url =
+            self.remote_url
+                .replacen(&format!("{}/", upstream_owner), &format!("{}/", login), 1);
+
+        run_git(&["remote", "add", login, &fork_url])
+            .ok_or_else(|| format!("Failed to add remote {} ({})", login, fork_url))?;
+        status_log!("🔗 Added remote {} → {}", login, fork_url);
+        Ok(login.to_string())
+    }
+
+    /// Works out which fork `sync-fork` should update.
+    ///
+    /// If the configured remote already points at a fork, that fork is used; otherwise
+    /// the remote is taken to be upstream and the authenticated user's fork of it is used.
+    ///
+    /// # Returns
+    /// `(fork owner, remote is the fork)`.
+    pub(crate) fn resolve_fork(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> Result<(String, bool), Box<dyn Error>> {
+        let url = format!("{}/repos/{}/{}", self.api_base, owner, repo);
+        debug_log!("[DEBUG] Fetching repository from: {}", url);
+
+        let repo_json: serde_json::Value = self.get(&url, "Failed to fetch repository")?;
+        if repo_json["fork"].as_bool().unwrap_or(false) {
+            return Ok((owner.to_string(), true));
+        }
+
+        let login = self.authenticated_login()?;
+        if !self.repo_exists(&login, repo)? {
+            return Err(format!(
+                "No fork {}/{} found (create one with `git pr create --fork`)",
+                login, repo
+            )
+            .into());
+        }
+        Ok((login, false))
+    }
+
+    /// Fast-forwards a fork's branch from its upstream using the merge-upstream API.
+    ///
+    /// # Returns
+    /// GitHub's summary message (e.g. "This branch is not behind the upstream").
+    pub(crate) fn merge_upstream(
+        &self,
+        fork_owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/merge-upstream",
+            self.api_base, fork_owner, repo
+        );
+        debug_log!("[DEBUG] Syncing {} via: {}", branch, url);
+
+        let resp = self.dispatch(
+            self.authed(Method::POST, &url)
+                .json(&json!({ "branch": branch })),
+        )?;
+
+        // 409 means the fork's branch has commits upstream doesn't, so it can't fast-forward
+        if resp.status() == reqwest::StatusCode::CONFLICT {
+            return Err(format!(
+                "{}/{}:{} has diverged from upstream and can't be fast-forwarded",
+                fork_owner, repo, branch
+            )
+            .into());
+        }
+        let resp = self.check_response(resp, "Failed to sync fork")?;
+
+        let result: serde_json::Value = resp.json()?;
+        Ok(result["message"]
+            .as_str()
+            .unwrap_or("Fork synced")
+            .to_string())
+    }
+}
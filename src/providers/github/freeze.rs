@@ -0,0 +1,265 @@
+// `git pr freeze`: declaring and respecting a merge freeze.
+//
+// Before a release, or while production is on fire, a team stops merging into a branch
+// for a while. The freeze is a file in the repository, `.github/MERGE_FREEZE`, on the
+// branch it freezes, so everyone (and every clone of git-pr) sees the same state and its
+// history shows who froze what and when:
+//
+//   reason: Release 2.0 stabilization
+//   by: alice
+//   since: 2024-05-01T12:00:00Z
+//   until: 2024-05-03
+//
+// `git pr freeze start -m <reason> [--until <date>]` writes it, `git pr freeze end`
+// removes it and `git pr freeze` shows it (`--branch` picks another branch than the
+// default one). A freeze with an `until` date lifts itself when that date starts (UTC);
+// the file is then ignored until someone removes it.
+//
+// While a branch is frozen, `git pr merge` refuses PRs into it unless `--override` is
+// given, and `list` marks the PRs waiting for the freeze to end with ❄️.
+use crate::debug_log;
+use crate::providers::github::models::*;
+use crate::providers::methods::{FreezeAction, SourceControlProvider};
+use crate::status_log;
+use crate::ui_println;
+use base64::Engine;
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use colored::Colorize;
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+use std::error::Error;
+
+/// Where the freeze marker lives, on the frozen branch.
+pub(crate) const FREEZE_PATH: &str = ".github/MERGE_FREEZE";
+
+/// A merge freeze as declared in `FREEZE_PATH`.
+///
+/// Fields:
+/// - `reason`: Why merges are frozen
+/// - `by`: Who declared the freeze
+/// - `since`: When it was declared
+/// - `until`: When it lifts itself, if it does
+/// - `sha`: Blob SHA of the marker file, needed to replace or remove it
+pub(crate) struct Freeze {
+    pub reason: String,
+    pub by: String,
+    pub since: String,
+    pub until: Option<String>,
+    pub sha: String,
+}
+
+impl Freeze {
+    /// Reads the marker's `key: value` lines; unknown keys are ignored.
+    fn parse(text: &str, sha: &str) -> Freeze {
+        let mut freeze = Freeze {
+            reason: String::new(),
+            by: String::new(),
+            since: String::new(),
+            until: None,
+            sha: sha.to_string(),
+        };
+        for line in text.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let value = value.trim().to_string();
+            match key.trim() {
+                "reason" => freeze.reason = value,
+                "by" => freeze.by = value,
+                "since" => freeze.since = value,
+                "until" if !value.is_empty() => freeze.until = Some(value),
+                _ => {}
+            }
+        }
+        freeze
+    }
+
+    /// The marker's contents.
+    fn render(&self) -> String {
+        let mut text = format!(
+            "reason: {}\nby: {}\nsince: {}\n",
+            self.reason, self.by, self.since
+        );
+        if let Some(until) = &self.until {
+            text.push_str(&format!("until: {}\n", until));
+        }
+        text
+    }
+
+    /// Whether the `until` date has passed.
+    pub fn expired(&self) -> bool {
+        self.until
+            .as_deref()
+            .and_then(parse_until)
+            .is_some_and(|until| Utc::now() >= until)
+    }
+
+    /// One-line description, e.g. `Release 2.0 (by alice since 2024-05-01, until 2024-05-03)`.
+    pub fn describe(&self) -> String {
+        let since = self.since.split('T').next().unwrap_or("");
+        let mut text = format!("{} (by {} since {}", self.reason, self.by, since);
+        if let Some(until) = &self.until {
+            text.push_str(&format!(", until {}", until));
+        }
+        text.push(')');
+        text
+    }
+}
+
+/// Parses an `until` value: a date (the freeze lifts when it starts, UTC) or an RFC 3339
+/// timestamp.
+fn parse_until(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Some(time.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|time| time.and_utc())
+}
+
+impl GitHubProvider {
+    /// Reads the freeze marker of a branch.
+    ///
+    /// # Returns
+    /// - `Ok(None)` if the branch has no marker
+    /// - The marker, expired or not (see [`Freeze::expired`])
+    pub(crate) fn merge_freeze(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<Option<Freeze>, Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/contents/{}",
+            self.api_base, owner, repo, FREEZE_PATH
+        );
+        let resp = self.dispatch(self.authed(Method::GET, &url).query(&[("ref", branch)]))?;
+        if resp.status() == StatusCode::NOT_FOUND {
+            debug_log!("[DEBUG] No merge freeze on {}", branch);
+            return Ok(None);
+        }
+        let file: serde_json::Value = self
+            .check_response(resp, "Failed to read the merge freeze")?
+            .json()?;
+        // The API wraps base64 content at 60 columns
+        let encoded: String = file["content"]
+            .as_str()
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect();
+        let bytes = base64::engine::general_purpose::STANDARD.decode(encoded)?;
+        let freeze = Freeze::parse(
+            &String::from_utf8_lossy(&bytes),
+            file["sha"].as_str().unwrap_or_default(),
+        );
+        debug_log!(
+            "[DEBUG] Merge freeze on {}: {} (expired: {})",
+            branch,
+            freeze.describe(),
+            freeze.expired()
+        );
+        Ok(Some(freeze))
+    }
+
+    /// The freeze in force on a branch: its marker, unless expired.
+    pub(crate) fn active_freeze(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<Option<Freeze>, Box<dyn Error>> {
+        Ok(self
+            .merge_freeze(owner, repo, branch)?
+            .filter(|freeze| !freeze.expired()))
+    }
+
+    /// Shows, declares or ends a merge freeze (see the module comment).
+    pub(crate) fn freeze_action(&self, action: &FreezeAction) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let branch = match action {
+            FreezeAction::Status { branch }
+            | FreezeAction::Start { branch, .. }
+            | FreezeAction::End { branch } => branch.clone(),
+        };
+        let branch = match branch {
+            Some(branch) => branch,
+            None => self.default_branch(&owner, &repo)?,
+        };
+        let url = format!(
+            "{}/repos/{}/{}/contents/{}",
+            self.api_base, owner, repo, FREEZE_PATH
+        );
+        let current = self.merge_freeze(&owner, &repo, &branch)?;
+
+        match action {
+            FreezeAction::Status { .. } => match current {
+                Some(freeze) if freeze.expired() => ui_println!(
+                    "✅ {} isn't frozen; the freeze {} has expired (remove it with `git pr freeze end`)",
+                    branch.cyan(),
+                    freeze.describe()
+                ),
+                Some(freeze) => ui_println!(
+                    "❄️  Merges into {} are frozen: {}",
+                    branch.cyan(),
+                    freeze.describe()
+                ),
+                None => ui_println!("✅ {} isn't frozen.", branch.cyan()),
+            },
+            FreezeAction::Start { reason, until, .. } => {
+                if let Some(until) = until {
+                    if parse_until(until).is_none() {
+                        return Err(format!(
+                            "Invalid --until '{}' (expected a date like 2024-05-03 or an RFC 3339 time)",
+                            until
+                        )
+                        .into());
+                    }
+                }
+                let freeze = Freeze {
+                    reason: reason.clone(),
+                    by: self.authenticated_login()?,
+                    since: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+                    until: until.clone(),
+                    sha: String::new(),
+                };
+                let mut payload = json!({
+                    "message": format!("Freeze merges into {}: {}", branch, reason),
+                    "content": base64::engine::general_purpose::STANDARD.encode(freeze.render()),
+                    "branch": branch,
+                });
+                // Declaring a freeze again replaces the marker (new reason or date)
+                if let Some(current) = &current {
+                    payload["sha"] = json!(current.sha);
+                }
+                self.send(
+                    self.authed(Method::PUT, &url).json(&payload),
+                    &format!("Failed to write {} to {}", FREEZE_PATH, branch),
+                )?;
+                status_log!(
+                    "❄️  Froze merges into {}: {}",
+                    branch.cyan(),
+                    freeze.describe()
+                );
+            }
+            FreezeAction::End { .. } => {
+                let Some(current) = current else {
+                    status_log!("ℹ️  {} isn't frozen.", branch.cyan());
+                    return Ok(());
+                };
+                self.send(
+                    self.authed(Method::DELETE, &url).json(&json!({
+                        "message": format!("End the merge freeze of {}", branch),
+                        "sha": current.sha,
+                        "branch": branch,
+                    })),
+                    &format!("Failed to remove {} from {}", FREEZE_PATH, branch),
+                )?;
+                status_log!("✅ Ended the merge freeze of {}", branch.cyan());
+            }
+        }
+        Ok(())
+    }
+}
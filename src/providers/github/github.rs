@@ -1,877 +1,2805 @@
+use crate::columns::{parse_columns, render as render_columns, Column, DEFAULT_COLUMNS};
+use crate::compose;
+use crate::config::Config;
 use crate::debug_log;
-use crate::providers::github::methods::*;
+use crate::drafts;
+use crate::generated::{strip_generated, GeneratedFiles};
+use crate::journal;
+use crate::lfs;
+use crate::links::link;
+use crate::lock;
+use crate::providers::blocking::Blocking;
+use crate::providers::github::actions::human_size;
+use crate::providers::github::attribution::print_attribution;
+use crate::providers::github::binaries::{has_binary_changes, render_binary_changes};
+use crate::providers::github::blockers::{dependency_refs, print_dependencies};
+use crate::providers::github::checks::{check_rows, overall_state, summarize, CheckState};
+use crate::providers::github::dependencies::print_dependency_changes;
+use crate::providers::github::deployments::print_deployments;
+use crate::providers::github::freeze::Freeze;
 use crate::providers::github::models::*;
-use crate::utils::get_remote_url;
-use chrono::{DateTime, Utc};
+use crate::providers::github::participants::print_participants;
+use crate::providers::github::pending::print_pending_reviews;
+use crate::providers::github::permissions::WriteAccess;
+use crate::providers::github::porcelain;
+use crate::providers::github::reviews::print_review_requirements;
+use crate::providers::github::signatures::print_commit_signatures;
+use crate::providers::github::sla::{paint, ReviewSla, SlaLevel};
+use crate::providers::github::viewed::{print_change_size, print_file_states, print_view_progress};
+use crate::providers::github::welcome::is_first_time;
+use crate::providers::methods::*;
+use crate::sensitive::SensitivePaths;
+use crate::status_log;
+use crate::submodules;
+use crate::table::{self, Shrink};
+use crate::tracker;
+use crate::ui_eprintln;
+use crate::ui_println;
+use crate::utils::{
+    branch_needs_push, current_branch, exit, format_duration, is_quiet, push_branch,
+    push_current_branch, record_branch_pr_number, render_template, run_git,
+};
+use chrono::Utc;
 use colored::Colorize;
-use owo_colors::OwoColorize;
 use reqwest::blocking::Client;
+use reqwest::{Method, Url};
 use serde_json::json;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::env;
 use std::error::Error;
 use std::io::Write;
 use std::process::{Command, Stdio};
-use tabled::{settings::Style, Table};
+use std::time::Instant;
 use textwrap::{fill, Options};
 use which::which;
 
+/// Default REST API endpoint for github.com
+const DEFAULT_API_BASE: &str = "https://api.github.com";
+
 impl GitHubProvider {
     /// Creates a new GitHubProvider instance by reading the GitHub token from the environment.
     /// The token must be set in `GITHUB_TOKEN` for authentication with the GitHub API.
-    pub fn new(remote_url: String) -> Result<Self, Box<dyn Error>> {
+    /// The remote name and API base URL come from the resolved `Config`.
+    pub fn new(remote_url: String, config: &Config) -> Result<Self, Box<dyn Error>> {
         debug_log!("[DEBUG] Creating GitHubProvider instance");
         let token = env::var("GITHUB_TOKEN")?;
+        let api_base = config
+            .base_url
+            .as_deref()
+            .unwrap_or(DEFAULT_API_BASE)
+            .trim_end_matches('/')
+            .to_string();
+        debug_log!("[DEBUG] Using API base: {}", api_base);
         Ok(GitHubProvider {
             remote_url,
             client: Client::new(),
             token,
+            remote: config.remote().to_string(),
+            api_base,
+            config: config.clone(),
         })
     }
 
-    /// Extracts the GitHub `owner` and `repo` name from the remote URL.
-    /// Handles both HTTPS and SSH GitHub URLs.
-    fn infer_repo_details(&self) -> Option<(String, String)> {
-        debug_log!("[DEBUG] Inferring repo details from remote URL");
-        let url = self.remote_url.trim_end_matches(".git");
-
-        if url.contains("github.com") {
-            let parts: Vec<&str> = if url.starts_with("http") {
-                url.split('/').collect()
-            } else {
-                url.split(':').last()?.split('/').collect()
-            };
-
-            debug_log!("[DEBUG] Split URL parts: {:?}", parts);
+    /// Computes the local branch name for a pulled PR.
+    ///
+    /// If `branch-template` is configured it is used for both same-repo and fork PRs,
+    /// with the placeholders `{number}`, `{author}`, `{head}` and `{owner}` substituted.
+    /// Otherwise the historical defaults apply: the head branch name for same-repo PRs
+    /// and `<fork-owner>-pr-<number>` for forks.
+    fn local_branch_name(
+        &self,
+        pr_number: PrNumber,
+        author: &str,
+        head_branch: &str,
+        head_owner: &str,
+        is_fork: bool,
+    ) -> Result<String, Box<dyn Error>> {
+        let name = match self.config.branch_template.as_deref() {
+            Some(template) => render_template(
+                template,
+                &[
+                    ("number", &pr_number.to_string()),
+                    ("author", author),
+                    ("head", head_branch),
+                    ("owner", head_owner),
+                ],
+            ),
+            None if is_fork => format!("{}-pr-{}", head_owner, pr_number),
+            None => head_branch.to_string(),
+        };
 
-            if parts.len() >= 2 {
-                return Some((
-                    parts[parts.len() - 2].to_string(),
-                    parts[parts.len() - 1].to_string(),
-                ));
-            }
+        // Reject templates that expand to something git won't accept as a branch name
+        if run_git(&["check-ref-format", "--branch", &name]).is_none() {
+            return Err(format!(
+                "'{}' is not a valid branch name (check branch-template)",
+                name
+            )
+            .into());
         }
-        debug_log!("[DEBUG] Failed to infer repo details");
-        None
+
+        debug_log!("[DEBUG] Local branch name: {}", name);
+        Ok(name)
     }
-}
 
-impl SourceControlProvider for GitHubProvider {
-    /// Submits a code review for a specific pull request on GitHub.
-    ///
-    /// This function supports submitting one of three review types:
-    /// - APPROVE: Approves the changes (`--approve` flag)
-    /// - REQUEST_CHANGES: Rejects the changes and asks for changes (`--reject` flag)
-    /// - COMMENT: Only adds a comment without approving or rejecting (`--comment-only` flag)
+    /// Fetches the head commit SHA of a pull request.
     ///
-    /// The method uses GitHub's REST API and requires the head commit SHA of the PR,
-    /// which must be included in the review payload.
-    fn submit_pull_request_review(
+    /// Many features (checks, deployments, workflow logs) are keyed by the head commit
+    /// rather than the PR number.
+    pub(crate) fn pull_request_head_sha(
         &self,
-        pr_number: &str, // The pull request number, as a string (e.g. "42")
-        message: &str,   // The review message to be attached to the review
-        event: &str,     // The type of review: APPROVE, REQUEST_CHANGES, or COMMENT
-    ) -> Result<(), Box<dyn Error>> {
-        // Log debug message that a review is being initiated
-        debug_log!("[DEBUG] Submitting review for PR #{}", pr_number);
-
-        // Infer the repository owner and name from the remote URL
-        let (owner, repo) = self
-            .infer_repo_details()
-            .ok_or("Could not parse owner/repo")?; // Return error if parsing fails
-
-        // Build the URL to fetch the pull request details (needed to get the commit SHA)
-        let pr_url = format!(
-            "https://api.github.com/repos/{}/{}/pulls/{}",
-            owner, repo, pr_number
-        );
-
-        debug_log!("[DEBUG] Fetching PR for commit_id from: {}", pr_url);
-
-        // Make a GET request to fetch the PR data
-        let pr_response = self
-            .client
-            .get(&pr_url)
-            .bearer_auth(&self.token) // Use GitHub token for authentication
-            .header("User-Agent", "git-pr") // Required by GitHub's API
-            .send()?; // Send request and propagate errors
-
-        // Parse the response body as JSON
-        let pr_json: serde_json::Value = pr_response.json()?;
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+    ) -> Result<String, Box<dyn Error>> {
+        Ok(self.fetch_pull_request(owner, repo, pr_number)?.head.sha)
+    }
 
-        // Extract the head commit SHA from the PR JSON
-        let commit_id = pr_json["head"]["sha"]
-            .as_str()
-            .ok_or("Could not extract commit_id")?; // Error if SHA is missing
+    /// Fetches full details for each PR number, newest first.
+    ///
+    /// PRs whose details can't be fetched are reported and skipped.
+    pub(crate) fn fetch_pr_details(
+        &self,
+        numbers: Vec<u32>,
+    ) -> Result<Vec<GitHubPR>, Box<dyn Error>> {
+        // We'll store (GitHubPR, age_days) so we can sort later
+        let mut detailed_prs = Vec::new();
 
-        debug_log!("[DEBUG] commit_id for PR #{}: {}", pr_number, commit_id);
+        // Fetch all PRs concurrently; one request per PR adds up on long lists
+        let numbers: Vec<PrNumber> = numbers.into_iter().map(|n| PrNumber(n.into())).collect();
+        debug_log!("[DEBUG] Fetching details for {} PRs", numbers.len());
+        let provider = Blocking::new(self.clone())?;
+
+        for (number, result) in provider.pull_requests(&numbers) {
+            let pr = match result {
+                Ok(pr) => pr,
+                Err(e) => {
+                    ui_eprintln!("⚠️  Failed to fetch details for PR #{}: {}", number, e);
+                    continue;
+                }
+            };
+            let age_days = (Utc::now() - pr.created_at).num_days();
 
-        // Construct the URL to submit the review to GitHub's review API
-        let review_url = format!(
-            "https://api.github.com/repos/{}/{}/pulls/{}/reviews",
-            owner, repo, pr_number
-        );
+            // Store PR with age_days for later sorting
+            detailed_prs.push((pr, age_days));
+        }
 
-        // Create the JSON payload for the review submission
-        let body = json!({
-            "body": message,       // The review message text
-            "event": event,       // Review type (APPROVE, REQUEST_CHANGES, COMMENT)
-            "commit_id": commit_id // Required commit SHA for the review
-        });
+        // Sort PRs by age_days ASCENDING (oldest first). Use `rev()` to make it newest first.
+        detailed_prs.sort_by_key(|(_, age_days)| *age_days);
 
-        debug_log!("[DEBUG] Submitting review to: {}", review_url);
-        debug_log!("[DEBUG] Payload: {}", body);
+        debug_log!("[DEBUG] Sorted PRs by age");
 
-        // Send the POST request to submit the review
-        let response = self
-            .client
-            .post(&review_url)
-            .bearer_auth(&self.token) // Again use the GitHub token
-            .header("User-Agent", "git-pr") // Required user-agent
-            .json(&body) // Attach the JSON payload
-            .send()?; // Send and propagate any errors
-
-        // Log the HTTP status for debug
-        debug_log!("[DEBUG] Response status: {}", response.status());
-
-        // Check if the submission was successful
-        if response.status().is_success() {
-            println!("✅ Review submitted successfully for PR #{}", pr_number);
-            Ok(()) // Return success
-        } else {
-            // Try to extract and include the error response text for clarity
-            Err(format!("Failed to submit review: {}", response.text()?).into())
-        }
+        Ok(detailed_prs.into_iter().map(|(pr, _)| pr).collect())
     }
 
-    /// Shows the GitHub Pull Request diff without requiring a local pull.
+    /// Lists every open PR of a repository, over all pages, optionally only those
+    /// targeting `base`.
     ///
-    /// If `--raw` is set, the diff is printed directly to stdout without pager.
-    /// Otherwise, tries to pipe to `delta`, or falls back to `less` or `cat`.
-    fn show_pull_request_diff(&self, pr_number: &str, raw: bool) -> Result<(), Box<dyn Error>> {
-        debug_log!("[DEBUG] Fetching diff for PR #{}", pr_number);
+    /// The list endpoint returns lightweight PR objects without commit or file counts.
+    pub(crate) fn open_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        base: Option<&str>,
+    ) -> Result<Vec<BasicGitHubPR>, Box<dyn Error>> {
+        self.all_pull_requests(
+            owner,
+            repo,
+            &ListOptions {
+                base: base.map(str::to_string),
+                ..Default::default()
+            },
+        )
+    }
 
-        let (owner, repo) = self
-            .infer_repo_details()
-            .ok_or("Could not parse owner/repo")?;
+    /// Lists the PRs of a repository matching the `list` filters: up to `page-size` (100)
+    /// of them.
+    ///
+    /// State, base branch and order are passed to the API. The pulls endpoint can't
+    /// filter by label or milestone (the issues and search APIs can, but they don't know
+    /// the base branch), so `labels` (all must be present) and `milestone` are matched
+    /// here, case-insensitively. `mergeable` and `owned_by_me` are checked per PR. Any of
+    /// these filters reads every page rather than just the first, so matches past it
+    /// aren't lost.
+    pub(crate) fn query_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<BasicGitHubPR>, Box<dyn Error>> {
+        let filtered = !options.labels.is_empty()
+            || options.milestone.is_some()
+            || options.mergeable
+            || options.owned_by_me;
+        if !filtered {
+            let url = format!(
+                "{}&per_page={}",
+                self.pulls_url(owner, repo, options)?,
+                self.config.page_size()
+            );
+            debug_log!("[DEBUG] Fetching PRs from URL: {}", url);
 
-        let pr_url = format!(
-            "https://api.github.com/repos/{}/{}/pulls/{}",
-            owner, repo, pr_number
-        );
+            // Make the HTTP GET request to fetch the list of PRs
+            let resp = self.send(self.authed(Method::GET, &url), "Failed to list PRs")?;
 
-        let pr_resp = self
-            .client
-            .get(&pr_url)
-            .bearer_auth(&self.token)
-            .header("User-Agent", "git-pr")
-            .send()?;
+            // Read the raw response body (also printed in DEBUG mode)
+            let text = resp.text()?;
+            debug_log!("[DEBUG] Response body: {}", text);
 
-        if !pr_resp.status().is_success() {
-            return Err(format!("❌ Failed to fetch PR metadata: {}", pr_resp.status()).into());
+            // Deserialize the basic PR list into a lightweight struct
+            // This does NOT include fields like commits or file count
+            return Ok(serde_json::from_str(&text)?);
         }
 
-        let pr_json: serde_json::Value = pr_resp.json()?;
-        let diff_url = pr_json["diff_url"]
-            .as_str()
-            .ok_or("Could not extract diff_url")?;
+        let mut basic_prs = self.all_pull_requests(owner, repo, options)?;
 
-        debug_log!("[DEBUG] Found diff_url: {}", diff_url);
+        // Keep only the PRs a maintainer could merge right now
+        if options.mergeable {
+            let numbers: Vec<u32> = basic_prs.iter().map(|pr| pr.number).collect();
+            let readiness = self.merge_readiness(owner, repo, &numbers)?;
+            basic_prs.retain(|pr| readiness.get(&pr.number).is_some_and(|r| r.is_ready()));
+        }
 
-        let diff_resp = self
-            .client
-            .get(diff_url)
-            .bearer_auth(&self.token)
-            .header("User-Agent", "git-pr")
-            .header("Accept", "application/vnd.github.v3.diff")
-            .send()?;
+        // Keep only the PRs touching paths the user owns (see `ownership.rs`)
+        if options.owned_by_me {
+            self.retain_owned_pull_requests(owner, repo, &mut basic_prs)?;
+        }
+        basic_prs.truncate(self.config.page_size() as usize);
 
-        if !diff_resp.status().is_success() {
-            return Err(format!(
-                "❌ Failed to fetch diff from GitHub: {}",
-                diff_resp.status()
-            )
-            .into());
+        Ok(basic_prs)
+    }
+
+    /// Lists every PR of a repository matching the state, base, label and milestone
+    /// filters of `options`, over all pages.
+    fn all_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<BasicGitHubPR>, Box<dyn Error>> {
+        let url = self.pulls_url(owner, repo, options)?;
+        let mut basic_prs: Vec<BasicGitHubPR> = self.get_pages(&url, "Failed to list PRs")?;
+        basic_prs.retain(|pr| {
+            options.labels.iter().all(|wanted| {
+                pr.labels
+                    .iter()
+                    .any(|l| l.name.eq_ignore_ascii_case(wanted))
+            })
+        });
+        if let Some(wanted) = &options.milestone {
+            basic_prs.retain(|pr| {
+                pr.milestone
+                    .as_ref()
+                    .is_some_and(|m| m.title.eq_ignore_ascii_case(wanted))
+            });
         }
+        Ok(basic_prs)
+    }
 
-        let diff_body = diff_resp.text()?;
+    /// Builds the pulls endpoint URL for the state, order and base branch of `options`,
+    /// newest first.
+    fn pulls_url(
+        &self,
+        owner: &str,
+        repo: &str,
+        options: &ListOptions,
+    ) -> Result<String, Box<dyn Error>> {
+        let mut query = vec![
+            ("state", options.state.as_deref().unwrap_or("open")),
+            ("sort", options.sort.as_deref().unwrap_or("created")),
+            ("direction", "desc"),
+        ];
+
+        // Narrow down to a single base branch (e.g. a release line) if requested; branch
+        // names may hold `&`, `#` or `+`, so the query is encoded rather than pasted together
+        query.extend(options.base.as_deref().map(|base| ("base", base)));
+        Ok(Url::parse_with_params(
+            &format!("{}/repos/{}/{}/pulls", self.api_base, owner, repo),
+            &query,
+        )?
+        .to_string())
+    }
 
-        if raw {
-            // Print raw diff to stdout
-            println!("{}", diff_body);
-            return Ok(());
+    /// Fetches full details for each PR number and prints the standard PR table.
+    ///
+    /// Shared by `list` and `search` so both render identical tables. PRs are shown newest
+    /// first, by creation time unless `sort` is `updated`. `columns` picks the columns and
+    /// their order (see `columns.rs`), falling back to the `list-columns` setting; review
+    /// and CI state are only fetched when their column is shown or a review SLA is
+    /// configured (see `sla.rs`), in which case rows are colored by how long they waited.
+    ///
+    /// # Returns
+    /// - The number of PRs past the review SLA
+    pub(crate) fn print_pr_table(
+        &self,
+        numbers: Vec<u32>,
+        sort: Option<&str>,
+        columns: Option<&str>,
+    ) -> Result<usize, Box<dyn Error>> {
+        let spec = columns
+            .or(self.config.list_columns.as_deref())
+            .unwrap_or(DEFAULT_COLUMNS);
+        let mut columns = parse_columns(spec)?;
+        // With a tracker configured, the default table links each PR's tickets
+        if spec == DEFAULT_COLUMNS && self.config.tracker_url.is_some() {
+            columns.insert(2, Column::Ticket);
         }
+        debug_log!("[DEBUG] Table columns: {:?}", columns);
 
-        // Try using `delta`, fallback to `less`, fallback to `cat`
-        let pager = if which("delta").is_ok() {
-            "delta"
-        } else if which("less").is_ok() {
-            "less"
+        let sensitive = if columns.contains(&Column::Title) {
+            self.sensitive_pull_requests(&numbers)?
         } else {
-            "cat"
+            HashSet::new()
         };
+        let sla = ReviewSla::from_config(&self.config);
+        let mut statuses = if sla.is_some()
+            || columns.contains(&Column::Checks)
+            || columns.contains(&Column::Review)
+        {
+            self.pull_request_statuses_of(&numbers)?
+        } else {
+            BTreeMap::new()
+        };
+        let mut projects = if columns.contains(&Column::Project) {
+            self.project_statuses_of(&numbers)
+        } else {
+            BTreeMap::new()
+        };
+        let mut prs = self.fetch_pr_details(numbers)?;
+        sort_pull_requests(&mut prs, sort);
+
+        // Title badges: ⛔ waiting on an open dependency, 🔐 touching sensitive paths,
+        // ❄️ waiting for a merge freeze to end
+        let blocked = match self.infer_repo_details() {
+            Some((owner, repo)) if columns.contains(&Column::Title) => {
+                let bodies: Vec<(u32, &str)> = prs
+                    .iter()
+                    .map(|pr| (pr.number, pr.body.as_deref().unwrap_or("")))
+                    .collect();
+                self.blocked_pull_requests(&owner, &repo, &bodies)
+            }
+            _ => HashSet::new(),
+        };
+        // ❄️ queued: open and into a branch under a merge freeze (each base is read once)
+        let frozen: BTreeMap<String, Freeze> = match self.infer_repo_details() {
+            Some((owner, repo)) if columns.contains(&Column::Title) => prs
+                .iter()
+                .filter(|pr| pr.state == "open")
+                .map(|pr| pr.base.ref_name.clone())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .filter_map(|base| {
+                    let freeze = self.active_freeze(&owner, &repo, &base).ok().flatten()?;
+                    Some((base, freeze))
+                })
+                .collect(),
+            _ => BTreeMap::new(),
+        };
+        let queued = |pr: &GitHubPR| pr.state == "open" && frozen.contains_key(&pr.base.ref_name);
+        let mut waiting: BTreeMap<String, usize> = BTreeMap::new();
+        for pr in prs.iter().filter(|pr| queued(pr)) {
+            *waiting.entry(pr.base.ref_name.clone()).or_default() += 1;
+        }
+        let badges: BTreeMap<u32, String> = prs
+            .iter()
+            .map(|pr| {
+                let mut badge = String::new();
+                if queued(pr) {
+                    badge.push_str("❄️ ");
+                }
+                if blocked.contains(&pr.number) {
+                    badge.push_str("⛔ ");
+                }
+                if sensitive.contains(&pr.number) {
+                    badge.push_str("🔐 ");
+                }
+                (pr.number, badge)
+            })
+            .collect();
 
-        debug_log!("[DEBUG] Using pager: {}", pager);
-
-        let mut child = Command::new(pager)
-            .stdin(Stdio::piped())
-            .spawn()
-            .expect("Failed to spawn pager");
+        // Build table rows after sorting
+        let mut breached = 0;
+        let rows: Vec<Vec<String>> = prs
+            .into_iter()
+            .map(|pr| {
+                debug_log!("[DEBUG] Mapping PR #{} to table row", pr.number);
+                let status = statuses.remove(&pr.number);
+                let boards = projects.remove(&pr.number);
+                // Closed PRs aren't waiting on anyone
+                let level = match &sla {
+                    Some(sla) if pr.state == "open" => {
+                        sla.level(pr.created_at, status.as_ref().map(|s| s.review.as_str()))
+                    }
+                    _ => SlaLevel::Within,
+                };
+                if level == SlaLevel::Breached {
+                    breached += 1;
+                }
+                columns
+                    .iter()
+                    .map(|column| {
+                        let cell =
+                            self.pr_cell(&pr, *column, &badges, status.as_ref(), boards.as_deref());
+                        paint(level, &cell)
+                    })
+                    .collect()
+            })
+            .collect();
 
-        child
-            .stdin
-            .as_mut()
-            .ok_or("Failed to open stdin for pager")?
-            .write_all(diff_body.as_bytes())?;
+        // On narrow terminals the description wraps first, then the title, then labels get
+        // cut off
+        println!("{}", render_columns(&columns, rows));
+        for (base, freeze) in &frozen {
+            status_log!(
+                "❄️  {} is frozen: {}; {} PR(s) queued until the freeze ends.",
+                base.cyan(),
+                freeze.describe(),
+                waiting.get(base).copied().unwrap_or(0)
+            );
+        }
 
-        child.wait()?;
-        Ok(())
+        Ok(breached)
     }
 
-    /// Pulls a GitHub pull request (PR) and checks out a corresponding local branch.
-    /// This function supports two main scenarios for how PRs are created on GitHub:
-    ///
-    /// ---
-    ///
-    /// ## 🔁 Scenario 1: PR From Same Repository (Not Forked)
+    /// Text of one PR table cell.
     ///
-    /// - The contributor created a branch in the **same repository** (upstream/original repo).
-    /// - The `head.repo.full_name` is the same as the `base.repo.full_name`.
-    /// - This function:
-    ///   - Fetches the PR's head branch from `origin`.
-    ///   - Creates a local branch with the same name (e.g., `feature-x`).
-    ///   - Sets it to track `origin/feature-x`.
-    /// - ✅ The user can directly push commits to this branch if they have write access to the repo.
-    /// - This is the **ideal flow for collaboration** within the same team/org.
+    /// # Parameters
+    /// - `badges`: Markers put in front of each PR's title (frozen base, blocked, sensitive
+    ///   paths)
+    /// - `status`: Review and CI state of the PR, `None` if it couldn't be fetched
+    /// - `boards`: Project boards the PR is on, with its status on each
+    fn pr_cell(
+        &self,
+        pr: &GitHubPR,
+        column: Column,
+        badges: &BTreeMap<u32, String>,
+        status: Option<&PullRequestStatus>,
+        boards: Option<&[String]>,
+    ) -> String {
+        match column {
+            Column::Number => link(&format!("#{}", pr.number), &pr.html_url),
+            Column::Title => {
+                // Flag newcomers' PRs too
+                let badge = badges.get(&pr.number).map_or("", String::as_str);
+                let newcomer = if is_first_time(&pr.author_association) {
+                    "🌱 "
+                } else {
+                    ""
+                };
+                format!("{}{}{}", newcomer, badge, pr.title)
+            }
+            Column::Author => pr.user.login.clone(),
+            Column::Created => self.config.format_time(pr.created_at),
+            Column::Updated => self.config.format_time(pr.updated_at),
+            Column::Commits => pr.commits.to_string(),
+            Column::Files => pr.changed_files.to_string(),
+            Column::Head => head_branch(pr),
+            Column::Base => pr.base.ref_name.clone(),
+            Column::Labels if pr.labels.is_empty() => "-".to_string(),
+            Column::Labels => pr
+                .labels
+                .iter()
+                .map(|l| l.name.clone())
+                .collect::<Vec<_>>()
+                .join(", "),
+            Column::Description => {
+                let wrap_opts = Options::new(60).break_words(false);
+                fill(pr.body.as_deref().unwrap_or("-"), wrap_opts)
+            }
+            Column::Milestone => pr
+                .milestone
+                .as_ref()
+                .map_or("-".to_string(), |m| m.title.clone()),
+            Column::Project => boards.map_or("-".to_string(), |boards| boards.join("\n")),
+            Column::Checks => status.map_or("?", |s| s.checks.as_str()).to_string(),
+            Column::Review => status.map_or("?", |s| s.review.as_str()).to_string(),
+            Column::Ticket => {
+                let keys = tracker::ticket_keys(&[&pr.title, &pr.head.ref_name]);
+                if keys.is_empty() {
+                    return "-".to_string();
+                }
+                keys.iter()
+                    .map(|key| match tracker::ticket_url(&self.config, key) {
+                        Some(url) => link(key, &url),
+                        None => key.clone(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        }
+    }
+
+    /// Review and CI state of the PRs among `numbers`, fetched concurrently.
     ///
-    /// ---
+    /// PRs whose state can't be fetched are left out.
+    fn pull_request_statuses_of(
+        &self,
+        numbers: &[u32],
+    ) -> Result<BTreeMap<u32, PullRequestStatus>, Box<dyn Error>> {
+        let numbers: Vec<PrNumber> = numbers.iter().map(|&n| PrNumber(n.into())).collect();
+        let provider = Blocking::new(self.clone())?;
+        let mut statuses = BTreeMap::new();
+        for (number, result) in provider.pull_request_statuses(&numbers) {
+            match result {
+                Ok(status) => {
+                    statuses.insert(number.0 as u32, status);
+                }
+                Err(e) => debug_log!("[DEBUG] No status for PR #{}: {}", number, e),
+            }
+        }
+        Ok(statuses)
+    }
+
+    /// Project boards of the PRs among `numbers`, for the `project` column.
     ///
-    /// ## 🍴 Scenario 2: PR From a Forked Repository
+    /// Boards are nice to have: if they can't be read (typically a token without the
+    /// `read:project` scope), the column shows `?` and the table is printed anyway.
+    fn project_statuses_of(&self, numbers: &[u32]) -> BTreeMap<u32, Vec<String>> {
+        let result = self
+            .infer_repo_details()
+            .ok_or_else(|| "Failed to infer repository details from remote URL".into())
+            .and_then(|(owner, repo)| self.project_statuses(&owner, &repo, numbers));
+        match result {
+            Ok(boards) => boards,
+            Err(e) => {
+                ui_eprintln!("⚠️  Couldn't read project boards: {}", e);
+                numbers
+                    .iter()
+                    .map(|&n| (n, vec!["?".to_string()]))
+                    .collect()
+            }
+        }
+    }
+
+    /// Numbers of the PRs among `numbers` that touch a `sensitive-paths` path.
     ///
-    /// - The contributor forked the upstream repo, pushed changes to the fork, and opened a PR.
-    /// - The `head.repo.full_name` is different from `base.repo.full_name`.
-    /// - This function:
-    ///   - Uses GitHub’s special `refs/pull/<PR_NUMBER>/head` to fetch the PR as a read-only branch.
-    ///   - Creates a local branch named `<fork-owner>-pr-<PR_NUMBER>` (e.g., `alice-pr-42`).
-    ///   - Checks out this local branch, but does **not** connect it to any remote.
-    /// - ⚠️ This branch cannot be pushed back to the original PR directly, since the upstream user
-    ///   doesn’t have write access to the fork.
-    /// - If you need to make changes, you must create a **new branch** and open a **separate PR**.
+    /// The changed files of every PR are fetched concurrently; PRs whose files can't be
+    /// fetched aren't flagged. Nothing is fetched when detection is turned off.
+    fn sensitive_pull_requests(&self, numbers: &[u32]) -> Result<HashSet<u32>, Box<dyn Error>> {
+        let rules = SensitivePaths::new(&self.config);
+        if rules.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let numbers: Vec<PrNumber> = numbers.iter().map(|&n| PrNumber(n.into())).collect();
+        let provider = Blocking::new(self.clone())?;
+        let mut flagged = HashSet::new();
+        for (number, result) in provider.changed_paths_of(&numbers) {
+            match result {
+                Ok(paths) if !rules.matching(paths.iter().map(String::as_str)).is_empty() => {
+                    flagged.insert(number.0 as u32);
+                }
+                Ok(_) => {}
+                Err(e) => debug_log!("[DEBUG] No files for PR #{}: {}", number, e),
+            }
+        }
+        Ok(flagged)
+    }
+
+    /// URL of the GraphQL endpoint matching the configured REST API base.
     ///
-    /// ---
+    /// github.com serves GraphQL at `https://api.github.com/graphql`, while GitHub
+    /// Enterprise uses `https://<host>/api/graphql` next to the `/api/v3` REST root.
+    pub(crate) fn graphql_url(&self) -> String {
+        match self.api_base.strip_suffix("/v3") {
+            Some(root) => format!("{}/graphql", root),
+            None => format!("{}/graphql", self.api_base),
+        }
+    }
+
+    /// URL of a page of `owner/repo` in the web UI, matching the configured REST API base.
     ///
-    /// ## 💡 Tip:
-    /// When working with PRs from forks, you can cherry-pick or patch the commits to your own branch,
-    /// but cannot push directly to the fork’s branch unless you have permissions.
+    /// github.com is served at `https://github.com` and GitHub Enterprise at the host of
+    /// its `/api/v3` root; any other base (mock servers) is used as is.
     ///
-    /// ---
-    fn get_pull_request(&self, pr_number: &str) {
-        // Get the origin URL of the current Git repository (e.g., git@github.com:owner/repo.git)
-        let remote_url = get_remote_url().unwrap_or_else(|| {
-            eprintln!("{}", "❌ Could not determine remote URL.".red());
-            std::process::exit(1);
-        });
-
-        // Create a GitHub provider instance using the remote URL
-        // This gives access to authenticated API operations and utilities
-        let github = GitHubProvider::new(remote_url.clone()).unwrap_or_else(|e| {
-            eprintln!(
-                "{}",
-                format!("❌ Failed to create GitHubProvider: {}", e).red()
-            );
-            std::process::exit(1);
-        });
+    /// # Parameters
+    /// - `path`: The page below the repository, e.g. `pull/42`
+    pub(crate) fn web_url(&self, owner: &str, repo: &str, path: &str) -> String {
+        let root = if self.api_base == DEFAULT_API_BASE {
+            "https://github.com"
+        } else {
+            self.api_base
+                .strip_suffix("/api/v3")
+                .unwrap_or(&self.api_base)
+        };
+        format!("{}/{}/{}/{}", root, owner, repo, path)
+    }
 
-        // Infer GitHub repo owner and repo name from remote URL
-        // Example: git@github.com:foo/bar.git → ("foo", "bar")
-        let (owner, repo) = github.infer_repo_details().unwrap_or_else(|| {
-            eprintln!("{}", "❌ Could not infer owner/repo.".red());
-            std::process::exit(1);
-        });
+    /// Executes a GraphQL query/mutation and returns its `data` object.
+    ///
+    /// GraphQL reports most failures with HTTP 200 and an `errors` array, so both the
+    /// status and the payload are checked.
+    pub(crate) fn graphql(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<serde_json::Value, Box<dyn Error>> {
+        let url = self.graphql_url();
+        debug_log!("[DEBUG] GraphQL request to {}: {}", url, variables);
+
+        let body: serde_json::Value = self.request(
+            self.authed(Method::POST, &url)
+                .json(&json!({ "query": query, "variables": variables })),
+            "GraphQL request failed",
+        )?;
+        if let Some(errors) = body["errors"].as_array() {
+            let messages: Vec<&str> = errors
+                .iter()
+                .filter_map(|e| e["message"].as_str())
+                .collect();
+            return Err(format!("GraphQL error: {}", messages.join("; ")).into());
+        }
 
-        let client = &github.client;
-        let token = &github.token;
+        Ok(body["data"].clone())
+    }
 
-        // Construct GitHub API URL for fetching pull request metadata
+    /// Fetches the full payload of a pull request.
+    pub(crate) fn fetch_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+    ) -> Result<GitHubPRDetail, Box<dyn Error>> {
         let pr_url = format!(
-            "https://api.github.com/repos/{}/{}/pulls/{}",
-            owner, repo, pr_number
+            "{}/repos/{}/{}/pulls/{}",
+            self.api_base, owner, repo, pr_number
         );
-        debug_log!("[DEBUG] Fetching PR info from: {}", pr_url);
-
-        // Perform authenticated API GET request to retrieve PR details
-        let pr_resp = client
-            .get(&pr_url)
-            .bearer_auth(token)
-            .header("User-Agent", "git-pr")
-            .send()
-            .expect("Failed to fetch PR info");
-
-        // Abort if the response isn't a success
-        if !pr_resp.status().is_success() {
-            eprintln!(
-                "{}",
-                format!("❌ Failed to fetch PR: {}", pr_resp.status()).red()
-            );
-            std::process::exit(1);
-        }
+        debug_log!("[DEBUG] Fetching PR from: {}", pr_url);
+        self.get(&pr_url, &format!("Failed to fetch PR #{}", pr_number))
+    }
 
-        // Parse JSON response containing PR metadata
-        let pr_json: serde_json::Value = pr_resp.json().expect("Failed to parse PR JSON");
+    /// Lists the commits of a pull request, oldest first.
+    pub(crate) fn pull_request_commits(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+    ) -> Result<Vec<GitHubPRCommit>, Box<dyn Error>> {
+        let commits_url = format!(
+            "{}/repos/{}/{}/pulls/{}/commits",
+            self.api_base, owner, repo, pr_number
+        );
+        debug_log!("[DEBUG] Fetching commits from: {}", commits_url);
+        self.get_pages(&commits_url, "Failed to fetch commits")
+    }
 
-        // Extract head branch name from the PR
-        let head_branch = pr_json["head"]["ref"].as_str().unwrap_or("");
+    /// Fetches the GraphQL node ID of a pull request (needed by GraphQL mutations).
+    pub(crate) fn pull_request_node_id(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+    ) -> Result<String, Box<dyn Error>> {
+        Ok(self.fetch_pull_request(owner, repo, pr_number)?.node_id)
+    }
 
-        // Extract the full name of the head repo (e.g., "user/repo")
-        let head_repo = pr_json["head"]["repo"]["full_name"].as_str().unwrap_or("");
+    /// Fetches the repository's default branch (e.g. `main`).
+    pub(crate) fn default_branch(&self, owner: &str, repo: &str) -> Result<String, Box<dyn Error>> {
+        let url = format!("{}/repos/{}/{}", self.api_base, owner, repo);
+        debug_log!("[DEBUG] Fetching default branch from: {}", url);
 
-        // Extract the GitHub login of the user who owns the head repo
-        let head_repo_owner = pr_json["head"]["repo"]["owner"]["login"]
+        let repo_json: serde_json::Value = self.get(&url, "Failed to fetch repository")?;
+        Ok(repo_json["default_branch"]
             .as_str()
-            .unwrap_or("");
-
-        // Extract the full name of the base repository that the PR targets
-        let base_repo = pr_json["base"]["repo"]["full_name"].as_str().unwrap_or("");
+            .unwrap_or("main")
+            .to_string())
+    }
 
-        // Determine if the PR is from a fork (head repo != base repo)
-        let head_is_fork = head_repo != base_repo;
+    /// Splits the open PRs into the one already proposing `head_label`, if any, and the rest.
+    ///
+    /// Run before the title and body are edited, so an existing PR stops `create` before
+    /// any typing is lost; the rest are checked for near-identical titles afterwards.
+    ///
+    /// # Parameters
+    /// - `head_label`: `owner:branch` of the branch being proposed
+    ///
+    /// # Returns
+    /// `(same head, others)`: the open PR already proposing `head_label`, if any, and the
+    /// other open PRs of the newest page (a title warning doesn't need them all).
+    pub(crate) fn open_pull_requests_by_head(
+        &self,
+        owner: &str,
+        repo: &str,
+        head_label: &str,
+    ) -> Result<(Option<BasicGitHubPR>, Vec<BasicGitHubPR>), Box<dyn Error>> {
+        let pulls = format!("{}/repos/{}/{}/pulls", self.api_base, owner, repo);
+
+        // GitHub filters by head itself, so the PR is found however many others are open
+        let url = Url::parse_with_params(&pulls, &[("state", "open"), ("head", head_label)])?;
+        debug_log!("[DEBUG] Checking for duplicate PRs at: {}", url);
+        let same_head: Vec<BasicGitHubPR> = self.get(url.as_str(), "Failed to fetch open PRs")?;
+
+        let url = format!("{}?state=open&per_page={}", pulls, self.config.page_size());
+        let mut others: Vec<BasicGitHubPR> = self.get(&url, "Failed to fetch open PRs")?;
+        others.retain(|pr| !pr.head.label.eq_ignore_ascii_case(head_label));
+
+        Ok((same_head.into_iter().next(), others))
+    }
 
-        debug_log!(
-            "[DEBUG] PR head branch: {}, head repo: {}, head owner: {}, base repo: {}, is fork: {}",
-            head_branch,
-            head_repo,
-            head_repo_owner,
-            base_repo,
-            head_is_fork
+    /// Checks whether a branch exists in the repository.
+    pub(crate) fn branch_exists(
+        &self,
+        owner: &str,
+        repo: &str,
+        branch: &str,
+    ) -> Result<bool, Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/branches/{}",
+            self.api_base, owner, repo, branch
         );
+        debug_log!("[DEBUG] Checking branch at: {}", url);
 
-        // Get authenticated user's GitHub username (via /user endpoint)
-        let user_resp = client
-            .get("https://api.github.com/user")
-            .bearer_auth(token)
-            .header("User-Agent", "git-pr")
-            .send()
-            .expect("Failed to fetch authenticated user");
+        let resp = self.dispatch(self.authed(Method::GET, &url))?;
 
-        let user_json: serde_json::Value = user_resp.json().expect("Failed to parse user JSON");
-        let username = user_json["login"].as_str().unwrap_or("");
-        debug_log!("[DEBUG] Authenticated as: {}", username);
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        self.check_response(resp, &format!("Failed to check branch {}", branch))?;
+        Ok(true)
+    }
 
-        // Handle the case where the PR is from the same repository (not a fork)
+    /// Compares `head` against `base` (`head` may be `owner:branch` for forks).
+    ///
+    /// # Returns
+    /// `(ahead_by, behind_by)`: commits only on `head`, and commits only on `base`.
+    pub(crate) fn compare_commits(
+        &self,
+        owner: &str,
+        repo: &str,
+        base: &str,
+        head: &str,
+    ) -> Result<(u64, u64), Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/compare/{}...{}",
+            self.api_base, owner, repo, base, head
+        );
+        debug_log!("[DEBUG] Comparing commits at: {}", url);
+
+        let comparison: serde_json::Value =
+            self.get(&url, &format!("Failed to compare {}...{}", base, head))?;
+        Ok((
+            comparison["ahead_by"].as_u64().unwrap_or(0),
+            comparison["behind_by"].as_u64().unwrap_or(0),
+        ))
+    }
+
+    /// Fetches an issue's title and labels, rejecting numbers that belong to PRs.
+    pub(crate) fn fetch_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<GitHubIssue, Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}",
+            self.api_base, owner, repo, number
+        );
+        debug_log!("[DEBUG] Fetching issue from: {}", url);
+
+        let issue: GitHubIssue = self.get(&url, &format!("Failed to fetch issue #{}", number))?;
+        if issue.pull_request.is_some() {
+            return Err(format!("#{} is a pull request, not an issue", number).into());
+        }
+        Ok(issue)
+    }
+
+    /// Adds labels to an issue or pull request.
+    pub(crate) fn add_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: &str,
+        labels: &[String],
+    ) -> Result<(), Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}/labels",
+            self.api_base, owner, repo, number
+        );
+        debug_log!("[DEBUG] Adding labels {:?} via: {}", labels, url);
+
+        self.send(
+            self.authed(Method::POST, &url)
+                .json(&json!({ "labels": labels })),
+            "Failed to add labels",
+        )?;
+        Ok(())
+    }
+
+    /// Assigns users to an issue or pull request.
+    pub(crate) fn add_assignees(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: &str,
+        assignees: &[String],
+    ) -> Result<(), Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}/assignees",
+            self.api_base, owner, repo, number
+        );
+        debug_log!("[DEBUG] Assigning {:?} via: {}", assignees, url);
+
+        self.send(
+            self.authed(Method::POST, &url)
+                .json(&json!({ "assignees": assignees })),
+            "Failed to assign",
+        )?;
+        Ok(())
+    }
+
+    /// Posts a comment on the conversation of an issue or pull request.
+    pub(crate) fn post_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: &str,
+        body: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}/comments",
+            self.api_base, owner, repo, number
+        );
+        debug_log!("[DEBUG] Commenting via: {}", url);
+
+        self.send(
+            self.authed(Method::POST, &url)
+                .json(&json!({ "body": body })),
+            "Failed to post comment",
+        )?;
+        Ok(())
+    }
+
+    /// Posts an inline comment on lines of a PR's diff, as of its head commit.
+    ///
+    /// # Parameters
+    /// - `path`: The file, relative to the repository root
+    /// - `lines`: The line or lines the comment is on
+    /// - `side` / `start_side`: `RIGHT` or `LEFT`, for the last and the first line
+    /// - `body`: The comment text
+    ///
+    /// # Returns
+    /// - The comment's URL in the web UI
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn post_review_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+        path: &str,
+        lines: LineRange,
+        side: &str,
+        start_side: &str,
+        body: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        let commit_id = self.pull_request_head_sha(owner, repo, pr_number)?;
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/comments",
+            self.api_base, owner, repo, pr_number
+        );
+        let mut payload = json!({
+            "body": body,
+            "commit_id": commit_id,
+            "path": path,
+            "line": lines.end,
+            "side": side,
+        });
+        if !lines.is_single() {
+            payload["start_line"] = json!(lines.start);
+            payload["start_side"] = json!(start_side);
+        }
+        debug_log!("[DEBUG] Commenting on lines via: {} {}", url, payload);
+
+        let resp = self
+            .send(
+                self.authed(Method::POST, &url).json(&payload),
+                &format!("Failed to comment on {} lines {}", path, lines),
+            )
+            .map_err(|e| {
+                // GitHub rejects lines outside the diff with a bare "Validation Failed"
+                let e = e.to_string();
+                if e.contains("422") {
+                    format!(
+                        "{} (every line must be in the PR's diff, within one hunk; use --side left for removed lines)",
+                        e
+                    )
+                } else {
+                    e
+                }
+            })?;
+        let created: GitHubComment = resp.json()?;
+        Ok(created.html_url)
+    }
+
+    /// Prints the tracker tickets of a PR with their title and status, for `show-details`.
+    ///
+    /// Nothing is printed unless `tracker-url` is configured. Tickets that can't be looked
+    /// up (no token, tracker unreachable) are shown as links only.
+    fn print_tickets(&self, keys: &[String]) {
+        if keys.is_empty() || self.config.tracker_url.is_none() {
+            return;
+        }
+        let mut lines = Vec::new();
+        for key in keys {
+            let url = tracker::ticket_url(&self.config, key).unwrap_or_default();
+            let plain = format!("   {}  {}", link(key, &url).yellow(), url);
+            if !tracker::has_token() {
+                lines.push(plain);
+                continue;
+            }
+            match tracker::lookup(&self.config, key) {
+                Ok(Some(ticket)) => lines.push(format!(
+                    "   {}  {} ({})",
+                    link(&ticket.key, &url).yellow(),
+                    ticket.title,
+                    ticket.status.cyan()
+                )),
+                // Not a ticket the tracker knows: a false positive of the key pattern
+                Ok(None) => debug_log!("[DEBUG] No ticket {} in the tracker", key),
+                Err(e) => {
+                    ui_eprintln!("⚠️  Couldn't look up {}: {}", key, e);
+                    lines.push(plain);
+                }
+            }
+        }
+        if !lines.is_empty() {
+            ui_println!("{}", "🎫 Tickets:".bold());
+            for line in lines {
+                ui_println!("{}", line);
+            }
+        }
+    }
+
+    /// Extracts the GitHub `owner` and `repo` name from the remote URL.
+    /// Handles both HTTPS and SSH GitHub URLs.
+    pub(crate) fn infer_repo_details(&self) -> Option<(String, String)> {
+        debug_log!("[DEBUG] Inferring repo details from remote URL");
+        let url = self.remote_url.trim_end_matches(".git");
+
+        // Enterprise hosts don't contain "github.com", so a custom API base also qualifies
+        if url.contains("github.com") || self.api_base != DEFAULT_API_BASE {
+            let parts: Vec<&str> = if url.starts_with("http") {
+                url.split('/').collect()
+            } else {
+                url.split(':').next_back()?.split('/').collect()
+            };
+
+            debug_log!("[DEBUG] Split URL parts: {:?}", parts);
+
+            if parts.len() >= 2 {
+                return Some((
+                    parts[parts.len() - 2].to_string(),
+                    parts[parts.len() - 1].to_string(),
+                ));
+            }
+        }
+        debug_log!("[DEBUG] Failed to infer repo details");
+        None
+    }
+
+    /// Submits a review on a given commit of the PR.
+    ///
+    /// # Parameters
+    /// - `commit_id`: The commit the review is given on; the PR's current head if `None`.
+    ///   A review pinned to a checked commit doesn't silently cover commits pushed since.
+    pub(crate) fn submit_review_on(
+        &self,
+        pr_number: PrNumber,
+        message: &str,
+        event: &str,
+        commit_id: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
+        // Log debug message that a review is being initiated
+        debug_log!("[DEBUG] Submitting review for PR #{}", pr_number);
+
+        // Infer the repository owner and name from the remote URL
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?; // Return error if parsing fails
+
+        // Fail before anything else if the token can't submit reviews here
+        self.check_token_can_write(
+            &owner,
+            &repo,
+            &format!("reviewing PR #{}", pr_number),
+            WriteAccess::Any,
+        )?;
+
+        // Review the commit the caller checked, or else the current head
+        let commit_id = match commit_id {
+            Some(sha) => sha.to_string(),
+            None => self.pull_request_head_sha(&owner, &repo, pr_number)?,
+        };
+
+        debug_log!("[DEBUG] commit_id for PR #{}: {}", pr_number, commit_id);
+
+        // Construct the URL to submit the review to GitHub's review API
+        let review_url = format!(
+            "{}/repos/{}/{}/pulls/{}/reviews",
+            self.api_base, owner, repo, pr_number
+        );
+
+        // Create the JSON payload for the review submission
+        let body = json!({
+            "body": message,       // The review message text
+            "event": event,       // Review type (APPROVE, REQUEST_CHANGES, COMMENT)
+            "commit_id": commit_id // Required commit SHA for the review
+        });
+
+        debug_log!("[DEBUG] Submitting review to: {}", review_url);
+        debug_log!("[DEBUG] Payload: {}", body);
+
+        // Send the POST request to submit the review
+        self.send(
+            self.authed(Method::POST, &review_url).json(&body),
+            "Failed to submit review",
+        )?;
+        status_log!("✅ Review submitted successfully for PR #{}", pr_number);
+        Ok(())
+    }
+}
+
+impl SourceControlProvider for GitHubProvider {
+    /// Submits a code review for a specific pull request on GitHub.
+    ///
+    /// This function supports submitting one of three review types:
+    /// - APPROVE: Approves the changes (`--approve` flag)
+    /// - REQUEST_CHANGES: Rejects the changes and asks for changes (`--reject` flag)
+    /// - COMMENT: Only adds a comment without approving or rejecting (`--comment-only` flag)
+    ///
+    /// The method uses GitHub's REST API and requires the head commit SHA of the PR,
+    /// which must be included in the review payload (see `submit_review_on`).
+    fn submit_pull_request_review(
+        &self,
+        pr_number: PrNumber, // The pull request number (e.g. 42)
+        message: &str,       // The review message to be attached to the review
+        event: &str,         // The type of review: APPROVE, REQUEST_CHANGES, or COMMENT
+    ) -> Result<(), Box<dyn Error>> {
+        self.submit_review_on(pr_number, message, event, None)
+    }
+
+    /// Shows the GitHub Pull Request diff without requiring a local pull.
+    ///
+    /// If `--raw` is set, the diff is printed directly to stdout without pager.
+    /// Otherwise, tries to pipe to `delta`, or falls back to `less` or `cat`.
+    /// Generated files are dropped first (see `generated.rs`) unless `--include-generated`.
+    /// With `--blame` the removed lines are annotated (see `blame.rs`); the annotated diff
+    /// is colored here, so it goes to `less -R` instead of `delta`.
+    fn show_pull_request_diff(
+        &self,
+        pr_number: PrNumber,
+        options: &DiffOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        debug_log!("[DEBUG] Fetching diff for PR #{}", pr_number);
+
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        let pr = self.fetch_pull_request(&owner, &repo, pr_number)?;
+        let diff_url = pr.diff_url.as_str();
+
+        debug_log!("[DEBUG] Found diff_url: {}", diff_url);
+
+        let diff_resp = self.send(
+            self.authed(Method::GET, diff_url)
+                .header("Accept", "application/vnd.github.v3.diff"),
+            "Failed to fetch diff",
+        )?;
+
+        let mut diff_body = diff_resp.text()?;
+        let mut hidden = Vec::new();
+        if !options.include_generated {
+            let kept;
+            (kept, hidden) = strip_generated(&diff_body, &self.config);
+            if !hidden.is_empty() && !is_quiet() {
+                // stderr, so `--raw | git apply` still gets a clean diff
+                eprintln!(
+                    "ℹ️  Hiding {} generated file(s): {} (use --include-generated to show them)",
+                    hidden.len(),
+                    hidden.join(", ")
+                );
+            }
+            diff_body = kept;
+        }
+
+        // The diff only says "Binary files ... differ"; report what actually changed
+        let mut report = String::new();
+        if has_binary_changes(&diff_body) {
+            let mut files = self.pull_request_files(&owner, &repo, pr_number)?;
+            files.retain(|f| !hidden.contains(&f.filename));
+            let changes = self.binary_changes(&owner, &repo, &pr.base.sha, &pr.head.sha, &files);
+            report = render_binary_changes(&changes);
+        }
+        // Likewise a submodule bump only shows two commit ids; list the commits in between
+        report.push_str(&submodules::describe_bumps(
+            &diff_body,
+            self.config.submodule_fetch(),
+        ));
+
+        if options.blame {
+            diff_body = self.annotate_diff(&owner, &repo, &pr.base.sha, &diff_body)?;
+        }
+
+        if options.raw {
+            // Print raw diff to stdout; the report goes to stderr to keep the diff applicable
+            if !report.is_empty() && !is_quiet() {
+                eprint!("{}", report);
+            }
+            println!("{}", diff_body);
+            return Ok(());
+        }
+        // Pagers pass text before the first `diff --git` through, like a commit message
+        diff_body.insert_str(0, &report);
+
+        page_diff(&diff_body, !options.blame)
+    }
+
+    /// Pulls a GitHub pull request (PR) and checks out a corresponding local branch.
+    /// This function supports two main scenarios for how PRs are created on GitHub:
+    ///
+    /// ---
+    ///
+    /// ## 🔁 Scenario 1: PR From Same Repository (Not Forked)
+    ///
+    /// - The contributor created a branch in the **same repository** (upstream/original repo).
+    /// - The `head.repo.full_name` is the same as the `base.repo.full_name`.
+    /// - This function:
+    ///   - Fetches the PR's head branch from `origin`.
+    ///   - Creates a local branch with the same name (e.g., `feature-x`), or the name
+    ///     produced by the `branch-template` setting.
+    ///   - Sets it to track `origin/feature-x`.
+    /// - ✅ The user can directly push commits to this branch if they have write access to the repo.
+    /// - This is the **ideal flow for collaboration** within the same team/org.
+    ///
+    /// ---
+    ///
+    /// ## 🍴 Scenario 2: PR From a Forked Repository
+    ///
+    /// - The contributor forked the upstream repo, pushed changes to the fork, and opened a PR.
+    /// - The `head.repo.full_name` is different from `base.repo.full_name`.
+    /// - This function:
+    ///   - Uses GitHub’s special `refs/pull/<PR_NUMBER>/head` to fetch the PR as a read-only branch.
+    ///   - Creates a local branch named `<fork-owner>-pr-<PR_NUMBER>` (e.g., `alice-pr-42`),
+    ///     or the name produced by the `branch-template` setting.
+    ///   - Checks out this local branch, but does **not** connect it to any remote.
+    /// - ⚠️ This branch cannot be pushed back to the original PR directly, since the upstream user
+    ///   doesn’t have write access to the fork.
+    /// - If you need to make changes, you must create a **new branch** and open a **separate PR**.
+    ///
+    /// ---
+    ///
+    /// ## 💡 Tip:
+    /// When working with PRs from forks, you can cherry-pick or patch the commits to your own branch,
+    /// but cannot push directly to the fork’s branch unless you have permissions.
+    ///
+    /// ---
+    fn get_pull_request(&self, pr_number: PrNumber, options: &PullOptions) {
+        // Infer GitHub repo owner and repo name from remote URL
+        // Example: git@github.com:foo/bar.git → ("foo", "bar")
+        let (owner, repo) = self.infer_repo_details().unwrap_or_else(|| {
+            ui_eprintln!("{}", "❌ Could not infer owner/repo.".red());
+            exit(1);
+        });
+
+        let remote = self.remote.as_str();
+
+        // Retrieve the PR metadata, aborting with a hint if the request fails
+        let pr = match self.fetch_pull_request(&owner, &repo, pr_number) {
+            Ok(pr) => pr,
+            Err(e) => {
+                ui_eprintln!("{}", format!("❌ {}", e).red());
+                exit(1);
+            }
+        };
+
+        // Extract head branch name from the PR
+        let head_branch = pr.head.ref_name.as_str();
+
+        // The head repo is gone when the contributor deleted their fork
+        let head_repo_ref = pr.head.repo.as_ref().unwrap_or_else(|| {
+            ui_eprintln!(
+                "{}",
+                format!(
+                    "❌ The source repository of PR #{} no longer exists.",
+                    pr_number
+                )
+                .red()
+            );
+            exit(1);
+        });
+
+        // Extract the full name of the head repo (e.g., "user/repo")
+        let head_repo = head_repo_ref.full_name.as_str();
+
+        // Extract the GitHub login of the user who owns the head repo
+        let head_repo_owner = head_repo_ref.owner.login.as_str();
+
+        // Extract the GitHub login of the PR author
+        let author = pr.user.login.as_str();
+
+        // Extract the full name of the base repository that the PR targets
+        let base_repo = pr
+            .base
+            .repo
+            .as_ref()
+            .map(|r| r.full_name.as_str())
+            .unwrap_or("");
+
+        // Determine if the PR is from a fork (head repo != base repo)
+        let head_is_fork = head_repo != base_repo;
+
+        debug_log!(
+            "[DEBUG] PR head branch: {}, head repo: {}, head owner: {}, base repo: {}, is fork: {}",
+            head_branch,
+            head_repo,
+            head_repo_owner,
+            base_repo,
+            head_is_fork
+        );
+
+        // Resolve the local branch name from the configured template (or the defaults)
+        let local_branch = self
+            .local_branch_name(
+                pr_number,
+                author,
+                head_branch,
+                head_repo_owner,
+                head_is_fork,
+            )
+            .unwrap_or_else(|e| {
+                ui_eprintln!("{}", format!("❌ {}", e).red());
+                exit(1);
+            });
+
+        // Handle the case where the PR is from the same repository (not a fork)
         if !head_is_fork {
-            debug_log!("[DEBUG] PR is from same repository. Using origin tracking.");
+            debug_log!(
+                "[DEBUG] PR is from same repository. Using {} tracking.",
+                remote
+            );
+
+            // Fetch the PR branch from the remote and create a local branch with same name.
+            // The second refspec updates <remote>/<branch> for the upstream set below, which
+            // single-branch clones (the usual shallow ones) wouldn't map on their own.
+            let _ = Command::new("git")
+                .arg("fetch")
+                .args(options.fetch_args())
+                .args([
+                    remote,
+                    &format!("{}:{}", head_branch, local_branch),
+                    &format!(
+                        "+refs/heads/{}:refs/remotes/{}/{}",
+                        head_branch, remote, head_branch
+                    ),
+                ])
+                .status();
+
+            // Check out the local branch just created
+            let _ = Command::new("git")
+                .args(["checkout", &local_branch])
+                .status();
+
+            // Set the upstream for the branch to track <remote>/<branch>. Written to the config
+            // directly: `branch --set-upstream-to` refuses refs outside the remote's fetch
+            // refspec, which in a single-branch clone is every branch but the cloned one.
+            let _ = run_git(&["config", &format!("branch.{}.remote", local_branch), remote]);
+            let _ = run_git(&[
+                "config",
+                &format!("branch.{}.merge", local_branch),
+                &format!("refs/heads/{}", head_branch),
+            ]);
+
+            // Remember which PR this branch belongs to, for zero-argument commands later
+            record_branch_pr_number(&local_branch, &pr_number.to_string());
+
+            // Inform user of success and push capability (just the branch when quiet)
+            if is_quiet() {
+                println!("{}", local_branch);
+            }
+            status_log!(
+                "✅ Switched to branch {} tracking {}/{}",
+                local_branch.green(),
+                remote,
+                head_branch
+            );
+        } else {
+            // Handle case where PR is from a fork (read-only access to head repo)
+            debug_log!("[DEBUG] PR is from fork. Will fetch as read-only checkout.");
+
+            // Use GitHub's pull/<ID>/head ref to fetch a temporary read-only copy
+            let fetch = Command::new("git")
+                .arg("fetch")
+                .args(options.fetch_args())
+                .args([remote, &format!("pull/{}/head:{}", pr_number, local_branch)])
+                .status()
+                .expect("Failed to fetch PR");
+
+            if !fetch.success() {
+                ui_eprintln!("{}", "❌ Failed to fetch PR.".red());
+                exit(1);
+            }
+
+            // Checkout the read-only branch
+            let checkout = Command::new("git")
+                .args(["checkout", &local_branch])
+                .status()
+                .expect("Failed to checkout PR branch");
+
+            if checkout.success() {
+                // Remember which PR this branch belongs to, for zero-argument commands later
+                record_branch_pr_number(&local_branch, &pr_number.to_string());
+
+                // Let user know that branch is local, detached from the fork
+                if is_quiet() {
+                    println!("{}", local_branch);
+                }
+                status_log!("✅ Switched to branch {}", local_branch.green());
+                status_log!(
+                    "This branch is a read-only checkout of PR #{}, since it comes from a fork.",
+                    pr_number
+                );
+            } else {
+                ui_eprintln!("{}", "❌ Failed to checkout PR branch.".red());
+                return;
+            }
+        }
+
+        // LFS-tracked files would otherwise stay pointer files (see `lfs.rs`)
+        match self.pull_request_files(&owner, &repo, pr_number) {
+            Ok(files) => {
+                let paths: Vec<String> = files.into_iter().map(|f| f.filename).collect();
+                lfs::pull_tracked(remote, &local_branch, &paths);
+            }
+            Err(e) => debug_log!("[DEBUG] Skipping the LFS check: {}", e),
+        }
+
+        if options.recurse_submodules {
+            status_log!("📦 Updating submodules...");
+            if let Err(e) = submodules::update() {
+                ui_eprintln!("{}", format!("❌ {}", e).red());
+                exit(1);
+            }
+        }
+    }
+
+    /// Lists all open pull requests for the current repository.
+    /// This function:
+    /// - Parses the remote to determine the owner/repo
+    /// - Fetches open PRs from the GitHub API
+    /// - For each PR, fetches detailed info like commits, labels, etc.
+    /// - Displays the data in a well-formatted table using `tabled`
+    ///
+    /// `options.base` restricts the listing to PRs targeting one base branch.
+    fn list_pull_requests(&self, options: &ListOptions) -> Result<(), Box<dyn Error>> {
+        debug_log!("[DEBUG] Listing pull requests");
+        // A dashboard that keeps refreshing instead of a one-off table
+        if let Some(interval) = options.watch {
+            return self.watch_pull_requests(
+                options.base.as_deref(),
+                interval,
+                options.fail_on_sla,
+            );
+        }
+
+        // Infer owner and repo from git remote. This returns (user, repo_name)
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        let basic_prs = self.query_pull_requests(&owner, &repo, options)?;
+        let numbers = basic_prs.iter().map(|pr| pr.number).collect();
+
+        // Scripts get one line per PR (and nothing at all when there are none)
+        if options.porcelain {
+            let mut prs = self.fetch_pr_details(numbers)?;
+            sort_pull_requests(&mut prs, options.sort.as_deref());
+            porcelain::print_pull_requests(&prs);
+            return Ok(());
+        }
+
+        // Early exit if no PRs found
+        if basic_prs.is_empty() {
+            let state = match options.state.as_deref() {
+                Some("all") => "",
+                Some("closed") => "closed ",
+                _ => "open ",
+            };
+            let mut labels = if options.labels.is_empty() {
+                String::new()
+            } else {
+                format!(" labeled {}", options.labels.join(", "))
+            };
+            if let Some(milestone) = &options.milestone {
+                labels.push_str(&format!(" in milestone {}", milestone));
+            }
+            if options.mergeable {
+                labels.push_str(" ready to merge");
+            }
+            if options.owned_by_me {
+                labels.push_str(" touching paths you own");
+            }
+            match &options.base {
+                Some(base) => status_log!(
+                    "ℹ️  No {}pull requests{} found against {}.",
+                    state,
+                    labels,
+                    base
+                ),
+                None => status_log!("ℹ️  No {}pull requests{} found.", state, labels),
+            }
+            return Ok(());
+        }
+
+        debug_log!("[DEBUG] {} PRs found", basic_prs.len());
+
+        let breached =
+            self.print_pr_table(numbers, options.sort.as_deref(), options.columns.as_deref())?;
+        if options.fail_on_sla && breached > 0 {
+            return Err(format!(
+                "{} PR{} awaiting review past the review-sla",
+                breached,
+                if breached == 1 { "" } else { "s" }
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// This is only used with `submit-review --reject` option, if `--reject` switch is used with
+    /// `submit-review` then PR will be closed as REJECTED. `close_pull_request` helps to close the
+    /// pull request (PR) on GitHub by setting its state to "closed" via the GitHub REST API.
+    /// This method sends an authenticated PATCH request to the GitHub API to change
+    /// the PR's state, effectively closing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `pr_number` - The pull request number (as a string) to close.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the PR was successfully closed.
+    /// * `Err(...)` if there was a failure during the API request or processing.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// close_pull_request("42")?;
+    /// ```
+    ///
+    /// ```no_run
+    /// git pr submit-review 10 --message "garbage pr" --reject
+    /// ```
+    ///
+    fn close_pull_request(&self, pr_number: PrNumber) -> Result<(), Box<dyn Error>> {
+        // Log debug message indicating the start of the PR close operation.
+        debug_log!("[DEBUG] Closing PR #{}", pr_number);
+
+        // Attempt to parse the repository owner and name from the remote Git URL.
+        // This is essential for constructing the API endpoint URL.
+        //
+        // `infer_repo_details()` returns an Option<(owner, repo)>, so we
+        // handle the None case by returning an error.
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        // Fail fast with a precise message if the token can't write to the repository.
+        self.check_token_can_write(
+            &owner,
+            &repo,
+            &format!("closing PR #{}", pr_number),
+            WriteAccess::Any,
+        )?;
+
+        // Construct the GitHub API endpoint URL for the specific pull request.
+        //
+        // Example URL:
+        // https://api.github.com/repos/owner/repo/pulls/42
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}",
+            self.api_base, owner, repo, pr_number
+        );
+
+        // Create the JSON payload to send in the PATCH request.
+        // Setting `"state": "closed"` instructs GitHub to close the PR.
+        //
+        // This JSON body will be sent as the request payload.
+        let body = json!({ "state": "closed" });
+
+        // Debug log the outgoing request body and URL for troubleshooting.
+        debug_log!("📬 [DEBUG] Request Sent: {} to URL: {}", body, url);
+
+        // Send a PATCH request to the GitHub API to update the PR.
+        //
+        // - `authed` attaches the bearer token and the "User-Agent" header GitHub requires.
+        // - Send the JSON body created above.
+        // - `send` turns a non-2xx status into an error with a remediation hint.
+        //
+        // This call may return an error (e.g., network failure), so we propagate it with `?`.
+        self.send(
+            self.authed(Method::PATCH, &url).json(&body),
+            &format!("Failed to close PR #{}", pr_number),
+        )?;
+
+        // Inform the user that the PR was successfully closed.
+        status_log!("✅ Successfully closed PR #{}", pr_number);
+        Ok(())
+    }
+
+    /// Shows detailed information about a pull request (PR),
+    /// including metadata like title, author, status, age,
+    /// and lists all commits along with their changed files.
+    /// A review-requirements section follows, showing how many approvals are still
+    /// needed, who is blocking with requested changes, and CODEOWNERS coverage,
+    /// then everyone who participated (commits, reviews, comments),
+    /// followed by the deployment status of each environment built from the head commit.
+    ///
+    /// This method uses the GitHub REST API to fetch all relevant data.
+    ///
+    /// # Arguments
+    ///
+    /// * `pr_number` - The number of the pull request to display.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` on success, after printing the PR details table.
+    /// * `Err(...)` if any API request or parsing step fails.
+    ///
+    fn show_pull_request_details(
+        &self,
+        pr_number: PrNumber,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        // Log debug info that we're starting to show details for the specified PR
+        debug_log!("[DEBUG] Showing Details for PR #{}", pr_number);
+
+        // Infer the GitHub repo owner and repository name from the remote URL
+        // This is necessary to build the API URLs for requests.
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        // Fetch the PR metadata (title, author, status, creation date, etc.)
+        let pr = self.fetch_pull_request(&owner, &repo, pr_number)?;
+        let title = pr.title.as_str();
+        let status = pr.state.as_str();
+        let user = pr.user.login.as_str();
+
+        // Creation and last-update times, in the configured `--timestamps` style
+        let created = self.config.format_time(pr.created_at);
+        let updated = self.config.format_time(pr.updated_at);
+
+        // Debug log all extracted metadata for troubleshooting
+        debug_log!(
+            "[DEBUG] PR #{}: title={}, status={}, author={}, created={}, updated={}",
+            pr_number,
+            title,
+            status,
+            user,
+            created,
+            updated
+        );
+
+        // Retrieve the list of commits on this PR
+        let commits = self.pull_request_commits(&owner, &repo, pr_number)?;
+
+        // Vector to hold rows for tabular output
+        let mut rows = Vec::new();
+        // Lines added/deleted per commit, for the authorship breakdown
+        let mut commit_stats = BTreeMap::new();
+
+        // Iterate over each commit to collect details and changed files
+        for (i, commit) in commits.iter().enumerate() {
+            // Extract the full commit SHA and create a shortened SHA (first 7 chars)
+            let sha = commit.sha.as_str();
+            let short_sha = &sha[..7.min(sha.len())];
+
+            // Construct the GitHub API URL to fetch detailed commit info (including changed files)
+            let commit_url = format!("{}/repos/{}/{}/commits/{}", self.api_base, owner, repo, sha);
+
+            // Log the commit we're fetching files for
+            debug_log!("[DEBUG] Fetching files for commit {}", short_sha);
+
+            // Fetch detailed commit info JSON via authenticated GET request
+            let commit_resp = self.dispatch(self.authed(Method::GET, &commit_url))?;
+
+            // If fetching commit details failed, print warning and skip this commit
+            if !commit_resp.status().is_success() {
+                ui_eprintln!(
+                    "⚠️  Failed to fetch commit {}: {}",
+                    sha,
+                    commit_resp.text()?
+                );
+                continue;
+            }
+
+            // Parse commit JSON to extract list of changed files
+            let commit_detail: GitHubCommitDetail = commit_resp.json()?;
+            if let Some(stats) = commit_detail.stats {
+                commit_stats.insert(sha.to_string(), stats);
+            }
+            let files = commit_detail
+                .files
+                .iter()
+                .map(|f| link(&f.filename, &f.blob_url))
+                .collect::<Vec<_>>() // collect into Vec<String>
+                .join(", "); // join filenames as comma-separated string
+
+            // Build a PRDetailsRow for this commit.
+            // For the first commit row, include PR metadata fields.
+            // For subsequent commits, leave PR metadata blank to avoid repetition.
+            let row = PRDetailsRow {
+                pr_number: if i == 0 {
+                    link(&format!("#{}", pr_number), &pr.html_url)
+                } else {
+                    "".to_string()
+                },
+                title: if i == 0 {
+                    title.to_string()
+                } else {
+                    "".to_string()
+                },
+                status: if i == 0 {
+                    status.to_string()
+                } else {
+                    "".to_string()
+                },
+                created: if i == 0 {
+                    created.clone()
+                } else {
+                    "".to_string()
+                },
+                updated: if i == 0 {
+                    updated.clone()
+                } else {
+                    "".to_string()
+                },
+                github_username: if i == 0 {
+                    user.to_string()
+                } else {
+                    "".to_string()
+                },
+                commit_sha: link(short_sha, &commit.html_url),
+                changed_files: files,
+            };
+
+            // Append the row to the rows vector
+            rows.push(row);
+        }
+
+        // Print the table, letting the file list and then the title give way on
+        // narrow terminals
+        println!(
+            "{}",
+            table::render(rows, &[(7, Shrink::Wrap), (1, Shrink::Wrap)])
+        );
+
+        // Newcomers deserve a warm welcome and some extra guidance
+        if is_first_time(&pr.author_association) {
+            ui_println!(
+                "{} {} (welcome them with `git pr welcome {}`)",
+                "🌱 First-time contributor:".green().bold(),
+                user,
+                pr_number
+            );
+        }
+
+        // Tickets in the tracker this PR works on
+        self.print_tickets(&tracker::ticket_keys(&[title, &pr.head.ref_name]));
+
+        // PRs and issues this one has to wait for
+        let refs = dependency_refs(pr.body.as_deref().unwrap_or(""));
+        print_dependencies(&self.pull_request_dependencies(&owner, &repo, &refs));
+
+        // Who wrote what, pairing partners included
+        print_attribution(&commits, &commit_stats);
+
+        // Who signed and signed off each commit
+        print_commit_signatures(&commits);
+
+        // Changes that deserve a security review
+        let files = self.pull_request_files(&owner, &repo, pr_number)?;
+        let sensitive = SensitivePaths::new(&self.config);
+        let touched = sensitive.matching(files.iter().map(|f| f.filename.as_str()));
+        if !touched.is_empty() {
+            ui_println!(
+                "{} {}",
+                "🔐 Touches sensitive paths:".yellow().bold(),
+                touched.join(", ")
+            );
+        }
+
+        // Added, removed and upgraded dependencies, instead of raw lockfile diffs
+        print_dependency_changes(&self.dependency_changes(
+            &owner,
+            &repo,
+            &pr.base.sha,
+            &pr.head.sha,
+            &files,
+        ));
+
+        // Explain what still stands between this PR and a merge
+        let requirements = self.review_requirements(&owner, &repo, pr_number, &pr.base.ref_name)?;
+        print_review_requirements(&requirements);
+
+        // Everyone involved so far: committers, reviewers, commenters
+        let commit_authors: Vec<String> = commits
+            .iter()
+            .map(|c| match &c.author {
+                Some(author) => author.login.clone(),
+                None => c.commit.author.name.clone(),
+            })
+            .collect();
+        print_participants(self.participant_rows(&owner, &repo, pr_number, &commit_authors)?);
+
+        // Preview environments and other deployments of the head commit
+        print_deployments(self.deployment_rows(&owner, &repo, &pr.head.sha)?);
+
+        // Return success
+        Ok(())
+    }
+
+    /// Looks up the open pull request whose head is the given branch.
+    ///
+    /// Uses the `head=<owner>:<branch>` filter of the pulls endpoint, which only matches
+    /// branches living in the base repository (fork branches are resolved from local
+    /// metadata before this fallback is reached).
+    fn find_pull_request_for_branch(
+        &self,
+        branch: &str,
+    ) -> Result<Option<PrNumber>, Box<dyn Error>> {
+        debug_log!("[DEBUG] Looking up PR for branch {}", branch);
+
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        let url = Url::parse_with_params(
+            &format!("{}/repos/{}/{}/pulls", self.api_base, owner, repo),
+            &[
+                ("state", "open"),
+                ("head", format!("{}:{}", owner, branch).as_str()),
+            ],
+        )?;
 
-            let local_branch = head_branch.to_string();
+        let prs: Vec<BasicGitHubPR> = self.get(url.as_str(), "Failed to look up PR for branch")?;
+        Ok(prs.first().map(|pr| PrNumber(pr.number as u64)))
+    }
 
-            // Fetch the PR branch from origin and create a local branch with same name
-            let _ = Command::new("git")
-                .args([
-                    "fetch",
-                    "origin",
-                    &format!("{}:{}", head_branch, local_branch),
-                ])
-                .status();
+    /// Pushes the current branch, comments on the PR and re-requests review from every
+    /// reviewer whose most recent review is still `CHANGES_REQUESTED`.
+    ///
+    /// Reviewers who later approved (or whose review was dismissed) are not pinged again.
+    fn reopen_review(&self, pr_number: PrNumber, message: &str) -> Result<(), Box<dyn Error>> {
+        debug_log!("[DEBUG] Re-opening review for PR #{}", pr_number);
 
-            // Check out the local branch just created
-            let _ = Command::new("git")
-                .args(["checkout", &local_branch])
-                .status();
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
 
-            // Set the upstream for the branch to track origin/<branch>
-            let _ = Command::new("git")
-                .args([
-                    "branch",
-                    "--set-upstream-to",
-                    &format!("origin/{}", head_branch),
-                    &local_branch,
-                ])
-                .status();
+        // Step 1: push the latest commits so reviewers see the fixes
+        status_log!("{}", "📤 Pushing current branch...".green());
+        push_current_branch(&self.remote)?;
 
-            // Inform user of success and push capability
-            println!(
-                "{}",
-                format!(
-                    "✅ Switched to branch {} tracking origin/{}",
-                    local_branch.green(),
-                    head_branch
-                )
+        // Step 2: collect reviewers whose latest review requested changes
+        let reviewers: Vec<String> = self
+            .latest_review_states(&owner, &repo, pr_number)?
+            .into_iter()
+            .filter(|(_, state)| state == "CHANGES_REQUESTED")
+            .map(|(login, _)| login)
+            .collect();
+
+        debug_log!("[DEBUG] Reviewers to re-request: {:?}", reviewers);
+
+        // Step 3: leave a comment on the PR conversation
+        let comment_url = format!(
+            "{}/repos/{}/{}/issues/{}/comments",
+            self.api_base, owner, repo, pr_number
+        );
+        self.send(
+            self.authed(Method::POST, &comment_url)
+                .json(&json!({ "body": message })),
+            "Failed to post comment",
+        )?;
+        status_log!("💬 Commented on PR #{}", pr_number);
+
+        // Step 4: re-request review
+        if reviewers.is_empty() {
+            status_log!("ℹ️  No reviewers with outstanding change requests to re-request.");
+            return Ok(());
+        }
+
+        self.request_reviewers(&owner, &repo, pr_number, &reviewers)?;
+
+        status_log!(
+            "✅ Re-requested review from {} on PR #{}",
+            reviewers.join(", ").green(),
+            pr_number
+        );
+        Ok(())
+    }
+
+    /// Shows the unified CI status (check runs + commit statuses) of a PR's head commit.
+    ///
+    /// With a size limit (`--max-file-size` or the `max-file-size` setting), changed
+    /// files above it are flagged after the table and make the command fail.
+    fn show_pull_request_checks(
+        &self,
+        pr_number: PrNumber,
+        porcelain: bool,
+        max_file_size: Option<u64>,
+    ) -> Result<(), Box<dyn Error>> {
+        debug_log!("[DEBUG] Showing checks for PR #{}", pr_number);
+
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        let head_sha = self.pull_request_head_sha(&owner, &repo, pr_number)?;
+        let entries = self.combined_checks(&owner, &repo, &head_sha)?;
+        if porcelain {
+            porcelain::print_checks(&entries);
+            return Ok(());
+        }
+        if entries.is_empty() {
+            status_log!("ℹ️  No checks reported for PR #{}.", pr_number);
+        } else {
+            println!("{}", table::render(check_rows(&entries), &[]));
+
+            let summary = summarize(&entries);
+            match overall_state(&entries) {
+                Some(CheckState::Failure) => println!("{}", summary.red()),
+                Some(CheckState::Pending) => println!("{}", summary.yellow()),
+                _ => println!("{}", summary.green()),
+            }
+        }
+
+        if let Some(limit) = max_file_size.or_else(|| self.config.max_file_size()) {
+            self.check_file_sizes(&owner, &repo, pr_number, limit)?;
+        }
+        Ok(())
+    }
+
+    /// Tails GitHub Actions job logs for the PR's head commit.
+    fn show_pull_request_logs(
+        &self,
+        pr_number: PrNumber,
+        job: Option<&str>,
+        follow: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        debug_log!("[DEBUG] Showing workflow logs for PR #{}", pr_number);
+
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        let head_sha = self.pull_request_head_sha(&owner, &repo, pr_number)?;
+        self.stream_job_logs(&owner, &repo, &head_sha, job, follow)
+    }
+
+    /// Lists workflow artifacts for the PR's head commit and downloads the selected one.
+    fn pull_request_artifacts(
+        &self,
+        pr_number: PrNumber,
+        download: Option<&str>,
+        out_dir: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        debug_log!("[DEBUG] Listing artifacts for PR #{}", pr_number);
+
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        let head_sha = self.pull_request_head_sha(&owner, &repo, pr_number)?;
+        let artifacts = self.artifacts_for_sha(&owner, &repo, &head_sha)?;
+
+        if artifacts.is_empty() {
+            status_log!("ℹ️  No artifacts found for PR #{}.", pr_number);
+            return Ok(());
+        }
+
+        // Download mode: fetch every artifact with the requested name
+        if let Some(name) = download {
+            let selected: Vec<_> = artifacts.iter().filter(|(_, a)| a.name == name).collect();
+            if selected.is_empty() {
+                return Err(format!("No artifact named '{}' for PR #{}", name, pr_number).into());
+            }
+            for (_, artifact) in selected {
+                let path = self.download_artifact(artifact, std::path::Path::new(out_dir))?;
+                status_log!(
+                    "✅ Downloaded {} ({}) to {}",
+                    artifact.name.green(),
+                    human_size(artifact.size_in_bytes),
+                    path.display()
+                );
+            }
+            return Ok(());
+        }
+
+        let rows: Vec<ArtifactRow> = artifacts
+            .into_iter()
+            .map(|(workflow, a)| ArtifactRow {
+                workflow,
+                size: human_size(a.size_in_bytes),
+                status: if a.expired {
+                    "expired".red().to_string()
+                } else {
+                    "available".green().to_string()
+                },
+                name: a.name,
+            })
+            .collect();
+
+        println!("{}", table::render(rows, &[]));
+        Ok(())
+    }
+
+    /// Counts open PRs per base branch, paging through every open PR.
+    fn show_base_branch_counts(&self) -> Result<(), Box<dyn Error>> {
+        debug_log!("[DEBUG] Counting open PRs per base branch");
+
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        let url = format!(
+            "{}/repos/{}/{}/pulls?state=open",
+            self.api_base, owner, repo
+        );
+        let prs: Vec<BasicGitHubPR> = self.get_pages(&url, "Failed to list PRs")?;
+
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for pr in prs {
+            *counts.entry(pr.base.ref_name).or_default() += 1;
+        }
+
+        if counts.is_empty() {
+            status_log!("ℹ️  No open pull requests found.");
+            return Ok(());
+        }
+
+        // Busiest base branches first, then alphabetical
+        let mut entries: Vec<(String, usize)> = counts.into_iter().collect();
+        entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let rows: Vec<BaseCountRow> = entries
+            .into_iter()
+            .map(|(base, open)| BaseCountRow {
+                base,
+                open: open.to_string(),
+            })
+            .collect();
+
+        println!("{}", table::render(rows, &[]));
+        Ok(())
+    }
+
+    /// Searches PR titles and bodies via the GitHub search API and prints the results
+    /// in the same table as `list`.
+    fn search_pull_requests(
+        &self,
+        query: &str,
+        state: &str,
+        author: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
+        debug_log!("[DEBUG] Searching PRs for: {}", query);
+
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        // Build the search qualifiers: scope to this repo, PRs only, title/body matches
+        let mut q = format!("{} repo:{}/{} is:pr in:title,body", query, owner, repo);
+        match state {
+            "open" => q.push_str(" is:open"),
+            "closed" => q.push_str(" is:closed"),
+            "merged" => q.push_str(" is:merged"),
+            _ => {}
+        }
+        if let Some(author) = author {
+            q.push_str(&format!(" author:{}", author));
+        }
+        debug_log!("[DEBUG] Search qualifiers: {}", q);
+
+        let url = format!("{}/search/issues", self.api_base);
+        let result: GitHubSearchResult = self.request(
+            self.authed(Method::GET, &url)
+                .query(&[("q", q.as_str()), ("per_page", "50")]),
+            "Failed to search PRs",
+        )?;
+        if result.items.is_empty() {
+            status_log!("ℹ️  No pull requests match \"{}\".", query);
+            return Ok(());
+        }
+
+        let numbers: Vec<u32> = result.items.iter().map(|item| item.number).collect();
+        if result.total_count > numbers.len() as u64 {
+            status_log!(
+                "🔎 Showing {} of {} matches; refine the query to narrow it down.",
+                numbers.len(),
+                result.total_count
             );
-            return;
+        }
+
+        self.print_pr_table(numbers, None, None)?;
+        Ok(())
+    }
+
+    /// Toggles a PR between draft and ready-for-review via GraphQL
+    /// (the REST API has no endpoint for either direction).
+    fn set_pull_request_draft(
+        &self,
+        pr_number: PrNumber,
+        draft: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        debug_log!("[DEBUG] Setting draft={} on PR #{}", draft, pr_number);
+
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        let node_id = self.pull_request_node_id(&owner, &repo, pr_number)?;
+        let mutation = if draft {
+            "mutation($id: ID!) { convertPullRequestToDraft(input: {pullRequestId: $id}) { pullRequest { isDraft } } }"
         } else {
-            // Handle case where PR is from a fork (read-only access to head repo)
-            debug_log!("[DEBUG] PR is from fork. Will fetch as read-only checkout.");
+            "mutation($id: ID!) { markPullRequestReadyForReview(input: {pullRequestId: $id}) { pullRequest { isDraft } } }"
+        };
 
-            // Create local branch name using format "<username>-pr-<number>"
-            let local_branch = format!("{}-pr-{}", head_repo_owner, pr_number);
+        self.graphql(mutation, json!({ "id": node_id }))?;
 
-            // Use GitHub's pull/<ID>/head ref to fetch a temporary read-only copy
-            let fetch = Command::new("git")
-                .args([
-                    "fetch",
-                    "origin",
-                    &format!("pull/{}/head:{}", pr_number, local_branch),
-                ])
-                .status()
-                .expect("Failed to fetch PR");
+        if draft {
+            status_log!("✅ PR #{} converted to draft", pr_number);
+        } else {
+            status_log!("✅ PR #{} marked ready for review", pr_number);
+        }
+        Ok(())
+    }
 
-            if !fetch.success() {
-                eprintln!("{}", "❌ Failed to fetch PR.".red());
-                std::process::exit(1);
+    /// Creates a PR for the current branch, pre-filling the title from the single commit
+    /// subject (or the branch name) and the body from commit bodies plus the repository's
+    /// PR template, then letting the user finish both in `$EDITOR`.
+    /// With `fill_issue`, the issue referenced by the branch name or commits supplies the
+    /// title, a `Fixes #n` closing keyword and its labels.
+    /// Refuses to open a second PR for the same head branch and warns about open PRs with
+    /// a near-identical title.
+    /// Unpushed branches are pushed first — to the upstream remote, or with `fork` to the
+    /// user's fork (created via the API if needed) — so the whole flow is one command.
+    fn create_pull_request(&self, options: &CreateOptions) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        let head = current_branch().ok_or("HEAD is not on a branch")?;
+        let base = match &options.base {
+            Some(base) => base.clone(),
+            None => self.default_branch(&owner, &repo)?,
+        };
+        if head == base {
+            return Err(format!("Current branch is the base branch ({})", base).into());
+        }
+        debug_log!("[DEBUG] Creating PR {} → {}", head, base);
+
+        // Compare against the remote's copy of the base branch
+        let _ = run_git(&["fetch", "--quiet", &self.remote, &base]);
+        let commits = compose::commits_since(&format!("{}/{}", self.remote, base));
+        debug_log!("[DEBUG] {} commits on branch", commits.len());
+
+        let mut title = options
+            .title
+            .clone()
+            .unwrap_or_else(|| compose::default_title(&commits, &head));
+        let mut body = options
+            .body
+            .clone()
+            .unwrap_or_else(|| compose::default_body(&commits));
+
+        // Cross-link the issue this branch is about: title, closing keyword and labels
+        let mut labels = Vec::new();
+        if options.fill_issue {
+            match compose::referenced_issue(&head, &commits) {
+                Some(number) => {
+                    let issue = self.fetch_issue(&owner, &repo, number)?;
+                    status_log!("🔗 Linking issue #{}: {}", number, issue.title);
+                    if options.title.is_none() {
+                        title = issue.title;
+                    }
+                    if !compose::mentions_closing(&body, number) {
+                        body = format!("Fixes #{}\n\n{}", number, body)
+                            .trim_end()
+                            .to_string();
+                    }
+                    labels = issue.labels.into_iter().map(|l| l.name).collect();
+                }
+                None => println!("ℹ️  No issue reference found in branch name or commits."),
             }
+        }
 
-            // Checkout the read-only branch
-            let checkout = Command::new("git")
-                .args(["checkout", &local_branch])
-                .status()
-                .expect("Failed to checkout PR branch");
+        // With --fork the head lives in the user's fork, so it's addressed as `login:branch`
+        let login = if options.fork {
+            Some(self.authenticated_login()?)
+        } else {
+            None
+        };
+        let head_owner = login.as_deref().unwrap_or(&owner);
+        let head_label = format!("{}:{}", head_owner, head);
 
-            if checkout.success() {
-                // Let user know that branch is local, detached from the fork
-                println!("✅ Switched to branch {}", local_branch.green());
-                println!(
-                    "This branch is a read-only checkout of PR #{}, since it comes from a fork.",
-                    pr_number
-                );
-            } else {
-                eprintln!("{}", "❌ Failed to checkout PR branch.".red());
+        // Automation (or a teammate) may already have opened this PR; checked before the
+        // editor opens, so nothing typed there is thrown away
+        let (existing, others) = self.open_pull_requests_by_head(&owner, &repo, &head_label)?;
+        if let Some(pr) = existing {
+            return Err(format!(
+                "PR #{} already exists for {}: {}",
+                pr.number, head_label, pr.title
+            )
+            .into());
+        }
+
+        if options.edit {
+            (title, body) = compose::edit_in_editor(&title, &body)?;
+        }
+
+        for pr in others
+            .iter()
+            .filter(|pr| compose::titles_similar(&pr.title, &title))
+        {
+            ui_eprintln!(
+                "{}",
+                format!(
+                    "⚠️  Open PR #{} by {} has a near-identical title: {}",
+                    pr.number, pr.user.login, pr.title
+                )
+                .yellow()
+            );
+        }
+
+        // Make sure the branch exists remotely: on the upstream remote, or on the user's
+        // fork (created on demand) when contributing without write access
+        let push_remote = match &login {
+            Some(login) => {
+                self.ensure_fork(&owner, &repo, login)?;
+                self.fork_remote(&owner, login, &repo)?
             }
+            None => self.remote.clone(),
+        };
+        if branch_needs_push(&push_remote, &head) {
+            status_log!("📤 Pushing {} to {}...", head, push_remote);
+            push_branch(&push_remote, &head)?;
+        }
+        let head_ref = if login.is_some() {
+            head_label
+        } else {
+            head.clone()
+        };
+
+        let url = format!("{}/repos/{}/{}/pulls", self.api_base, owner, repo);
+        let payload = json!({
+            "title": title,
+            "head": head_ref,
+            "base": base,
+            "body": body,
+            "draft": options.draft,
+        });
+        debug_log!("[DEBUG] Payload: {}", payload);
+
+        let created: serde_json::Value = self.request(
+            self.authed(Method::POST, &url).json(&payload),
+            "Failed to create PR",
+        )?;
+        let number = created["number"].as_u64().unwrap_or_default().to_string();
+        if !labels.is_empty() {
+            self.add_labels(&owner, &repo, &number, &labels)?;
+            status_log!("🏷️  Applied labels: {}", labels.join(", "));
         }
+        let html_url = created["html_url"].as_str().unwrap_or("-");
+        if is_quiet() {
+            // Scripts only need the URL, e.g. `url=$(git pr create -q)`
+            println!("{}", html_url);
+        }
+        status_log!(
+            "✅ Created PR #{}: {}",
+            created["number"]
+                .as_u64()
+                .unwrap_or_default()
+                .to_string()
+                .green(),
+            html_url
+        );
+        Ok(())
     }
 
-    /// Lists all open pull requests for the current repository.
-    /// This function:
-    /// - Parses the remote to determine the owner/repo
-    /// - Fetches open PRs from the GitHub API
-    /// - For each PR, fetches detailed info like commits, labels, etc.
-    /// - Displays the data in a well-formatted table using `tabled`
-    fn list_pull_requests(&self) -> Result<(), Box<dyn Error>> {
-        debug_log!("[DEBUG] Listing pull requests");
-        // Infer owner and repo from git remote. This returns (user, repo_name)
+    fn sync_fork(&self, branch: Option<&str>) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        let (fork_owner, remote_is_fork) = self.resolve_fork(&owner, &repo)?;
+        let branch = match branch {
+            Some(branch) => branch.to_string(),
+            None => self.default_branch(&fork_owner, &repo)?,
+        };
+
+        status_log!(
+            "🔄 Syncing {}/{}:{} from upstream...",
+            fork_owner,
+            repo,
+            branch.cyan()
+        );
+        let message = self.merge_upstream(&fork_owner, &repo, &branch)?;
+
+        // Refresh the local remote-tracking branch so new branches start from upstream
+        let fork_remote = if remote_is_fork {
+            self.remote.clone()
+        } else {
+            self.fork_remote(&owner, &fork_owner, &repo)?
+        };
+        let _ = run_git(&["fetch", "--quiet", &fork_remote, &branch]);
+
+        status_log!("✅ {} ({}/{} fetched)", message, fork_remote, branch);
+        Ok(())
+    }
+
+    /// Retargets a PR to another base branch and/or renames it.
+    ///
+    /// The new base must exist and the PR must still contain commits relative to it;
+    /// otherwise nothing is changed. A summary of the commit delta is printed afterwards.
+    fn move_pull_request(
+        &self,
+        pr_number: PrNumber,
+        base: Option<&str>,
+        title: Option<&str>,
+    ) -> Result<(), Box<dyn Error>> {
+        if base.is_none() && title.is_none() {
+            return Err("Nothing to do: pass --base and/or --title".into());
+        }
+
         let (owner, repo) = self
             .infer_repo_details()
             .ok_or("Could not parse owner/repo")?;
 
-        // Construct the API endpoint to list open PRs (up to 50)
         let url = format!(
-            "https://api.github.com/repos/{}/{}/pulls?state=open&per_page=50",
-            owner, repo
+            "{}/repos/{}/{}/pulls/{}",
+            self.api_base, owner, repo, pr_number
         );
 
-        debug_log!("[DEBUG] Fetching PRs from URL: {}", url);
+        let pr = self.fetch_pull_request(&owner, &repo, pr_number)?;
+        let old_base = pr.base.ref_name;
+        let head_label = pr.head.label;
 
-        // Make the HTTP GET request to fetch the list of PRs
-        let resp = self
-            .client
-            .get(&url)
-            .bearer_auth(&self.token) // Authenticate with GitHub token
-            .header("User-Agent", "git-pr") // Required GitHub header
-            .send()?; // Execute the request
+        let mut payload = serde_json::Map::new();
+        let mut delta = None;
 
-        // Extract the HTTP status code and raw response body
-        let status = resp.status();
-        let text = resp.text()?;
+        if let Some(new_base) = base {
+            if new_base == old_base {
+                status_log!("ℹ️  PR #{} already targets {}", pr_number, new_base);
+            } else {
+                // Validate before touching the PR: the base must exist and the PR must
+                // still propose at least one commit against it
+                if !self.branch_exists(&owner, &repo, new_base)? {
+                    return Err(format!("Base branch '{}' does not exist", new_base).into());
+                }
+                let (old_ahead, _) = self.compare_commits(&owner, &repo, &old_base, &head_label)?;
+                let (ahead, behind) = self.compare_commits(&owner, &repo, new_base, &head_label)?;
+                if ahead == 0 {
+                    return Err(format!(
+                        "PR #{} would be empty against '{}' (all its commits are already there)",
+                        pr_number, new_base
+                    )
+                    .into());
+                }
+                payload.insert("base".into(), json!(new_base));
+                delta = Some((old_ahead, ahead, behind));
+            }
+        }
+        if let Some(title) = title {
+            payload.insert("title".into(), json!(title));
+        }
+        if payload.is_empty() {
+            return Ok(());
+        }
 
-        // If DEBUG is enabled, print status and body for inspection
-        debug_log!("[DEBUG] Response status: {}", status);
-        debug_log!("[DEBUG] Response body: {}", text);
+        self.send(
+            self.authed(Method::PATCH, &url).json(&payload),
+            "Failed to update PR",
+        )?;
 
-        // If GitHub returned a non-200 response, treat as an error
-        if !status.is_success() {
-            return Err(format!("Failed to list PRs: {}", text).into());
+        if let Some(title) = title {
+            status_log!("✏️  Renamed PR #{} to: {}", pr_number, title);
+        }
+        if let (Some(new_base), Some((old_ahead, ahead, behind))) = (base, delta) {
+            status_log!(
+                "🎯 Retargeted PR #{}: {} → {}",
+                pr_number,
+                old_base,
+                new_base.cyan()
+            );
+            status_log!(
+                "   Commits in PR: {} → {} ({} behind {})",
+                old_ahead,
+                ahead,
+                behind,
+                new_base
+            );
+            if ahead > old_ahead {
+                ui_eprintln!(
+                    "{}",
+                    format!(
+                        "   ⚠️  {} extra commit(s) from {} are now part of the PR",
+                        ahead - old_ahead,
+                        old_base
+                    )
+                    .yellow()
+                );
+            }
         }
+        Ok(())
+    }
 
-        // Deserialize the basic PR list into a lightweight struct
-        // This does NOT include fields like commits or file count
-        let basic_prs: Vec<BasicGitHubPR> = serde_json::from_str(&text)?;
+    fn show_pull_request_files(
+        &self,
+        pr_number: PrNumber,
+        include_generated: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
 
-        // Early exit if no PRs found
-        if basic_prs.is_empty() {
-            println!("ℹ️  No open pull requests found.");
+        let (_, mut files) = self.file_view_states(&owner, &repo, pr_number)?;
+        if files.is_empty() {
+            println!("No files changed in PR #{}", pr_number);
             return Ok(());
         }
 
-        debug_log!("[DEBUG] {} PRs found", basic_prs.len());
+        // Generated files neither show up nor count towards the size of the change
+        let paths: Vec<String> = files.iter().map(|f| f.path.clone()).collect();
+        let generated = GeneratedFiles::new(&self.config, &paths);
+        let hidden = if include_generated {
+            0
+        } else {
+            let before = files.len();
+            files.retain(|f| !generated.contains(&f.path));
+            before - files.len()
+        };
 
-        // We'll store (GitHubPR, age_days) so we can sort later
-        let mut detailed_prs = Vec::new();
+        print_file_states(&files);
+        print_change_size(&files, hidden);
+
+        // Binary and LFS files show as +0 -0 above; report their sizes instead
+        let pr = self.fetch_pull_request(&owner, &repo, pr_number)?;
+        let mut rest_files = self.pull_request_files(&owner, &repo, pr_number)?;
+        rest_files.retain(|f| files.iter().any(|shown| shown.path == f.filename));
+        let changes = self.binary_changes(&owner, &repo, &pr.base.sha, &pr.head.sha, &rest_files);
+        print!("{}", render_binary_changes(&changes));
+        Ok(())
+    }
+
+    /// Prints the change totals by directory and file type (see `diff_stats.rs`).
+    fn diff_stats(
+        &self,
+        pr_number: PrNumber,
+        options: &DiffStatsOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        self.print_diff_stats(pr_number, options)
+    }
+
+    /// Reports the coverage of the lines a PR adds (see `coverage_report.rs`).
+    fn coverage(
+        &self,
+        pr_number: PrNumber,
+        options: &CoverageOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        self.print_changed_lines_coverage(pr_number, options)
+    }
 
-        // Loop through each basic PR and fetch its full details
-        for basic_pr in basic_prs {
-            // Fetching PR details in DEBUG
-            debug_log!("[DEBUG] Fetching details for PR #{}", basic_pr.number);
+    /// Runs a command on the PR's head in a temporary worktree (see `runner.rs`).
+    fn run_command(
+        &self,
+        pr_number: PrNumber,
+        options: &RunOptions,
+    ) -> Result<i32, Box<dyn Error>> {
+        self.run_in_worktree(pr_number, options)
+    }
 
-            let detail_url = format!(
-                "https://api.github.com/repos/{}/{}/pulls/{}",
-                owner, repo, basic_pr.number
-            );
+    /// Searches the PR's commits for the first failing one (see `bisect.rs`).
+    fn bisect(&self, pr_number: PrNumber, options: &BisectOptions) -> Result<(), Box<dyn Error>> {
+        self.bisect_pull_request(pr_number, options)
+    }
 
-            let detail_resp = self
-                .client
-                .get(&detail_url)
-                .bearer_auth(&self.token)
-                .header("User-Agent", "git-pr")
-                .send()?;
+    /// Answers editor plugins' JSON-RPC requests on stdio (see `server.rs`).
+    fn serve(&self, options: &ServeOptions) -> Result<(), Box<dyn Error>> {
+        self.serve_stdio(options)
+    }
 
-            let detail_status = detail_resp.status();
-            let detail_text = detail_resp.text()?; // Will be parsed as JSON
+    /// Shows a PR's comments, marking outdated ones (see `comments.rs`).
+    fn comments(&self, pr_number: PrNumber, hide_outdated: bool) -> Result<(), Box<dyn Error>> {
+        self.print_comments(pr_number, hide_outdated)
+    }
 
-            if !detail_status.is_success() {
-                eprintln!(
-                    "⚠️  Failed to fetch details for PR #{}: {}",
-                    basic_pr.number, detail_text
-                );
-                continue;
-            }
+    /// Shows, declares or ends a merge freeze (see `freeze.rs`).
+    fn freeze(&self, action: &FreezeAction) -> Result<(), Box<dyn Error>> {
+        self.freeze_action(action)
+    }
 
-            let pr: GitHubPR = serde_json::from_str(&detail_text)?;
-            let age_days = (Utc::now() - pr.created_at).num_days();
+    /// Lists, approves and merges PRs from trusted automation (see `autopilot.rs`).
+    fn autopilot(&self, options: &AutopilotOptions) -> Result<(), Box<dyn Error>> {
+        self.run_autopilot(options)
+    }
 
-            // Store PR with age_days for later sorting
-            detailed_prs.push((pr, age_days));
-        }
+    /// Shows the open PRs of dependency bots (see `bots.rs`).
+    fn bots(&self, options: &BotsOptions) -> Result<(), Box<dyn Error>> {
+        self.print_bot_pull_requests(options)
+    }
 
-        // Sort PRs by age_days ASCENDING (oldest first). Use `rev()` to make it newest first.
-        detailed_prs.sort_by_key(|(_, age_days)| *age_days);
+    /// Confirms changes to PRs of high-risk repositories (see `confirm.rs`).
+    fn confirm_high_risk(
+        &self,
+        pr_number: PrNumber,
+        action: &str,
+        confirm: Option<PrNumber>,
+    ) -> Result<(), Box<dyn Error>> {
+        self.confirm_high_risk_action(pr_number, action, confirm)
+    }
 
-        debug_log!("[DEBUG] Sorted PRs by age");
+    /// Returns the token's login, cached per token (see `forks.rs` and `logins.rs`).
+    fn authenticated_login(&self) -> Result<String, Box<dyn Error>> {
+        self.lookup_login()
+    }
 
-        // Build table rows after sorting
-        let display_rows: Vec<DisplayPR> = detailed_prs
-            .into_iter()
-            .map(|(pr, age_days)| {
-                debug_log!("[DEBUG] Mapping PR #{} to table row", pr.number);
-                let age = if age_days == 0 {
-                    "today".to_string()
-                } else {
-                    format!("{}d", age_days)
-                };
+    fn mark_files_viewed(
+        &self,
+        pr_number: PrNumber,
+        paths: &[String],
+        all: bool,
+        viewed: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
 
-                let labels = if pr.labels.is_empty() {
-                    "-".to_string()
-                } else {
-                    pr.labels
-                        .iter()
-                        .map(|l| l.name.clone())
-                        .collect::<Vec<_>>()
-                        .join(", ")
-                };
+        let (node_id, mut files) = self.file_view_states(&owner, &repo, pr_number)?;
 
-                let description_raw = pr.body.as_deref().unwrap_or("-");
-                let wrap_opts = Options::new(60).break_words(false);
-                let description_wrapped = fill(description_raw, wrap_opts);
-
-                DisplayPR {
-                    number: format!("#{}", pr.number),
-                    title: pr.title.clone(),
-                    author: pr.user.login.clone(),
-                    age,
-                    commits: pr.commits.to_string(),
-                    files: pr.changed_files.to_string(),
-                    labels,
-                    description: description_wrapped,
-                }
-            })
-            .collect();
+        let targets: Vec<String> = if all {
+            files
+                .iter()
+                .filter(|f| f.viewed != viewed)
+                .map(|f| f.path.clone())
+                .collect()
+        } else {
+            if paths.is_empty() {
+                return Err("Pass --file <path> (repeatable) or --all".into());
+            }
+            if let Some(unknown) = paths.iter().find(|p| !files.iter().any(|f| &f.path == *p)) {
+                return Err(format!("{} is not changed in PR #{}", unknown, pr_number).into());
+            }
+            paths.to_vec()
+        };
 
-        // Create and print the final table
-        let mut table = Table::new(display_rows);
-        table.with(Style::rounded());
-        println!("{table}");
+        self.set_files_viewed(&node_id, &targets, viewed)?;
+        for file in files.iter_mut().filter(|f| targets.contains(&f.path)) {
+            file.viewed = viewed;
+        }
 
+        let verb = if viewed { "viewed" } else { "not viewed" };
+        status_log!("✅ Marked {} file(s) as {}", targets.len(), verb);
+        print_view_progress(&files);
         Ok(())
     }
 
-    /// This is only used with `submit-review --reject` option, if `--reject` switch is used with
-    /// `submit-review` then PR will be closed as REJECTED. `close_pull_request` helps to close the
-    /// pull request (PR) on GitHub by setting its state to "closed" via the GitHub REST API.
-    /// This method sends an authenticated PATCH request to the GitHub API to change
-    /// the PR's state, effectively closing it.
-    ///
-    /// # Arguments
-    ///
-    /// * `pr_number` - The pull request number (as a string) to close.
-    ///
-    /// # Returns
-    ///
-    /// * `Ok(())` if the PR was successfully closed.
-    /// * `Err(...)` if there was a failure during the API request or processing.
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// close_pull_request("42")?;
-    /// ```
-    ///
-    /// ```no_run
-    /// git pr submit-review 10 --message "garbage pr" --reject
-    /// ```
-    ///
-    fn close_pull_request(&self, pr_number: &str) -> Result<(), Box<dyn Error>> {
-        // Log debug message indicating the start of the PR close operation.
-        debug_log!("[DEBUG] Closing PR #{}", pr_number);
-
-        // Attempt to parse the repository owner and name from the remote Git URL.
-        // This is essential for constructing the API endpoint URL.
-        //
-        // `infer_repo_details()` returns an Option<(owner, repo)>, so we
-        // handle the None case by returning an error.
+    /// Rebuilds the description from the PR's commits, changed files and referenced
+    /// issues, keeping any existing "Test plan" section, so descriptions stay current
+    /// after force-pushes.
+    fn describe_pull_request(
+        &self,
+        pr_number: PrNumber,
+        regenerate: bool,
+    ) -> Result<(), Box<dyn Error>> {
         let (owner, repo) = self
             .infer_repo_details()
             .ok_or("Could not parse owner/repo")?;
 
-        // Construct the GitHub API endpoint URL for the specific pull request.
-        //
-        // Example URL:
-        // https://api.github.com/repos/owner/repo/pulls/42
         let url = format!(
-            "https://api.github.com/repos/{}/{}/pulls/{}",
-            owner, repo, pr_number
+            "{}/repos/{}/{}/pulls/{}",
+            self.api_base, owner, repo, pr_number
         );
 
-        // Create the JSON payload to send in the PATCH request.
-        // Setting `"state": "closed"` instructs GitHub to close the PR.
-        //
-        // This JSON body will be sent as the request payload.
-        let body = json!({ "state": "closed" });
+        let pr = self.fetch_pull_request(&owner, &repo, pr_number)?;
+        let title = pr.title.as_str();
+        let current_body = pr.body.as_deref().unwrap_or_default();
 
-        // Debug log the outgoing request body and URL for troubleshooting.
-        debug_log!("📬 [DEBUG] Request Sent: {} to URL: {}", body, url);
+        // Commits in the PR, oldest first
+        let commits: Vec<(String, String)> = self
+            .pull_request_commits(&owner, &repo, pr_number)?
+            .into_iter()
+            .map(|c| (c.sha[..7.min(c.sha.len())].to_string(), c.commit.message))
+            .collect();
 
-        // Send a PATCH request to the GitHub API to update the PR.
-        //
-        // - Use the authenticated HTTP client stored in `self.client`.
-        // - Bearer token authentication with `self.token` ensures the request is authorized.
-        // - Set a "User-Agent" header to identify the client, which GitHub requires.
-        // - Send the JSON body created above.
-        //
-        // This call may return an error (e.g., network failure), so we propagate it with `?`.
-        let response = self
-            .client
-            .patch(&url)
-            .bearer_auth(&self.token)
-            .header("User-Agent", "git-pr")
-            .json(&body)
-            .send()?;
-
-        // Log the HTTP response status code for debugging purposes.
-        debug_log!(
-            "📬 [DEBUG] Response Received: {} from URL: {}",
-            response.status(),
-            url
-        );
+        let files: Vec<String> = self
+            .pull_request_files(&owner, &repo, pr_number)?
+            .into_iter()
+            .map(|f| f.filename)
+            .collect();
 
-        // Check if the HTTP response indicates success (status 2xx).
-        if response.status().is_success() {
-            // Inform the user that the PR was successfully closed.
-            println!("✅ Successfully closed PR #{}", pr_number);
-            Ok(())
-        } else {
-            // On failure, read the response body text (error message from GitHub)
-            // and convert it into an error returned from this method.
-            Err(format!("Failed to close PR: {}", response.text()?).into())
+        let template = compose::load_describe_template(self.config.describe_template.as_deref());
+        let body = compose::render_description(&template, title, &commits, &files, current_body);
+
+        if !regenerate {
+            println!("{}", body);
+            println!();
+            status_log!(
+                "{}",
+                "ℹ️  Preview only; pass --regenerate to update the PR.".dimmed()
+            );
+            return Ok(());
         }
-    }
 
-    /// Shows detailed information about a pull request (PR),
-    /// including metadata like title, author, status, age,
-    /// and lists all commits along with their changed files.
-    ///
-    /// This method uses the GitHub REST API to fetch all relevant data.
-    ///
-    /// # Arguments
-    ///
-    /// * `pr_number` - The number of the pull request to display.
-    ///
-    /// # Returns
-    ///
-    /// * `Ok(())` on success, after printing the PR details table.
-    /// * `Err(...)` if any API request or parsing step fails.
-    ///
-    fn show_pull_request_details(&self, pr_number: &str) -> Result<(), Box<dyn std::error::Error>> {
-        // Log debug info that we're starting to show details for the specified PR
-        debug_log!("[DEBUG] Showing Details for PR #{}", pr_number);
+        self.send(
+            self.authed(Method::PATCH, &url)
+                .json(&json!({ "body": body })),
+            "Failed to update PR description",
+        )?;
 
-        // Infer the GitHub repo owner and repository name from the remote URL
-        // This is necessary to build the API URLs for requests.
+        status_log!("✅ Regenerated description of PR #{}", pr_number);
+        Ok(())
+    }
+
+    fn list_pending_reviews(&self) -> Result<(), Box<dyn Error>> {
         let (owner, repo) = self
             .infer_repo_details()
             .ok_or("Could not parse owner/repo")?;
 
-        // Construct the GitHub API endpoint URL to fetch PR metadata.
-        // This includes title, author, status, creation date, etc.
-        let pr_url = format!(
-            "https://api.github.com/repos/{}/{}/pulls/{}",
-            owner, repo, pr_number
-        );
-
-        // Debug log the API URL for fetching PR metadata
-        debug_log!("[DEBUG] Fetching PR metadata from: {}", pr_url);
-
-        // Perform an authenticated GET request to the GitHub API
-        // to retrieve the PR metadata as JSON.
-        let pr_resp = self
-            .client
-            .get(&pr_url)
-            .bearer_auth(&self.token)
-            .header("User-Agent", "git-pr")
-            .send()?;
+        let mut rows: Vec<PendingReviewRow> = drafts::load_drafts()
+            .into_iter()
+            .map(|draft| PendingReviewRow {
+                pr: link(
+                    &format!("#{}", draft.pr_number),
+                    &self.web_url(&owner, &repo, &format!("pull/{}", draft.pr_number)),
+                ),
+                title: "-".to_string(),
+                source: "local".to_string(),
+                event: draft.event,
+                summary: draft.message.lines().next().unwrap_or_default().to_string(),
+            })
+            .collect();
 
-        // Check if the HTTP response was successful (status 2xx).
-        // If not, return an error with the response body text.
-        if !pr_resp.status().is_success() {
-            return Err(format!("Failed to fetch PR details: {}", pr_resp.text()?).into());
+        for review in self.server_pending_reviews(&owner, &repo)? {
+            rows.push(PendingReviewRow {
+                pr: link(
+                    &format!("#{}", review.pr_number),
+                    &self.web_url(&owner, &repo, &format!("pull/{}", review.pr_number)),
+                ),
+                title: review.title,
+                source: "github".to_string(),
+                event: "-".to_string(),
+                summary: format!(
+                    "{} ({} inline comment(s))",
+                    review.body.lines().next().unwrap_or_default(),
+                    review.comments
+                ),
+            });
         }
 
-        // Parse the JSON response into a serde_json::Value for flexible access.
-        let pr_json: serde_json::Value = pr_resp.json()?;
+        print_pending_reviews(rows);
+        Ok(())
+    }
 
-        // Extract useful fields from the JSON:
-        // - title: The PR title
-        // - state: The PR status (open, closed, merged)
-        // - user.login: The username of the PR author
-        // - created_at: Timestamp when the PR was created (RFC 3339 format)
-        //
-        // Use `unwrap_or("-")` to provide a default if fields are missing.
-        let title = pr_json["title"].as_str().unwrap_or("-");
-        let status = pr_json["state"].as_str().unwrap_or("-");
-        let user = pr_json["user"]["login"].as_str().unwrap_or("-");
-        let created_at = pr_json["created_at"].as_str().unwrap_or("-");
-
-        // Parse the creation timestamp into a DateTime<Utc> for calculations
-        let created_date = DateTime::parse_from_rfc3339(created_at)?.with_timezone(&Utc);
-
-        // Calculate the age of the PR in days, relative to now (UTC)
-        let age_days = (Utc::now() - created_date).num_days();
-
-        // Convert the age into a human-readable string:
-        // - "today" if less than 1 day old
-        // - "<n>d" otherwise (e.g., "5d" for 5 days)
-        let age = if age_days == 0 {
-            "today".to_string()
-        } else {
-            format!("{}d", age_days)
+    fn pending_review_action(
+        &self,
+        pr_number: PrNumber,
+        action: &PendingReviewAction,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+
+        // Two invocations submitting the same draft would post the review twice. Resume
+        // isn't locked: it waits on the editor, and its save takes the lock itself.
+        let _lock = match action {
+            PendingReviewAction::Resume => None,
+            _ => Some(lock::acquire()?),
         };
 
-        // Debug log all extracted metadata for troubleshooting
-        debug_log!(
-            "[DEBUG] PR #{}: title={}, status={}, author={}, age={}d",
-            pr_number,
-            title,
-            status,
-            user,
-            age_days
-        );
+        // Local drafts first: they're what `submit-review --save` produced
+        if let Some(mut draft) = drafts::load_draft(pr_number) {
+            match action {
+                PendingReviewAction::Resume => {
+                    // The time in the editor is review time; it goes to the journal
+                    let started = Instant::now();
+                    let edited = compose::edit_text("REVIEW_EDITMSG", &draft.message)?;
+                    let seconds = started.elapsed().as_secs();
+                    draft.message = edited.trim().to_string();
+                    drafts::save_draft(&draft)?;
+                    status_log!("💾 Saved review draft for PR #{}", pr_number);
+                    if seconds > 0 {
+                        journal::record_review_time(pr_number, seconds, journal::Source::Editor)?;
+                        status_log!("⏱️  Recorded {} of review time", format_duration(seconds));
+                    }
+                }
+                PendingReviewAction::Submit { event } => {
+                    let event = event.as_deref().unwrap_or(&draft.event);
+                    self.submit_pull_request_review(pr_number, &draft.message, event)?;
+                    drafts::discard_draft(pr_number);
+                }
+                PendingReviewAction::Discard => {
+                    drafts::discard_draft(pr_number);
+                    status_log!("🗑️  Discarded review draft for PR #{}", pr_number);
+                }
+            }
+            return Ok(());
+        }
 
-        // Construct the GitHub API URL to fetch the list of commits on this PR
-        let commits_url = format!(
-            "https://api.github.com/repos/{}/{}/pulls/{}/commits",
-            owner, repo, pr_number
-        );
+        let review = self
+            .server_pending_review(&owner, &repo, pr_number)?
+            .ok_or_else(|| format!("No pending review on PR #{}", pr_number))?;
+        match action {
+            PendingReviewAction::Resume => {
+                // Inline comments can only be edited in the web UI
+                status_log!("📝 Pending review on PR #{}: {}", pr_number, review.title);
+                if !review.body.is_empty() {
+                    println!("{}", review.body);
+                }
+                println!("   {} inline comment(s) so far", review.comments);
+                println!("   Continue at: {}/files", review.url.cyan());
+            }
+            PendingReviewAction::Submit { event } => {
+                let event = event.as_deref().unwrap_or("COMMENT");
+                self.submit_server_review(&owner, &repo, &review, event)?;
+                status_log!(
+                    "✅ Submitted pending review on PR #{} ({})",
+                    pr_number,
+                    event
+                );
+            }
+            PendingReviewAction::Discard => {
+                self.delete_server_review(&owner, &repo, &review)?;
+                status_log!("🗑️  Discarded pending review on PR #{}", pr_number);
+            }
+        }
+        Ok(())
+    }
 
-        // Perform authenticated GET request to retrieve commits as JSON
-        let commits_resp = self
-            .client
-            .get(&commits_url)
-            .bearer_auth(&self.token)
-            .header("User-Agent", "git-pr")
-            .send()?;
+    /// Runs the API-side `doctor` checks (see `diagnostics.rs`).
+    fn diagnose(&self) -> Vec<Diagnostic> {
+        self.diagnostics()
+    }
 
-        // Return an error if the commits API call fails
-        if !commits_resp.status().is_success() {
-            return Err(format!("Failed to fetch commits: {}", commits_resp.text()?).into());
-        }
+    /// Suggests (and optionally requests) a reviewer (see `suggest.rs`).
+    fn suggest_reviewer(&self, pr_number: PrNumber, request: bool) -> Result<(), Box<dyn Error>> {
+        self.suggest_reviewer_for(pr_number, request)
+    }
 
-        // Parse the commits response JSON into a vector of JSON values (each a commit)
-        let commits: Vec<serde_json::Value> = commits_resp.json()?;
+    /// Assigns the next reviewer of the rotation (see `rotation.rs`).
+    fn assign_next(&self, pr_number: PrNumber, dry_run: bool) -> Result<(), Box<dyn Error>> {
+        self.assign_next_reviewer(pr_number, dry_run)
+    }
 
-        // Vector to hold rows for tabular output
-        let mut rows = Vec::new();
+    /// Merges a PR after its pre-flight (see `merge.rs`).
+    fn merge(&self, pr_number: PrNumber, options: &MergeOptions) -> Result<(), Box<dyn Error>> {
+        self.merge_pull_request_checked(pr_number, options)
+    }
 
-        // Iterate over each commit to collect details and changed files
-        for (i, commit) in commits.iter().enumerate() {
-            // Extract the full commit SHA and create a shortened SHA (first 7 chars)
-            let sha = commit["sha"].as_str().unwrap_or("-");
-            let short_sha = &sha[..7.min(sha.len())];
+    /// Lists (and resolves) unresolved review threads (see `threads.rs`).
+    fn unresolved(
+        &self,
+        pr_number: PrNumber,
+        resolve_all_mine: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        self.print_unresolved_threads(pr_number, resolve_all_mine)
+    }
 
-            // Construct the GitHub API URL to fetch detailed commit info (including changed files)
-            let commit_url = format!(
-                "https://api.github.com/repos/{}/{}/commits/{}",
-                owner, repo, sha
-            );
+    /// Comments on a PR, with attachments (see `attachments.rs`).
+    fn comment(&self, pr_number: PrNumber, options: &CommentOptions) -> Result<(), Box<dyn Error>> {
+        self.comment_on_pull_request(pr_number, options)
+    }
 
-            // Log the commit we're fetching files for
-            debug_log!("[DEBUG] Fetching files for commit {}", short_sha);
+    /// Shows or reviews one commit of a PR (see `commit_review.rs`).
+    fn review_commit(
+        &self,
+        pr_number: PrNumber,
+        sha: &str,
+        options: &CommitReviewOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        self.review_pull_request_commit(pr_number, sha, options)
+    }
 
-            // Fetch detailed commit info JSON via authenticated GET request
-            let commit_resp = self
-                .client
-                .get(&commit_url)
-                .bearer_auth(&self.token)
-                .header("User-Agent", "git-pr")
-                .send()?;
+    /// Submits a review with a GPG-signed receipt (see `signed_reviews.rs`).
+    fn submit_signed_review(
+        &self,
+        pr_number: PrNumber,
+        message: &str,
+        event: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        self.submit_signed_pull_request_review(pr_number, message, event)
+    }
 
-            // If fetching commit details failed, print warning and skip this commit
-            if !commit_resp.status().is_success() {
-                eprintln!(
-                    "⚠️  Failed to fetch commit {}: {}",
-                    sha,
-                    commit_resp.text()?
-                );
-                continue;
-            }
+    /// Checks the signed receipt of a review (see `signed_reviews.rs`).
+    fn verify_review(&self, pr_number: PrNumber, review_id: u64) -> Result<(), Box<dyn Error>> {
+        self.verify_pull_request_review(pr_number, review_id)
+    }
 
-            // Parse commit JSON to extract list of changed files
-            let commit_json: serde_json::Value = commit_resp.json()?;
-            let files = commit_json["files"]
-                .as_array()
-                .unwrap_or(&vec![]) // fallback to empty array if missing
-                .iter()
-                .filter_map(|f| f["filename"].as_str()) // extract filename strings
-                .collect::<Vec<_>>() // collect into Vec<&str>
-                .join(", "); // join filenames as comma-separated string
+    /// Records or submits per-path verdicts on a PR (see `scoped.rs`).
+    fn scoped_review(
+        &self,
+        pr_number: PrNumber,
+        action: &ScopeAction,
+    ) -> Result<(), Box<dyn Error>> {
+        self.run_scope_action(pr_number, action)
+    }
 
-            // Build a PRDetailsRow for this commit.
-            // For the first commit row, include PR metadata fields.
-            // For subsequent commits, leave PR metadata blank to avoid repetition.
-            let row = PRDetailsRow {
-                pr_number: if i == 0 {
-                    format!("#{}", pr_number)
-                } else {
-                    "".to_string()
-                },
-                title: if i == 0 {
-                    title.to_string()
-                } else {
-                    "".to_string()
-                },
-                status: if i == 0 {
-                    status.to_string()
-                } else {
-                    "".to_string()
-                },
-                age: if i == 0 { age.clone() } else { "".to_string() },
-                github_username: if i == 0 {
-                    user.to_string()
-                } else {
-                    "".to_string()
-                },
-                commit_sha: short_sha.to_string(),
-                changed_files: files,
-            };
+    /// Runs the selected policy checks on a PR (see `lint.rs`).
+    fn lint_pull_request(
+        &self,
+        pr_number: PrNumber,
+        options: &LintOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        self.lint_pull_request_for(pr_number, options)
+    }
 
-            // Append the row to the rows vector
-            rows.push(row);
-        }
+    /// Posts the welcome comment for a first-time contributor (see `welcome.rs`).
+    fn welcome(&self, pr_number: PrNumber, force: bool) -> Result<(), Box<dyn Error>> {
+        self.welcome_contributor(pr_number, force)
+    }
 
-        // Create a table using the collected rows
-        let mut table = Table::new(rows);
+    /// Runs an interactive triage session (see `triage.rs`).
+    fn triage(&self, restart: bool) -> Result<(), Box<dyn Error>> {
+        self.triage_session(restart)
+    }
 
-        // Apply a rounded style to the table for nicer visual appearance
-        table.with(Style::rounded());
+    /// Prints a team's review queue (see `queue.rs`).
+    fn queue(&self, team: &str) -> Result<(), Box<dyn Error>> {
+        self.team_queue(team)
+    }
+}
 
-        // Print the completed table to stdout
-        println!("{table}");
+/// Orders PRs newest first by `sort` (`created` or `updated`; `None` keeps the creation order
+/// of [`GitHubProvider::fetch_pr_details`]).
+fn sort_pull_requests(prs: &mut [GitHubPR], sort: Option<&str>) {
+    if sort == Some("updated") {
+        prs.sort_by_key(|pr| std::cmp::Reverse(pr.updated_at));
+    }
+}
 
-        // Return success
-        Ok(())
+/// Name of a PR's head branch for the `head` column.
+///
+/// Branches of forks show as `owner:branch`, since a fork's `main` isn't this repo's `main`.
+fn head_branch(pr: &GitHubPR) -> String {
+    let owner = |branch: &GitHubBranchRef| {
+        branch
+            .label
+            .split_once(':')
+            .map(|(owner, _)| owner.to_string())
+    };
+    if owner(&pr.head) != owner(&pr.base) {
+        pr.head.label.clone()
+    } else {
+        pr.head.ref_name.clone()
     }
 }
+
+/// Shows a diff in a pager: `delta` if installed (and `use_delta`), else `less -R`, else `cat`.
+///
+/// Callers that color the diff themselves pass `use_delta = false`, since `delta` would
+/// re-render it.
+pub(crate) fn page_diff(diff: &str, use_delta: bool) -> Result<(), Box<dyn Error>> {
+    let (pager, args): (&str, &[&str]) = if which("delta").is_ok() && use_delta {
+        ("delta", &[])
+    } else if which("less").is_ok() {
+        ("less", &["-R"])
+    } else {
+        ("cat", &[])
+    };
+
+    debug_log!("[DEBUG] Using pager: {}", pager);
+
+    let mut child = Command::new(pager)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+        .expect("Failed to spawn pager");
+
+    // Write the diff to the pager; a failed write still waits on the child below
+    let write_result: Result<(), Box<dyn Error>> = match child.stdin.as_mut() {
+        Some(stdin) => stdin.write_all(diff.as_bytes()).map_err(|e| e.into()),
+        None => Err("Failed to open stdin for pager".into()),
+    };
+
+    child.wait()?;
+    write_result?;
+    Ok(())
+}
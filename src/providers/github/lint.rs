@@ -0,0 +1,336 @@
+// `git pr lint`: policy checks on a PR, for use as a pre-merge gate.
+//
+// Each check is opt-in with its own flag; the command exits non-zero when any selected
+// check fails, after reporting every problem it found.
+//   - `--require-dco`: every commit carries a `Signed-off-by:` trailer for its author
+//   - `--body`: the PR description is complete: its relative links point at files that
+//     exist at the PR's head, it keeps every heading of the repository's PR template,
+//     the template's mandatory checkboxes are ticked, and no TODO/FIXME/TBD is left
+//
+// A template checkbox is mandatory when its line says so, e.g. `- [ ] Tests added
+// (required)` or with a `<!-- required -->` comment. HTML comments and fenced code in the
+// description are ignored, so template hints and code samples don't count.
+use crate::compose;
+use crate::providers::github::models::*;
+use crate::providers::github::signatures::{sign_off, SignOff};
+use crate::providers::methods::{LintOptions, PrNumber};
+use crate::status_log;
+use crate::ui_println;
+use crate::utils::run_git;
+use colored::Colorize;
+use regex::Regex;
+use std::error::Error;
+
+/// Words that mark an unfinished description.
+const TODO_MARKERS: &[&str] = &["TODO", "FIXME", "TBD"];
+
+impl GitHubProvider {
+    /// Runs the selected lint checks on a PR.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The PR to lint
+    /// - `options`: Which checks to run
+    ///
+    /// # Returns
+    /// - `Err` summarizing the failed checks, or if the PR can't be fetched.
+    pub(crate) fn lint_pull_request_for(
+        &self,
+        pr_number: PrNumber,
+        options: &LintOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let mut failures: Vec<String> = Vec::new();
+
+        if options.require_dco {
+            let commits = self.pull_request_commits(&owner, &repo, pr_number)?;
+            if let Some(failure) = lint_dco(&commits) {
+                failures.push(failure);
+            }
+        }
+
+        if options.body {
+            let pr = self.fetch_pull_request(&owner, &repo, pr_number)?;
+            self.ensure_pull_request_head(pr_number, &pr.head.sha)?;
+            let template = compose::read_pr_template();
+            if let Some(failure) = lint_body(
+                pr.body.as_deref().unwrap_or(""),
+                template.as_deref(),
+                &pr.head.sha,
+            ) {
+                failures.push(failure);
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures.join("; ").into())
+        }
+    }
+}
+
+/// Checks that every commit is signed off by its author, printing the offenders.
+///
+/// # Returns
+/// - A failure summary, or `None` if every commit passes.
+fn lint_dco(commits: &[GitHubPRCommit]) -> Option<String> {
+    let offenders: Vec<(&GitHubPRCommit, SignOff)> = commits
+        .iter()
+        .map(|c| (c, sign_off(c)))
+        .filter(|(_, s)| *s != SignOff::Matching)
+        .collect();
+    if offenders.is_empty() {
+        status_log!(
+            "{}",
+            format!(
+                "✅ DCO: all {} commit(s) are signed off by their author",
+                commits.len()
+            )
+            .green()
+        );
+        return None;
+    }
+
+    ui_println!(
+        "{}",
+        format!(
+            "❌ DCO: {} of {} commit(s) lack a matching Signed-off-by:",
+            offenders.len(),
+            commits.len()
+        )
+        .red()
+    );
+    for (commit, state) in &offenders {
+        let subject = commit.commit.message.lines().next().unwrap_or("");
+        let problem = match state {
+            SignOff::Mismatched => format!("signed off, but not by {}", commit.commit.author.email),
+            _ => "no Signed-off-by trailer".to_string(),
+        };
+        println!(
+            "   {} {} ({})",
+            commit.sha[..commit.sha.len().min(7)].yellow(),
+            subject,
+            problem
+        );
+    }
+    println!("   Fix with: git rebase --signoff <base> && git push --force-with-lease");
+    Some(format!("{} commit(s) fail the DCO check", offenders.len()))
+}
+
+/// Removes HTML comments and fenced code blocks, which the rendered description doesn't
+/// show as text.
+fn visible_markdown(text: &str) -> String {
+    let comments = Regex::new(r"(?s)<!--.*?-->").expect("valid regex");
+    let mut in_fence = false;
+    comments
+        .replace_all(text, "")
+        .lines()
+        .filter(|line| {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+                return false;
+            }
+            !in_fence
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Headings (`#` to `######`) of a Markdown document, as their text.
+fn headings(text: &str) -> Vec<String> {
+    text.lines()
+        .filter(|line| line.starts_with('#'))
+        .map(|line| line.trim_start_matches('#').trim().to_string())
+        .filter(|heading| !heading.is_empty())
+        .collect()
+}
+
+/// A checkbox item of a Markdown document.
+///
+/// Fields:
+/// - `ticked`: Whether the box is ticked
+/// - `text`: The item's text, without HTML comments
+/// - `line`: The whole line the item is on, comments included
+#[derive(Debug, PartialEq)]
+struct Checkbox<'a> {
+    ticked: bool,
+    text: String,
+    line: &'a str,
+}
+
+/// Checkbox items of a Markdown document.
+fn checkboxes(text: &str) -> Vec<Checkbox<'_>> {
+    let item = Regex::new(r"^\s*[-*+]\s+\[([ xX])\]\s*(.*)$").expect("valid regex");
+    let comments = Regex::new(r"<!--.*?-->").expect("valid regex");
+    text.lines()
+        .filter_map(|line| {
+            let c = item.captures(line)?;
+            Some(Checkbox {
+                ticked: &c[1] != " ",
+                text: comments.replace_all(&c[2], "").trim().to_string(),
+                line,
+            })
+        })
+        .collect()
+}
+
+/// The mandatory checkboxes of the template (see the module comment) that aren't ticked
+/// in the description.
+///
+/// # Parameters
+/// - `visible`: The description, without comments and code (see [`visible_markdown`])
+/// - `template`: The raw template; the mandatory marker may sit in a comment
+fn unticked_mandatory(visible: &str, template: &str) -> Vec<String> {
+    let ticked = checkboxes(visible);
+    checkboxes(template)
+        .into_iter()
+        // The line the item came from, not the first one containing its text
+        .filter(|item| item.line.to_lowercase().contains("required"))
+        .filter(|item| {
+            !ticked
+                .iter()
+                .any(|done| done.ticked && done.text.eq_ignore_ascii_case(&item.text))
+        })
+        .map(|item| {
+            if item.text.is_empty() {
+                item.line.trim().to_string()
+            } else {
+                item.text
+            }
+        })
+        .collect()
+}
+
+/// Targets of the relative links and images of a Markdown document, without their
+/// `#anchor` or `?query` and relative to the repository root.
+fn relative_links(text: &str) -> Vec<String> {
+    let link =
+        Regex::new(r#"!?\[[^\]]*\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#).expect("valid regex");
+    let scheme = Regex::new(r"^[a-zA-Z][a-zA-Z0-9+.-]*:").expect("valid regex");
+    let mut targets: Vec<String> = link
+        .captures_iter(text)
+        .map(|c| c[1].to_string())
+        .filter(|target| !scheme.is_match(target) && !target.starts_with('#'))
+        .filter(|target| !target.starts_with("//"))
+        .filter_map(|target| {
+            let path = target.split(['#', '?']).next().unwrap_or("");
+            let path = path.trim_start_matches("./").trim_start_matches('/');
+            (!path.is_empty()).then(|| path.to_string())
+        })
+        .collect();
+    targets.dedup();
+    targets
+}
+
+/// Checks a PR description against the template and the PR's head, printing the problems.
+///
+/// # Parameters
+/// - `body`: The PR description
+/// - `template`: The repository's PR template, if it has one
+/// - `head`: The PR's head commit, which relative links are checked against
+///
+/// # Returns
+/// - A failure summary, or `None` if the description passes.
+fn lint_body(body: &str, template: Option<&str>, head: &str) -> Option<String> {
+    let visible = visible_markdown(body);
+    let mut problems: Vec<String> = Vec::new();
+
+    for path in relative_links(&visible) {
+        if run_git(&["cat-file", "-e", &format!("{}:{}", head, path)]).is_none() {
+            problems.push(format!("broken relative link: {}", path));
+        }
+    }
+
+    if let Some(template) = template {
+        let present: Vec<String> = headings(&visible)
+            .iter()
+            .map(|h| h.to_lowercase())
+            .collect();
+        for heading in headings(&visible_markdown(template)) {
+            if !present.contains(&heading.to_lowercase()) {
+                problems.push(format!("missing template section: {}", heading));
+            }
+        }
+
+        for item in unticked_mandatory(&visible, template) {
+            problems.push(format!("unchecked mandatory checkbox: {}", item));
+        }
+    }
+
+    let marker = Regex::new(&format!(r"\b({})\b", TODO_MARKERS.join("|"))).expect("valid regex");
+    for line in visible.lines().filter(|line| marker.is_match(line)) {
+        problems.push(format!("TODO marker: {}", line.trim()));
+    }
+
+    if problems.is_empty() {
+        status_log!("{}", "✅ Body: the description is complete".green());
+        return None;
+    }
+    ui_println!(
+        "{}",
+        format!("❌ Body: {} problem(s) in the description:", problems.len()).red()
+    );
+    for problem in &problems {
+        println!("   {}", problem);
+    }
+    Some(format!(
+        "{} problem(s) in the PR description",
+        problems.len()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visible_markdown_drops_comments_and_code() {
+        let text = "Intro <!-- hint -->\n<!--\nmulti\nline\n-->\n```rust\n// TODO\n```\nOutro";
+        assert_eq!(visible_markdown(text), "Intro \n\nOutro");
+    }
+
+    #[test]
+    fn headings_are_read_at_every_level() {
+        let text = "# Summary\ntext #not\n### Test plan ###\n#\n###### Notes";
+        assert_eq!(headings(text), ["Summary", "Test plan ###", "Notes"]);
+    }
+
+    #[test]
+    fn checkboxes_keep_their_line() {
+        let text =
+            "- [x] Tests added\n* [ ] Docs <!-- required -->\nnot - [ ] a box\n  + [X] Nested";
+        let boxes = checkboxes(text);
+        assert_eq!(
+            boxes
+                .iter()
+                .map(|b| (b.ticked, b.text.as_str()))
+                .collect::<Vec<_>>(),
+            [(true, "Tests added"), (false, "Docs"), (true, "Nested")]
+        );
+        assert_eq!(boxes[1].line, "* [ ] Docs <!-- required -->");
+    }
+
+    #[test]
+    fn relative_links_skip_urls_and_anchors() {
+        let text = "[a](docs/a.md#part) ![img](./img/b.png \"B\") [c](https://x.io/c) \
+                    [d](#top) [e](//cdn.x.io/e) [f](mailto:f@x.io) [g](</src/g.rs?plain=1>)";
+        assert_eq!(relative_links(text), ["docs/a.md", "img/b.png", "src/g.rs"]);
+    }
+
+    #[test]
+    fn mandatory_checkboxes_are_matched_by_their_own_line() {
+        let template =
+            "- [ ] Tests added <!-- required -->\n- [ ] Changelog\n- [ ] <!-- required -->";
+        assert_eq!(
+            unticked_mandatory("- [x] Tests added\n- [ ] Changelog", template),
+            ["- [ ] <!-- required -->"]
+        );
+        assert_eq!(
+            unticked_mandatory("- [ ] tests added\n- [x]", template),
+            ["Tests added"]
+        );
+        assert!(unticked_mandatory("- [X] TESTS ADDED\n- [x]", template).is_empty());
+    }
+}
@@ -0,0 +1,527 @@
+// `git pr merge`: merging a PR after a pre-flight against branch protection.
+//
+// When branch protection forbids a merge, GitHub answers with a bare 405 ("Pull Request
+// is not mergeable") that doesn't say why. So before calling the merge API, the same
+// requirements are looked up and reported one by one: draft state, approvals and
+// requested changes, CODEOWNERS approval, required status checks, resolved
+// conversations, the merge method (allowed by the repository, and by linear-history
+// protection for merge commits), conflicts and branches behind their base. The
+// `--require-label` gate adds one more: a PR touching sensitive paths (see `sensitive`)
+// must carry the given label, and each `merge-policy.<name>` setting matching the base
+// branch (see `policy`) one of its own. The merge is only attempted when nothing blocks.
+//
+// Branch protection is only readable with admin rights. Without it, the requirements that
+// depend on it are shown as unknown, and GitHub's own merge state decides.
+use crate::debug_log;
+use crate::policy;
+use crate::providers::github::checks::CheckState;
+use crate::providers::github::freeze::FREEZE_PATH;
+use crate::providers::github::models::*;
+use crate::providers::github::permissions::WriteAccess;
+use crate::providers::github::reviews::CodeOwnersStatus;
+use crate::providers::methods::{MergeOptions, PrNumber, MERGE_METHODS};
+use crate::sensitive::SensitivePaths;
+use crate::status_log;
+use crate::ui_println;
+use crate::utils::is_quiet;
+use colored::Colorize;
+use reqwest::Method;
+use serde_json::json;
+use std::error::Error;
+
+/// The outcome of one pre-flight requirement.
+enum Verdict {
+    /// Met
+    Pass(String),
+    /// Not met: the merge would be refused
+    Block(String),
+    /// Couldn't be determined (branch protection not readable)
+    Unknown(String),
+}
+
+/// One line of the pre-flight report.
+///
+/// Fields:
+/// - `name`: The requirement (`Approvals`, `Checks`, ...)
+/// - `verdict`: Whether it is met, with the details
+struct Requirement {
+    name: &'static str,
+    verdict: Verdict,
+}
+
+impl Requirement {
+    fn new(name: &'static str, verdict: Verdict) -> Self {
+        Requirement { name, verdict }
+    }
+}
+
+/// The branch protection settings the pre-flight looks at.
+///
+/// Fields:
+/// - `required_checks`: Status check contexts that must pass
+/// - `strict`: Whether the branch must be up to date with its base
+/// - `conversation_resolution`: Whether every review thread must be resolved
+/// - `linear_history`: Whether merge commits are forbidden
+struct Protection {
+    required_checks: Vec<String>,
+    strict: bool,
+    conversation_resolution: bool,
+    linear_history: bool,
+}
+
+impl Protection {
+    /// Reads the settings from the `GET /branches/{branch}/protection` payload.
+    fn from_json(value: &serde_json::Value) -> Self {
+        let checks = &value["required_status_checks"];
+        let mut required_checks: Vec<String> = checks["contexts"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|c| c.as_str().map(String::from))
+            .collect();
+        for check in checks["checks"].as_array().into_iter().flatten() {
+            if let Some(context) = check["context"].as_str() {
+                if !required_checks.iter().any(|c| c == context) {
+                    required_checks.push(context.to_string());
+                }
+            }
+        }
+        Protection {
+            required_checks,
+            strict: checks["strict"].as_bool().unwrap_or(false),
+            conversation_resolution: value["required_conversation_resolution"]["enabled"]
+                .as_bool()
+                .unwrap_or(false),
+            linear_history: value["required_linear_history"]["enabled"]
+                .as_bool()
+                .unwrap_or(false),
+        }
+    }
+}
+
+impl GitHubProvider {
+    /// Runs the pre-flight and, if nothing blocks, merges the PR.
+    ///
+    /// # Returns
+    /// - `Err` naming every unmet requirement, or if the merge itself fails
+    pub(crate) fn merge_pull_request_checked(
+        &self,
+        pr_number: PrNumber,
+        options: &MergeOptions,
+    ) -> Result<(), Box<dyn Error>> {
+        let method = options
+            .method
+            .as_deref()
+            .or(self.config.merge_strategy.as_deref())
+            .unwrap_or("merge");
+        if !MERGE_METHODS.contains(&method) {
+            return Err(format!(
+                "Unknown merge method '{}' (expected one of: {})",
+                method,
+                MERGE_METHODS.join(", ")
+            )
+            .into());
+        }
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        if !options.dry_run {
+            self.check_token_can_write(
+                &owner,
+                &repo,
+                &format!("merging PR #{}", pr_number),
+                WriteAccess::Push,
+            )?;
+        }
+
+        let pr = self.fetch_pull_request(&owner, &repo, pr_number)?;
+        if pr.state != "open" {
+            return Err(format!("PR #{} is {}", pr_number, pr.state).into());
+        }
+        if let Some(expected) = &options.expected_head {
+            if pr.head.sha != *expected {
+                return Err(format!(
+                    "PR #{} has new commits since it was checked ({} is now at {})",
+                    pr_number,
+                    &expected[..expected.len().min(7)],
+                    &pr.head.sha[..pr.head.sha.len().min(7)]
+                )
+                .into());
+            }
+        }
+
+        let requirements = self.merge_preflight(&owner, &repo, pr_number, &pr, method, options)?;
+        // Under --quiet only what blocks is shown, on stderr
+        if !is_quiet() {
+            ui_println!(
+                "🛫 Pre-flight for merging PR #{} into {} ({}):",
+                pr_number,
+                pr.base.ref_name.cyan(),
+                method
+            );
+        }
+        for requirement in &requirements {
+            let (mark, text) = match &requirement.verdict {
+                Verdict::Block(text) if is_quiet() => {
+                    eprintln!("{}: {}", requirement.name, text);
+                    continue;
+                }
+                _ if is_quiet() => continue,
+                Verdict::Pass(text) => ("✅", text.normal()),
+                Verdict::Block(text) => ("❌", text.red()),
+                Verdict::Unknown(text) => ("❔", text.dimmed()),
+            };
+            ui_println!("   {} {:<14} {}", mark, requirement.name, text);
+        }
+
+        let mut blockers: Vec<String> = requirements
+            .iter()
+            .filter(|r| matches!(r.verdict, Verdict::Block(_)))
+            .map(|r| r.name.to_lowercase())
+            .collect();
+        // Several merge policies can block at once
+        blockers.dedup();
+        if !blockers.is_empty() {
+            return Err(format!("Merge blocked by: {}", blockers.join(", ")).into());
+        }
+        if options.dry_run {
+            status_log!("✅ Nothing blocks the merge (dry run; not merged).");
+            return Ok(());
+        }
+        self.confirm_high_risk_pr(
+            &owner,
+            &repo,
+            pr_number,
+            &pr.title,
+            &pr.user.login,
+            "merge",
+            options.confirm,
+        )?;
+
+        // Pinning the head commit makes GitHub refuse the merge if someone pushed meanwhile
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/merge",
+            self.api_base, owner, repo, pr_number
+        );
+        let merged: GitHubMergeResult = self.request(
+            self.authed(Method::PUT, &url)
+                .json(&json!({ "merge_method": method, "sha": pr.head.sha })),
+            &format!("Failed to merge PR #{}", pr_number),
+        )?;
+        debug_log!("[DEBUG] Merge response: {}", merged.message);
+
+        if is_quiet() {
+            println!("{}", merged.sha);
+        } else {
+            ui_println!(
+                "✅ Merged PR #{} into {} as {}",
+                pr_number,
+                pr.base.ref_name,
+                &merged.sha[..merged.sha.len().min(7)]
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks every merge requirement of a PR.
+    ///
+    /// # Returns
+    /// - One entry per requirement, in the order they are reported
+    /// - `Err` if the PR's reviews, checks or merge state can't be fetched
+    fn merge_preflight(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+        pr: &GitHubPRDetail,
+        method: &str,
+        options: &MergeOptions,
+    ) -> Result<Vec<Requirement>, Box<dyn Error>> {
+        let mut requirements = Vec::new();
+        let base = pr.base.ref_name.as_str();
+
+        if pr.draft {
+            requirements.push(Requirement::new(
+                "Draft",
+                Verdict::Block("still a draft (mark it ready with `git pr ready`)".to_string()),
+            ));
+        }
+
+        // A declared merge freeze of the base branch
+        match self.active_freeze(owner, repo, base) {
+            Ok(None) => {}
+            Ok(Some(freeze)) => requirements.push(Requirement::new(
+                "Freeze",
+                if options.override_freeze {
+                    Verdict::Pass(format!(
+                        "{} is frozen, overridden: {}",
+                        base,
+                        freeze.describe()
+                    ))
+                } else {
+                    Verdict::Block(format!(
+                        "{} is frozen: {} (--override to merge anyway)",
+                        base,
+                        freeze.describe()
+                    ))
+                },
+            )),
+            Err(e) => requirements.push(Requirement::new(
+                "Freeze",
+                Verdict::Unknown(format!("couldn't read {}: {}", FREEZE_PATH, e)),
+            )),
+        }
+
+        // Branch protection, if this token may read it
+        let protection_url = format!(
+            "{}/repos/{}/{}/branches/{}/protection",
+            self.api_base, owner, repo, base
+        );
+        let protection = self
+            .dispatch(self.authed(Method::GET, &protection_url))
+            .ok()
+            .filter(|resp| resp.status().is_success())
+            .and_then(|resp| resp.json::<serde_json::Value>().ok())
+            .map(|value| Protection::from_json(&value));
+        debug_log!(
+            "[DEBUG] Branch protection of {} readable: {}",
+            base,
+            protection.is_some()
+        );
+
+        // Approvals, requested changes and code owners
+        let reviews = self.review_requirements(owner, repo, pr_number, base)?;
+        let approved = reviews.approvers.len() as u32;
+        requirements.push(Requirement::new(
+            "Approvals",
+            match reviews.required_approvals {
+                Some(required) if approved >= required => {
+                    Verdict::Pass(format!("{} of {} required", approved, required))
+                }
+                Some(required) => Verdict::Block(format!(
+                    "{} of {} required; needs {} more",
+                    approved,
+                    required,
+                    required - approved
+                )),
+                None => Verdict::Unknown(format!(
+                    "{} approval{} (required count unknown)",
+                    approved,
+                    if approved == 1 { "" } else { "s" }
+                )),
+            },
+        ));
+        if !reviews.blocking.is_empty() {
+            requirements.push(Requirement::new(
+                "Changes",
+                Verdict::Block(format!("requested by {}", reviews.blocking.join(", "))),
+            ));
+        }
+        if reviews.require_code_owner_reviews {
+            if let CodeOwnersStatus::Missing(files) = &reviews.codeowners {
+                requirements.push(Requirement::new(
+                    "Code owners",
+                    Verdict::Block(format!(
+                        "{} file{} without an owner's approval",
+                        files.len(),
+                        if files.len() == 1 { "" } else { "s" }
+                    )),
+                ));
+            }
+        }
+
+        // Required status checks
+        let checks = self.combined_checks(owner, repo, &pr.head.sha)?;
+        requirements.push(Requirement::new(
+            "Checks",
+            match &protection {
+                Some(protection) => {
+                    let unmet: Vec<String> = protection
+                        .required_checks
+                        .iter()
+                        .filter_map(|name| {
+                            match checks.iter().find(|c| &c.name == name).map(|c| c.state) {
+                                Some(CheckState::Success) | Some(CheckState::Neutral) => None,
+                                Some(state) => Some(format!("{} ({})", name, state.as_str())),
+                                None => Some(format!("{} (missing)", name)),
+                            }
+                        })
+                        .collect();
+                    if unmet.is_empty() {
+                        Verdict::Pass(format!(
+                            "{} required check{} passing",
+                            protection.required_checks.len(),
+                            if protection.required_checks.len() == 1 {
+                                ""
+                            } else {
+                                "s"
+                            }
+                        ))
+                    } else {
+                        Verdict::Block(format!("required: {}", unmet.join(", ")))
+                    }
+                }
+                None => {
+                    let failing: Vec<&str> = checks
+                        .iter()
+                        .filter(|c| c.state >= CheckState::Pending)
+                        .map(|c| c.name.as_str())
+                        .collect();
+                    if failing.is_empty() {
+                        Verdict::Pass(format!("{} check(s) passing", checks.len()))
+                    } else {
+                        Verdict::Unknown(format!(
+                            "not passing: {} (which are required is unknown)",
+                            failing.join(", ")
+                        ))
+                    }
+                }
+            },
+        ));
+
+        // Conflicts and branch freshness in one query; review threads are paged separately
+        let data = self.graphql(
+            "query($owner: String!, $repo: String!, $number: Int!) { repository(owner: $owner, name: $repo) { pullRequest(number: $number) { mergeable mergeStateStatus } } }",
+            json!({ "owner": owner, "repo": repo, "number": pr_number.0 }),
+        )?;
+        let node = &data["repository"]["pullRequest"];
+        let mergeable = node["mergeable"].as_str().unwrap_or("UNKNOWN");
+        let merge_state = node["mergeStateStatus"].as_str().unwrap_or("UNKNOWN");
+        let unresolved = self.unresolved_threads(owner, repo, pr_number)?.len();
+        debug_log!(
+            "[DEBUG] PR #{}: {}, {}, {} unresolved thread(s)",
+            pr_number,
+            mergeable,
+            merge_state,
+            unresolved
+        );
+
+        let required = protection.as_ref().map(|p| p.conversation_resolution);
+        requirements.push(Requirement::new(
+            "Conversations",
+            match (unresolved, required) {
+                (0, _) => Verdict::Pass("all resolved".to_string()),
+                (n, Some(true)) => Verdict::Block(format!(
+                    "{} unresolved thread{} (resolution required)",
+                    n,
+                    if n == 1 { "" } else { "s" }
+                )),
+                (n, Some(false)) => Verdict::Pass(format!("{} unresolved (not required)", n)),
+                (n, None) => Verdict::Unknown(format!(
+                    "{} unresolved thread{} (whether resolution is required is unknown)",
+                    n,
+                    if n == 1 { "" } else { "s" }
+                )),
+            },
+        ));
+
+        // The merge method, as allowed by the repository settings and linear history
+        let repo_url = format!("{}/repos/{}/{}", self.api_base, owner, repo);
+        let settings: serde_json::Value =
+            self.get(&repo_url, "Failed to fetch repository settings")?;
+        let allowed = settings[format!(
+            "allow_{}",
+            match method {
+                "merge" => "merge_commit",
+                "squash" => "squash_merge",
+                _ => "rebase_merge",
+            }
+        )]
+        .as_bool()
+        .unwrap_or(true);
+        let linear = protection.as_ref().is_some_and(|p| p.linear_history);
+        let alternatives: Vec<&str> = MERGE_METHODS
+            .iter()
+            .copied()
+            .filter(|m| *m != method)
+            .collect();
+        requirements.push(Requirement::new(
+            "Method",
+            if !allowed {
+                Verdict::Block(format!(
+                    "{} is disabled for this repository; try --method {}",
+                    method,
+                    alternatives.join(" or --method ")
+                ))
+            } else if linear && method == "merge" {
+                Verdict::Block(
+                    "merge commits are forbidden by linear history; use --method squash or --method rebase"
+                        .to_string(),
+                )
+            } else {
+                Verdict::Pass(format!("{} allowed", method))
+            },
+        ));
+
+        // Rulesets and other protection the token can't see still show up as BLOCKED
+        let explained = requirements
+            .iter()
+            .any(|r| matches!(r.verdict, Verdict::Block(_)));
+        requirements.push(Requirement::new(
+            "Mergeable",
+            match (mergeable, merge_state) {
+                ("CONFLICTING", _) | (_, "DIRTY") => {
+                    Verdict::Block(format!("conflicts with {}; rebase or merge it in", base))
+                }
+                (_, "BEHIND") if protection.as_ref().is_none_or(|p| p.strict) => Verdict::Block(
+                    format!("behind {}; update the branch first (required)", base),
+                ),
+                (_, "BLOCKED") if !explained => Verdict::Block(
+                    "GitHub reports the merge as blocked by branch protection or rulesets"
+                        .to_string(),
+                ),
+                ("UNKNOWN", _) => {
+                    Verdict::Unknown("GitHub is still computing mergeability".to_string())
+                }
+                _ => Verdict::Pass("no conflicts".to_string()),
+            },
+        ));
+
+        // Sensitive changes need a sign-off label
+        if let Some(label) = &options.require_label {
+            let sensitive = SensitivePaths::new(&self.config);
+            let files = self.pull_request_files(owner, repo, pr_number)?;
+            let touched = sensitive.matching(files.iter().map(|f| f.filename.as_str()));
+            let labeled = pr.labels.iter().any(|l| l.name.eq_ignore_ascii_case(label));
+            requirements.push(Requirement::new(
+                "Label",
+                match (touched.is_empty(), labeled) {
+                    (true, _) => Verdict::Pass("no sensitive paths touched".to_string()),
+                    (false, true) => Verdict::Pass(format!("'{}' present", label)),
+                    (false, false) => Verdict::Block(format!(
+                        "touches sensitive paths ({}) but isn't labeled '{}'",
+                        touched.join(", "),
+                        label
+                    )),
+                },
+            ));
+        }
+
+        // The team's own rules for this base branch
+        let labels: Vec<&str> = pr.labels.iter().map(|l| l.name.as_str()).collect();
+        for policy in policy::policies_for(&self.config, base) {
+            let unmet = policy.unmet(&labels, approved);
+            debug_log!(
+                "[DEBUG] Merge policy {} ({}) unmet: {:?}",
+                policy.name,
+                policy.branches,
+                unmet
+            );
+            requirements.push(Requirement::new(
+                "Policy",
+                if unmet.is_empty() {
+                    Verdict::Pass(format!("{}: {}", policy.name, policy.describe()))
+                } else {
+                    Verdict::Block(format!(
+                        "{} ({}): {}",
+                        policy.name,
+                        policy.branches,
+                        unmet.join(", ")
+                    ))
+                },
+            ));
+        }
+
+        Ok(requirements)
+    }
+}
@@ -1,15 +1,199 @@
 // Declare the `github` module as public so it can be accessed from outside this module.
 // This typically contains core GitHub-related functionality such as the main provider implementation,
 // API interaction methods, or high-level orchestration code.
+#[allow(clippy::module_inception)]
 pub mod github;
 
-// Declare the `methods` module with `pub(crate)` visibility.
-// This means the module is public *within the current crate only* but not exposed outside.
-// Usually used for helper functions or internal logic that should not be part of the public API.
-// Keeping this restricted helps encapsulate implementation details and maintain a clean API surface.
-pub(crate) mod methods;
+// Declare the `api` module with `pub(crate)` visibility.
+// It holds the HTTP plumbing shared by every endpoint (status checks and error hints).
+pub(crate) mod api;
 
 // Declare the `models` module as public.
 // This module likely contains data structures, such as API response models, domain models, and data transfer objects (DTOs).
 // Making it public means these models can be used throughout the crate and by external users if the crate is published.
 pub mod models;
+
+// Declare the `reviews` module with `pub(crate)` visibility.
+// It holds review-state helpers (current verdict per reviewer, branch protection and
+// CODEOWNERS checks) shared by commands that need to explain why a PR is blocked.
+pub(crate) mod reviews;
+
+// Declare the `deployments` module with `pub(crate)` visibility.
+// It looks up deployment statuses (preview/staging environments) for a PR's head commit.
+pub(crate) mod deployments;
+
+// Declare the `checks` module with `pub(crate)` visibility.
+// It merges check runs and legacy commit statuses into one CI view per commit.
+pub(crate) mod checks;
+
+// Declare the `actions` module with `pub(crate)` visibility.
+// It finds GitHub Actions workflow runs/jobs for a commit and renders their logs.
+pub(crate) mod actions;
+
+// Declare the `participants` module with `pub(crate)` visibility.
+// It aggregates who committed, reviewed and commented on a PR.
+pub(crate) mod participants;
+
+// Declare the `forks` module with `pub(crate)` visibility.
+// It finds or creates the user's fork and the local remote pointing at it.
+pub(crate) mod forks;
+
+// Declare the `viewed` module with `pub(crate)` visibility.
+// It reads and toggles the per-file "Viewed" state used to track review progress.
+pub(crate) mod viewed;
+
+// Declare the `pending` module with `pub(crate)` visibility.
+// It finds, submits and discards the viewer's unsubmitted (PENDING) reviews.
+pub(crate) mod pending;
+
+// Declare the `porcelain` module with `pub(crate)` visibility.
+// It prints the versioned, line-oriented `--porcelain` formats meant for scripts.
+pub(crate) mod porcelain;
+
+// Declare the `diagnostics` module with `pub(crate)` visibility.
+// It runs the API-side checks of `git pr doctor` (reachability, token, rate limit).
+pub(crate) mod diagnostics;
+
+// Declare the `concurrent` module with `pub(crate)` visibility.
+// It implements the async provider trait, fetching several PRs at once.
+pub(crate) mod concurrent;
+
+// Declare the `watch` module with `pub(crate)` visibility.
+// It redraws the PR list with review/CI state for `git pr list --watch`.
+pub(crate) mod watch;
+
+// Declare the `suggest` module with `pub(crate)` visibility.
+// It ranks CODEOWNERS and blamed authors by review load for `git pr suggest-reviewer`.
+pub(crate) mod suggest;
+
+// Declare the `blame` module with `pub(crate)` visibility.
+// It blames a PR's base commit, for `show-diff --blame` and reviewer suggestions.
+pub(crate) mod blame;
+
+// Declare the `binaries` module with `pub(crate)` visibility.
+// It sizes binary and LFS files for `show-diff`/`files` and the `checks` size gate.
+pub(crate) mod binaries;
+
+// Declare the `dependencies` module with `pub(crate)` visibility.
+// It compares the dependency manifests a PR changes, for `git pr show-details`.
+pub(crate) mod dependencies;
+
+// Declare the `signatures` module with `pub(crate)` visibility.
+// It reports commit signature verification and DCO sign-offs.
+pub(crate) mod signatures;
+
+// Declare the `lint` module with `pub(crate)` visibility.
+// It runs the policy checks of `git pr lint`.
+pub(crate) mod lint;
+
+// Declare the `attribution` module with `pub(crate)` visibility.
+// It breaks a PR's commits and lines down per author and co-author.
+pub(crate) mod attribution;
+
+// Declare the `welcome` module with `pub(crate)` visibility.
+// It detects first-time contributors and posts `git pr welcome` comments.
+pub(crate) mod welcome;
+
+// Declare the `projects` module with `pub(crate)` visibility.
+// It looks up the project-board status of PRs for the `project` column.
+pub(crate) mod projects;
+
+// Declare the `readiness` module with `pub(crate)` visibility.
+// It works out which PRs can be merged right now, for `list --mergeable`.
+pub(crate) mod readiness;
+
+// Declare the `blockers` module with `pub(crate)` visibility.
+// It finds the PRs and issues a PR depends on ("depends on #123", "blocked by #120").
+pub(crate) mod blockers;
+
+// Declare the `triage` module with `pub(crate)` visibility.
+// It runs the interactive `git pr triage` session over untriaged open PRs.
+pub(crate) mod triage;
+
+// Declare the `queue` module with `pub(crate)` visibility.
+// It lists the PRs waiting on a team's review for `git pr queue`.
+pub(crate) mod queue;
+
+// Declare the `sla` module with `pub(crate)` visibility.
+// It rates PRs awaiting review against the `review-sla` thresholds.
+pub(crate) mod sla;
+
+// Declare the `rotation` module with `pub(crate)` visibility.
+// It picks the next reviewer of the `review-rotation` for `git pr assign-next`.
+pub(crate) mod rotation;
+
+// Declare the `merge` module with `pub(crate)` visibility.
+// It runs the branch-protection pre-flight of `git pr merge` and merges the PR.
+pub(crate) mod merge;
+
+// Declare the `threads` module with `pub(crate)` visibility.
+// It lists (and resolves) unresolved review threads for `git pr unresolved`.
+pub(crate) mod threads;
+
+// Declare the `attachments` module with `pub(crate)` visibility.
+// It uploads the files of `git pr comment --attach` and embeds them in the comment.
+pub(crate) mod attachments;
+
+// Declare the `commit_review` module with `pub(crate)` visibility.
+// It shows and reviews single commits of a PR (`git pr review-commit`).
+pub(crate) mod commit_review;
+
+// Declare the `scoped` module with `pub(crate)` visibility.
+// It compiles per-directory verdicts into one review (`git pr scope`).
+pub(crate) mod scoped;
+
+// Declare the `signed_reviews` module with `pub(crate)` visibility.
+// It submits and verifies reviews carrying a GPG-signed receipt.
+pub(crate) mod signed_reviews;
+
+// Declare the `ownership` module with `pub(crate)` visibility.
+// It narrows `git pr list --owned-by-me` down to PRs touching paths the user owns.
+pub(crate) mod ownership;
+
+// Declare the `permissions` module with `pub(crate)` visibility.
+// It checks the token's scopes and the user's role before reviews, merges and closes.
+pub(crate) mod permissions;
+
+// Declare the `diff_stats` module with `pub(crate)` visibility.
+// It prints a PR's change totals by directory and file type (`git pr diff-stats`).
+pub(crate) mod diff_stats;
+
+// Declare the `coverage_report` module with `pub(crate)` visibility.
+// It reports the test coverage of the lines a PR adds (`git pr coverage`).
+pub(crate) mod coverage_report;
+
+// Declare the `runner` module with `pub(crate)` visibility.
+// It runs a command against a PR's head in a throwaway worktree (`git pr run`).
+pub(crate) mod runner;
+
+// Declare the `bisect` module with `pub(crate)` visibility.
+// It finds the first commit of a PR that fails a test command (`git pr bisect`).
+pub(crate) mod bisect;
+
+// Declare the `server` module with `pub(crate)` visibility.
+// It answers JSON-RPC requests from editor plugins on stdio (`git pr serve`).
+pub(crate) mod server;
+
+// Declare the `comments` module with `pub(crate)` visibility.
+// It shows a PR's conversation and inline comments, marking outdated ones (`git pr comments`).
+pub(crate) mod comments;
+
+// Declare the `freeze` module with `pub(crate)` visibility.
+// It declares and reads merge freezes kept in `.github/MERGE_FREEZE` (`git pr freeze`).
+pub(crate) mod freeze;
+
+// Declare the `autopilot` module with `pub(crate)` visibility.
+// It approves and merges dependency-only PRs from trusted bots (`git pr autopilot`).
+pub(crate) mod autopilot;
+
+// Declare the `bots` module with `pub(crate)` visibility.
+// It groups dependency bot PRs by ecosystem and dependency (`git pr bots`).
+pub(crate) mod bots;
+
+// Declare the `confirm` module with `pub(crate)` visibility.
+// It asks for the PR number before merging or closing in high-risk repositories.
+pub(crate) mod confirm;
+
+// Declare the `webhook` module with `pub(crate)` visibility.
+// It drops cached PR data on signed webhook deliveries (`git pr serve --webhook`).
+pub(crate) mod webhook;
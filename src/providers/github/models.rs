@@ -1,3 +1,5 @@
+use crate::config::Config;
+// Resolved user/repo settings carried by the provider
 use chrono::{DateTime, Utc};
 // For handling date/time, specifically with UTC timezone
 use reqwest::blocking::Client;
@@ -13,13 +15,20 @@ use tabled::Tabled;
 /// - `remote_url`: The GitHub repository remote URL (e.g., https://github.com/user/repo.git)
 /// - `client`: HTTP client instance to perform API requests
 /// - `token`: Authentication token for GitHub API access (typically a personal access token)
+/// - `remote`: Name of the git remote the repository was resolved from (e.g. `origin`)
+/// - `api_base`: Base URL of the REST API (`https://api.github.com` or a GitHub Enterprise host)
+/// - `config`: The resolved `git-pr` configuration (branch template, merge strategy, etc.)
 ///
 /// All fields are `pub(crate)` to restrict direct access outside the current crate,
 /// providing encapsulation while allowing internal use.
+#[derive(Clone)]
 pub struct GitHubProvider {
     pub(crate) remote_url: String,
     pub(crate) client: Client,
     pub(crate) token: String,
+    pub(crate) remote: String,
+    pub(crate) api_base: String,
+    pub(crate) config: Config,
 }
 
 /// Struct representing a full GitHub Pull Request response from the API.
@@ -32,20 +41,39 @@ pub struct GitHubProvider {
 /// - `title`: The title/summary of the PR
 /// - `user`: The user who created the PR (nested struct)
 /// - `created_at`: The creation date/time of the PR in UTC
+/// - `updated_at`: When the PR last changed (push, comment, review, edit)
 /// - `body`: Optional detailed description of the PR
 /// - `labels`: Labels/tags attached to the PR (e.g. "bug", "enhancement")
 /// - `commits`: Number of commits in the PR
 /// - `changed_files`: Number of files changed by the PR
+/// - `head`: The branch the PR proposes
+/// - `base`: The branch the PR targets
+/// - `milestone`: The milestone the PR is planned for, if any
+/// - `author_association`: The author's relation to the repository (`FIRST_TIME_CONTRIBUTOR`,
+///   `CONTRIBUTOR`, `MEMBER`, ...)
+/// - `state`: `open` or `closed`
+/// - `html_url`: The PR's page in the web UI
 #[derive(Deserialize)]
 pub(crate) struct GitHubPR {
     pub number: u32,
     pub title: String,
     pub user: GitHubUser,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
     pub body: Option<String>,
     pub labels: Vec<Label>,
     pub commits: u32,
     pub changed_files: u32,
+    pub head: GitHubBranchRef,
+    pub base: GitHubBranchRef,
+    #[serde(default)]
+    pub milestone: Option<Milestone>,
+    #[serde(default)]
+    pub author_association: String,
+    #[serde(default)]
+    pub state: String,
+    #[serde(default)]
+    pub html_url: String,
 }
 
 /// A simplified GitHub PR struct used for lightweight API calls,
@@ -59,6 +87,17 @@ pub(crate) struct GitHubPR {
 /// - `title`: PR title
 /// - `user`: PR author info
 /// - `created_at`: PR creation timestamp
+/// - `updated_at`: When the PR last changed
+/// - `base`: The branch the PR targets
+/// - `head`: The branch the PR proposes
+/// - `labels`: Labels attached to the PR
+/// - `milestone`: The milestone the PR is planned for, if any
+/// - `assignees`: Users the PR is assigned to
+/// - `requested_reviewers`: Users whose review is still requested
+/// - `requested_teams`: Teams whose review is still requested
+/// - `html_url`: The PR's page in the web UI
+/// - `draft`: Whether the PR is still a draft
+/// - `body`: The PR's description, if any
 #[allow(dead_code)]
 #[derive(Deserialize)]
 pub(crate) struct BasicGitHubPR {
@@ -66,6 +105,168 @@ pub(crate) struct BasicGitHubPR {
     pub title: String,
     pub user: GitHubUser,
     pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub base: GitHubBranchRef,
+    pub head: GitHubBranchRef,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    #[serde(default)]
+    pub milestone: Option<Milestone>,
+    #[serde(default)]
+    pub assignees: Vec<GitHubUser>,
+    #[serde(default)]
+    pub requested_reviewers: Vec<GitHubUser>,
+    #[serde(default)]
+    pub requested_teams: Vec<GitHubTeam>,
+    #[serde(default)]
+    pub html_url: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub body: Option<String>,
+}
+
+/// Represents the `head` or `base` side of a pull request.
+///
+/// Fields:
+/// - `ref_name`: The branch name (`ref` in the API payload)
+/// - `label`: `owner:branch`, which tells same-named branches of different forks apart
+/// - `sha`: The commit the branch points at
+/// - `repo`: The repository holding the branch (`None` once a fork has been deleted)
+#[derive(Deserialize)]
+pub(crate) struct GitHubBranchRef {
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub label: String,
+    pub sha: String,
+    pub repo: Option<GitHubRepoRef>,
+}
+
+/// A repository as embedded in PR payloads.
+///
+/// Fields:
+/// - `full_name`: `owner/name`
+/// - `owner`: The owning user or organization
+#[derive(Deserialize)]
+pub(crate) struct GitHubRepoRef {
+    pub full_name: String,
+    pub owner: GitHubUser,
+}
+
+/// The full payload of `GET /repos/{owner}/{repo}/pulls/{number}`.
+///
+/// Fields:
+/// - `node_id`: GraphQL node ID (needed by mutations)
+/// - `title` / `body`: PR title and description (`body` is `null` when empty)
+/// - `state`: `open` or `closed`
+/// - `user`: PR author
+/// - `created_at`: PR creation timestamp
+/// - `updated_at`: When the PR last changed
+/// - `diff_url`: Where the unified diff can be downloaded
+/// - `head` / `base`: The proposed branch and the branch it targets
+/// - `author_association`: The author's relation to the repository (see [`GitHubPR`])
+/// - `draft`: Whether the PR is still a draft
+/// - `labels`: Labels attached to the PR
+/// - `html_url`: The PR's page in the web UI
+#[derive(Deserialize)]
+pub(crate) struct GitHubPRDetail {
+    pub node_id: String,
+    pub title: String,
+    pub body: Option<String>,
+    pub state: String,
+    pub user: GitHubUser,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub diff_url: String,
+    pub head: GitHubBranchRef,
+    pub base: GitHubBranchRef,
+    #[serde(default)]
+    pub author_association: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    #[serde(default)]
+    pub html_url: String,
+}
+
+/// The response of `PUT /repos/{owner}/{repo}/pulls/{number}/merge`.
+///
+/// Fields:
+/// - `sha`: The commit the PR was merged as
+/// - `message`: GitHub's confirmation message
+#[derive(Deserialize)]
+pub(crate) struct GitHubMergeResult {
+    pub sha: String,
+    #[serde(default)]
+    pub message: String,
+}
+
+/// An entry of `GET /repos/{owner}/{repo}/pulls/{number}/commits`.
+///
+/// Fields:
+/// - `sha`: Commit SHA
+/// - `commit`: The git-level commit data (message, author name)
+/// - `author`: The GitHub account linked to the commit author, if any
+/// - `html_url`: The commit's page in the web UI
+#[derive(Deserialize)]
+pub(crate) struct GitHubPRCommit {
+    pub sha: String,
+    pub commit: GitHubCommitData,
+    pub author: Option<GitHubUser>,
+    #[serde(default)]
+    pub html_url: String,
+}
+
+/// Git-level data of a commit.
+///
+/// Fields:
+/// - `message`: Full commit message
+/// - `author`: Author as recorded in git
+/// - `verification`: GitHub's verdict on the commit's GPG/SSH signature
+#[derive(Deserialize)]
+pub(crate) struct GitHubCommitData {
+    pub message: String,
+    pub author: GitHubGitAuthor,
+    #[serde(default)]
+    pub verification: Option<GitHubVerification>,
+}
+
+/// A commit author as recorded in git (not necessarily a GitHub account).
+#[derive(Deserialize)]
+pub(crate) struct GitHubGitAuthor {
+    pub name: String,
+    #[serde(default)]
+    pub email: String,
+}
+
+/// Signature verification of a commit.
+///
+/// Fields:
+/// - `verified`: Whether GitHub verified the signature against a key of the author
+/// - `reason`: `valid`, `unsigned`, `unknown_key`, `bad_email`, ...
+/// - `signature`: The armored signature, if the commit is signed (tells GPG from SSH)
+#[derive(Deserialize)]
+pub(crate) struct GitHubVerification {
+    pub verified: bool,
+    pub reason: String,
+    pub signature: Option<String>,
+}
+
+/// The payload of `GET /repos/{owner}/{repo}/commits/{sha}`, reduced to its files and stats.
+#[derive(Deserialize)]
+pub(crate) struct GitHubCommitDetail {
+    #[serde(default)]
+    pub files: Vec<GitHubPRFile>,
+    #[serde(default)]
+    pub stats: Option<GitHubCommitStats>,
+}
+
+/// Lines added and deleted by a commit.
+#[derive(Deserialize, Clone, Copy, Default)]
+pub(crate) struct GitHubCommitStats {
+    pub additions: u64,
+    pub deletions: u64,
 }
 
 /// Represents a GitHub user (author of PR, commenter, etc.)
@@ -78,6 +279,369 @@ pub(crate) struct GitHubUser {
     pub login: String,
 }
 
+/// A team, as referenced by review requests.
+///
+/// Fields:
+/// - `slug`: The team's URL name (`platform-core`), unique within its organization
+#[derive(Deserialize)]
+pub(crate) struct GitHubTeam {
+    pub slug: String,
+}
+
+/// An entry of a PR's event log (`/issues/{n}/events`).
+///
+/// Fields:
+/// - `event`: What happened (`review_requested`, `labeled`, ...)
+/// - `created_at`: When it happened
+/// - `requested_reviewer`: The user a review was requested from, for review requests
+/// - `requested_team`: The team a review was requested from, for review requests
+#[derive(Deserialize)]
+pub(crate) struct GitHubIssueEvent {
+    pub event: String,
+    pub created_at: DateTime<Utc>,
+    pub requested_reviewer: Option<GitHubUser>,
+    pub requested_team: Option<GitHubTeam>,
+}
+
+/// The payload of `GET /repos/{owner}/{repo}`, reduced to what the token pre-check needs.
+///
+/// Fields:
+/// - `private`: Whether the repository is private (classic tokens then need `repo`)
+/// - `permissions`: The authenticated user's role, absent for anonymous requests
+#[derive(Deserialize)]
+pub(crate) struct GitHubRepository {
+    #[serde(default)]
+    pub private: bool,
+    pub permissions: Option<GitHubRepoPermissions>,
+}
+
+/// What the authenticated user may do in a repository, as reported with the repository.
+#[derive(Deserialize)]
+pub(crate) struct GitHubRepoPermissions {
+    #[serde(default)]
+    pub push: bool,
+}
+
+/// The payload of `GET /rate_limit`, reduced to the core REST budget.
+#[derive(Deserialize)]
+pub(crate) struct GitHubRateLimit {
+    pub resources: GitHubRateResources,
+}
+
+/// Rate limit buckets; only the core REST bucket matters to `git-pr`.
+#[derive(Deserialize)]
+pub(crate) struct GitHubRateResources {
+    pub core: GitHubRateBucket,
+}
+
+/// One rate limit bucket.
+///
+/// Fields:
+/// - `limit`: Requests allowed per window
+/// - `remaining`: Requests left in the current window
+/// - `reset`: When the window resets (Unix seconds)
+#[derive(Deserialize)]
+pub(crate) struct GitHubRateBucket {
+    pub limit: u64,
+    pub remaining: u64,
+    pub reset: i64,
+}
+
+/// Represents a single review submitted on a pull request.
+///
+/// Returned by the `/pulls/{n}/reviews` endpoint, in chronological order.
+///
+/// Fields:
+/// - `user`: The reviewer
+/// - `state`: Review state (`APPROVED`, `CHANGES_REQUESTED`, `COMMENTED`, `DISMISSED`, `PENDING`)
+/// - `body`: The review's summary text
+/// - `commit_id`: The commit the review was submitted against
+#[derive(Deserialize)]
+pub(crate) struct GitHubReview {
+    pub user: GitHubUser,
+    pub state: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub commit_id: Option<String>,
+}
+
+/// Represents a file changed by a pull request.
+///
+/// Returned by the `/pulls/{n}/files` endpoint.
+///
+/// Fields:
+/// - `filename`: Repo-relative path of the file
+/// - `status`: `added`, `removed`, `modified`, `renamed`, ...
+/// - `patch`: Unified diff of the file; GitHub omits it for binary and very large files
+/// - `blob_url`: The file's content at the change, in the web UI
+/// - `additions` / `deletions`: Changed line counts (0 for binary files)
+#[derive(Deserialize)]
+pub(crate) struct GitHubPRFile {
+    pub filename: String,
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub additions: u64,
+    #[serde(default)]
+    pub deletions: u64,
+    pub patch: Option<String>,
+    #[serde(default)]
+    pub blob_url: String,
+}
+
+/// The payload of `GET /repos/{owner}/{repo}/git/trees/{sha}?recursive=1`.
+///
+/// Fields:
+/// - `tree`: Every entry of the tree, subdirectories included
+/// - `truncated`: `true` if GitHub cut the listing short (very large repositories)
+#[derive(Deserialize)]
+pub(crate) struct GitHubTree {
+    pub tree: Vec<GitHubTreeEntry>,
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+/// One entry of a git tree.
+///
+/// Fields:
+/// - `path`: Repo-relative path
+/// - `size`: Blob size in bytes (absent for subtrees and submodules)
+#[derive(Deserialize)]
+pub(crate) struct GitHubTreeEntry {
+    pub path: String,
+    pub size: Option<u64>,
+}
+
+/// File metadata from `GET /repos/{owner}/{repo}/contents/{path}`.
+///
+/// Fields:
+/// - `size`: Blob size in bytes
+#[derive(Deserialize)]
+pub(crate) struct GitHubContentMeta {
+    pub size: u64,
+}
+
+/// Represents a deployment created for a commit (e.g. a preview environment).
+///
+/// Returned by the `/deployments` endpoint, newest first.
+///
+/// Fields:
+/// - `id`: Deployment identifier, used to fetch its statuses
+/// - `environment`: Target environment name (e.g. "preview", "staging")
+#[derive(Deserialize)]
+pub(crate) struct GitHubDeployment {
+    pub id: u64,
+    pub environment: String,
+}
+
+/// Represents a status update on a deployment.
+///
+/// Fields:
+/// - `state`: Deployment state (`success`, `pending`, `in_progress`, `failure`, `inactive`, ...)
+/// - `environment_url`: URL of the running environment, if the deployer reported one
+/// - `target_url`: Fallback URL (usually the deploy log)
+/// - `created_at`: When this status was reported
+#[derive(Deserialize)]
+pub(crate) struct GitHubDeploymentStatus {
+    pub state: String,
+    pub environment_url: Option<String>,
+    pub target_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Represents a single check run (GitHub Checks API), as listed under `check_runs` by the
+/// `/commits/{sha}/check-runs` endpoint.
+///
+/// Fields:
+/// - `name`: Check name, e.g. "build (ubuntu-latest)"
+/// - `status`: `queued`, `in_progress` or `completed`
+/// - `conclusion`: Final result once completed (`success`, `failure`, `neutral`, ...)
+/// - `html_url`: Link to the check in the web UI
+#[derive(Deserialize)]
+pub(crate) struct GitHubCheckRun {
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub html_url: Option<String>,
+}
+
+/// Represents a legacy commit status (Statuses API), as still used by many CI systems;
+/// the `/commits/{sha}/status` (combined status) endpoint lists them under `statuses`.
+///
+/// Fields:
+/// - `context`: Status context name, e.g. "ci/jenkins"
+/// - `state`: `success`, `pending`, `failure` or `error`
+/// - `target_url`: Link to the CI job
+/// - `description`: Short description set by the CI system
+#[derive(Deserialize)]
+pub(crate) struct GitHubCommitStatus {
+    pub context: String,
+    pub state: String,
+    pub target_url: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Response wrapper of the `/actions/runs` endpoint.
+#[derive(Deserialize)]
+pub(crate) struct GitHubWorkflowRuns {
+    pub workflow_runs: Vec<GitHubWorkflowRun>,
+}
+
+/// Represents a GitHub Actions workflow run.
+///
+/// Fields:
+/// - `id`: Run identifier
+/// - `name`: Workflow name (e.g. "CI")
+#[derive(Deserialize)]
+pub(crate) struct GitHubWorkflowRun {
+    pub id: u64,
+    pub name: Option<String>,
+}
+
+/// Response wrapper of the `/actions/runs/{id}/jobs` endpoint.
+#[derive(Deserialize)]
+pub(crate) struct GitHubJobs {
+    pub jobs: Vec<GitHubJob>,
+}
+
+/// Represents a job within a workflow run.
+///
+/// Fields:
+/// - `id`: Job identifier, used to download logs
+/// - `name`: Job name (e.g. "build (ubuntu-latest)")
+/// - `status`: `queued`, `in_progress` or `completed`
+/// - `conclusion`: Final result once completed
+/// - `steps`: The job's steps, in execution order
+#[derive(Deserialize)]
+pub(crate) struct GitHubJob {
+    pub id: u64,
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    #[serde(default)]
+    pub steps: Vec<GitHubJobStep>,
+}
+
+/// Represents a step within a job.
+///
+/// Fields:
+/// - `name`: Step name (e.g. "Run cargo test")
+/// - `status`: `queued`, `in_progress` or `completed`
+/// - `conclusion`: Final result once completed
+#[derive(Deserialize)]
+pub(crate) struct GitHubJobStep {
+    pub name: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+}
+
+/// Response wrapper of the `/actions/runs/{id}/artifacts` endpoint.
+#[derive(Deserialize)]
+pub(crate) struct GitHubArtifacts {
+    pub artifacts: Vec<GitHubArtifact>,
+}
+
+/// Represents a build artifact uploaded by a workflow run.
+///
+/// Fields:
+/// - `id`: Artifact ID, unique across re-runs and workflows
+/// - `name`: Artifact name as given to `actions/upload-artifact`
+/// - `size_in_bytes`: Size of the (zipped) artifact
+/// - `expired`: Whether the retention period has passed
+/// - `archive_download_url`: API URL returning the artifact as a zip archive
+#[derive(Deserialize)]
+pub(crate) struct GitHubArtifact {
+    pub id: u64,
+    pub name: String,
+    pub size_in_bytes: u64,
+    pub expired: bool,
+    pub archive_download_url: String,
+}
+
+/// An entry of `GET /repos/{owner}/{repo}/commits/{sha}/pulls`.
+///
+/// Fields:
+/// - `number`: Number of a PR that contains the commit
+#[derive(Deserialize)]
+pub(crate) struct GitHubCommitPullRequest {
+    pub number: u32,
+}
+
+/// Response of the `/search/issues` endpoint.
+///
+/// Fields:
+/// - `total_count`: Total number of matches (may exceed the returned page)
+/// - `items`: The matching issues/PRs on this page
+#[derive(Deserialize)]
+pub(crate) struct GitHubSearchResult {
+    pub total_count: u64,
+    pub items: Vec<GitHubSearchItem>,
+}
+
+/// A single search hit; only the number is needed to fetch full PR details.
+#[derive(Deserialize)]
+pub(crate) struct GitHubSearchItem {
+    pub number: u32,
+}
+
+/// Represents a comment on a PR, either on the conversation (issue comments endpoint)
+/// or inline on the diff (review comments endpoint).
+///
+/// Fields:
+/// - `id`: The comment's ID
+/// - `user`: The comment author
+/// - `body`: The comment text
+/// - `created_at`: When the comment was posted
+/// - `html_url`: The comment in the web UI
+/// - `path` / `line`: Where an inline comment sits in the diff (`None` on the conversation,
+///   and `line` also once the line is gone from the diff)
+/// - `original_line`: The line an inline comment was written on, as of its commit
+/// - `diff_hunk`: The hunk an inline comment was written on, ending at its line
+/// - `subject_type`: `line` or `file` (a comment on a whole file has no line)
+/// - `in_reply_to_id`: The inline comment this one answers, if any
+#[derive(Deserialize)]
+pub(crate) struct GitHubComment {
+    #[serde(default)]
+    pub id: u64,
+    pub user: GitHubUser,
+    #[serde(default)]
+    pub body: String,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub html_url: String,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub line: Option<u64>,
+    #[serde(default)]
+    pub original_line: Option<u64>,
+    #[serde(default)]
+    pub diff_hunk: Option<String>,
+    #[serde(default)]
+    pub subject_type: Option<String>,
+    #[serde(default)]
+    pub in_reply_to_id: Option<u64>,
+}
+
+/// Represents an issue (from the `/issues/{n}` endpoint).
+///
+/// Fields:
+/// - `title`: Issue title
+/// - `state`: `open` or `closed`
+/// - `labels`: Labels attached to the issue
+/// - `pull_request`: Present when the "issue" is actually a pull request (with its
+///   `merged_at`)
+#[derive(Deserialize)]
+pub(crate) struct GitHubIssue {
+    pub title: String,
+    #[serde(default)]
+    pub state: String,
+    pub labels: Vec<Label>,
+    pub pull_request: Option<serde_json::Value>,
+}
+
 /// Represents a label assigned to a GitHub PR.
 ///
 /// Labels are tags like "bug", "feature", or "urgent".
@@ -88,40 +652,13 @@ pub(crate) struct Label {
     pub name: String,
 }
 
-/// A display-friendly struct for summarizing PR info in tables.
-///
-/// Uses the `Tabled` derive macro for easy conversion into formatted tables.
-/// This struct is NOT for deserialization, but for showing info in CLI output.
+/// A milestone a PR is planned for (e.g. "v2.0").
 ///
-/// Fields are all strings, since they're formatted for display.
-///
-/// Fields and their table header names:
-/// - `number`: PR number (e.g. "#123")
-/// - `title`: Title of the PR
-/// - `author`: Author username
-/// - `age`: Age of PR (e.g. "3d" or "today")
-/// - `commits`: Total number of commits as string
-/// - `files`: Number of changed files as string
-/// - `labels`: Comma-separated list of label names
-/// - `description`: Wrapped PR description text
-#[derive(Tabled)]
-pub(crate) struct DisplayPR {
-    #[tabled(rename = "Number")]
-    pub number: String,
-    #[tabled(rename = "Title")]
+/// Fields:
+/// - `title`: Name of the milestone
+#[derive(Deserialize)]
+pub struct Milestone {
     pub title: String,
-    #[tabled(rename = "Author")]
-    pub author: String,
-    #[tabled(rename = "Age")]
-    pub age: String,
-    #[tabled(rename = "Total Commits")]
-    pub commits: String,
-    #[tabled(rename = "Number of Changed Files")]
-    pub files: String,
-    #[tabled(rename = "Labels")]
-    pub labels: String,
-    #[tabled(rename = "Description")]
-    pub description: String,
 }
 
 /// Represents a detailed row of PR information for displaying commit-level details.
@@ -132,7 +669,8 @@ pub(crate) struct DisplayPR {
 /// - `pr_number`: PR number, shown only in the first row for visual grouping
 /// - `title`: PR title, shown only in first row
 /// - `status`: PR state (open/closed), first row only
-/// - `age`: PR age (days), first row only
+/// - `created`: When the PR was opened, first row only
+/// - `updated`: When the PR last changed, first row only
 /// - `github_username`: PR author, first row only
 /// - `commit_sha`: Short SHA of the commit for the row
 /// - `changed_files`: Files changed in this commit
@@ -144,8 +682,10 @@ pub(crate) struct PRDetailsRow {
     pub title: String,
     #[tabled(rename = "Status")]
     pub status: String,
-    #[tabled(rename = "Age")]
-    pub age: String,
+    #[tabled(rename = "Created")]
+    pub created: String,
+    #[tabled(rename = "Updated")]
+    pub updated: String,
     #[tabled(rename = "Authors")]
     pub github_username: String,
     #[tabled(rename = "Commit SHA")]
@@ -153,3 +693,146 @@ pub(crate) struct PRDetailsRow {
     #[tabled(rename = "Changed Files")]
     pub changed_files: String,
 }
+
+/// A display row for the deployments section of `show-details`.
+///
+/// Fields and their table header names:
+/// - `environment`: Environment name
+/// - `state`: Latest deployment state, colorized
+/// - `url`: Environment URL (or log URL when none was reported)
+/// - `updated`: Age of the latest status
+#[derive(Tabled)]
+pub(crate) struct DeploymentRow {
+    #[tabled(rename = "Environment")]
+    pub environment: String,
+    #[tabled(rename = "State")]
+    pub state: String,
+    #[tabled(rename = "URL")]
+    pub url: String,
+    #[tabled(rename = "Updated")]
+    pub updated: String,
+}
+
+/// A display row for the `checks` command.
+///
+/// Fields and their table header names:
+/// - `name`: Check run name or status context
+/// - `source`: "check" (Checks API) or "status" (commit Statuses API)
+/// - `state`: Normalized, colorized state
+/// - `details`: Description or link to the CI job
+#[derive(Tabled)]
+pub(crate) struct CheckRow {
+    #[tabled(rename = "Name")]
+    pub name: String,
+    #[tabled(rename = "Source")]
+    pub source: String,
+    #[tabled(rename = "State")]
+    pub state: String,
+    #[tabled(rename = "Details")]
+    pub details: String,
+}
+
+/// A display row for the `artifacts` command.
+///
+/// Fields and their table header names:
+/// - `workflow`: Workflow that produced the artifact
+/// - `name`: Artifact name
+/// - `size`: Human-readable size
+/// - `status`: "available" or "expired"
+#[derive(Tabled)]
+pub(crate) struct ArtifactRow {
+    #[tabled(rename = "Workflow")]
+    pub workflow: String,
+    #[tabled(rename = "Artifact")]
+    pub name: String,
+    #[tabled(rename = "Size")]
+    pub size: String,
+    #[tabled(rename = "Status")]
+    pub status: String,
+}
+
+/// A display row for `list --bases`.
+///
+/// Fields and their table header names:
+/// - `base`: Base branch name
+/// - `open`: Number of open PRs targeting it
+#[derive(Tabled)]
+pub(crate) struct BaseCountRow {
+    #[tabled(rename = "Base Branch")]
+    pub base: String,
+    #[tabled(rename = "Open PRs")]
+    pub open: String,
+}
+
+/// A display row for the participants section of `show-details`.
+///
+/// Fields and their table header names:
+/// - `login`: Participant's GitHub login (or git author name when unlinked)
+/// - `commits`: Commits authored in the PR
+/// - `reviews`: Reviews submitted
+/// - `comments`: Conversation plus inline comments
+#[derive(Tabled)]
+pub(crate) struct ParticipantRow {
+    #[tabled(rename = "Participant")]
+    pub login: String,
+    #[tabled(rename = "Commits")]
+    pub commits: String,
+    #[tabled(rename = "Reviews")]
+    pub reviews: String,
+    #[tabled(rename = "Comments")]
+    pub comments: String,
+}
+
+/// A display row for the `files` / `viewed` listing.
+///
+/// Fields and their table header names:
+/// - `viewed`: `[x]` once the viewer marked the file as viewed
+/// - `path`: Repo-relative path of the file
+/// - `changes`: Added/removed line counts
+#[derive(Tabled)]
+pub(crate) struct FileRow {
+    #[tabled(rename = "Viewed")]
+    pub viewed: String,
+    #[tabled(rename = "File")]
+    pub path: String,
+    #[tabled(rename = "Changes")]
+    pub changes: String,
+}
+
+/// A display row for `git pr coverage`.
+///
+/// Fields and their table header names:
+/// - `path`: Repo-relative path of the file
+/// - `covered`: Covered of coverable added lines, with the percentage
+/// - `uncovered`: Added lines no test runs, as ranges
+#[derive(Tabled)]
+pub(crate) struct CoverageRow {
+    #[tabled(rename = "File")]
+    pub path: String,
+    #[tabled(rename = "Covered")]
+    pub covered: String,
+    #[tabled(rename = "Uncovered lines")]
+    pub uncovered: String,
+}
+
+/// A display row for `git pr pending`.
+///
+/// Fields and their table header names:
+/// - `pr`: PR number
+/// - `title`: PR title (local drafts only know the number)
+/// - `source`: `local` for saved drafts, `github` for server-side PENDING reviews
+/// - `event`: Event the draft will be submitted with
+/// - `summary`: First line of the review message, plus inline comment count
+#[derive(Tabled)]
+pub(crate) struct PendingReviewRow {
+    #[tabled(rename = "PR")]
+    pub pr: String,
+    #[tabled(rename = "Title")]
+    pub title: String,
+    #[tabled(rename = "Source")]
+    pub source: String,
+    #[tabled(rename = "Event")]
+    pub event: String,
+    #[tabled(rename = "Summary")]
+    pub summary: String,
+}
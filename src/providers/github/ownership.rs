@@ -0,0 +1,113 @@
+// `git pr list --owned-by-me`: only the PRs touching paths the user owns.
+//
+// A changed file is "owned" when the CODEOWNERS rule for it names the user, a team they
+// belong to or their git email, or when it matches one of the `owned-paths` patterns from
+// the config (for areas CODEOWNERS doesn't spell out, or teams the token can't see into).
+// CODEOWNERS is read from each PR's base branch, as GitHub does when it requests reviews.
+// A PR is kept if any of its files is owned.
+use crate::codeowners::{compile_pattern_list, CodeOwners};
+use crate::debug_log;
+use crate::progress;
+use crate::providers::github::models::*;
+use crate::providers::methods::{PrNumber, SourceControlProvider};
+use crate::utils::run_git;
+use std::collections::HashMap;
+use std::error::Error;
+
+/// Who "me" is, and the team memberships looked up so far.
+///
+/// Fields:
+/// - `login`: Login the token belongs to
+/// - `email`: The local git email, matched against email owners
+/// - `teams`: `org/team` slug → membership, so each team is only asked about once
+struct Me {
+    login: String,
+    email: Option<String>,
+    teams: HashMap<String, bool>,
+}
+
+impl GitHubProvider {
+    /// Whether a CODEOWNERS owner (`login`, `org/team` or email) is the user.
+    fn is_me(&self, owner: &str, me: &mut Me) -> bool {
+        if owner.contains('/') {
+            let login = me.login.clone();
+            return *me
+                .teams
+                .entry(owner.to_lowercase())
+                .or_insert_with(|| self.is_team_member(owner, &login));
+        }
+        if owner.contains('@') {
+            return me
+                .email
+                .as_deref()
+                .is_some_and(|email| email.eq_ignore_ascii_case(owner));
+        }
+        owner.eq_ignore_ascii_case(&me.login)
+    }
+
+    /// Drops the PRs that touch no path the user owns (see the module comment).
+    ///
+    /// # Returns
+    /// - `Err` if there is neither a CODEOWNERS file nor `owned-paths` to go by, or an
+    ///   API call fails
+    pub(crate) fn retain_owned_pull_requests(
+        &self,
+        owner: &str,
+        repo: &str,
+        prs: &mut Vec<BasicGitHubPR>,
+    ) -> Result<(), Box<dyn Error>> {
+        let patterns = compile_pattern_list(self.config.owned_paths.as_deref().unwrap_or(""));
+        let mut rules: HashMap<String, Option<CodeOwners>> = HashMap::new();
+        for pr in prs.iter() {
+            let base = &pr.base.ref_name;
+            if !rules.contains_key(base) {
+                rules.insert(base.clone(), self.fetch_codeowners(owner, repo, base));
+            }
+        }
+        if patterns.is_empty() && !prs.is_empty() && rules.values().all(Option::is_none) {
+            return Err(
+                "No CODEOWNERS file and no `owned-paths` configured, so there is no telling which paths are yours"
+                    .into(),
+            );
+        }
+
+        let mut me = Me {
+            login: self.authenticated_login()?,
+            email: run_git(&["config", "user.email"]),
+            teams: HashMap::new(),
+        };
+        let mut owned = Vec::new();
+        let total = prs.len();
+        for (i, pr) in prs.drain(..).enumerate() {
+            progress::report(
+                "ownership",
+                i + 1,
+                Some(total),
+                &format!("PR #{}", pr.number),
+            );
+            let files = self.pull_request_files(owner, repo, PrNumber(pr.number.into()))?;
+            let codeowners = rules.get(&pr.base.ref_name).and_then(Option::as_ref);
+            let mine = files.iter().find(|file| {
+                patterns.iter().any(|p| p.is_match(&file.filename))
+                    || codeowners.is_some_and(|c| {
+                        c.owners_of(&file.filename)
+                            .iter()
+                            .any(|o| self.is_me(o, &mut me))
+                    })
+            });
+            match mine {
+                Some(file) => {
+                    debug_log!(
+                        "[DEBUG] PR #{} touches {}, which you own",
+                        pr.number,
+                        file.filename
+                    );
+                    owned.push(pr);
+                }
+                None => debug_log!("[DEBUG] PR #{} touches nothing you own", pr.number),
+            }
+        }
+        *prs = owned;
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+// Participants of a pull request: everyone who committed, reviewed or commented,
+// so handing a PR over (or finding who has context) is trivial.
+use crate::debug_log;
+use crate::providers::github::models::*;
+use crate::providers::methods::PrNumber;
+use crate::status_log;
+use crate::table;
+use std::collections::BTreeMap;
+use std::error::Error;
+
+/// Per-person involvement counters.
+#[derive(Default)]
+struct Involvement {
+    commits: usize,
+    reviews: usize,
+    comments: usize,
+}
+
+impl GitHubProvider {
+    /// Fetches the authors of all comments on a PR from the given endpoint
+    /// (`issues/{n}/comments` or `pulls/{n}/comments`).
+    fn comment_authors(
+        &self,
+        owner: &str,
+        repo: &str,
+        endpoint: &str,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/{}?per_page=100",
+            self.api_base, owner, repo, endpoint
+        );
+        debug_log!("[DEBUG] Fetching comments from: {}", url);
+
+        let comments: Vec<GitHubComment> = self.get(&url, "Failed to fetch comments")?;
+        Ok(comments.into_iter().map(|c| c.user.login).collect())
+    }
+
+    /// Builds participant rows for a PR, busiest participant first.
+    ///
+    /// `commit_authors` is passed in because `show-details` has already fetched the commits.
+    pub(crate) fn participant_rows(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+        commit_authors: &[String],
+    ) -> Result<Vec<ParticipantRow>, Box<dyn Error>> {
+        let mut people: BTreeMap<String, Involvement> = BTreeMap::new();
+
+        for author in commit_authors {
+            people.entry(author.clone()).or_default().commits += 1;
+        }
+
+        for review in self.pull_request_reviews(owner, repo, pr_number)? {
+            // Pending reviews are unsubmitted drafts and not visible to others yet
+            if review.state != "PENDING" {
+                people.entry(review.user.login).or_default().reviews += 1;
+            }
+        }
+
+        let conversation = format!("issues/{}/comments", pr_number);
+        let inline = format!("pulls/{}/comments", pr_number);
+        for endpoint in [conversation, inline] {
+            for login in self.comment_authors(owner, repo, &endpoint)? {
+                people.entry(login).or_default().comments += 1;
+            }
+        }
+
+        let mut entries: Vec<(String, Involvement)> = people.into_iter().collect();
+        entries.sort_by_key(|(_, i)| std::cmp::Reverse(i.commits + i.reviews + i.comments));
+
+        Ok(entries
+            .into_iter()
+            .map(|(login, i)| ParticipantRow {
+                login,
+                commits: i.commits.to_string(),
+                reviews: i.reviews.to_string(),
+                comments: i.comments.to_string(),
+            })
+            .collect())
+    }
+}
+
+/// Prints the "Participants" section of `show-details`.
+pub(crate) fn print_participants(rows: Vec<ParticipantRow>) {
+    if rows.is_empty() {
+        return;
+    }
+
+    println!();
+    status_log!("👥 Participants");
+    println!("{}", table::render(rows, &[]));
+}
@@ -0,0 +1,140 @@
+// Pending (unsubmitted) reviews: the viewer's server-side PENDING reviews, found via
+// GraphQL, and the REST calls to submit or discard them. Local drafts live in
+// `crate::drafts`; this module only covers what GitHub stores.
+use crate::debug_log;
+use crate::providers::github::models::*;
+use crate::providers::methods::PrNumber;
+use crate::status_log;
+use crate::table;
+use reqwest::Method;
+use serde_json::json;
+use std::error::Error;
+
+/// Selection of the viewer's pending review on a `pullRequest` GraphQL node.
+///
+/// GitHub only ever exposes the viewer's own pending review, so `first: 1` is enough.
+const PENDING_REVIEW_FIELDS: &str =
+    "number title url reviews(states: PENDING, first: 1) { nodes { databaseId body comments { totalCount } } }";
+
+/// A review the viewer started on GitHub but never submitted.
+///
+/// Fields:
+/// - `pr_number` / `title` / `url`: The PR the review belongs to
+/// - `review_id`: REST id of the review (for submit/delete)
+/// - `body`: Review summary written so far
+/// - `comments`: Number of inline comments attached to the review
+pub(crate) struct ServerPendingReview {
+    pub pr_number: u64,
+    pub title: String,
+    pub url: String,
+    pub review_id: u64,
+    pub body: String,
+    pub comments: u64,
+}
+
+impl GitHubProvider {
+    /// Lists the viewer's pending reviews across the repository's open PRs.
+    pub(crate) fn server_pending_reviews(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> Result<Vec<ServerPendingReview>, Box<dyn Error>> {
+        let query = format!(
+            "query($owner: String!, $repo: String!) {{ repository(owner: $owner, name: $repo) {{ pullRequests(states: OPEN, first: 100) {{ nodes {{ {} }} }} }} }}",
+            PENDING_REVIEW_FIELDS
+        );
+        let data = self.graphql(&query, json!({ "owner": owner, "repo": repo }))?;
+
+        Ok(data["repository"]["pullRequests"]["nodes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(pending_review_from_node)
+            .collect())
+    }
+
+    /// Returns the viewer's pending review on one PR, if any.
+    pub(crate) fn server_pending_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+    ) -> Result<Option<ServerPendingReview>, Box<dyn Error>> {
+        let number = pr_number.0;
+        let query = format!(
+            "query($owner: String!, $repo: String!, $number: Int!) {{ repository(owner: $owner, name: $repo) {{ pullRequest(number: $number) {{ {} }} }} }}",
+            PENDING_REVIEW_FIELDS
+        );
+        let data = self.graphql(
+            &query,
+            json!({ "owner": owner, "repo": repo, "number": number }),
+        )?;
+
+        Ok(pending_review_from_node(&data["repository"]["pullRequest"]))
+    }
+
+    /// Submits a server-side pending review with the given event.
+    pub(crate) fn submit_server_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        review: &ServerPendingReview,
+        event: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/reviews/{}/events",
+            self.api_base, owner, repo, review.pr_number, review.review_id
+        );
+        debug_log!("[DEBUG] Submitting pending review via: {}", url);
+
+        self.send(
+            self.authed(Method::POST, &url)
+                .json(&json!({ "event": event, "body": review.body })),
+            "Failed to submit pending review",
+        )?;
+        Ok(())
+    }
+
+    /// Deletes a server-side pending review (and its unsubmitted comments).
+    pub(crate) fn delete_server_review(
+        &self,
+        owner: &str,
+        repo: &str,
+        review: &ServerPendingReview,
+    ) -> Result<(), Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/reviews/{}",
+            self.api_base, owner, repo, review.pr_number, review.review_id
+        );
+        debug_log!("[DEBUG] Deleting pending review via: {}", url);
+
+        self.send(
+            self.authed(Method::DELETE, &url),
+            "Failed to discard pending review",
+        )?;
+        Ok(())
+    }
+}
+
+/// Extracts the viewer's pending review from a `pullRequest` node, if it has one.
+fn pending_review_from_node(pr: &serde_json::Value) -> Option<ServerPendingReview> {
+    let review = pr["reviews"]["nodes"].as_array()?.first()?;
+    Some(ServerPendingReview {
+        pr_number: pr["number"].as_u64()?,
+        title: pr["title"].as_str().unwrap_or_default().to_string(),
+        url: pr["url"].as_str().unwrap_or_default().to_string(),
+        review_id: review["databaseId"].as_u64()?,
+        body: review["body"].as_str().unwrap_or_default().to_string(),
+        comments: review["comments"]["totalCount"].as_u64().unwrap_or(0),
+    })
+}
+
+/// Prints local drafts and server-side pending reviews in one table.
+pub(crate) fn print_pending_reviews(rows: Vec<PendingReviewRow>) {
+    if rows.is_empty() {
+        status_log!("✅ No pending reviews.");
+        return;
+    }
+    println!("{}", table::render(rows, &[]));
+    println!("Resume, submit or discard with: git pr pending <resume|submit|discard> <pr_number>");
+}
@@ -0,0 +1,106 @@
+// Token pre-check before writes (`submit-review`, `approve`, `request-changes`, `merge`,
+// `close`).
+//
+// A token that can read a repository but not write to it only fails when the write is sent,
+// with a bare 403 (and, for `merge`, after the whole pre-flight). One `GET /repos/...`
+// answers the question upfront: classic tokens list their scopes in `X-OAuth-Scopes`, and
+// the repository says whether it is private (which needs `repo`, not just `public_repo`)
+// and whether the user may push (which merging needs). Fine-grained tokens don't report
+// their permissions; for them GitHub's `X-Accepted-GitHub-Permissions` on the failed write
+// names what was missing (see `api.rs`).
+//
+// The check never blocks on its own failure: if the repository can't be read, the write
+// is sent anyway and fails with its own, more specific error.
+use crate::debug_log;
+use crate::providers::github::models::*;
+use reqwest::Method;
+use std::error::Error;
+
+/// What a write needs from the user, on top of a token scope that allows writing at all.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum WriteAccess {
+    /// Reviewing and closing: anyone who can see the PR (or authored it)
+    Any,
+    /// Merging: push access to the repository
+    Push,
+}
+
+impl GitHubProvider {
+    /// Fails fast if the token can't perform a write (see the module comment).
+    ///
+    /// # Parameters
+    /// - `action`: What is about to happen, for the message (e.g. "merging PR #42")
+    /// - `access`: What the write needs besides the scope
+    ///
+    /// # Returns
+    /// - `Err` saying precisely what the token or the user lacks
+    pub(crate) fn check_token_can_write(
+        &self,
+        owner: &str,
+        repo: &str,
+        action: &str,
+        access: WriteAccess,
+    ) -> Result<(), Box<dyn Error>> {
+        let url = format!("{}/repos/{}/{}", self.api_base, owner, repo);
+        let resp = match self.dispatch(self.authed(Method::GET, &url)) {
+            Ok(resp) if resp.status().is_success() => resp,
+            Ok(resp) => {
+                debug_log!("[DEBUG] Token pre-check skipped: HTTP {}", resp.status());
+                return Ok(());
+            }
+            Err(e) => {
+                debug_log!("[DEBUG] Token pre-check skipped: {}", e);
+                return Ok(());
+            }
+        };
+        let scopes: Option<Vec<String>> = resp
+            .headers()
+            .get("x-oauth-scopes")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| {
+                v.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            });
+        let Ok(repository) = resp.json::<GitHubRepository>() else {
+            return Ok(());
+        };
+        let slug = format!("{}/{}", owner, repo);
+
+        // Classic tokens only; fine-grained ones send no scope header
+        if let Some(scopes) = scopes {
+            debug_log!("[DEBUG] Token scopes: {:?}", scopes);
+            let has = |scope: &str| scopes.iter().any(|s| s == scope);
+            let held = if scopes.is_empty() {
+                "none".to_string()
+            } else {
+                scopes.join(", ")
+            };
+            if repository.private && !has("repo") {
+                return Err(format!(
+                    "Your token lacks the `repo` scope, which {} needs in the private repository {} (token scopes: {}). Regenerate it with `repo`",
+                    action, slug, held
+                )
+                .into());
+            }
+            if !has("repo") && !has("public_repo") {
+                return Err(format!(
+                    "Your token lacks the `public_repo` scope (or `repo`), which {} needs (token scopes: {}). Regenerate it with `public_repo`",
+                    action, held
+                )
+                .into());
+            }
+        }
+
+        let cannot_push = repository.permissions.is_some_and(|p| !p.push);
+        if access == WriteAccess::Push && cannot_push {
+            return Err(format!(
+                "You lack write access to {}, which {} needs; ask a maintainer to merge it or grant you the write role",
+                slug, action
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
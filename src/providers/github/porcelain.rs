@@ -0,0 +1,66 @@
+// Machine-readable `--porcelain` output.
+//
+// Like git's porcelain formats, these are a contract with scripts: one record per line,
+// fields separated by a single tab, free text last. Fields are only ever appended at the
+// end within a version; anything that would reorder or drop a field needs a new version.
+// Human-readable tables can change freely because nothing here depends on them.
+//
+// v1 formats:
+//   list:   <number> <author> <created> <updated> <commits> <changed-files> <title>
+//   checks: <state> <source> <name> <details>
+//
+// Times are RFC 3339 in UTC; check states are success, failure, pending or neutral;
+// sources are check or status; an empty field is printed as `-`.
+use crate::providers::github::checks::CheckEntry;
+use crate::providers::github::models::GitHubPR;
+use chrono::SecondsFormat;
+
+/// Makes a value safe for a tab-separated record: tabs and line breaks become spaces,
+/// and an empty value becomes `-` so the field count never changes.
+fn field(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|c| {
+            if c == '\t' || c == '\n' || c == '\r' {
+                ' '
+            } else {
+                c
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim();
+    if cleaned.is_empty() {
+        "-".to_string()
+    } else {
+        cleaned.to_string()
+    }
+}
+
+/// Prints one `list` record per PR.
+pub(crate) fn print_pull_requests(prs: &[GitHubPR]) {
+    for pr in prs {
+        println!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            pr.number,
+            field(&pr.user.login),
+            pr.created_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            pr.updated_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+            pr.commits,
+            pr.changed_files,
+            field(&pr.title)
+        );
+    }
+}
+
+/// Prints one `checks` record per CI signal.
+pub(crate) fn print_checks(entries: &[CheckEntry]) {
+    for entry in entries {
+        println!(
+            "{}\t{}\t{}\t{}",
+            entry.state.as_str(),
+            entry.source,
+            field(&entry.name),
+            field(&entry.details)
+        );
+    }
+}
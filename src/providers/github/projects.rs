@@ -0,0 +1,74 @@
+// Project-board status of pull requests, for the `project` column of the PR table.
+//
+// Projects (the v2 boards) are only reachable through GraphQL. The boards of every PR in
+// the table are fetched in a single query, one aliased `pullRequest` field per PR, and
+// shown as `Board: Status` (the board's `Status` field, which most boards use for their
+// columns).
+use crate::debug_log;
+use crate::providers::github::models::*;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::error::Error;
+
+/// Board items looked at per PR; a PR rarely sits on more than a couple of boards.
+const MAX_PROJECT_ITEMS: u32 = 10;
+
+impl GitHubProvider {
+    /// Project boards of several PRs, with the PR's status on each.
+    ///
+    /// # Parameters
+    /// - `numbers`: The PRs to look up
+    ///
+    /// # Returns
+    /// - PR number → `Board: Status` entries (just `Board` for items without a status);
+    ///   PRs on no board are left out
+    /// - `Err` if the query failed, e.g. because the token can't read projects
+    pub(crate) fn project_statuses(
+        &self,
+        owner: &str,
+        repo: &str,
+        numbers: &[u32],
+    ) -> Result<BTreeMap<u32, Vec<String>>, Box<dyn Error>> {
+        if numbers.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        let fields: String = numbers
+            .iter()
+            .map(|n| {
+                format!(
+                    "pr{n}: pullRequest(number: {n}) {{ projectItems(first: {max}) {{ nodes {{ project {{ title }} status: fieldValueByName(name: \"Status\") {{ ... on ProjectV2ItemFieldSingleSelectValue {{ name }} }} }} }} }} ",
+                    n = n,
+                    max = MAX_PROJECT_ITEMS
+                )
+            })
+            .collect();
+        let query = format!(
+            "query($owner: String!, $repo: String!) {{ repository(owner: $owner, name: $repo) {{ {}}} }}",
+            fields
+        );
+        let data = self.graphql(&query, json!({ "owner": owner, "repo": repo }))?;
+
+        let mut statuses = BTreeMap::new();
+        for &number in numbers {
+            let boards: Vec<String> = data["repository"][format!("pr{}", number)]["projectItems"]
+                ["nodes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|item| {
+                    let board = item["project"]["title"].as_str()?;
+                    Some(match item["status"]["name"].as_str() {
+                        Some(status) => format!("{}: {}", board, status),
+                        None => board.to_string(),
+                    })
+                })
+                .collect();
+            debug_log!("[DEBUG] PR #{} project boards: {:?}", number, boards);
+            if !boards.is_empty() {
+                statuses.insert(number, boards);
+            }
+        }
+        Ok(statuses)
+    }
+}
@@ -0,0 +1,172 @@
+// `git pr queue --team <team>`: the PRs waiting on a team's review.
+//
+// A PR is in a team's queue while a review is requested from the team itself
+// (`requested_teams`) or from one of its members. The team is given as `org/slug`, or
+// just `slug` for a team of the repository's owner. Listing members needs the `read:org`
+// scope; without it only requests to the team itself are found.
+//
+// The longest-waiting PR comes first. The wait starts at the latest matching review
+// request in the PR's event log, or at the PR's creation when none is found there.
+use crate::debug_log;
+use crate::links::link;
+use crate::providers::github::models::*;
+use crate::status_log;
+use crate::table::{self, Shrink};
+use crate::ui_eprintln;
+use crate::ui_println;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use std::collections::HashSet;
+use std::error::Error;
+use tabled::Tabled;
+
+/// One row of the queue table.
+#[derive(Tabled)]
+struct QueueRow {
+    #[tabled(rename = "PR")]
+    number: String,
+    #[tabled(rename = "Title")]
+    title: String,
+    #[tabled(rename = "Author")]
+    author: String,
+    #[tabled(rename = "Requested")]
+    requested: String,
+    #[tabled(rename = "Waiting since")]
+    since: String,
+}
+
+/// A PR in the queue.
+///
+/// Fields:
+/// - `pr`: The PR
+/// - `requested`: Who the review is requested from (`@org/team`, `@member`)
+/// - `since`: When the wait started
+struct Queued {
+    pr: BasicGitHubPR,
+    requested: Vec<String>,
+    since: DateTime<Utc>,
+}
+
+impl GitHubProvider {
+    /// Prints the open PRs requesting review from a team, longest-waiting first.
+    ///
+    /// # Parameters
+    /// - `team`: `org/slug`, or `slug` for a team of the repository's owner
+    ///
+    /// # Returns
+    /// - `Err` if the PR list can't be fetched
+    pub(crate) fn team_queue(&self, team: &str) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let team = team.trim_start_matches('@');
+        let team = if team.contains('/') {
+            team.to_string()
+        } else {
+            format!("{}/{}", owner, team)
+        };
+        let slug = team.split_once('/').map_or(team.as_str(), |(_, slug)| slug);
+
+        let members: HashSet<String> = self.team_members(&team).into_iter().collect();
+        if members.is_empty() {
+            ui_eprintln!(
+                "⚠️  Couldn't list the members of {} (needs read:org); only reviews requested from the team itself are shown.",
+                team
+            );
+        }
+
+        let mut queue = Vec::new();
+        for pr in self.open_pull_requests(&owner, &repo, None)? {
+            let mut requested: Vec<String> = Vec::new();
+            if pr.requested_teams.iter().any(|t| t.slug == slug) {
+                requested.push(format!("@{}", team));
+            }
+            requested.extend(
+                pr.requested_reviewers
+                    .iter()
+                    .filter(|user| members.contains(&user.login))
+                    .map(|user| format!("@{}", user.login)),
+            );
+            if requested.is_empty() {
+                continue;
+            }
+
+            let since = self
+                .review_requested_at(&owner, &repo, pr.number, slug, &members)
+                .unwrap_or(pr.created_at);
+            queue.push(Queued {
+                pr,
+                requested,
+                since,
+            });
+        }
+
+        if queue.is_empty() {
+            status_log!("✅ No open PR is waiting on a review from {}.", team);
+            return Ok(());
+        }
+        queue.sort_by_key(|queued| queued.since);
+
+        ui_println!(
+            "{}",
+            format!("👀 Review queue of {} ({}):", team, queue.len()).bold()
+        );
+        let rows: Vec<QueueRow> = queue
+            .into_iter()
+            .map(|queued| QueueRow {
+                number: link(&queued.pr.number.to_string(), &queued.pr.html_url),
+                title: queued.pr.title,
+                author: queued.pr.user.login,
+                requested: queued.requested.join(", "),
+                since: self.config.format_time(queued.since),
+            })
+            .collect();
+        println!(
+            "{}",
+            table::render(rows, &[(1, Shrink::Wrap), (3, Shrink::Truncate)])
+        );
+        Ok(())
+    }
+
+    /// When a review was last requested from the team or one of its members.
+    ///
+    /// # Returns
+    /// - `None` if the event log can't be read or has no such request (only the PR's
+    ///   first 100 events are read)
+    fn review_requested_at(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u32,
+        slug: &str,
+        members: &HashSet<String>,
+    ) -> Option<DateTime<Utc>> {
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}/events?per_page=100",
+            self.api_base, owner, repo, number
+        );
+        let events: Vec<GitHubIssueEvent> = self
+            .get(
+                &url,
+                &format!("Failed to fetch the events of PR #{}", number),
+            )
+            .map_err(|e| debug_log!("[DEBUG] No request time for PR #{}: {}", number, e))
+            .ok()?;
+
+        events
+            .into_iter()
+            .filter(|event| event.event == "review_requested")
+            .filter(|event| {
+                event
+                    .requested_team
+                    .as_ref()
+                    .is_some_and(|t| t.slug == slug)
+                    || event
+                        .requested_reviewer
+                        .as_ref()
+                        .is_some_and(|user| members.contains(&user.login))
+            })
+            .map(|event| event.created_at)
+            .max()
+    }
+}
@@ -0,0 +1,99 @@
+// Merge readiness of pull requests, behind `list --mergeable`.
+//
+// A PR is ready when a maintainer could press the merge button right now: the review
+// decision is APPROVED, GitHub computed it as conflict-free, and branch protection isn't
+// holding it back (required checks failing or pending, branch behind its base). These
+// fields only exist in GraphQL, so every PR of the list is looked up in a single query,
+// one aliased `pullRequest` field per PR.
+use crate::debug_log;
+use crate::providers::github::models::*;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::error::Error;
+
+/// `mergeStateStatus` values that still allow merging: `CLEAN`, `HAS_HOOKS` (merge hooks
+/// will run) and `UNSTABLE` (only non-required checks are failing).
+const MERGEABLE_STATES: [&str; 3] = ["CLEAN", "HAS_HOOKS", "UNSTABLE"];
+
+/// What GitHub says about merging a PR.
+///
+/// Fields:
+/// - `review_decision`: `APPROVED`, `CHANGES_REQUESTED` or `REVIEW_REQUIRED`; `None` when
+///   the branch requires no review
+/// - `mergeable`: `MERGEABLE`, `CONFLICTING` or `UNKNOWN` (not computed yet)
+/// - `merge_state`: `mergeStateStatus`, e.g. `CLEAN`, `BLOCKED`, `BEHIND` or `DIRTY`
+pub(crate) struct Readiness {
+    pub review_decision: Option<String>,
+    pub mergeable: String,
+    pub merge_state: String,
+}
+
+impl Readiness {
+    /// Whether the PR is approved, conflict-free and passing its required checks.
+    pub(crate) fn is_ready(&self) -> bool {
+        self.review_decision.as_deref() == Some("APPROVED")
+            && self.mergeable == "MERGEABLE"
+            && MERGEABLE_STATES.contains(&self.merge_state.as_str())
+    }
+}
+
+impl GitHubProvider {
+    /// Merge readiness of several PRs.
+    ///
+    /// # Parameters
+    /// - `numbers`: The PRs to look up
+    ///
+    /// # Returns
+    /// - PR number → readiness; PRs GitHub didn't return are left out
+    /// - `Err` if the query failed
+    pub(crate) fn merge_readiness(
+        &self,
+        owner: &str,
+        repo: &str,
+        numbers: &[u32],
+    ) -> Result<BTreeMap<u32, Readiness>, Box<dyn Error>> {
+        if numbers.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        let fields: String = numbers
+            .iter()
+            .map(|n| {
+                format!(
+                    "pr{n}: pullRequest(number: {n}) {{ reviewDecision mergeable mergeStateStatus }} ",
+                    n = n
+                )
+            })
+            .collect();
+        let query = format!(
+            "query($owner: String!, $repo: String!) {{ repository(owner: $owner, name: $repo) {{ {}}} }}",
+            fields
+        );
+        let data = self.graphql(&query, json!({ "owner": owner, "repo": repo }))?;
+
+        let mut readiness = BTreeMap::new();
+        for &number in numbers {
+            let node = &data["repository"][format!("pr{}", number)];
+            if node.is_null() {
+                continue;
+            }
+            let state = Readiness {
+                review_decision: node["reviewDecision"].as_str().map(String::from),
+                mergeable: node["mergeable"].as_str().unwrap_or("UNKNOWN").to_string(),
+                merge_state: node["mergeStateStatus"]
+                    .as_str()
+                    .unwrap_or("UNKNOWN")
+                    .to_string(),
+            };
+            debug_log!(
+                "[DEBUG] PR #{} review {:?}, {}, {}",
+                number,
+                state.review_decision,
+                state.mergeable,
+                state.merge_state
+            );
+            readiness.insert(number, state);
+        }
+        Ok(readiness)
+    }
+}
@@ -0,0 +1,352 @@
+// Review-state helpers for GitHub pull requests: collapsing the review history into
+// each reviewer's current verdict, and checking it against branch protection and
+// CODEOWNERS to explain what still blocks a PR.
+use crate::codeowners::{CodeOwners, CODEOWNERS_PATHS};
+use crate::debug_log;
+use crate::providers::github::api::encode_branch;
+use crate::providers::github::models::*;
+use crate::providers::methods::PrNumber;
+use crate::ui_println;
+use colored::Colorize;
+use reqwest::{Method, StatusCode, Url};
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+
+/// Outcome of checking CODEOWNERS coverage for a PR.
+pub(crate) enum CodeOwnersStatus {
+    /// The repository has no CODEOWNERS file on the base branch
+    NoFile,
+    /// Every owned file has an approval from one of its owners
+    Satisfied,
+    /// Files still lacking an owner approval, with their owners
+    Missing(Vec<(String, Vec<String>)>),
+}
+
+/// Everything needed to explain whether a PR has enough approvals to merge.
+///
+/// Fields:
+/// - `base`: The base branch whose protection rules apply
+/// - `required_approvals`: Approvals required by branch protection (`Some(0)` for an
+///   unprotected branch, `None` if the protection can't be read)
+/// - `require_code_owner_reviews`: Whether branch protection demands code-owner approval
+/// - `approvers`: Reviewers whose latest verdict is APPROVED
+/// - `blocking`: Reviewers whose latest verdict is still CHANGES_REQUESTED
+/// - `codeowners`: CODEOWNERS coverage of the changed files
+pub(crate) struct ReviewRequirements {
+    pub base: String,
+    pub required_approvals: Option<u32>,
+    pub require_code_owner_reviews: bool,
+    pub approvers: Vec<String>,
+    pub blocking: Vec<String>,
+    pub codeowners: CodeOwnersStatus,
+}
+
+impl GitHubProvider {
+    /// Fetches every review submitted on a PR, in chronological order.
+    pub(crate) fn pull_request_reviews(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+    ) -> Result<Vec<GitHubReview>, Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/reviews?per_page=100",
+            self.api_base, owner, repo, pr_number
+        );
+        debug_log!("[DEBUG] Fetching reviews from: {}", url);
+
+        self.get(&url, "Failed to fetch reviews")
+    }
+
+    /// Returns each reviewer's current verdict on a PR.
+    ///
+    /// Reviews are returned chronologically, so later verdicts overwrite earlier ones.
+    /// `COMMENTED` and `PENDING` reviews don't change a verdict and are skipped.
+    pub(crate) fn latest_review_states(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+    ) -> Result<BTreeMap<String, String>, Box<dyn Error>> {
+        let mut latest = BTreeMap::new();
+        for review in self.pull_request_reviews(owner, repo, pr_number)? {
+            if review.state != "COMMENTED" && review.state != "PENDING" {
+                latest.insert(review.user.login, review.state);
+            }
+        }
+        Ok(latest)
+    }
+
+    /// Lists the paths of all files changed by a PR.
+    pub(crate) fn pull_request_files(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+    ) -> Result<Vec<GitHubPRFile>, Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/files",
+            self.api_base, owner, repo, pr_number
+        );
+        debug_log!("[DEBUG] Fetching changed files from: {}", url);
+
+        self.get_pages(&url, "Failed to fetch changed files")
+    }
+
+    /// Requests (or re-requests) a review from each of `reviewers` on a PR.
+    pub(crate) fn request_reviewers(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+        reviewers: &[String],
+    ) -> Result<(), Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/requested_reviewers",
+            self.api_base, owner, repo, pr_number
+        );
+        debug_log!("[DEBUG] Requesting review from {:?}", reviewers);
+        self.send(
+            self.authed(Method::POST, &url)
+                .json(&json!({ "reviewers": reviewers })),
+            "Failed to request review",
+        )?;
+        Ok(())
+    }
+
+    /// Fetches and parses the CODEOWNERS file from the given ref, if the repo has one.
+    ///
+    /// A location that can't be fetched is skipped like one without a file, so a failed
+    /// request doesn't hide a CODEOWNERS file at the next location.
+    pub(crate) fn fetch_codeowners(
+        &self,
+        owner: &str,
+        repo: &str,
+        git_ref: &str,
+    ) -> Option<CodeOwners> {
+        for path in CODEOWNERS_PATHS {
+            // Branch names may hold `&`, `#` or `+`, so the ref is encoded
+            let Ok(url) = Url::parse_with_params(
+                &format!(
+                    "{}/repos/{}/{}/contents/{}",
+                    self.api_base, owner, repo, path
+                ),
+                &[("ref", git_ref)],
+            ) else {
+                continue;
+            };
+            let url = url.to_string();
+            debug_log!("[DEBUG] Looking for CODEOWNERS at: {}", url);
+
+            let resp = match self.dispatch(
+                self.authed(Method::GET, &url)
+                    .header("Accept", "application/vnd.github.raw"),
+            ) {
+                Ok(resp) => resp,
+                Err(e) => {
+                    debug_log!("[DEBUG] Could not fetch {}: {}", path, e);
+                    continue;
+                }
+            };
+
+            if resp.status().is_success() {
+                match resp.text() {
+                    Ok(text) => return Some(CodeOwners::parse(&text)),
+                    Err(e) => debug_log!("[DEBUG] Could not read {}: {}", path, e),
+                }
+            }
+        }
+        None
+    }
+
+    /// Checks whether `user` is a member of the `org/team` slug.
+    ///
+    /// Lookup failures (missing `read:org` scope, secret teams) count as "not a member".
+    pub(crate) fn is_team_member(&self, team: &str, user: &str) -> bool {
+        let Some((org, slug)) = team.split_once('/') else {
+            return false;
+        };
+        let url = format!(
+            "{}/orgs/{}/teams/{}/memberships/{}",
+            self.api_base, org, slug, user
+        );
+
+        self.dispatch(self.authed(Method::GET, &url))
+            .map(|resp| resp.status().is_success())
+            .unwrap_or(false)
+    }
+
+    /// Computes the review requirements of a PR against its base branch.
+    ///
+    /// Branch protection is only readable with admin rights; without it the required
+    /// approval count is reported as unknown rather than failing the whole command. A
+    /// branch GitHub reports as not protected requires no approvals.
+    pub(crate) fn review_requirements(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+        base: &str,
+    ) -> Result<ReviewRequirements, Box<dyn Error>> {
+        debug_log!(
+            "[DEBUG] Computing review requirements for PR #{}",
+            pr_number
+        );
+
+        // Branch protection: required approvals and code-owner enforcement
+        let protection_url = format!(
+            "{}/repos/{}/{}/branches/{}/protection",
+            self.api_base,
+            owner,
+            repo,
+            encode_branch(base)
+        );
+        let protection: Option<serde_json::Value> =
+            match self.dispatch(self.authed(Method::GET, &protection_url)) {
+                Ok(resp) if resp.status().is_success() => resp.json().ok(),
+                // A 404 is also what a token without access gets, so check which one it is
+                Ok(resp) if resp.status() == StatusCode::NOT_FOUND => resp
+                    .text()
+                    .ok()
+                    .filter(|body| body.contains("Branch not protected"))
+                    .map(|_| json!({})),
+                _ => None,
+            };
+
+        let reviews_rule = protection
+            .as_ref()
+            .map(|p| p["required_pull_request_reviews"].clone());
+        let required_approvals = reviews_rule.as_ref().map(|rule| {
+            rule["required_approving_review_count"]
+                .as_u64()
+                .unwrap_or(0) as u32
+        });
+        let require_code_owner_reviews = reviews_rule
+            .as_ref()
+            .and_then(|rule| rule["require_code_owner_reviews"].as_bool())
+            .unwrap_or(false);
+
+        // Current verdict of each reviewer
+        let states = self.latest_review_states(owner, repo, pr_number)?;
+        let approvers: Vec<String> = states
+            .iter()
+            .filter(|(_, state)| *state == "APPROVED")
+            .map(|(login, _)| login.clone())
+            .collect();
+        let blocking: Vec<String> = states
+            .iter()
+            .filter(|(_, state)| *state == "CHANGES_REQUESTED")
+            .map(|(login, _)| login.clone())
+            .collect();
+
+        // CODEOWNERS coverage: every owned file needs an approval from one of its owners.
+        // Each (team, approver) pair is looked up once, however many files the team owns.
+        let mut memberships: HashMap<(String, String), bool> = HashMap::new();
+        let codeowners = match self.fetch_codeowners(owner, repo, base) {
+            None => CodeOwnersStatus::NoFile,
+            Some(rules) => {
+                let mut missing = Vec::new();
+                for file in self.pull_request_files(owner, repo, pr_number)? {
+                    let owners = rules.owners_of(&file.filename);
+                    if owners.is_empty() {
+                        continue;
+                    }
+                    let covered = owners.iter().any(|o| {
+                        if o.contains('/') {
+                            approvers.iter().any(|a| {
+                                *memberships
+                                    .entry((o.to_lowercase(), a.to_lowercase()))
+                                    .or_insert_with(|| self.is_team_member(o, a))
+                            })
+                        } else {
+                            approvers.iter().any(|a| a.eq_ignore_ascii_case(o))
+                        }
+                    });
+                    if !covered {
+                        missing.push((file.filename, owners));
+                    }
+                }
+                if missing.is_empty() {
+                    CodeOwnersStatus::Satisfied
+                } else {
+                    CodeOwnersStatus::Missing(missing)
+                }
+            }
+        };
+
+        Ok(ReviewRequirements {
+            base: base.to_string(),
+            required_approvals,
+            require_code_owner_reviews,
+            approvers,
+            blocking,
+            codeowners,
+        })
+    }
+}
+
+/// Prints the "Review requirements" section of `show-details`.
+pub(crate) fn print_review_requirements(req: &ReviewRequirements) {
+    println!();
+    ui_println!("🛡️  Review requirements (base: {})", req.base.cyan());
+
+    // Approvals
+    let approved = req.approvers.len() as u32;
+    match req.required_approvals {
+        Some(0) => println!("  Approvals:  {} (none required)", approved),
+        Some(required) if approved >= required => ui_println!(
+            "  Approvals:  {} / {} required {}",
+            approved,
+            required,
+            "✅".green()
+        ),
+        Some(required) => println!(
+            "  Approvals:  {} / {} required → {}",
+            approved,
+            required,
+            format!("needs {} more approval(s)", required - approved).yellow()
+        ),
+        None => println!(
+            "  Approvals:  {} (required count unknown: branch protection not readable)",
+            approved
+        ),
+    }
+    if !req.approvers.is_empty() {
+        println!("  Approved by: {}", req.approvers.join(", ").green());
+    }
+
+    // Blocking reviewers
+    if req.blocking.is_empty() {
+        println!("  Blocking:   none");
+    } else {
+        println!(
+            "  Blocking:   {} (changes requested)",
+            req.blocking.join(", ").red()
+        );
+    }
+
+    // CODEOWNERS
+    let enforcement = if req.require_code_owner_reviews {
+        " (required by branch protection)"
+    } else {
+        ""
+    };
+    match &req.codeowners {
+        CodeOwnersStatus::NoFile => println!("  CODEOWNERS: no CODEOWNERS file"),
+        CodeOwnersStatus::Satisfied => {
+            ui_println!("  CODEOWNERS: {}{}", "satisfied ✅".green(), enforcement)
+        }
+        CodeOwnersStatus::Missing(files) => {
+            ui_println!(
+                "  CODEOWNERS: {}{}",
+                "missing owner approval ❌".red(),
+                enforcement
+            );
+            for (file, owners) in files {
+                let owners: Vec<String> = owners.iter().map(|o| format!("@{}", o)).collect();
+                println!("    - {} ({})", file, owners.join(" "));
+            }
+        }
+    }
+}
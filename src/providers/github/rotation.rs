@@ -0,0 +1,94 @@
+// `git pr assign-next`: round-robin review assignment.
+//
+// Small teams without GitHub's auto-assignment list their reviewers in `review-rotation`
+// (`alice,bob,carol`). Every run requests a review from the person after the one assigned
+// last, skipping the PR's author and anyone in `review-unavailable`. The last assignee is
+// remembered in the repository's git config (`review-rotation-last`), so the rotation
+// itself can be shared through the global config file. Remembering a login rather than a
+// position means editing the rotation doesn't change whose turn it is.
+use crate::config;
+use crate::providers::github::models::*;
+use crate::providers::methods::PrNumber;
+use crate::ui_println;
+use crate::utils::is_quiet;
+use std::error::Error;
+
+/// Picks whose turn it is.
+///
+/// # Parameters
+/// - `rotation`: The reviewers, in order
+/// - `last`: Who was assigned last; the turn starts after them (at the top if they
+///   aren't in the rotation anymore)
+/// - `skip`: Logins passed over this time (author, unavailable people)
+///
+/// # Returns
+/// - The next reviewer, or `None` if everybody is skipped
+fn next_in_rotation<'a>(
+    rotation: &'a [String],
+    last: Option<&str>,
+    skip: &[String],
+) -> Option<&'a String> {
+    let start = last
+        .and_then(|last| rotation.iter().position(|login| login == last))
+        .map_or(0, |i| i + 1);
+    rotation
+        .iter()
+        .cycle()
+        .skip(start)
+        .take(rotation.len())
+        .find(|login| !skip.contains(login))
+}
+
+impl GitHubProvider {
+    /// Requests a review from the next reviewer of the rotation.
+    ///
+    /// # Parameters
+    /// - `dry_run`: Only print whose turn it is
+    ///
+    /// # Returns
+    /// - `Err` if no rotation is configured, everybody is skipped, or the request fails
+    pub(crate) fn assign_next_reviewer(
+        &self,
+        pr_number: PrNumber,
+        dry_run: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let rotation = self.config.review_rotation();
+        if rotation.is_empty() {
+            return Err("No review rotation configured; set one with `git pr config set review-rotation alice,bob,carol`".into());
+        }
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let pr = self.fetch_pull_request(&owner, &repo, pr_number)?;
+
+        let unavailable = self.config.review_unavailable();
+        let mut skip = unavailable.clone();
+        skip.push(pr.user.login.clone());
+        let last = self.config.review_rotation_last.as_deref();
+        let next = next_in_rotation(&rotation, last, &skip).ok_or_else(|| {
+            format!(
+                "Nobody in the rotation can review PR #{} ({} unavailable, @{} is the author)",
+                pr_number,
+                if unavailable.is_empty() {
+                    "nobody".to_string()
+                } else {
+                    unavailable.join(", ")
+                },
+                pr.user.login
+            )
+        })?;
+
+        if dry_run {
+            ui_println!("👉 Next in the rotation for PR #{}: @{}", pr_number, next);
+            return Ok(());
+        }
+
+        self.request_reviewers(&owner, &repo, pr_number, std::slice::from_ref(next))?;
+        config::set_value("review-rotation-last", next, false)
+            .map_err(|e| format!("Review requested, but the rotation wasn't advanced: {}", e))?;
+        if !is_quiet() {
+            ui_println!("✅ Requested review from @{} on PR #{}", next, pr_number);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,96 @@
+// `git pr run <n> -- <command>`: "does it even build?" without touching the checkout.
+//
+// The PR's head commit is fetched (`pull/<n>/head`, which works for forks too) and checked
+// out in a throwaway worktree (see `crate::worktree`), the command runs there with its
+// output streamed straight through, and the worktree is removed again, Ctrl-C included. The command's exit
+// status becomes git-pr's, so the line works in scripts and bisect-style loops.
+use crate::providers::github::models::*;
+use crate::providers::methods::{PrNumber, RunOptions};
+use crate::status_log;
+use crate::ui_eprintln;
+use crate::utils::run_git;
+use crate::worktree::{self, Worktree};
+use colored::Colorize;
+use std::error::Error;
+use std::process::Command;
+
+impl GitHubProvider {
+    /// Makes sure the PR's head commit (and so its whole series) is available locally,
+    /// fetching `pull/<n>/head` from the remote if not.
+    pub(crate) fn ensure_pull_request_head(
+        &self,
+        pr_number: PrNumber,
+        sha: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        if run_git(&["cat-file", "-e", &format!("{}^{{commit}}", sha)]).is_some() {
+            return Ok(());
+        }
+        status_log!("📥 Fetching PR #{}...", pr_number);
+        let status = Command::new("git")
+            .args(["fetch", "--quiet", &self.remote])
+            .arg(format!("pull/{}/head", pr_number))
+            .status()?;
+        if !status.success() {
+            return Err(format!("git fetch of PR #{} failed ({})", pr_number, status).into());
+        }
+        Ok(())
+    }
+
+    /// Runs a command against the PR's head in a temporary worktree.
+    ///
+    /// # Returns
+    /// - The command's exit code (1 if it was killed by a signal, 130 after Ctrl-C)
+    /// - `Err` if the PR can't be fetched, the worktree can't be made or the command
+    ///   can't be started
+    pub(crate) fn run_in_worktree(
+        &self,
+        pr_number: PrNumber,
+        options: &RunOptions,
+    ) -> Result<i32, Box<dyn Error>> {
+        let (program, args) = options
+            .command
+            .split_first()
+            .ok_or("No command given; put it after `--`")?;
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let sha = self.pull_request_head_sha(&owner, &repo, pr_number)?;
+        self.ensure_pull_request_head(pr_number, &sha)?;
+
+        let worktree = Worktree::add(&format!("pr-{}", pr_number), &sha, options.keep)?;
+        let shown = options.command.join(" ");
+        status_log!(
+            "▶️  Running `{}` on PR #{} ({}) in {}",
+            shown,
+            pr_number,
+            &sha[..sha.len().min(7)],
+            worktree.dir.display()
+        );
+        let status = worktree::run(Command::new(program).args(args).current_dir(&worktree.dir));
+        drop(worktree);
+
+        let status = status.map_err(|e| format!("Could not run '{}': {}", program, e))?;
+        if worktree::interrupted() {
+            ui_eprintln!(
+                "{} `{}` interrupted on PR #{}",
+                "❌".red(),
+                shown,
+                pr_number
+            );
+            return Ok(130);
+        }
+        let code = status.code().unwrap_or(1);
+        if status.success() {
+            status_log!("✅ `{}` passed on PR #{}", shown, pr_number);
+        } else {
+            ui_eprintln!(
+                "{} `{}` failed on PR #{} ({})",
+                "❌".red(),
+                shown,
+                pr_number,
+                status
+            );
+        }
+        Ok(code)
+    }
+}
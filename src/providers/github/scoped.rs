@@ -0,0 +1,189 @@
+// `git pr scope`: per-directory verdicts compiled into one review.
+//
+// The verdicts themselves are kept locally (see `scopes`); this side knows the PR's changed
+// files, which decide how much each scope covers, which files nobody reviewed yet, and
+// whether the compiled review may approve the PR.
+use crate::providers::github::models::*;
+use crate::providers::methods::{PrNumber, ScopeAction, SourceControlProvider};
+use crate::scopes::{self, ScopeVerdict};
+use crate::status_log;
+use crate::table::{self, Shrink};
+use crate::ui_println;
+use colored::Colorize;
+use std::error::Error;
+use tabled::Tabled;
+
+/// How many uncovered files are named before the rest is summarized.
+const UNCOVERED_SHOWN: usize = 10;
+
+/// One row of the `scope` table.
+#[derive(Tabled)]
+struct ScopeRow {
+    #[tabled(rename = "Scope")]
+    scope: String,
+    #[tabled(rename = "Verdict")]
+    verdict: String,
+    #[tabled(rename = "Files")]
+    files: usize,
+    #[tabled(rename = "Note")]
+    note: String,
+}
+
+/// Changed files of a PR that no verdict covers.
+fn uncovered<'a>(verdicts: &[ScopeVerdict], files: &'a [String]) -> Vec<&'a String> {
+    files
+        .iter()
+        .filter(|f| scopes::scope_of(verdicts, f).is_none())
+        .collect()
+}
+
+/// Prints the changed files no verdict covers yet, if any.
+fn print_uncovered(files: &[&String]) {
+    if files.is_empty() {
+        return;
+    }
+    let mut names: Vec<&str> = files
+        .iter()
+        .take(UNCOVERED_SHOWN)
+        .map(|f| f.as_str())
+        .collect();
+    if files.len() > UNCOVERED_SHOWN {
+        names.push("...");
+    }
+    ui_println!(
+        "{} {} changed file{} not covered by any scope: {}",
+        "⚠️ ".yellow(),
+        files.len(),
+        if files.len() == 1 { "" } else { "s" },
+        names.join(", ")
+    );
+}
+
+impl GitHubProvider {
+    /// Carries out a `git pr scope` action (see [`ScopeAction`]).
+    ///
+    /// # Returns
+    /// - `Err` if a new scope matches none of the PR's files, there are no verdicts to
+    ///   submit or discard, or the API calls fail
+    pub(crate) fn run_scope_action(
+        &self,
+        pr_number: PrNumber,
+        action: &ScopeAction,
+    ) -> Result<(), Box<dyn Error>> {
+        match action {
+            ScopeAction::Discard => {
+                if !scopes::discard(pr_number) {
+                    return Err(format!("No scope verdicts recorded on PR #{}", pr_number).into());
+                }
+                status_log!("🗑️  Discarded the scope verdicts on PR #{}", pr_number);
+                Ok(())
+            }
+            ScopeAction::Set { path, event, note } => {
+                let files = self.changed_file_names(pr_number)?;
+                let path = scopes::normalize_path(path);
+                let count = files.iter().filter(|f| scopes::covers(&path, f)).count();
+                if count == 0 {
+                    return Err(format!(
+                        "'{}' covers none of the {} file(s) changed by PR #{}",
+                        path,
+                        files.len(),
+                        pr_number
+                    )
+                    .into());
+                }
+                scopes::record(
+                    pr_number,
+                    ScopeVerdict {
+                        path: path.clone(),
+                        event: event.clone(),
+                        note: note.clone().unwrap_or_default(),
+                    },
+                )?;
+                let verdicts = scopes::load(pr_number);
+                status_log!(
+                    "✅ Recorded {} on `{}` ({} file{}) for PR #{}; {} scope{} so far",
+                    scopes::verdict_label(event),
+                    path,
+                    count,
+                    if count == 1 { "" } else { "s" },
+                    pr_number,
+                    verdicts.len(),
+                    if verdicts.len() == 1 { "" } else { "s" }
+                );
+                print_uncovered(&uncovered(&verdicts, &files));
+                Ok(())
+            }
+            ScopeAction::Show => {
+                let verdicts = scopes::load(pr_number);
+                if verdicts.is_empty() {
+                    ui_println!(
+                        "No scope verdicts on PR #{} yet (record one with `git pr scope {} approve <path>`).",
+                        pr_number,
+                        pr_number
+                    );
+                    return Ok(());
+                }
+                let files = self.changed_file_names(pr_number)?;
+                let rows: Vec<ScopeRow> = verdicts
+                    .iter()
+                    .map(|v| ScopeRow {
+                        scope: v.path.clone(),
+                        verdict: scopes::verdict_label(&v.event).to_string(),
+                        files: files
+                            .iter()
+                            .filter(|f| {
+                                scopes::scope_of(&verdicts, f).is_some_and(|s| s.path == v.path)
+                            })
+                            .count(),
+                        note: v.note.clone(),
+                    })
+                    .collect();
+                ui_println!(
+                    "{}",
+                    format!("🧩 Scope verdicts on PR #{}:", pr_number).bold()
+                );
+                println!(
+                    "{}",
+                    table::render(rows, &[(3, Shrink::Wrap), (0, Shrink::Truncate)])
+                );
+                print_uncovered(&uncovered(&verdicts, &files));
+                Ok(())
+            }
+            ScopeAction::Submit { summary } => {
+                let verdicts = scopes::load(pr_number);
+                if verdicts.is_empty() {
+                    return Err(format!(
+                        "No scope verdicts recorded on PR #{}; record one with `git pr scope {} approve <path>`",
+                        pr_number, pr_number
+                    )
+                    .into());
+                }
+                let files = self.changed_file_names(pr_number)?;
+                let (event, body) =
+                    scopes::compile_review(summary.as_deref().unwrap_or(""), &verdicts, &files);
+                status_log!(
+                    "📝 Submitting {} review for PR #{} from {} scope verdict{}...",
+                    event,
+                    pr_number,
+                    verdicts.len(),
+                    if verdicts.len() == 1 { "" } else { "s" }
+                );
+                self.submit_pull_request_review(pr_number, &body, &event)?;
+                scopes::discard(pr_number);
+                Ok(())
+            }
+        }
+    }
+
+    /// Paths of the files a PR changes.
+    fn changed_file_names(&self, pr_number: PrNumber) -> Result<Vec<String>, Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        Ok(self
+            .pull_request_files(&owner, &repo, pr_number)?
+            .into_iter()
+            .map(|f| f.filename)
+            .collect())
+    }
+}
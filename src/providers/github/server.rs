@@ -0,0 +1,477 @@
+// `git pr serve`: git-pr as the PR backend of an editor plugin.
+//
+// VS Code and Neovim plugins would otherwise reimplement the GitHub calls, the token lookup
+// and the remote detection git-pr already has. `serve` is a long-running JSON-RPC 2.0 server
+// on stdin/stdout for the repository it is started in. Messages are framed either the LSP
+// way (`Content-Length: N` header, blank line, N bytes of JSON) or as one JSON object per
+// line; each response is framed like its request, so the plugin can use whatever its
+// JSON-RPC library speaks.
+//
+// Methods (`number` is a PR number, as a number or `"#42"`):
+//   initialize                            → server name, version, repository, methods
+//   list      { state?, base?, limit? }   → open (or closed/all) PRs, newest first, at most
+//                                           `limit` (default 100) of them
+//   details   { number }                  → the PR's metadata and description
+//   files     { number }                  → changed files with their patches
+//   comments  { number, outdated? }       → conversation and inline comments; inline ones
+//                                           whose lines are gone are marked `outdated` and
+//                                           left out with `outdated: false`
+//   comment   { number, body }            → posts a comment on the conversation
+//   review    { number, event, body? }    → submits a review (approve, request_changes,
+//                                           comment)
+//   shutdown / exit                       → as in LSP; `exit` (or end of input) stops
+//
+// `--webhook <addr>` also receives GitHub webhook deliveries, which drop the cached data of
+// the PRs they are about (see `webhook.rs`); `--webhook-only` runs just that listener.
+//
+// stdout belongs to the protocol, so status lines are silenced as under `--quiet`;
+// warnings, `--debug` and `--progress json` output still go to stderr. Writes pass
+// through the usual checks: `--read-only` refuses them and they land in the audit log.
+use crate::debug_log;
+use crate::providers::github::comments;
+use crate::providers::github::models::*;
+use crate::providers::github::webhook::{self, SECRET_ENV};
+use crate::providers::methods::{PrNumber, ServeOptions, SourceControlProvider, LIST_STATES};
+use crate::utils;
+use reqwest::Url;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::io::{self, BufRead, Read, Write};
+
+/// JSON-RPC error codes used by the server.
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// A request that was understood but failed (API error, read-only mode, ...).
+const SERVER_ERROR: i64 = -32000;
+
+/// How many PRs `list` returns when the client doesn't give a `limit`.
+const DEFAULT_LIST_LIMIT: u64 = 100;
+
+/// Methods the server answers, as reported by `initialize`.
+const METHODS: [&str; 9] = [
+    "initialize",
+    "list",
+    "details",
+    "files",
+    "comments",
+    "comment",
+    "review",
+    "shutdown",
+    "exit",
+];
+
+/// How a message was framed; responses are framed the same way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Framing {
+    /// `Content-Length` header and a blank line before the body, as in LSP
+    Headers,
+    /// One JSON object per line
+    Line,
+}
+
+/// An error answered to the client.
+///
+/// Fields:
+/// - `code`: One of the JSON-RPC error codes above
+/// - `message`: What went wrong, for the plugin to show
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<Box<dyn Error>> for RpcError {
+    fn from(e: Box<dyn Error>) -> Self {
+        RpcError::new(SERVER_ERROR, e.to_string())
+    }
+}
+
+/// Reads the next message.
+///
+/// # Returns
+/// - `Ok(None)` at the end of input
+/// - `Err` if stdin fails, a `Content-Length` header is malformed, or the body is cut short
+fn read_message(input: &mut impl BufRead) -> io::Result<Option<(Framing, String)>> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        if !line.trim().is_empty() {
+            break;
+        }
+    }
+    let Some(length) = line
+        .split_once(':')
+        .filter(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .map(|(_, value)| value.trim().to_string())
+    else {
+        return Ok(Some((Framing::Line, line.trim().to_string())));
+    };
+    let length: usize = length.parse().map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid Content-Length '{}'", length),
+        )
+    })?;
+    // Further headers (`Content-Type`) end at a blank line
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+    }
+    // Grow the body with what actually arrives rather than trusting Content-Length
+    let mut body = Vec::new();
+    input.by_ref().take(length as u64).read_to_end(&mut body)?;
+    if body.len() < length {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(Some((
+        Framing::Headers,
+        String::from_utf8_lossy(&body).into_owned(),
+    )))
+}
+
+/// Writes a message framed like the request it answers.
+fn write_message(out: &mut impl Write, framing: Framing, message: &Value) -> io::Result<()> {
+    let text = message.to_string();
+    match framing {
+        Framing::Headers => write!(out, "Content-Length: {}\r\n\r\n{}", text.len(), text)?,
+        Framing::Line => writeln!(out, "{}", text)?,
+    }
+    out.flush()
+}
+
+/// The PR number of a request (`42` or `"#42"`).
+fn number_param(params: &Value) -> Result<PrNumber, RpcError> {
+    let number = &params["number"];
+    number
+        .as_u64()
+        .map(|n| n.to_string())
+        .or_else(|| number.as_str().map(str::to_string))
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, "missing `number`"))?
+        .parse()
+        .map_err(|e: String| RpcError::new(INVALID_PARAMS, e))
+}
+
+/// A string parameter, if given.
+fn text_param<'a>(params: &'a Value, name: &str) -> Option<&'a str> {
+    params[name].as_str()
+}
+
+/// A user as sent to the client: just the login.
+fn login(user: &GitHubUser) -> Value {
+    json!(user.login)
+}
+
+/// A comment as sent to the client.
+fn comment_json(comment: &GitHubComment) -> Value {
+    json!({
+        "id": comment.id,
+        "author": login(&comment.user),
+        "body": comment.body,
+        "created_at": comment.created_at,
+        "url": comment.html_url,
+        "path": comment.path,
+        "line": comment.line,
+        "original_line": comment.original_line,
+        "outdated": comments::is_outdated(comment),
+        "diff_hunk": comment.diff_hunk,
+        "in_reply_to": comment.in_reply_to_id,
+    })
+}
+
+/// A branch of a PR as sent to the client.
+fn branch_json(branch: &GitHubBranchRef) -> Value {
+    json!({
+        "ref": branch.ref_name,
+        "label": branch.label,
+        "sha": branch.sha,
+    })
+}
+
+impl GitHubProvider {
+    /// Answers JSON-RPC requests on stdin until `exit` or the end of input, and webhook
+    /// deliveries if asked to (see the module comment).
+    ///
+    /// # Returns
+    /// - `Err` if the repository can't be inferred from the remote, stdin/stdout fail, or
+    ///   the webhook listener can't start; failing requests are answered with an error and
+    ///   the server carries on
+    pub(crate) fn serve_stdio(&self, options: &ServeOptions) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        if let Some(addr) = &options.webhook {
+            let secret = std::env::var(SECRET_ENV)
+                .ok()
+                .filter(|secret| !secret.is_empty())
+                .ok_or_else(|| format!("--webhook needs the webhook's secret in {}", SECRET_ENV))?;
+            let listener = webhook::spawn_listener(addr, secret, format!("{}/{}", owner, repo))?;
+            if options.webhook_only {
+                // The listener runs until the process is stopped
+                let _ = listener.join();
+                return Ok(());
+            }
+        }
+        // Status lines would corrupt the protocol
+        utils::set_quiet(true);
+
+        let stdin = io::stdin();
+        let mut input = stdin.lock();
+        let stdout = io::stdout();
+        let mut out = stdout.lock();
+        while let Some((framing, text)) = read_message(&mut input)? {
+            let request: Value = match serde_json::from_str(&text) {
+                Ok(request) => request,
+                Err(e) => {
+                    let error = RpcError::new(PARSE_ERROR, e.to_string());
+                    write_message(&mut out, framing, &error_response(Value::Null, error))?;
+                    continue;
+                }
+            };
+            // Requests carry an `id`; notifications don't and get no answer
+            let id = request.get("id").cloned();
+            let Some(method) = request["method"].as_str() else {
+                if let Some(id) = id {
+                    let error = RpcError::new(INVALID_REQUEST, "missing `method`");
+                    write_message(&mut out, framing, &error_response(id, error))?;
+                }
+                continue;
+            };
+            debug_log!("[DEBUG] JSON-RPC request: {}", method);
+            if method == "exit" {
+                break;
+            }
+
+            let result = self.handle_request(&owner, &repo, method, &request["params"]);
+            let Some(id) = id else {
+                continue;
+            };
+            let response = match result {
+                Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                Err(error) => error_response(id, error),
+            };
+            write_message(&mut out, framing, &response)?;
+        }
+        Ok(())
+    }
+
+    /// Runs one method.
+    fn handle_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        method: &str,
+        params: &Value,
+    ) -> Result<Value, RpcError> {
+        match method {
+            "initialize" => Ok(json!({
+                "name": "git-pr",
+                "version": env!("CARGO_PKG_VERSION"),
+                "repository": format!("{}/{}", owner, repo),
+                "methods": METHODS,
+            })),
+            "shutdown" => Ok(Value::Null),
+            "list" => {
+                let state = text_param(params, "state").unwrap_or("open");
+                if !LIST_STATES.contains(&state) {
+                    return Err(RpcError::new(
+                        INVALID_PARAMS,
+                        format!("`state` must be one of: {}", LIST_STATES.join(", ")),
+                    ));
+                }
+                let limit = match &params["limit"] {
+                    Value::Null => DEFAULT_LIST_LIMIT,
+                    limit => limit.as_u64().filter(|&n| n > 0).ok_or_else(|| {
+                        RpcError::new(INVALID_PARAMS, "`limit` must be a positive number")
+                    })?,
+                };
+                // `base` comes from the client, so it is encoded rather than pasted in
+                let mut query = vec![("state", state)];
+                query.extend(text_param(params, "base").map(|base| ("base", base)));
+                let url = Url::parse_with_params(
+                    &format!("{}/repos/{}/{}/pulls", self.api_base, owner, repo),
+                    &query,
+                )
+                .map_err(|e| RpcError::new(SERVER_ERROR, format!("Failed to list PRs: {}", e)))?;
+                // `state: all` could otherwise page through the repository's whole history
+                let prs: Vec<BasicGitHubPR> =
+                    self.get_pages_up_to(url.as_str(), "Failed to list PRs", limit as usize)?;
+                Ok(prs
+                    .iter()
+                    .map(|pr| {
+                        json!({
+                            "number": pr.number,
+                            "title": pr.title,
+                            "author": login(&pr.user),
+                            "created_at": pr.created_at,
+                            "updated_at": pr.updated_at,
+                            "head": branch_json(&pr.head),
+                            "base": branch_json(&pr.base),
+                            "labels": pr.labels.iter().map(|l| &l.name).collect::<Vec<_>>(),
+                            "url": pr.html_url,
+                        })
+                    })
+                    .collect())
+            }
+            "details" => {
+                let number = number_param(params)?;
+                let pr = self.fetch_pull_request(owner, repo, number)?;
+                Ok(json!({
+                    "number": number.0,
+                    "title": pr.title,
+                    "body": pr.body,
+                    "state": pr.state,
+                    "draft": pr.draft,
+                    "author": login(&pr.user),
+                    "created_at": pr.created_at,
+                    "updated_at": pr.updated_at,
+                    "head": branch_json(&pr.head),
+                    "base": branch_json(&pr.base),
+                    "labels": pr.labels.iter().map(|l| &l.name).collect::<Vec<_>>(),
+                    "url": pr.html_url,
+                }))
+            }
+            "files" => {
+                let number = number_param(params)?;
+                let files = self.pull_request_files(owner, repo, number)?;
+                Ok(files
+                    .iter()
+                    .map(|file| {
+                        json!({
+                            "path": file.filename,
+                            "status": file.status,
+                            "additions": file.additions,
+                            "deletions": file.deletions,
+                            "patch": file.patch,
+                        })
+                    })
+                    .collect())
+            }
+            "comments" => {
+                let number = number_param(params)?;
+                let conversation = self.conversation_comments(owner, repo, number)?;
+                let mut inline = self.inline_comments(owner, repo, number)?;
+                if params["outdated"].as_bool() == Some(false) {
+                    // Replies go with the comment they answer
+                    let hidden: Vec<u64> = inline
+                        .iter()
+                        .filter(|c| comments::is_outdated(c))
+                        .map(|c| c.id)
+                        .collect();
+                    inline.retain(|c| {
+                        !hidden.contains(&c.id)
+                            && !c.in_reply_to_id.is_some_and(|id| hidden.contains(&id))
+                    });
+                }
+                Ok(json!({
+                    "conversation": conversation.iter().map(comment_json).collect::<Vec<_>>(),
+                    "inline": inline.iter().map(comment_json).collect::<Vec<_>>(),
+                }))
+            }
+            "comment" => {
+                let number = number_param(params)?;
+                let body = text_param(params, "body")
+                    .filter(|body| !body.trim().is_empty())
+                    .ok_or_else(|| RpcError::new(INVALID_PARAMS, "missing `body`"))?;
+                self.post_comment(owner, repo, &number.to_string(), body)?;
+                Ok(json!({ "ok": true }))
+            }
+            "review" => {
+                let number = number_param(params)?;
+                let event = match text_param(params, "event") {
+                    Some("approve") => "APPROVE",
+                    Some("request_changes") => "REQUEST_CHANGES",
+                    Some("comment") => "COMMENT",
+                    _ => {
+                        return Err(RpcError::new(
+                            INVALID_PARAMS,
+                            "`event` must be one of: approve, request_changes, comment",
+                        ))
+                    }
+                };
+                let body = text_param(params, "body").unwrap_or("");
+                self.submit_pull_request_review(number, body, event)?;
+                Ok(json!({ "ok": true }))
+            }
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("unknown method '{}'", method),
+            )),
+        }
+    }
+}
+
+/// An error response to the request `id`.
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every message of `input`, until the end or the first error.
+    fn messages(input: &str) -> (Vec<(Framing, String)>, Option<io::Error>) {
+        let mut input = input.as_bytes();
+        let mut read = Vec::new();
+        loop {
+            match read_message(&mut input) {
+                Ok(Some(message)) => read.push(message),
+                Ok(None) => return (read, None),
+                Err(e) => return (read, Some(e)),
+            }
+        }
+    }
+
+    #[test]
+    fn reads_line_and_header_framed_messages() {
+        let (read, error) = messages("{\"id\":1}\nContent-Length: 8\r\n\r\n{\"id\":2}{\"id\":3}\n");
+        assert!(error.is_none());
+        assert_eq!(
+            read,
+            [
+                (Framing::Line, "{\"id\":1}".to_string()),
+                (Framing::Headers, "{\"id\":2}".to_string()),
+                (Framing::Line, "{\"id\":3}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_blank_lines_and_extra_headers() {
+        let (read, error) = messages(
+            "\n\r\ncontent-length: 2\r\nContent-Type: application/vscode-jsonrpc\r\n\r\n{}\n\n",
+        );
+        assert!(error.is_none());
+        assert_eq!(read, [(Framing::Headers, "{}".to_string())]);
+    }
+
+    #[test]
+    fn rejects_bad_lengths() {
+        let (read, error) = messages("Content-Length: ten\r\n\r\n{}");
+        assert!(read.is_empty());
+        assert_eq!(error.unwrap().kind(), io::ErrorKind::InvalidData);
+
+        let (read, error) = messages("Content-Length: 99999999999\r\n\r\n{}");
+        assert!(read.is_empty());
+        assert_eq!(error.unwrap().kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -0,0 +1,132 @@
+// Commit signatures and DCO sign-offs of a PR.
+//
+// GitHub verifies the GPG or SSH signature of every commit against the keys of its author;
+// the verdict comes with the PR's commit list, so no extra calls are needed. Projects that
+// follow the Developer Certificate of Origin (DCO) also require a `Signed-off-by:` trailer
+// matching the commit author on every commit. `show-details` reports both per commit, and
+// `git pr lint --require-dco` fails when a sign-off is missing or doesn't match the author.
+use crate::links::link;
+use crate::providers::github::models::*;
+use crate::table::{self, Shrink};
+use crate::ui_println;
+use colored::Colorize;
+use tabled::Tabled;
+
+/// DCO sign-off state of a commit.
+#[derive(PartialEq, Eq)]
+pub(crate) enum SignOff {
+    /// A `Signed-off-by:` trailer names the commit author's email
+    Matching,
+    /// Signed off, but only by someone else
+    Mismatched,
+    /// No `Signed-off-by:` trailer
+    Missing,
+}
+
+/// One row of the commit signatures table.
+#[derive(Tabled)]
+struct SignatureRow {
+    #[tabled(rename = "Commit")]
+    sha: String,
+    #[tabled(rename = "Author")]
+    author: String,
+    #[tabled(rename = "Signature")]
+    signature: String,
+    #[tabled(rename = "Signed-off-by")]
+    sign_off: String,
+}
+
+/// The DCO sign-off state of a commit.
+pub(crate) fn sign_off(commit: &GitHubPRCommit) -> SignOff {
+    let emails = sign_off_emails(&commit.commit.message);
+    if emails.is_empty() {
+        SignOff::Missing
+    } else if emails
+        .iter()
+        .any(|e| e.eq_ignore_ascii_case(&commit.commit.author.email))
+    {
+        SignOff::Matching
+    } else {
+        SignOff::Mismatched
+    }
+}
+
+/// Emails of the `Signed-off-by: Name <email>` trailers of a commit message.
+fn sign_off_emails(message: &str) -> Vec<String> {
+    message
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            if !key.trim().eq_ignore_ascii_case("Signed-off-by") {
+                return None;
+            }
+            let (_, email) = value.split_once('<')?;
+            Some(email.split('>').next()?.trim().to_string())
+        })
+        .collect()
+}
+
+/// Describes a commit's signature, e.g. `✅ GPG` or `❌ SSH (unknown_key)`.
+fn signature_label(commit: &GitHubPRCommit) -> String {
+    let Some(verification) = &commit.commit.verification else {
+        return "?".to_string();
+    };
+    let kind = match verification.signature.as_deref() {
+        Some(s) if s.contains("SSH SIGNATURE") => "SSH",
+        Some(s) if s.contains("SIGNED MESSAGE") => "S/MIME",
+        Some(_) => "GPG",
+        None => return "unsigned".dimmed().to_string(),
+    };
+    if verification.verified {
+        format!("✅ {}", kind)
+    } else {
+        format!("❌ {} ({})", kind, verification.reason)
+            .red()
+            .to_string()
+    }
+}
+
+/// Prints whether each commit is signed and verified, and signed off.
+pub(crate) fn print_commit_signatures(commits: &[GitHubPRCommit]) {
+    if commits.is_empty() {
+        return;
+    }
+
+    let rows: Vec<SignatureRow> = commits
+        .iter()
+        .map(|c| SignatureRow {
+            sha: link(&c.sha[..c.sha.len().min(7)], &c.html_url),
+            author: match &c.author {
+                Some(author) => author.login.clone(),
+                None => c.commit.author.name.clone(),
+            },
+            signature: signature_label(c),
+            sign_off: match sign_off(c) {
+                SignOff::Matching => "✅".to_string(),
+                SignOff::Mismatched => "⚠️  not the author".yellow().to_string(),
+                SignOff::Missing => "-".to_string(),
+            },
+        })
+        .collect();
+
+    let verified = commits
+        .iter()
+        .filter(|c| c.commit.verification.as_ref().is_some_and(|v| v.verified))
+        .count();
+    let signed_off = commits
+        .iter()
+        .filter(|c| sign_off(c) == SignOff::Matching)
+        .count();
+    ui_println!(
+        "{}",
+        format!(
+            "✍️  Commit signatures ({}/{} verified, {}/{} signed off):",
+            verified,
+            commits.len(),
+            signed_off,
+            commits.len()
+        )
+        .bold()
+    );
+    println!("{}", table::render(rows, &[(1, Shrink::Truncate)]));
+}
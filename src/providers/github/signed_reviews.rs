@@ -0,0 +1,168 @@
+// Reviews carrying a GPG-signed receipt (`--sign`, `git pr verify-review`).
+//
+// The receipt itself (format, signing, parsing, GPG verification) lives in `crate::receipts`;
+// this side knows what a receipt has to match on GitHub: the repository, the PR, the
+// review's author, the head commit the review was submitted against and the review's state.
+use crate::debug_log;
+use crate::providers::github::models::*;
+use crate::providers::github::permissions::WriteAccess;
+use crate::providers::methods::PrNumber;
+use crate::receipts::{self, Receipt};
+use crate::status_log;
+use crate::ui_println;
+use colored::Colorize;
+use reqwest::Method;
+use serde_json::json;
+use std::error::Error;
+
+/// The review state GitHub reports for a submitted review event.
+fn state_of_event(event: &str) -> &str {
+    match event {
+        "APPROVE" => "APPROVED",
+        "REQUEST_CHANGES" => "CHANGES_REQUESTED",
+        "COMMENT" => "COMMENTED",
+        other => other,
+    }
+}
+
+impl GitHubProvider {
+    /// Signs a receipt for the review and submits the review with it appended.
+    ///
+    /// The receipt names the PR's current head, which the review is pinned to as well, and
+    /// the login the token authenticates as, which GitHub records as the review's author.
+    ///
+    /// # Returns
+    /// - `Err` if signing fails (nothing is submitted) or the review can't be submitted
+    pub(crate) fn submit_signed_pull_request_review(
+        &self,
+        pr_number: PrNumber,
+        message: &str,
+        event: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        self.check_token_can_write(
+            &owner,
+            &repo,
+            &format!("reviewing PR #{}", pr_number),
+            WriteAccess::Any,
+        )?;
+        let commit_id = self.pull_request_head_sha(&owner, &repo, pr_number)?;
+        let receipt = Receipt {
+            repo: format!("{}/{}", owner, repo),
+            pr_number: pr_number.0,
+            reviewer: self.lookup_login()?,
+            event: event.to_string(),
+            commit: commit_id.clone(),
+            body_sha256: receipts::body_hash(message),
+        };
+        let body = receipts::sign(message, &receipt)?;
+        debug_log!("[DEBUG] Signed review body:\n{}", body);
+
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/reviews",
+            self.api_base, owner, repo, pr_number
+        );
+        self.send(
+            self.authed(Method::POST, &url).json(&json!({
+                "body": body,
+                "event": event,
+                "commit_id": commit_id,
+            })),
+            "Failed to submit review",
+        )?;
+        status_log!(
+            "🔏 Signed review submitted successfully for PR #{}",
+            pr_number
+        );
+        Ok(())
+    }
+
+    /// Checks a review's receipt: the signature, then the receipt against the review.
+    ///
+    /// # Returns
+    /// - `Err` listing every mismatch, or if the review has no receipt or the signature
+    ///   can't be verified
+    pub(crate) fn verify_pull_request_review(
+        &self,
+        pr_number: PrNumber,
+        review_id: u64,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let review: GitHubReview = self.get(
+            &format!(
+                "{}/repos/{}/{}/pulls/{}/reviews/{}",
+                self.api_base, owner, repo, pr_number, review_id
+            ),
+            &format!("Failed to fetch review {} of PR #{}", review_id, pr_number),
+        )?;
+        let signed = receipts::parse(review.body.as_deref().unwrap_or("")).ok_or_else(|| {
+            format!(
+                "Review {} by @{} carries no signed receipt",
+                review_id, review.user.login
+            )
+        })?;
+        let signer = receipts::verify(&signed.payload, &signed.signature)?;
+        ui_println!(
+            "{} Good signature from {} (key {})",
+            "🔏".green(),
+            signer.user_id.bold(),
+            signer.fingerprint
+        );
+
+        let receipt = &signed.receipt;
+        let mut problems = Vec::new();
+        if receipts::body_hash(&signed.body) != receipt.body_sha256 {
+            problems.push("the review text was edited after it was signed".to_string());
+        }
+        let here = format!("{}/{}", owner, repo);
+        if receipt.repo != here || receipt.pr_number != pr_number.0 {
+            problems.push(format!(
+                "the receipt was signed for {}#{}, not {}#{}",
+                receipt.repo, receipt.pr_number, here, pr_number
+            ));
+        }
+        if !receipt.reviewer.eq_ignore_ascii_case(&review.user.login) {
+            problems.push(format!(
+                "the receipt was signed for a review by @{}, not @{}",
+                receipt.reviewer, review.user.login
+            ));
+        }
+        if review.commit_id.as_deref() != Some(receipt.commit.as_str()) {
+            problems.push(format!(
+                "the receipt names commit {}, the review was submitted on {}",
+                receipt.commit,
+                review.commit_id.as_deref().unwrap_or("no commit")
+            ));
+        }
+        if review.state == "DISMISSED" {
+            ui_println!(
+                "{} The review has been dismissed since it was submitted",
+                "⚠️ ".yellow()
+            );
+        } else if review.state != state_of_event(&receipt.event) {
+            problems.push(format!(
+                "the receipt says {}, the review is {}",
+                receipt.event, review.state
+            ));
+        }
+        if !problems.is_empty() {
+            return Err(format!("Receipt does not match: {}", problems.join("; ")).into());
+        }
+
+        ui_println!(
+            "{} Review {} by @{}: {} of {}#{} at {}, unchanged since signing",
+            "✅".green(),
+            review_id,
+            review.user.login,
+            receipt.event,
+            receipt.repo,
+            receipt.pr_number,
+            &receipt.commit[..receipt.commit.len().min(7)]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+// Review-time SLAs (`review-sla`, `review-sla-warn`).
+//
+// Teams with a review-time SLA configure how long a PR may await review: past
+// `review-sla-warn` its row in `list` (and `list --watch`) turns yellow, past `review-sla`
+// red. A PR awaits review while it has neither an approval nor a change request; the
+// wait is counted from its creation. `list --fail-on-sla` exits non-zero when a PR is past
+// the SLA, for CI jobs and cron alerts; in watch mode it stops the dashboard instead.
+use crate::config::Config;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+
+/// How a PR stands against the review SLA.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum SlaLevel {
+    /// Reviewed, or awaiting review for less than `review-sla-warn`
+    Within,
+    /// Awaiting review for longer than `review-sla-warn`
+    Aging,
+    /// Awaiting review for longer than `review-sla`
+    Breached,
+}
+
+/// The configured thresholds.
+///
+/// Fields:
+/// - `warn`: Seconds after which a PR awaiting review is aging
+/// - `breach`: Seconds after which a PR awaiting review is past the SLA
+pub(crate) struct ReviewSla {
+    warn: Option<u64>,
+    breach: Option<u64>,
+}
+
+impl ReviewSla {
+    /// The thresholds of `config`, or `None` if neither is set.
+    pub(crate) fn from_config(config: &Config) -> Option<Self> {
+        match config.review_sla() {
+            (None, None) => None,
+            (warn, breach) => Some(ReviewSla { warn, breach }),
+        }
+    }
+
+    /// Where a PR stands.
+    ///
+    /// # Parameters
+    /// - `created_at`: When the PR was opened
+    /// - `review`: Its review verdict (`approved`, `changes requested` or `pending`);
+    ///   `None` if it couldn't be fetched, which never counts against the SLA
+    pub(crate) fn level(&self, created_at: DateTime<Utc>, review: Option<&str>) -> SlaLevel {
+        if review != Some("pending") {
+            return SlaLevel::Within;
+        }
+        let waited = (Utc::now() - created_at).num_seconds().max(0) as u64;
+        if self.breach.is_some_and(|limit| waited > limit) {
+            SlaLevel::Breached
+        } else if self.warn.is_some_and(|limit| waited > limit) {
+            SlaLevel::Aging
+        } else {
+            SlaLevel::Within
+        }
+    }
+}
+
+/// Colors a table cell by SLA level: yellow for aging PRs, red past the SLA.
+pub(crate) fn paint(level: SlaLevel, text: &str) -> String {
+    match level {
+        SlaLevel::Within => text.to_string(),
+        SlaLevel::Aging => text.yellow().to_string(),
+        SlaLevel::Breached => text.red().to_string(),
+    }
+}
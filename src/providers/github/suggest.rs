@@ -0,0 +1,307 @@
+// `git pr suggest-reviewer`: picks the least-loaded qualified reviewer for a PR.
+//
+// Candidates come from two sources: the CODEOWNERS of the changed files (teams expanded
+// to their members) and the authors of the lines the PR changes, found with `git blame`
+// on the base commit. Each candidate's load is the number of open PRs in the owner's
+// repositories currently requesting their review. Code owners are the qualified set when
+// the repository has any for the changed files; otherwise the blamed authors are.
+use crate::debug_log;
+use crate::providers::github::blame::{blame, old_ranges};
+use crate::providers::github::models::*;
+use crate::providers::methods::PrNumber;
+use crate::table::{self, Shrink};
+use crate::ui_eprintln;
+use crate::ui_println;
+use crate::utils::is_quiet;
+use colored::Colorize;
+use reqwest::Method;
+use std::collections::BTreeMap;
+use std::error::Error;
+use tabled::Tabled;
+
+/// How many of the most-blamed commits are mapped to GitHub accounts.
+const MAX_BLAMED_COMMITS: usize = 15;
+
+/// How many candidates have their review load looked up (one search call each).
+const MAX_CANDIDATES: usize = 10;
+
+/// What is known about one potential reviewer.
+///
+/// Fields:
+/// - `owned_files`: Changed files this person owns via CODEOWNERS (directly or via a team)
+/// - `blamed_lines`: Changed lines on the base commit last touched by this person
+/// - `open_requests`: Open PRs currently requesting their review (`None` if the search failed)
+#[derive(Default)]
+struct Candidate {
+    owned_files: usize,
+    blamed_lines: usize,
+    open_requests: Option<u64>,
+}
+
+/// One row of the suggestion table.
+#[derive(Tabled)]
+struct SuggestionRow {
+    #[tabled(rename = "Reviewer")]
+    login: String,
+    #[tabled(rename = "CODEOWNERS")]
+    owned_files: String,
+    #[tabled(rename = "Blamed lines")]
+    blamed_lines: String,
+    #[tabled(rename = "Open requests")]
+    open_requests: String,
+}
+
+impl GitHubProvider {
+    /// Suggests the least-loaded qualified reviewer for a PR, and optionally requests them.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The PR to find a reviewer for
+    /// - `request`: Request a review from the suggested reviewer
+    ///
+    /// # Returns
+    /// - `Err` if the PR can't be fetched, no candidate was found, or the request failed.
+    pub(crate) fn suggest_reviewer_for(
+        &self,
+        pr_number: PrNumber,
+        request: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let pr = self.fetch_pull_request(&owner, &repo, pr_number)?;
+        let files = self.pull_request_files(&owner, &repo, pr_number)?;
+        let mut candidates: BTreeMap<String, Candidate> = BTreeMap::new();
+
+        // CODEOWNERS of every changed file, as of the base branch
+        match self.fetch_codeowners(&owner, &repo, &pr.base.ref_name) {
+            Some(codeowners) => {
+                let mut teams: BTreeMap<String, Vec<String>> = BTreeMap::new();
+                for file in &files {
+                    let mut owners: Vec<String> = Vec::new();
+                    for o in codeowners.owners_of(&file.filename) {
+                        if o.contains('@') {
+                            // Email owners can't be mapped to an account reliably
+                            continue;
+                        }
+                        if o.contains('/') {
+                            let members = teams
+                                .entry(o.clone())
+                                .or_insert_with(|| self.team_members(&o));
+                            owners.extend(members.iter().cloned());
+                        } else {
+                            owners.push(o);
+                        }
+                    }
+                    owners.sort();
+                    owners.dedup();
+                    for login in owners {
+                        candidates.entry(login).or_default().owned_files += 1;
+                    }
+                }
+            }
+            None => debug_log!("[DEBUG] No CODEOWNERS on {}", pr.base.ref_name),
+        }
+
+        // Authors of the changed lines, as of the base commit
+        match self.blame_authors(&owner, &repo, &pr.base.sha, &files) {
+            Some(blamed) => {
+                for (login, lines) in blamed {
+                    candidates.entry(login).or_default().blamed_lines += lines;
+                }
+            }
+            None => {
+                if !is_quiet() {
+                    ui_eprintln!(
+                        "⚠️  Base commit {} isn't available locally; skipping blame",
+                        &pr.base.sha[..pr.base.sha.len().min(7)]
+                    );
+                }
+            }
+        }
+
+        // Never suggest the author or a bot
+        candidates.retain(|login, _| {
+            !login.eq_ignore_ascii_case(&pr.user.login) && !login.ends_with("[bot]")
+        });
+        // Prefer code owners; fall back to whoever wrote the code being changed
+        if candidates.values().any(|c| c.owned_files > 0) {
+            candidates.retain(|_, c| c.owned_files > 0);
+        }
+        if candidates.is_empty() {
+            return Err(format!(
+                "No qualified reviewer found for PR #{} (no CODEOWNERS or blame matches)",
+                pr_number
+            )
+            .into());
+        }
+
+        // Only the strongest matches get their load looked up
+        let mut ranked: Vec<(String, Candidate)> = candidates.into_iter().collect();
+        ranked.sort_by(|(_, a), (_, b)| {
+            b.owned_files
+                .cmp(&a.owned_files)
+                .then(b.blamed_lines.cmp(&a.blamed_lines))
+        });
+        ranked.truncate(MAX_CANDIDATES);
+        for (login, candidate) in ranked.iter_mut() {
+            candidate.open_requests = self.open_review_requests(&owner, login);
+        }
+
+        // Least loaded first; an unknown load sorts last
+        ranked.sort_by(|(_, a), (_, b)| {
+            a.open_requests
+                .unwrap_or(u64::MAX)
+                .cmp(&b.open_requests.unwrap_or(u64::MAX))
+                .then(b.owned_files.cmp(&a.owned_files))
+                .then(b.blamed_lines.cmp(&a.blamed_lines))
+        });
+        let (best, best_candidate) = &ranked[0];
+
+        if is_quiet() {
+            println!("{}", best);
+        } else {
+            let rows: Vec<SuggestionRow> = ranked
+                .iter()
+                .map(|(login, c)| SuggestionRow {
+                    login: login.clone(),
+                    owned_files: count_or_dash(c.owned_files),
+                    blamed_lines: count_or_dash(c.blamed_lines),
+                    open_requests: c
+                        .open_requests
+                        .map_or_else(|| "?".to_string(), |n| n.to_string()),
+                })
+                .collect();
+            println!("{}", table::render(rows, &[(0, Shrink::Truncate)]));
+            ui_println!(
+                "💡 Suggested: {} ({})",
+                best.green().bold(),
+                reason(best_candidate)
+            );
+        }
+
+        if request {
+            self.request_reviewers(&owner, &repo, pr_number, std::slice::from_ref(best))?;
+            if !is_quiet() {
+                ui_println!("✅ Requested review from {} on PR #{}", best, pr_number);
+            }
+        }
+        Ok(())
+    }
+
+    /// Lists the logins of the members of an `org/team` slug.
+    ///
+    /// Lookup failures (missing `read:org` scope, secret teams) yield an empty list.
+    pub(crate) fn team_members(&self, team: &str) -> Vec<String> {
+        let Some((org, slug)) = team.split_once('/') else {
+            return Vec::new();
+        };
+        let url = format!(
+            "{}/orgs/{}/teams/{}/members?per_page=100",
+            self.api_base, org, slug
+        );
+        match self.get::<Vec<GitHubUser>>(&url, "Failed to list team members") {
+            Ok(members) => members.into_iter().map(|m| m.login).collect(),
+            Err(e) => {
+                debug_log!("[DEBUG] Can't expand team {}: {}", team, e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// Counts, per GitHub login, the changed lines each person last touched on `base_sha`.
+    ///
+    /// Only lines that exist on the base (the old side of each hunk) can be blamed, so new
+    /// files contribute nothing. Commits are mapped to accounts through the API, for the
+    /// `MAX_BLAMED_COMMITS` commits covering the most lines.
+    ///
+    /// # Returns
+    /// - `None` if the base commit isn't available locally and can't be fetched.
+    fn blame_authors(
+        &self,
+        owner: &str,
+        repo: &str,
+        base_sha: &str,
+        files: &[GitHubPRFile],
+    ) -> Option<BTreeMap<String, usize>> {
+        if !self.ensure_commit(base_sha) {
+            return None;
+        }
+
+        // Lines per blamed commit
+        let mut by_commit: BTreeMap<String, usize> = BTreeMap::new();
+        for file in files {
+            let Some(patch) = &file.patch else {
+                continue;
+            };
+            for (start, len) in old_ranges(patch) {
+                let Some(file_blame) = blame(base_sha, &file.filename, start, len) else {
+                    continue;
+                };
+                for sha in file_blame.lines.into_values() {
+                    *by_commit.entry(sha).or_default() += 1;
+                }
+            }
+        }
+
+        let mut commits: Vec<(String, usize)> = by_commit.into_iter().collect();
+        commits.sort_by_key(|(_, lines)| std::cmp::Reverse(*lines));
+        commits.truncate(MAX_BLAMED_COMMITS);
+
+        // Commit → account; commits by unlinked emails are dropped
+        let mut by_login: BTreeMap<String, usize> = BTreeMap::new();
+        for (sha, lines) in commits {
+            let url = format!("{}/repos/{}/{}/commits/{}", self.api_base, owner, repo, sha);
+            match self.get::<GitHubPRCommit>(&url, "Failed to fetch commit") {
+                Ok(GitHubPRCommit {
+                    author: Some(author),
+                    ..
+                }) => *by_login.entry(author.login).or_default() += lines,
+                Ok(_) => debug_log!("[DEBUG] Commit {} has no linked account", sha),
+                Err(e) => debug_log!("[DEBUG] Can't map commit {}: {}", sha, e),
+            }
+        }
+        Some(by_login)
+    }
+
+    /// Counts the open PRs in `owner`'s repositories that request a review from `login`.
+    ///
+    /// # Returns
+    /// - `None` if the search failed.
+    fn open_review_requests(&self, owner: &str, login: &str) -> Option<u64> {
+        let url = format!("{}/search/issues", self.api_base);
+        let query = format!("is:pr is:open review-requested:{} user:{}", login, owner);
+        debug_log!("[DEBUG] Counting review requests: {}", query);
+        self.request::<GitHubSearchResult>(
+            self.authed(Method::GET, &url)
+                .query(&[("q", query.as_str()), ("per_page", "1")]),
+            "Failed to search review requests",
+        )
+        .map(|result| result.total_count)
+        .ok()
+    }
+}
+
+/// Shows a count, or `-` for zero.
+fn count_or_dash(n: usize) -> String {
+    if n == 0 {
+        "-".to_string()
+    } else {
+        n.to_string()
+    }
+}
+
+/// Why a candidate was suggested, e.g. "owns 3 file(s), 2 open request(s)".
+fn reason(c: &Candidate) -> String {
+    let mut parts = Vec::new();
+    if c.owned_files > 0 {
+        parts.push(format!("owns {} file(s)", c.owned_files));
+    }
+    if c.blamed_lines > 0 {
+        parts.push(format!("wrote {} changed line(s)", c.blamed_lines));
+    }
+    match c.open_requests {
+        Some(n) => parts.push(format!("{} open request(s)", n)),
+        None => parts.push("load unknown".to_string()),
+    }
+    parts.join(", ")
+}
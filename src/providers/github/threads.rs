@@ -0,0 +1,225 @@
+// `git pr unresolved`: the review threads still open on a PR.
+//
+// "All conversations must be resolved" is a common merge blocker, and finding the last
+// open thread in the web UI means scrolling through the whole conversation. The threads
+// are listed with their file, line, the author who opened them and the start of the
+// first comment. `--resolve-all-mine` resolves every unresolved thread the user opened
+// themselves, e.g. once the author addressed a round of their comments.
+//
+// Threads are read through GraphQL (REST has no notion of threads or their resolution).
+// Outdated threads, whose lines changed since, still count as unresolved and are marked.
+use crate::debug_log;
+use crate::links::link;
+use crate::providers::github::models::*;
+use crate::providers::methods::{PrNumber, SourceControlProvider};
+use crate::status_log;
+use crate::table::{self, Shrink};
+use crate::ui_println;
+use colored::Colorize;
+use serde_json::json;
+use std::error::Error;
+use tabled::Tabled;
+
+/// How many characters of the first comment are shown.
+const SNIPPET_LENGTH: usize = 60;
+
+/// An unresolved review thread.
+///
+/// Fields:
+/// - `id`: GraphQL node ID, needed to resolve it
+/// - `path`: File the thread is on
+/// - `line`: Line in the current diff; `None` if outdated or on the whole file
+/// - `outdated`: Whether the commented lines changed since
+/// - `author`: Login of the author of the first comment
+/// - `body`: Text of the first comment
+/// - `url`: The first comment in the web UI
+pub(crate) struct ReviewThread {
+    pub id: String,
+    pub path: String,
+    pub line: Option<u64>,
+    pub outdated: bool,
+    pub author: String,
+    pub body: String,
+    pub url: String,
+}
+
+/// One row of the `unresolved` table.
+#[derive(Tabled)]
+struct ThreadRow {
+    #[tabled(rename = "File")]
+    file: String,
+    #[tabled(rename = "Line")]
+    line: String,
+    #[tabled(rename = "Author")]
+    author: String,
+    #[tabled(rename = "Comment")]
+    comment: String,
+}
+
+/// The first line of a comment, cut to `SNIPPET_LENGTH` characters.
+fn snippet(body: &str) -> String {
+    let first = body.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+    let first = first.trim();
+    if first.chars().count() > SNIPPET_LENGTH {
+        let cut: String = first.chars().take(SNIPPET_LENGTH - 1).collect();
+        format!("{}…", cut)
+    } else {
+        first.to_string()
+    }
+}
+
+impl GitHubProvider {
+    /// Fetches the unresolved review threads of a PR, in the order they were opened.
+    ///
+    /// Threads are paged 100 at a time.
+    pub(crate) fn unresolved_threads(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+    ) -> Result<Vec<ReviewThread>, Box<dyn Error>> {
+        let query = "query($owner: String!, $repo: String!, $number: Int!, $after: String) {
+            repository(owner: $owner, name: $repo) {
+                pullRequest(number: $number) {
+                    reviewThreads(first: 100, after: $after) {
+                        nodes {
+                            id isResolved isOutdated path line
+                            comments(first: 1) { nodes { author { login } body url } }
+                        }
+                        pageInfo { hasNextPage endCursor }
+                    }
+                }
+            }
+        }";
+
+        let mut threads = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let data = self.graphql(
+                query,
+                json!({ "owner": owner, "repo": repo, "number": pr_number.0, "after": after }),
+            )?;
+            let pr = &data["repository"]["pullRequest"];
+            if pr.is_null() {
+                return Err(format!("PR #{} not found", pr_number).into());
+            }
+
+            for node in pr["reviewThreads"]["nodes"]
+                .as_array()
+                .into_iter()
+                .flatten()
+            {
+                if node["isResolved"].as_bool() != Some(false) {
+                    continue;
+                }
+                let comment = &node["comments"]["nodes"][0];
+                threads.push(ReviewThread {
+                    id: node["id"].as_str().unwrap_or_default().to_string(),
+                    path: node["path"].as_str().unwrap_or_default().to_string(),
+                    line: node["line"].as_u64(),
+                    outdated: node["isOutdated"].as_bool() == Some(true),
+                    // Deleted accounts have no author
+                    author: comment["author"]["login"]
+                        .as_str()
+                        .unwrap_or("ghost")
+                        .to_string(),
+                    body: comment["body"].as_str().unwrap_or_default().to_string(),
+                    url: comment["url"].as_str().unwrap_or_default().to_string(),
+                });
+            }
+
+            let page = &pr["reviewThreads"]["pageInfo"];
+            if page["hasNextPage"].as_bool() != Some(true) {
+                break;
+            }
+            after = page["endCursor"].as_str().map(str::to_string);
+        }
+
+        debug_log!(
+            "[DEBUG] {} unresolved thread(s) on PR #{}",
+            threads.len(),
+            pr_number
+        );
+        Ok(threads)
+    }
+
+    /// Marks a review thread as resolved.
+    fn resolve_thread(&self, thread_id: &str) -> Result<(), Box<dyn Error>> {
+        self.graphql(
+            "mutation($id: ID!) { resolveReviewThread(input: {threadId: $id}) { thread { isResolved } } }",
+            json!({ "id": thread_id }),
+        )?;
+        Ok(())
+    }
+
+    /// Lists the unresolved review threads of a PR, optionally resolving the user's own.
+    ///
+    /// # Parameters
+    /// - `resolve_all_mine`: Resolve the unresolved threads opened by the token's user
+    ///   instead of only listing them; the threads left afterwards are listed
+    ///
+    /// # Returns
+    /// - `Err` if the threads can't be fetched or one can't be resolved
+    pub(crate) fn print_unresolved_threads(
+        &self,
+        pr_number: PrNumber,
+        resolve_all_mine: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let mut threads = self.unresolved_threads(&owner, &repo, pr_number)?;
+
+        if resolve_all_mine {
+            let login = self.authenticated_login()?;
+            let (mine, others): (Vec<ReviewThread>, Vec<ReviewThread>) =
+                threads.into_iter().partition(|t| t.author == login);
+            for thread in &mine {
+                self.resolve_thread(&thread.id).map_err(|e| {
+                    format!("Failed to resolve the thread on {}: {}", thread.path, e)
+                })?;
+                debug_log!("[DEBUG] Resolved thread {} on {}", thread.id, thread.path);
+            }
+            status_log!(
+                "✅ Resolved {} thread{} opened by @{}",
+                mine.len(),
+                if mine.len() == 1 { "" } else { "s" },
+                login
+            );
+            threads = others;
+        }
+
+        if threads.is_empty() {
+            status_log!("✅ No unresolved conversations on PR #{}.", pr_number);
+            return Ok(());
+        }
+
+        ui_println!(
+            "{}",
+            format!(
+                "💬 Unresolved conversations on PR #{} ({}):",
+                pr_number,
+                threads.len()
+            )
+            .bold()
+        );
+        let rows: Vec<ThreadRow> = threads
+            .iter()
+            .map(|t| ThreadRow {
+                file: link(&t.path, &t.url),
+                line: match (t.line, t.outdated) {
+                    (_, true) => "outdated".dimmed().to_string(),
+                    (Some(line), false) => line.to_string(),
+                    (None, false) => "-".to_string(),
+                },
+                author: format!("@{}", t.author),
+                comment: snippet(&t.body),
+            })
+            .collect();
+        println!(
+            "{}",
+            table::render(rows, &[(0, Shrink::Truncate), (3, Shrink::Wrap)])
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,219 @@
+// `git pr triage`: a guided session through the untriaged open PRs.
+//
+// A PR is untriaged while it has neither a label nor an assignee. The session shows the
+// oldest one first as a compact summary and asks what to do with it: label, assign,
+// request reviewers, comment, move on or skip. Every PR that was dealt with is recorded
+// locally (see `crate::triage`), so quitting and running the command again picks up
+// where the last session stopped.
+use crate::providers::github::models::*;
+use crate::providers::github::welcome::is_first_time;
+use crate::providers::methods::PrNumber;
+use crate::status_log;
+use crate::triage::{self, Outcome};
+use crate::ui_println;
+use crate::utils::prompt;
+use colored::Colorize;
+use std::error::Error;
+use std::io::{self, IsTerminal};
+
+/// Lines of the PR description shown in the summary.
+const DESCRIPTION_LINES: usize = 3;
+
+/// The actions offered for every PR.
+const ACTIONS: &str = "[l]abel [a]ssign [r]eviewers [c]omment [n]ext [s]kip [q]uit";
+
+impl GitHubProvider {
+    /// Runs a triage session.
+    ///
+    /// # Parameters
+    /// - `restart`: Forget earlier sessions and offer skipped PRs again
+    ///
+    /// # Returns
+    /// - `Err` if stdin isn't a terminal, or if the PR list can't be fetched. Failed
+    ///   actions are reported and the session goes on.
+    pub(crate) fn triage_session(&self, restart: bool) -> Result<(), Box<dyn Error>> {
+        if !io::stdin().is_terminal() {
+            return Err("triage is interactive; run it in a terminal".into());
+        }
+        if restart {
+            triage::reset_progress()?;
+        }
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+
+        let progress = triage::load_progress();
+        let mut untriaged: Vec<BasicGitHubPR> = self
+            .open_pull_requests(&owner, &repo, None)?
+            .into_iter()
+            .filter(|pr| pr.labels.is_empty() && pr.assignees.is_empty())
+            .collect();
+        let handled = untriaged.len();
+        untriaged.retain(|pr| !progress.contains_key(&pr.number.into()));
+        let handled = handled - untriaged.len();
+
+        if untriaged.is_empty() {
+            status_log!(
+                "✅ Nothing to triage: every open PR has a label or an assignee{}.",
+                if handled > 0 {
+                    " or was handled in an earlier session (`--restart` to see those again)"
+                } else {
+                    ""
+                }
+            );
+            return Ok(());
+        }
+
+        // Oldest first: those have waited longest
+        untriaged.sort_by_key(|pr| pr.created_at);
+        status_log!(
+            "🧭 {} untriaged PR{}{}",
+            untriaged.len(),
+            if untriaged.len() == 1 { "" } else { "s" },
+            if handled > 0 {
+                format!(" ({} handled in earlier sessions)", handled)
+            } else {
+                String::new()
+            }
+        );
+
+        let total = untriaged.len();
+        for (i, basic) in untriaged.into_iter().enumerate() {
+            let url = format!(
+                "{}/repos/{}/{}/pulls/{}",
+                self.api_base, owner, repo, basic.number
+            );
+            let pr: GitHubPR = self.get(&url, &format!("Failed to fetch PR #{}", basic.number))?;
+            self.print_triage_summary(i + 1, total, &pr);
+
+            match self.triage_one(&owner, &repo, &pr)? {
+                Some(outcome) => triage::record(pr.number.into(), outcome)?,
+                None => {
+                    ui_println!("💾 Progress saved; run `git pr triage` to resume.");
+                    return Ok(());
+                }
+            }
+        }
+
+        ui_println!("{}", "🎉 All untriaged PRs handled.".green());
+        Ok(())
+    }
+
+    /// Prints the compact summary of a PR.
+    fn print_triage_summary(&self, position: usize, total: usize, pr: &GitHubPR) {
+        println!();
+        println!(
+            "{} {} {}",
+            format!("[{}/{}]", position, total).dimmed(),
+            format!("#{}", pr.number).bold(),
+            pr.title.bold()
+        );
+        ui_println!(
+            "   👤 {} · opened {} · {} → {}",
+            pr.user.login,
+            self.config.format_time(pr.created_at),
+            pr.head.ref_name,
+            pr.base.ref_name
+        );
+        ui_println!(
+            "   📊 {} commit{}, {} file{} changed",
+            pr.commits,
+            if pr.commits == 1 { "" } else { "s" },
+            pr.changed_files,
+            if pr.changed_files == 1 { "" } else { "s" }
+        );
+        if is_first_time(&pr.author_association) {
+            ui_println!("   🌱 First-time contributor");
+        }
+
+        let body = pr.body.as_deref().unwrap_or("");
+        let mut lines = body.lines().map(str::trim).filter(|l| !l.is_empty());
+        for line in lines.by_ref().take(DESCRIPTION_LINES) {
+            println!("   {}", line.dimmed());
+        }
+        if lines.next().is_some() {
+            println!("   {}", "…".dimmed());
+        }
+    }
+
+    /// Asks what to do with one PR until the maintainer moves on.
+    ///
+    /// # Returns
+    /// - `Some(Done)` when moving on after acting on the PR, `Some(Skipped)` when skipping
+    ///   it (or moving on without acting)
+    /// - `None` when the session should stop (`q`, or end of input)
+    fn triage_one(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr: &GitHubPR,
+    ) -> Result<Option<Outcome>, Box<dyn Error>> {
+        let number = pr.number.to_string();
+        let mut acted = false;
+
+        loop {
+            let Some(answer) = prompt(&format!("   {} > ", ACTIONS)) else {
+                println!();
+                return Ok(None);
+            };
+            let result = match answer.to_lowercase().as_str() {
+                "l" | "label" => ask_list("Labels").map(|labels| {
+                    self.add_labels(owner, repo, &number, &labels)
+                        .map(|_| format!("🏷️  Labeled {}", labels.join(", ")))
+                }),
+                "a" | "assign" => ask_list("Assignees").map(|users| {
+                    self.add_assignees(owner, repo, &number, &users)
+                        .map(|_| format!("👤 Assigned {}", users.join(", ")))
+                }),
+                "r" | "reviewers" => ask_list("Reviewers").map(|users| {
+                    self.request_reviewers(owner, repo, PrNumber(pr.number.into()), &users)
+                        .map(|_| format!("👀 Requested review from {}", users.join(", ")))
+                }),
+                "c" | "comment" => {
+                    prompt("   Comment: ")
+                        .filter(|text| !text.is_empty())
+                        .map(|text| {
+                            self.post_comment(owner, repo, &number, &text)
+                                .map(|_| "💬 Commented".to_string())
+                        })
+                }
+                "n" | "next" | "" => {
+                    return Ok(Some(if acted {
+                        Outcome::Done
+                    } else {
+                        Outcome::Skipped
+                    }))
+                }
+                "s" | "skip" => return Ok(Some(Outcome::Skipped)),
+                "q" | "quit" => return Ok(None),
+                other => {
+                    ui_println!("   ❓ Unknown action '{}'", other);
+                    continue;
+                }
+            };
+
+            match result {
+                Some(Ok(done)) => {
+                    acted = true;
+                    ui_println!("   {}", done.green());
+                }
+                Some(Err(e)) => ui_println!("   {} {}", "❌".red(), e),
+                None => println!("   Nothing entered; no change."),
+            }
+        }
+    }
+}
+
+/// Asks for a comma-separated list (labels, logins).
+///
+/// # Returns
+/// - The non-empty entries, or `None` if nothing was entered
+fn ask_list(what: &str) -> Option<Vec<String>> {
+    let answer = prompt(&format!("   {} (comma-separated): ", what))?;
+    let entries: Vec<String> = answer
+        .split(',')
+        .map(|entry| entry.trim().trim_start_matches('@').to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect();
+    (!entries.is_empty()).then_some(entries)
+}
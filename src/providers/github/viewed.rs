@@ -0,0 +1,182 @@
+// Per-file review progress: the "Viewed" checkboxes of the web UI, read and written
+// through GraphQL so long reviews can be tracked from the terminal.
+use crate::debug_log;
+use crate::links::link;
+use crate::providers::github::models::*;
+use crate::providers::methods::PrNumber;
+use crate::table;
+use crate::ui_println;
+use colored::Colorize;
+use serde_json::json;
+use std::error::Error;
+
+/// A changed file together with the viewer's review state for it.
+///
+/// Fields:
+/// - `path`: Repo-relative path of the file
+/// - `additions` / `deletions`: Line counts of the change
+/// - `viewed`: `true` if the viewer marked the file as viewed (and it hasn't changed since)
+/// - `url`: The file at the PR's head in the web UI (empty if the head repository is gone)
+pub(crate) struct FileViewState {
+    pub path: String,
+    pub additions: u64,
+    pub deletions: u64,
+    pub viewed: bool,
+    pub url: String,
+}
+
+impl GitHubProvider {
+    /// Fetches the PR's node ID and the viewed state of every changed file.
+    ///
+    /// Files are paged 100 at a time; GitHub reports `DISMISSED` for files that changed
+    /// after being marked, which counts as unviewed (as in the web UI).
+    pub(crate) fn file_view_states(
+        &self,
+        owner: &str,
+        repo: &str,
+        pr_number: PrNumber,
+    ) -> Result<(String, Vec<FileViewState>), Box<dyn Error>> {
+        let number = pr_number.0;
+        let query = "query($owner: String!, $repo: String!, $number: Int!, $after: String) {
+            repository(owner: $owner, name: $repo) {
+                pullRequest(number: $number) {
+                    id
+                    headRefOid
+                    headRepository { url }
+                    files(first: 100, after: $after) {
+                        nodes { path additions deletions viewerViewedState }
+                        pageInfo { hasNextPage endCursor }
+                    }
+                }
+            }
+        }";
+
+        let mut node_id: Option<String> = None;
+        let mut files = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let data = self.graphql(
+                query,
+                json!({ "owner": owner, "repo": repo, "number": number, "after": after }),
+            )?;
+            let pr = &data["repository"]["pullRequest"];
+            if node_id.is_none() {
+                node_id = pr["id"].as_str().map(str::to_string);
+            }
+
+            // Files are shown as of the head commit, in the repository it lives in
+            let blob_root = match (
+                pr["headRepository"]["url"].as_str(),
+                pr["headRefOid"].as_str(),
+            ) {
+                (Some(repo_url), Some(sha)) => format!("{}/blob/{}/", repo_url, sha),
+                _ => String::new(),
+            };
+            for node in pr["files"]["nodes"].as_array().into_iter().flatten() {
+                let path = node["path"].as_str().unwrap_or_default();
+                files.push(FileViewState {
+                    path: path.to_string(),
+                    additions: node["additions"].as_u64().unwrap_or(0),
+                    deletions: node["deletions"].as_u64().unwrap_or(0),
+                    viewed: node["viewerViewedState"] == "VIEWED",
+                    url: if blob_root.is_empty() {
+                        String::new()
+                    } else {
+                        format!("{}{}", blob_root, path)
+                    },
+                });
+            }
+
+            let page = &pr["files"]["pageInfo"];
+            if page["hasNextPage"].as_bool() != Some(true) {
+                break;
+            }
+            after = page["endCursor"].as_str().map(str::to_string);
+        }
+
+        debug_log!("[DEBUG] {} changed files on PR #{}", files.len(), pr_number);
+        let node_id = node_id.ok_or_else(|| format!("PR #{} not found", pr_number))?;
+        Ok((node_id, files))
+    }
+
+    /// Marks (or unmarks) files as viewed on a PR.
+    pub(crate) fn set_files_viewed(
+        &self,
+        node_id: &str,
+        paths: &[String],
+        viewed: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let mutation = if viewed {
+            "mutation($id: ID!, $path: String!) { markFileAsViewed(input: {pullRequestId: $id, path: $path}) { clientMutationId } }"
+        } else {
+            "mutation($id: ID!, $path: String!) { unmarkFileAsViewed(input: {pullRequestId: $id, path: $path}) { clientMutationId } }"
+        };
+
+        for path in paths {
+            self.graphql(mutation, json!({ "id": node_id, "path": path }))?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints the changed files with their viewed checkbox, followed by the progress line.
+pub(crate) fn print_file_states(files: &[FileViewState]) {
+    let rows: Vec<FileRow> = files
+        .iter()
+        .map(|f| FileRow {
+            viewed: if f.viewed { "[x]" } else { "[ ]" }.to_string(),
+            path: link(&f.path, &f.url),
+            changes: format!("+{} -{}", f.additions, f.deletions),
+        })
+        .collect();
+
+    println!("{}", table::render(rows, &[]));
+    print_view_progress(files);
+}
+
+/// Prints `Viewed: x / y files`, colored by how far along the review is.
+pub(crate) fn print_view_progress(files: &[FileViewState]) {
+    let viewed = files.iter().filter(|f| f.viewed).count();
+    let line = format!("👀 Viewed: {} / {} files", viewed, files.len());
+    if viewed == files.len() {
+        ui_println!("{}", line.green());
+    } else {
+        ui_println!("{}", line.yellow());
+    }
+}
+
+/// Size buckets by changed lines (additions + deletions), as `(upper bound, label)`.
+const SIZES: &[(u64, &str)] = &[(10, "XS"), (30, "S"), (100, "M"), (500, "L"), (1000, "XL")];
+
+/// Classifies a change by its number of changed lines (XS, S, M, L, XL or XXL).
+pub(crate) fn size_label(lines: u64) -> &'static str {
+    SIZES
+        .iter()
+        .find(|(limit, _)| lines < *limit)
+        .map_or("XXL", |(_, label)| label)
+}
+
+/// Prints the size of the change, e.g. `📏 Size: M (+40 -12 in 3 file(s), 1 generated file(s) hidden; ...)`.
+///
+/// # Parameters
+/// - `files`: The files that count, i.e. without the hidden generated ones
+/// - `hidden`: How many generated files were left out
+pub(crate) fn print_change_size(files: &[FileViewState], hidden: usize) {
+    let additions: u64 = files.iter().map(|f| f.additions).sum();
+    let deletions: u64 = files.iter().map(|f| f.deletions).sum();
+    let mut line = format!(
+        "📏 Size: {} (+{} -{} in {} file(s)",
+        size_label(additions + deletions).bold(),
+        additions,
+        deletions,
+        files.len()
+    );
+    if hidden > 0 {
+        line.push_str(&format!(
+            ", {} generated file(s) hidden; --include-generated to list them",
+            hidden
+        ));
+    }
+    line.push(')');
+    ui_println!("{}", line);
+}
@@ -0,0 +1,332 @@
+// `git pr list --watch`: a terminal dashboard of open PRs.
+//
+// Every refresh lists the open PRs and fetches their review and CI state concurrently
+// (see `concurrent`), then redraws the table. Rows are compared with the previous refresh:
+// new PRs, changed verdicts, finished checks and new activity are highlighted and named in
+// the "Change" column, and PRs that disappeared (merged or closed) are listed below.
+// With a review SLA configured (see `sla`), rows are colored by how long they awaited
+// review, and a PR crossing the SLA is announced with the terminal bell; with
+// `--fail-on-sla` the dashboard stops and the command fails instead.
+use crate::config::Config;
+use crate::debug_log;
+use crate::links::link;
+use crate::providers::blocking::Blocking;
+use crate::providers::github::models::*;
+use crate::providers::github::sla::{paint, ReviewSla, SlaLevel};
+use crate::providers::methods::{PrNumber, PullRequestStatus};
+use crate::table::{self, Shrink};
+use crate::ui_eprintln;
+use crate::ui_println;
+use chrono::{DateTime, Local, Utc};
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io::{self, IsTerminal, Write};
+use std::thread;
+use std::time::Duration;
+use tabled::Tabled;
+
+/// One row of the dashboard.
+///
+/// Fields:
+/// - `number`: PR number (green for PRs that are new since the last refresh)
+/// - `title`: PR title
+/// - `author`: PR author
+/// - `updated`: When the PR last changed
+/// - `review`: Current verdict
+/// - `checks`: Overall CI state
+/// - `change`: What changed since the last refresh
+#[derive(Tabled)]
+struct WatchRow {
+    #[tabled(rename = "Number")]
+    number: String,
+    #[tabled(rename = "Title")]
+    title: String,
+    #[tabled(rename = "Author")]
+    author: String,
+    #[tabled(rename = "Updated")]
+    updated: String,
+    #[tabled(rename = "Review")]
+    review: String,
+    #[tabled(rename = "Checks")]
+    checks: String,
+    #[tabled(rename = "Change")]
+    change: String,
+}
+
+/// What one refresh saw of a PR; compared across refreshes to find changes.
+struct Seen {
+    url: String,
+    title: String,
+    author: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    status: Option<PullRequestStatus>,
+}
+
+impl Seen {
+    /// Where the PR stands against the review SLA.
+    fn sla_level(&self, sla: Option<&ReviewSla>) -> SlaLevel {
+        sla.map_or(SlaLevel::Within, |sla| {
+            sla.level(
+                self.created_at,
+                self.status.as_ref().map(|s| s.review.as_str()),
+            )
+        })
+    }
+}
+
+impl GitHubProvider {
+    /// Redraws the list of open PRs every `interval` seconds until interrupted.
+    ///
+    /// # Parameters
+    /// - `base`: Only show PRs targeting this base branch
+    /// - `interval`: Seconds between refreshes
+    /// - `fail_on_sla`: Stop as soon as a PR awaits review past the `review-sla`
+    ///
+    /// # Returns
+    /// - `Err` if the first refresh fails (later failures are shown and retried), or
+    ///   with `fail_on_sla` once a PR is past the SLA.
+    pub(crate) fn watch_pull_requests(
+        &self,
+        base: Option<&str>,
+        interval: u64,
+        fail_on_sla: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Could not parse owner/repo")?;
+        let provider = Blocking::new(self.clone())?;
+        let sla = ReviewSla::from_config(&self.config);
+        let mut previous: Option<BTreeMap<u32, Seen>> = None;
+
+        loop {
+            match self.refresh(&provider, &owner, &repo, base) {
+                Ok(current) => {
+                    draw(
+                        &self.config,
+                        &owner,
+                        &repo,
+                        interval,
+                        &current,
+                        previous.as_ref(),
+                        sla.as_ref(),
+                    );
+                    let breached = announce_breaches(&current, previous.as_ref(), sla.as_ref());
+                    if fail_on_sla && breached > 0 {
+                        return Err(format!(
+                            "{} PR{} awaiting review past the review-sla",
+                            breached,
+                            if breached == 1 { "" } else { "s" }
+                        )
+                        .into());
+                    }
+                    previous = Some(current);
+                }
+                Err(e) if previous.is_none() => return Err(e),
+                Err(e) => ui_eprintln!("⚠️  Refresh failed: {} (retrying in {}s)", e, interval),
+            }
+            thread::sleep(Duration::from_secs(interval));
+        }
+    }
+
+    /// Lists the open PRs and fetches the review/CI state of each.
+    ///
+    /// A PR whose state can't be fetched is still shown, with `?` for its state.
+    fn refresh(
+        &self,
+        provider: &Blocking<GitHubProvider>,
+        owner: &str,
+        repo: &str,
+        base: Option<&str>,
+    ) -> Result<BTreeMap<u32, Seen>, Box<dyn Error>> {
+        let prs = self.open_pull_requests(owner, repo, base)?;
+        let numbers: Vec<PrNumber> = prs.iter().map(|pr| PrNumber(pr.number.into())).collect();
+
+        let mut statuses: BTreeMap<u64, PullRequestStatus> = BTreeMap::new();
+        for (number, result) in provider.pull_request_statuses(&numbers) {
+            match result {
+                Ok(status) => {
+                    statuses.insert(number.0, status);
+                }
+                Err(e) => debug_log!("[DEBUG] No status for PR #{}: {}", number, e),
+            }
+        }
+
+        Ok(prs
+            .into_iter()
+            .map(|pr| {
+                let seen = Seen {
+                    status: statuses.remove(&pr.number.into()),
+                    url: pr.html_url,
+                    title: pr.title,
+                    author: pr.user.login,
+                    created_at: pr.created_at,
+                    updated_at: pr.updated_at,
+                };
+                (pr.number, seen)
+            })
+            .collect())
+    }
+}
+
+/// Clears the screen (on a terminal) and prints the dashboard.
+fn draw(
+    config: &Config,
+    owner: &str,
+    repo: &str,
+    interval: u64,
+    current: &BTreeMap<u32, Seen>,
+    previous: Option<&BTreeMap<u32, Seen>>,
+    sla: Option<&ReviewSla>,
+) {
+    if io::stdout().is_terminal() {
+        // Clear the screen and move the cursor home
+        print!("\x1B[2J\x1B[H");
+    } else if previous.is_some() {
+        println!();
+    }
+
+    ui_println!(
+        "👀 Open PRs in {}/{} · refreshed {} · every {}s (Ctrl-C to stop)",
+        owner,
+        repo,
+        Local::now().format("%H:%M:%S"),
+        interval
+    );
+
+    if current.is_empty() {
+        println!("ℹ️  No open pull requests found.");
+    } else {
+        // Newest activity first
+        let mut prs: Vec<(&u32, &Seen)> = current.iter().collect();
+        prs.sort_by_key(|(_, seen)| std::cmp::Reverse(seen.updated_at));
+
+        let rows: Vec<WatchRow> = prs
+            .into_iter()
+            .map(|(number, seen)| {
+                let row = row(config, *number, seen, previous.map(|p| p.get(number)));
+                paint_row(row, seen.sla_level(sla))
+            })
+            .collect();
+        println!("{}", table::render(rows, &[(1, Shrink::Wrap)]));
+    }
+
+    // PRs that were open last time but aren't anymore
+    if let Some(previous) = previous {
+        let gone: Vec<String> = previous
+            .keys()
+            .filter(|number| !current.contains_key(number))
+            .map(|number| format!("#{}", number))
+            .collect();
+        if !gone.is_empty() {
+            ui_println!("➖ No longer open: {}", gone.join(", ").dimmed());
+        }
+    }
+    let _ = io::stdout().flush();
+}
+
+/// Builds a dashboard row, highlighting what differs from the previous refresh.
+///
+/// # Parameters
+/// - `config`: Decides how the update time is shown (`timestamps`, `date-format`)
+/// - `before`: `None` on the first refresh (nothing is highlighted), `Some(None)` for a
+///   PR that wasn't open last time, `Some(Some(seen))` otherwise
+fn row(config: &Config, number: u32, seen: &Seen, before: Option<Option<&Seen>>) -> WatchRow {
+    let review = seen.status.as_ref().map_or("?", |s| s.review.as_str());
+    let checks = seen.status.as_ref().map_or("?", |s| s.checks.as_str());
+    let mut row = WatchRow {
+        number: link(&format!("#{}", number), &seen.url),
+        title: seen.title.clone(),
+        author: seen.author.clone(),
+        updated: config.format_time(seen.updated_at),
+        review: review.to_string(),
+        checks: checks.to_string(),
+        change: String::new(),
+    };
+
+    match before {
+        None => {}
+        Some(None) => {
+            row.number = row.number.green().bold().to_string();
+            row.change = "new".green().to_string();
+        }
+        Some(Some(old)) => {
+            let old_review = old.status.as_ref().map_or("?", |s| s.review.as_str());
+            let old_checks = old.status.as_ref().map_or("?", |s| s.checks.as_str());
+            let mut changes = Vec::new();
+            if old_review != review {
+                row.review = row.review.yellow().bold().to_string();
+                changes.push(format!("review: {} → {}", old_review, review));
+            }
+            if old_checks != checks {
+                row.checks = row.checks.yellow().bold().to_string();
+                changes.push(format!("checks: {} → {}", old_checks, checks));
+            }
+            if old.title != seen.title {
+                row.title = row.title.yellow().to_string();
+                changes.push("title".to_string());
+            }
+            if old.updated_at != seen.updated_at && changes.is_empty() {
+                row.updated = row.updated.yellow().to_string();
+                changes.push("activity".to_string());
+            }
+            row.change = changes.join(", ").yellow().to_string();
+        }
+    }
+    row
+}
+
+/// Colors a dashboard row by SLA level. Cells highlighted as changed keep their color.
+fn paint_row(row: WatchRow, level: SlaLevel) -> WatchRow {
+    WatchRow {
+        number: paint(level, &row.number),
+        title: paint(level, &row.title),
+        author: paint(level, &row.author),
+        updated: paint(level, &row.updated),
+        ..row
+    }
+}
+
+/// Announces the PRs that went past the review SLA since the previous refresh (all of
+/// them on the first one), ringing the terminal bell.
+///
+/// # Returns
+/// - The number of PRs currently past the SLA
+fn announce_breaches(
+    current: &BTreeMap<u32, Seen>,
+    previous: Option<&BTreeMap<u32, Seen>>,
+    sla: Option<&ReviewSla>,
+) -> usize {
+    let breached: Vec<u32> = current
+        .iter()
+        .filter(|(_, seen)| seen.sla_level(sla) == SlaLevel::Breached)
+        .map(|(number, _)| *number)
+        .collect();
+    let new: Vec<String> = breached
+        .iter()
+        .filter(|number| {
+            previous
+                .and_then(|p| p.get(number))
+                .is_none_or(|old| old.sla_level(sla) != SlaLevel::Breached)
+        })
+        .map(|number| format!("#{}", number))
+        .collect();
+
+    if !new.is_empty() {
+        let bell = if io::stdout().is_terminal() {
+            "\x07"
+        } else {
+            ""
+        };
+        ui_println!(
+            "{}{}",
+            format!("🚨 Past the review SLA: {}", new.join(", "))
+                .red()
+                .bold(),
+            bell
+        );
+        let _ = io::stdout().flush();
+    }
+    breached.len()
+}
@@ -0,0 +1,353 @@
+// Webhook listener of `git pr serve --webhook <addr>`: keeps the local PR cache fresh.
+//
+// With `cache-ttl` set, commands reuse fetched PR data for a while (see `cache.rs`). A
+// repository webhook pointed at this listener (content type `application/json`, the same
+// secret as `GIT_PR_WEBHOOK_SECRET`) drops a PR's cached data as soon as GitHub reports a
+// change to it, so every command on the machine sees the change right away without
+// refetching what didn't change.
+//
+// Only deliveries whose `X-Hub-Signature-256` is the HMAC-SHA256 of the body under the
+// secret are acted on; the listener refuses to start without a secret. Deliveries for
+// another repository are acknowledged and ignored. The affected PRs are read from the
+// payload: `pull_request` (PR, review, review comment and thread events), `issue`
+// (comments and labels on the conversation), and the `pull_requests` of check runs and
+// suites. Other events (`ping`, `push`, ...) are acknowledged without dropping anything.
+//
+// The listener speaks just enough HTTP/1.1 for webhook deliveries: one POST per
+// connection, answered with a status and no body. Connections are handled by a fixed pool
+// of `WORKERS` threads; each must send its whole delivery within `READ_TIMEOUT`, so a slow
+// client can't hold up the others for long, and a flood of connections waits in the
+// accept queue instead of spawning threads. A body is only read once the headers carry a
+// well-formed signature. Put it behind a reverse proxy or a tunnel (`smee`, `ngrok`) to
+// receive deliveries from github.com.
+use crate::cache;
+use crate::debug_log;
+use crate::ui_eprintln;
+use hmac::{Hmac, Mac};
+use serde_json::Value;
+use sha2::Sha256;
+use std::error::Error;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Environment variable holding the webhook secret.
+pub(crate) const SECRET_ENV: &str = "GIT_PR_WEBHOOK_SECRET";
+
+/// Largest delivery read (1 MB). The events acted on carry a single PR, issue or check
+/// run and stay far below it; bigger ones (pushes with many commits, up to GitHub's 25 MB)
+/// are refused rather than buffered.
+const MAX_BODY: usize = 1024 * 1024;
+
+/// How many deliveries are handled at once.
+const WORKERS: usize = 8;
+
+/// How long a client may take to send its whole delivery.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Whether `signature` (`sha256=<hex>`) is the HMAC-SHA256 of `body` under `secret`.
+///
+/// The comparison takes the same time whatever the signature, so it can't be guessed
+/// byte by byte.
+fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(hex) = signature_hex(signature) else {
+        return false;
+    };
+    let Some(expected) = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()
+    else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// The hex digest of a well-formed signature: `sha256=` and 64 hex digits.
+fn signature_hex(signature: &str) -> Option<&str> {
+    signature
+        .trim()
+        .strip_prefix("sha256=")
+        .filter(|hex| hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// The PRs an event is about (see the module comment).
+fn affected_prs(payload: &Value) -> Vec<u64> {
+    let mut prs: Vec<u64> = [&payload["pull_request"], &payload["issue"]]
+        .iter()
+        .filter_map(|object| object["number"].as_u64())
+        .collect();
+    for checks in [&payload["check_run"], &payload["check_suite"]] {
+        if let Some(linked) = checks["pull_requests"].as_array() {
+            prs.extend(linked.iter().filter_map(|pr| pr["number"].as_u64()));
+        }
+    }
+    prs.sort_unstable();
+    prs.dedup();
+    prs
+}
+
+/// Starts listening on `addr` in the background.
+///
+/// # Parameters
+/// - `addr`: Address to bind (`127.0.0.1:8787`; `:8787` binds every interface)
+/// - `secret`: The webhook's secret
+/// - `full_name`: `owner/repo` of the repository whose events are acted on
+///
+/// # Returns
+/// - `Err` if the address can't be bound; deliveries that fail are only logged
+pub(crate) fn spawn_listener(
+    addr: &str,
+    secret: String,
+    full_name: String,
+) -> Result<thread::JoinHandle<()>, Box<dyn Error>> {
+    let addr = if addr.starts_with(':') {
+        format!("0.0.0.0{}", addr)
+    } else {
+        addr.to_string()
+    };
+    let listener = TcpListener::bind(&addr)
+        .map_err(|e| format!("Can't listen for webhooks on {}: {}", addr, e))?;
+    ui_eprintln!("🪝 Listening for webhook deliveries on {}", addr);
+
+    // Accepting blocks while every worker is busy, so connections queue up in the backlog
+    let (connections, incoming) = mpsc::sync_channel::<TcpStream>(0);
+    let incoming = Arc::new(Mutex::new(incoming));
+    for _ in 0..WORKERS {
+        let (incoming, secret, full_name) =
+            (Arc::clone(&incoming), secret.clone(), full_name.clone());
+        thread::spawn(move || loop {
+            let next = incoming.lock().map(|incoming| incoming.recv());
+            let Ok(Ok(stream)) = next else {
+                return;
+            };
+            if let Err(e) = handle_delivery(stream, &secret, &full_name) {
+                debug_log!("[DEBUG] Webhook delivery failed: {}", e);
+            }
+        });
+    }
+    Ok(thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    if connections.send(stream).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => debug_log!("[DEBUG] Webhook connection failed: {}", e),
+            }
+        }
+    }))
+}
+
+/// Reads from a connection until a deadline for the whole delivery, not just each read.
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let left = self.deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "delivery not received in time",
+            ));
+        }
+        self.stream.set_read_timeout(Some(left))?;
+        self.stream.read(buf)
+    }
+}
+
+/// Reads one delivery, acts on it and answers with a status line.
+fn handle_delivery(stream: TcpStream, secret: &str, full_name: &str) -> io::Result<()> {
+    let mut reader = BufReader::new(DeadlineReader {
+        stream: &stream,
+        deadline: Instant::now() + READ_TIMEOUT,
+    });
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let is_post = line.starts_with("POST ");
+
+    let mut length = 0;
+    let mut signature = None;
+    let mut event = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => length = value.parse().unwrap_or(usize::MAX),
+            "x-hub-signature-256" => signature = Some(value),
+            "x-github-event" => event = value,
+            _ => {}
+        }
+    }
+
+    let status = if !is_post {
+        "405 Method Not Allowed"
+    } else if signature.as_deref().and_then(signature_hex).is_none() {
+        ui_eprintln!("⚠️  Rejected a webhook delivery with a missing or wrong signature");
+        "401 Unauthorized"
+    } else if length > MAX_BODY {
+        "413 Payload Too Large"
+    } else {
+        // Grow the body with what actually arrives rather than trusting Content-Length
+        let mut body = Vec::new();
+        (&mut reader).take(length as u64).read_to_end(&mut body)?;
+        if body.len() < length {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        process_delivery(&body, signature.as_deref(), &event, secret, full_name)
+    };
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    )?;
+    stream.flush()
+}
+
+/// Verifies a delivery and drops the cached data of the PRs it is about.
+///
+/// # Returns
+/// - The HTTP status to answer with
+fn process_delivery(
+    body: &[u8],
+    signature: Option<&str>,
+    event: &str,
+    secret: &str,
+    full_name: &str,
+) -> &'static str {
+    if !signature.is_some_and(|signature| verify_signature(secret, body, signature)) {
+        ui_eprintln!("⚠️  Rejected a webhook delivery with a missing or wrong signature");
+        return "401 Unauthorized";
+    }
+    let Ok(payload) = serde_json::from_slice::<Value>(body) else {
+        return "400 Bad Request";
+    };
+    let repository = payload["repository"]["full_name"].as_str().unwrap_or("");
+    if !repository.eq_ignore_ascii_case(full_name) {
+        debug_log!("[DEBUG] Ignoring a {} event of {}", event, repository);
+        return "202 Accepted";
+    }
+    let prs = affected_prs(&payload);
+    debug_log!("[DEBUG] Webhook {} event about PR(s) {:?}", event, prs);
+    for pr in prs {
+        cache::invalidate(pr);
+    }
+    "204 No Content"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// The `X-Hub-Signature-256` GitHub would send for `body` under `secret`.
+    fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        let hex: String = mac
+            .finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        format!("sha256={}", hex)
+    }
+
+    #[test]
+    fn good_signature_verifies() {
+        let body = br#"{"zen":"Keep it logically awesome."}"#;
+        assert!(verify_signature("s3cret", body, &sign("s3cret", body)));
+    }
+
+    #[test]
+    fn bad_signature_is_rejected() {
+        let body = br#"{"action":"opened"}"#;
+        assert!(!verify_signature("s3cret", body, &sign("other", body)));
+        assert!(!verify_signature("s3cret", b"{}", &sign("s3cret", body)));
+        assert!(!verify_signature("s3cret", body, "sha256=zz"));
+        assert!(!verify_signature(
+            "s3cret",
+            body,
+            &sign("s3cret", body).replace("sha256=", "sha1=")
+        ));
+    }
+
+    #[test]
+    fn missing_signature_is_rejected() {
+        let body = br#"{"action":"opened"}"#;
+        assert!(!verify_signature("s3cret", body, ""));
+        assert_eq!(
+            process_delivery(body, None, "pull_request", "s3cret", "o/r"),
+            "401 Unauthorized"
+        );
+    }
+
+    #[test]
+    fn affected_prs_reads_every_kind_of_event() {
+        assert_eq!(affected_prs(&json!({"pull_request": {"number": 7}})), [7]);
+        assert_eq!(affected_prs(&json!({"issue": {"number": 3}})), [3]);
+        assert_eq!(
+            affected_prs(&json!({
+                "check_run": {"pull_requests": [{"number": 9}, {"number": 2}]},
+                "check_suite": {"pull_requests": [{"number": 2}]},
+            })),
+            [2, 9]
+        );
+        assert!(affected_prs(&json!({"zen": "ping", "hook_id": 1})).is_empty());
+    }
+
+    #[test]
+    fn only_well_formed_signatures_pass() {
+        let hex = "0123456789abcdef".repeat(4);
+        assert_eq!(
+            signature_hex(&format!("sha256={}", hex)),
+            Some(hex.as_str())
+        );
+        assert_eq!(signature_hex(&format!("sha1={}", hex)), None);
+        assert_eq!(signature_hex(&format!("sha256={}0", hex)), None);
+        assert_eq!(
+            signature_hex(&format!("sha256={}", hex.replace('a', "g"))),
+            None
+        );
+        assert_eq!(signature_hex(&format!("sha256={}é", &hex[..62])), None);
+    }
+
+    #[test]
+    fn malformed_signature_is_refused_before_the_body() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        // Announces a body it never sends: only answering from the headers gets a reply
+        client
+            .write_all(
+                b"POST / HTTP/1.1\r\nContent-Length: 100\r\nX-Hub-Signature-256: sha256=zz\r\n\r\n",
+            )
+            .unwrap();
+        let (stream, _) = listener.accept().unwrap();
+        handle_delivery(stream, "s3cret", "o/r").unwrap();
+
+        let mut answer = String::new();
+        client.read_to_string(&mut answer).unwrap();
+        assert!(
+            answer.starts_with("HTTP/1.1 401 Unauthorized"),
+            "{}",
+            answer
+        );
+    }
+}
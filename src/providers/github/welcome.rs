@@ -0,0 +1,103 @@
+// First-time contributors, and `git pr welcome`.
+//
+// GitHub tells how a PR's author relates to the repository (`author_association`); PRs
+// from people who never contributed before are flagged in `list` and `show-details` so
+// maintainers can give them some extra attention. `git pr welcome` posts a friendly
+// comment built from the `welcome-template` setting. The comment carries a hidden marker,
+// so running the command twice doesn't greet the same contributor twice.
+use crate::compose;
+use crate::debug_log;
+use crate::providers::github::models::*;
+use crate::providers::methods::PrNumber;
+use crate::status_log;
+use crate::utils::render_template;
+use colored::Colorize;
+use reqwest::Method;
+use serde_json::json;
+use std::error::Error;
+
+/// Hidden marker appended to welcome comments, to recognize them later.
+const WELCOME_MARKER: &str = "<!-- git-pr:welcome -->";
+
+/// Whether an `author_association` marks a first-time contributor.
+///
+/// `FIRST_TIMER` is someone new to GitHub altogether, `FIRST_TIME_CONTRIBUTOR` someone new
+/// to this repository.
+pub(crate) fn is_first_time(association: &str) -> bool {
+    matches!(association, "FIRST_TIME_CONTRIBUTOR" | "FIRST_TIMER")
+}
+
+impl GitHubProvider {
+    /// Posts the welcome comment on a PR from a first-time contributor.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The PR to comment on
+    /// - `force`: Post even if the author isn't a first-time contributor or was welcomed already
+    ///
+    /// # Returns
+    /// - `Err` if the author isn't a first-timer or was welcomed already (without `force`),
+    ///   or if an API call failed.
+    pub(crate) fn welcome_contributor(
+        &self,
+        pr_number: PrNumber,
+        force: bool,
+    ) -> Result<(), Box<dyn Error>> {
+        let (owner, repo) = self
+            .infer_repo_details()
+            .ok_or("Failed to infer repository details from remote URL")?;
+        let pr = self.fetch_pull_request(&owner, &repo, pr_number)?;
+        let author = pr.user.login.as_str();
+
+        if !force && !is_first_time(&pr.author_association) {
+            return Err(format!(
+                "@{} isn't a first-time contributor ({}); use --force to welcome them anyway",
+                author,
+                pr.author_association.to_lowercase().replace('_', " ")
+            )
+            .into());
+        }
+
+        let url = format!(
+            "{}/repos/{}/{}/issues/{}/comments",
+            self.api_base, owner, repo, pr_number
+        );
+        if !force {
+            let comments: Vec<GitHubComment> =
+                self.get(&format!("{}?per_page=100", url), "Failed to fetch comments")?;
+            if comments.iter().any(|c| c.body.contains(WELCOME_MARKER)) {
+                return Err(format!(
+                    "@{} was already welcomed on PR #{}; use --force to post again",
+                    author, pr_number
+                )
+                .into());
+            }
+        }
+
+        let template = compose::load_welcome_template(self.config.welcome_template.as_deref());
+        let number = pr_number.to_string();
+        let full_name = format!("{}/{}", owner, repo);
+        let message = render_template(
+            &template,
+            &[
+                ("author", author),
+                ("number", &number),
+                ("title", &pr.title),
+                ("repo", &full_name),
+            ],
+        );
+        debug_log!("[DEBUG] Welcome comment: {}", message);
+
+        self.send(
+            self.authed(Method::POST, &url).json(&json!({
+                "body": format!("{}\n\n{}", message.trim_end(), WELCOME_MARKER)
+            })),
+            "Failed to post welcome comment",
+        )?;
+        status_log!(
+            "🌱 Welcomed {} on PR #{}",
+            format!("@{}", author).green(),
+            pr_number
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,1362 @@
+// Import the standard library's error trait for use in returning error types.
+use async_trait::async_trait;
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// A validated pull request number.
+///
+/// Parsed once at the CLI boundary (accepting `42` or `#42`), so provider methods never
+/// interpolate unchecked user input into API URLs. `Display` prints the bare number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PrNumber(pub u64);
+
+impl FromStr for PrNumber {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.trim();
+        let digits = digits.strip_prefix('#').unwrap_or(digits);
+        match parse_digits(digits) {
+            Some(number) if number > 0 => Ok(PrNumber(number)),
+            _ => Err(format!(
+                "invalid PR number '{}': expected a positive number like 42 or #42",
+                s
+            )),
+        }
+    }
+}
+
+/// Parses a number written only with ASCII digits (no sign, no spaces).
+fn parse_digits(s: &str) -> Option<u64> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+impl fmt::Display for PrNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Outcome of one `git pr doctor` check.
+///
+/// Fields:
+/// - `name`: What was checked (e.g. "Token")
+/// - `ok`: Whether the check passed
+/// - `detail`: What was found (version, login, error message)
+/// - `fix`: How to resolve a failure
+pub struct Diagnostic {
+    pub name: String,
+    pub ok: bool,
+    pub detail: String,
+    pub fix: Option<String>,
+}
+
+impl Diagnostic {
+    /// A passing check.
+    pub fn pass(name: &str, detail: impl Into<String>) -> Self {
+        Diagnostic {
+            name: name.to_string(),
+            ok: true,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    /// A failing check with a suggested fix.
+    pub fn fail(name: &str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Diagnostic {
+            name: name.to_string(),
+            ok: false,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Porcelain format versions this binary can print, accepted by `--porcelain=<version>`.
+pub const PORCELAIN_VERSIONS: [&str; 1] = ["v1"];
+
+/// Filters applied when listing pull requests.
+///
+/// Grouping the filters in one struct keeps the trait signature stable as new
+/// list options are added.
+///
+/// Fields:
+/// - `base`: Only list PRs targeting this base branch
+/// - `porcelain`: Print the stable `--porcelain` format instead of the table
+/// - `watch`: Keep refreshing the list every this many seconds (`--watch`)
+/// - `labels`: Only list PRs carrying all of these labels (`--label`)
+/// - `state`: `open` (the default), `closed` or `all`
+/// - `sort`: `created` (the default) or `updated`, newest first
+/// - `milestone`: Only list PRs planned for this milestone (`--milestone`)
+/// - `mergeable`: Only list PRs that are approved, passing required checks and
+///   conflict-free (`--mergeable`)
+/// - `columns`: Table columns, in order (`--columns`); falls back to `list-columns`
+/// - `fail_on_sla`: Fail when a PR awaits review past the `review-sla` (`--fail-on-sla`)
+/// - `owned_by_me`: Only list PRs touching paths the user owns per CODEOWNERS or
+///   `owned-paths` (`--owned-by-me`)
+#[derive(Default)]
+pub struct ListOptions {
+    pub base: Option<String>,
+    pub porcelain: bool,
+    pub watch: Option<u64>,
+    pub labels: Vec<String>,
+    pub state: Option<String>,
+    pub sort: Option<String>,
+    pub milestone: Option<String>,
+    pub mergeable: bool,
+    pub columns: Option<String>,
+    pub fail_on_sla: bool,
+    pub owned_by_me: bool,
+}
+
+/// How `git pr diff-stats` groups a PR's changes.
+///
+/// Fields:
+/// - `depth`: Directory levels kept when grouping (`src/providers` at 2)
+/// - `include_generated`: Count lockfiles, vendored and other generated files too
+pub struct DiffStatsOptions {
+    pub depth: usize,
+    pub include_generated: bool,
+}
+
+/// Where `git pr coverage` finds the report and when it fails.
+///
+/// Fields:
+/// - `artifact`: Name of the Actions artifact with the report (`--artifact`)
+/// - `fail_under`: Fail if the changed-lines coverage is below this percentage
+#[derive(Default)]
+pub struct CoverageOptions {
+    pub artifact: Option<String>,
+    pub fail_under: Option<f64>,
+}
+
+/// What `git pr run` executes against a PR.
+///
+/// Fields:
+/// - `command`: The program and its arguments (everything after `--`)
+/// - `keep`: Leave the worktree in place afterwards, for a closer look
+pub struct RunOptions {
+    pub command: Vec<String>,
+    pub keep: bool,
+}
+
+/// How `git pr bisect` searches a PR's commits for the one that broke it.
+///
+/// Fields:
+/// - `command`: The test command and its arguments (everything after `--`)
+/// - `check_base`: Run the command on the PR's base first and stop if it fails there too
+/// - `keep`: Leave the worktree in place afterwards
+pub struct BisectOptions {
+    pub command: Vec<String>,
+    pub check_base: bool,
+    pub keep: bool,
+}
+
+/// How `git pr pull` checks out a pull request.
+///
+/// Fields:
+/// - `recurse_submodules`: Update the submodules to the commits the PR records
+///   (`--recurse-submodules`, or git's `submodule.recurse`)
+/// - `depth`: Fetch only this many commits of the PR's history (`--depth`)
+/// - `filter`: Partial-clone filter for the fetch, e.g. `blob:none` (`--filter`)
+#[derive(Default)]
+pub struct PullOptions {
+    pub recurse_submodules: bool,
+    pub depth: Option<u32>,
+    pub filter: Option<String>,
+}
+
+impl PullOptions {
+    /// The `git fetch` arguments selecting how much to download.
+    pub fn fetch_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(depth) = self.depth {
+            args.push(format!("--depth={}", depth));
+        }
+        if let Some(filter) = &self.filter {
+            args.push(format!("--filter={}", filter));
+        }
+        args
+    }
+}
+
+/// How `git pr serve` runs.
+///
+/// Fields:
+/// - `webhook`: Address to receive webhook deliveries on, which drop stale cached PR data
+///   (`--webhook`)
+/// - `webhook_only`: Only run the webhook listener, without JSON-RPC on stdio
+#[derive(Default)]
+pub struct ServeOptions {
+    pub webhook: Option<String>,
+    pub webhook_only: bool,
+}
+
+/// How `git pr merge` merges a pull request.
+///
+/// Fields:
+/// - `method`: `merge`, `squash` or `rebase`; falls back to `merge-strategy`, then `merge`
+/// - `require_label`: A label PRs touching sensitive paths must carry (`--require-label`)
+/// - `dry_run`: Only run the pre-flight
+/// - `override_freeze`: Merge even though the base branch is frozen (`--override`)
+/// - `confirm`: The PR number, confirmed up front for high-risk repositories (`--confirm`)
+/// - `expected_head`: The head commit the caller checked; the merge is refused if the PR's
+///   head has moved on since
+#[derive(Default)]
+pub struct MergeOptions {
+    pub method: Option<String>,
+    pub require_label: Option<String>,
+    pub dry_run: bool,
+    pub override_freeze: bool,
+    pub confirm: Option<PrNumber>,
+    pub expected_head: Option<String>,
+}
+
+/// What `git pr autopilot` does with PRs from trusted automation.
+///
+/// Fields:
+/// - `authors`: Authors whose open PRs are considered (`--author`, repeatable)
+/// - `only_patch`: Only PRs changing nothing but dependency versions in the manifests read
+///   here, each by a patch upgrade (`--only-patch`)
+/// - `approve`: Approve the qualifying PRs (`--approve`)
+/// - `merge`: Merge the qualifying PRs after the merge pre-flight (`--merge`)
+/// - `method`: Merge method for `--merge`; falls back to `merge-strategy`, then `merge`
+#[derive(Default)]
+pub struct AutopilotOptions {
+    pub authors: Vec<String>,
+    pub only_patch: bool,
+    pub approve: bool,
+    pub merge: bool,
+    pub method: Option<String>,
+}
+
+/// Whose PRs `git pr bots` shows and whether it asks for rebases.
+///
+/// Fields:
+/// - `authors`: Bot accounts to show (`--author`, repeatable); all `[bot]` accounts if empty
+/// - `rebase`: Ask the bot to rebase every listed PR (`--rebase`)
+#[derive(Default)]
+pub struct BotsOptions {
+    pub authors: Vec<String>,
+    pub rebase: bool,
+}
+
+/// What `git pr comment` posts.
+///
+/// Fields:
+/// - `message`: The comment text (`-m`)
+/// - `attachments`: Files uploaded and embedded below the text (`--attach`)
+/// - `path`: File to comment on inline instead of on the conversation (`--file`)
+/// - `lines`: The lines of `path` the comment is on (`--line-range`)
+/// - `side`: Side of the diff of the last line, `right` (the PR's version) or `left`
+///   (the base's, for removed lines) (`--side`)
+/// - `start_side`: Side of the first line, if different (`--start-side`)
+#[derive(Default)]
+pub struct CommentOptions {
+    pub message: Option<String>,
+    pub attachments: Vec<PathBuf>,
+    pub path: Option<String>,
+    pub lines: Option<LineRange>,
+    pub side: String,
+    pub start_side: Option<String>,
+}
+
+/// Sides of a diff an inline comment can be on (`--side`, `--start-side`).
+pub const DIFF_SIDES: [&str; 2] = ["right", "left"];
+
+/// The lines an inline comment is on: one line (`12`) or a range (`10-14`).
+///
+/// Fields:
+/// - `start`: First line
+/// - `end`: Last line, the one GitHub anchors the comment at (`start` for a single line)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LineRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl LineRange {
+    /// Whether the range is a single line (sent without `start_line`).
+    pub fn is_single(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl FromStr for LineRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "invalid line range '{}': expected a line like 42 or a range like 40-45",
+                s
+            )
+        };
+        let (start, end) = s.split_once('-').unwrap_or((s, s));
+        let start = parse_digits(start.trim()).ok_or_else(invalid)?;
+        let end = parse_digits(end.trim()).ok_or_else(invalid)?;
+        if start == 0 || end < start {
+            return Err(invalid());
+        }
+        Ok(LineRange { start, end })
+    }
+}
+
+impl fmt::Display for LineRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_single() {
+            write!(f, "{}", self.start)
+        } else {
+            write!(f, "{}-{}", self.start, self.end)
+        }
+    }
+}
+
+/// An inline review comment given on the command line as `FILE:LINE:TEXT` (or
+/// `FILE:START-END:TEXT` for several lines).
+///
+/// Fields:
+/// - `path`: File the comment is on, relative to the repository root
+/// - `lines`: Line(s) of the file (as of the reviewed commit) the comment is on
+/// - `body`: The comment text
+#[derive(Clone, Debug)]
+pub struct InlineComment {
+    pub path: String,
+    pub lines: LineRange,
+    pub body: String,
+}
+
+impl FromStr for InlineComment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            format!(
+                "invalid comment '{}': expected FILE:LINE:TEXT or FILE:START-END:TEXT, e.g. src/lib.rs:42:Needs a test",
+                s
+            )
+        };
+        let mut parts = s.splitn(3, ':');
+        let path = parts.next().unwrap_or("").trim();
+        let lines = parts.next().and_then(|l| l.parse::<LineRange>().ok());
+        let body = parts.next().unwrap_or("").trim();
+        match lines {
+            Some(lines) if !path.is_empty() && !body.is_empty() => Ok(InlineComment {
+                path: path.to_string(),
+                lines,
+                body: body.to_string(),
+            }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// What `git pr review-commit` submits along with a commit.
+///
+/// Fields:
+/// - `message`: Review summary (`-m`)
+/// - `comments`: Inline comments on lines of the commit (`--comment`)
+#[derive(Default)]
+pub struct CommitReviewOptions {
+    pub message: Option<String>,
+    pub comments: Vec<InlineComment>,
+}
+
+/// Methods accepted by `merge --method` and the `merge-strategy` setting.
+pub const MERGE_METHODS: [&str; 3] = ["merge", "squash", "rebase"];
+
+/// States accepted by `list --state`.
+pub const LIST_STATES: [&str; 3] = ["open", "closed", "all"];
+
+/// Orders accepted by `list --sort`.
+pub const LIST_SORTS: [&str; 2] = ["created", "updated"];
+
+/// How `show-diff` presents a PR's diff.
+///
+/// Fields:
+/// - `raw`: Print the diff to stdout instead of a pager
+/// - `blame`: Annotate removed lines with who last touched them (`--blame`)
+/// - `include_generated`: Keep generated and vendored files (`--include-generated`)
+#[derive(Default)]
+pub struct DiffOptions {
+    pub raw: bool,
+    pub blame: bool,
+    pub include_generated: bool,
+}
+
+/// Which checks `git pr lint` runs.
+///
+/// Fields:
+/// - `require_dco`: Every commit must be signed off by its author (`--require-dco`)
+/// - `body`: The description must be complete: working relative links, the template's
+///   sections and mandatory checkboxes, no TODO markers (`--body`)
+#[derive(Default)]
+pub struct LintOptions {
+    pub require_dco: bool,
+    pub body: bool,
+}
+
+/// Review and CI state of a pull request, as shown by `list --watch`.
+///
+/// Fields:
+/// - `review`: `approved`, `changes requested` or `pending`
+/// - `checks`: Overall CI state (`success`, `failure`, `pending`, `neutral`), `-` if none
+#[derive(Clone, PartialEq, Eq)]
+pub struct PullRequestStatus {
+    pub review: String,
+    pub checks: String,
+}
+
+/// Inputs for creating a pull request.
+///
+/// Anything left as `None` is derived automatically (base from the repository's default
+/// branch, title/body from the branch's commits and the PR template).
+///
+/// Fields:
+/// - `base`: Branch to merge into
+/// - `title`: PR title
+/// - `body`: PR description
+/// - `draft`: Open the PR as a draft
+/// - `edit`: Open `$EDITOR` on the pre-filled title/body before submitting
+/// - `fill_issue`: Pre-fill title, closing keyword and labels from the referenced issue
+/// - `fork`: Push to the user's fork (creating it if needed) instead of the upstream remote
+#[derive(Default)]
+pub struct CreateOptions {
+    pub base: Option<String>,
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub draft: bool,
+    pub edit: bool,
+    pub fill_issue: bool,
+    pub fork: bool,
+}
+
+/// What to do with a pending review in `pending_review_action`.
+///
+/// Variants:
+/// - `Resume`: Continue editing a local draft (or show where a server-side one stands)
+/// - `Submit`: Submit it, optionally overriding the review event
+/// - `Discard`: Throw it away
+pub enum PendingReviewAction {
+    Resume,
+    Submit { event: Option<String> },
+    Discard,
+}
+
+/// What to do in `scoped_review` (`git pr scope`).
+///
+/// Variants:
+/// - `Show`: List the recorded verdicts and the changed files none of them covers
+/// - `Set`: Record (or replace) the verdict on a directory or file, with an optional note
+/// - `Submit`: Compile the verdicts into one review and submit it, with an optional summary
+/// - `Discard`: Forget the recorded verdicts
+pub enum ScopeAction {
+    Show,
+    Set {
+        path: String,
+        event: String,
+        note: Option<String>,
+    },
+    Submit {
+        summary: Option<String>,
+    },
+    Discard,
+}
+
+/// What to do in `freeze` (`git pr freeze`); `branch` defaults to the default branch.
+///
+/// Variants:
+/// - `Status`: Show whether the branch is frozen
+/// - `Start`: Freeze merges into the branch, with a reason and an optional end date
+/// - `End`: Lift the freeze
+pub enum FreezeAction {
+    Status {
+        branch: Option<String>,
+    },
+    Start {
+        branch: Option<String>,
+        reason: String,
+        until: Option<String>,
+    },
+    End {
+        branch: Option<String>,
+    },
+}
+
+/// A trait defining a common interface for interacting with source control providers.
+///
+/// This trait abstracts operations that a source control provider (like GitHub, GitLab, Bitbucket)
+/// should support for managing pull requests (PRs). It enables writing generic code that can
+/// work with multiple providers through a single interface.
+///
+/// The trait's methods typically return `Result` types to handle success or error scenarios gracefully.
+///
+/// # Design notes:
+/// - The trait uses `&self` (shared reference) implying these operations don't require mutable state,
+///   or the implementation manages interior mutability internally.
+/// - The error type is boxed `dyn Error` to allow flexibility in error handling and avoid
+///   binding to a specific error enum, simplifying interoperability.
+/// - The trait is focused on PR workflows: submitting reviews, listing PRs, closing PRs, and showing details.
+/// - The trait is synchronous. Only the batch fetches that benefit from concurrency are
+///   also offered as futures, by [`AsyncSourceControlProvider`]; the rest of the trait
+///   isn't being moved to async.
+pub trait SourceControlProvider {
+    /// Submits a review on a pull request.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The pull request identifier as a string slice, typically a numeric ID.
+    /// - `message`: The review message or comment body to submit.
+    /// - `event`: The review event type, e.g., "APPROVE", "REQUEST_CHANGES", "COMMENT".
+    ///
+    /// # Returns
+    /// - `Ok(())` if the review submission was successful.
+    /// - `Err` containing a boxed error if something went wrong (network error, invalid PR, etc).
+    ///
+    /// # Usage
+    /// This method encapsulates the entire review submission flow, including authentication,
+    /// sending the review comment, and handling response errors.
+    fn submit_pull_request_review(
+        &self,
+        pr_number: PrNumber,
+        message: &str,
+        event: &str,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Displays the diff between the PR branch and `origin/main`.
+    ///
+    /// Generated files are left out unless `options.include_generated` is set; with
+    /// `options.blame`, removed lines are annotated with who last touched them and in which PR.
+    fn show_pull_request_diff(
+        &self,
+        pr_number: PrNumber,
+        options: &DiffOptions,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Pulls a PR locally and checks out a corresponding local branch.
+    /// Behavior differs depending on whether the PR comes from the same repo or a fork.
+    /// With `options.recurse_submodules` the submodules are updated after the checkout.
+    fn get_pull_request(&self, pr_number: PrNumber, options: &PullOptions);
+
+    /// Lists the pull requests of the current repository (open ones by default).
+    ///
+    /// # Parameters
+    /// - `options`: Filters narrowing the listing (base branch, labels, state) and the order.
+    ///
+    /// # Returns
+    /// - `Ok(())` on success (typically, this method would internally handle displaying
+    ///   or returning the PR list; the signature can be adjusted for your use case).
+    /// - `Err` on failure (e.g., API unreachable, authentication failed).
+    ///
+    /// # Notes
+    /// This method abstracts the retrieval and possibly display of open PRs, hiding API details.
+    fn list_pull_requests(&self, options: &ListOptions) -> Result<(), Box<dyn Error>>;
+
+    /// Shows how many open pull requests target each base branch.
+    ///
+    /// # Returns
+    /// - `Ok(())` after printing the per-base counts.
+    /// - `Err` if the PR list could not be fetched.
+    ///
+    /// # Usage
+    /// Release management: see what's still pending against each release line.
+    fn show_base_branch_counts(&self) -> Result<(), Box<dyn Error>>;
+
+    /// Closes the specified pull request.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR to close.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the PR was closed successfully.
+    /// - `Err` if closing the PR failed.
+    ///
+    /// # Context
+    /// This can be used to implement rejecting a PR as part of a review workflow.
+    fn close_pull_request(&self, pr_number: PrNumber) -> Result<(), Box<dyn Error>>;
+
+    /// Displays detailed information about a specific pull request.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR to display.
+    ///
+    /// # Returns
+    /// - `Ok(())` after successfully displaying the PR details.
+    /// - `Err` if fetching or displaying details fails.
+    ///
+    /// # Usage
+    /// Useful for showing metadata like PR title, author, status, commits, files changed, etc.
+    fn show_pull_request_details(&self, pr_number: PrNumber) -> Result<(), Box<dyn Error>>;
+
+    /// Finds the open pull request whose head is the given branch.
+    ///
+    /// # Parameters
+    /// - `branch`: The head branch name (as pushed to the remote).
+    ///
+    /// # Returns
+    /// - `Ok(Some(number))` if an open PR exists for the branch.
+    /// - `Ok(None)` if no open PR uses this branch as its head.
+    /// - `Err` if the lookup itself failed.
+    ///
+    /// # Usage
+    /// Last-resort fallback when resolving the PR number for the checked-out branch.
+    fn find_pull_request_for_branch(
+        &self,
+        branch: &str,
+    ) -> Result<Option<PrNumber>, Box<dyn Error>>;
+
+    /// Re-opens the review cycle on a PR after the author addressed feedback.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `message`: The comment to post on the PR (e.g. "Addressed review feedback").
+    ///
+    /// # Returns
+    /// - `Ok(())` once the branch is pushed, the comment posted and reviews re-requested.
+    /// - `Err` if any of the steps failed.
+    ///
+    /// # Usage
+    /// Author-side workflow: push, comment and re-request review from everyone whose
+    /// latest review requested changes, in one go.
+    fn reopen_review(&self, pr_number: PrNumber, message: &str) -> Result<(), Box<dyn Error>>;
+
+    /// Displays the CI status of a PR's head commit.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `porcelain`: Print one line per check in the stable `--porcelain` format.
+    /// - `max_file_size`: Flag changed files larger than this many bytes (falls back to
+    ///   the `max-file-size` setting).
+    ///
+    /// # Returns
+    /// - `Ok(())` after printing the unified checks table.
+    /// - `Err` if the PR or its checks could not be fetched, or files exceed the size limit.
+    ///
+    /// # Usage
+    /// Combines check runs and legacy commit statuses into one view keyed by name.
+    fn show_pull_request_checks(
+        &self,
+        pr_number: PrNumber,
+        porcelain: bool,
+        max_file_size: Option<u64>,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Displays GitHub Actions job logs for a PR's head commit.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `job`: Optional case-insensitive substring to select jobs by name.
+    /// - `follow`: Keep polling and stream progress until the jobs finish.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the logs were printed.
+    /// - `Err` if no runs/jobs were found or the logs could not be downloaded.
+    fn show_pull_request_logs(
+        &self,
+        pr_number: PrNumber,
+        job: Option<&str>,
+        follow: bool,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Lists the artifacts produced by a PR's workflow runs, optionally downloading one.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `download`: Name of the artifact to download (all matching names are fetched).
+    /// - `out_dir`: Directory the zip archives are written to.
+    ///
+    /// # Returns
+    /// - `Ok(())` after listing (and downloading) the artifacts.
+    /// - `Err` if the artifacts could not be listed or the download failed.
+    fn pull_request_artifacts(
+        &self,
+        pr_number: PrNumber,
+        download: Option<&str>,
+        out_dir: &str,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Searches the repository's pull requests by title/body text.
+    ///
+    /// # Parameters
+    /// - `query`: Free-text search terms.
+    /// - `state`: `open`, `closed`, `merged` or `all`.
+    /// - `author`: Optional author login to restrict the search to.
+    ///
+    /// # Returns
+    /// - `Ok(())` after printing the matching PRs in the same table as `list`.
+    /// - `Err` if the search request failed.
+    fn search_pull_requests(
+        &self,
+        query: &str,
+        state: &str,
+        author: Option<&str>,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Converts a PR to draft, or marks a draft PR ready for review.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `draft`: `true` to convert to draft, `false` to mark ready for review.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the PR's draft state was changed.
+    /// - `Err` if the mutation failed (e.g. the PR is closed, or drafts aren't available).
+    fn set_pull_request_draft(
+        &self,
+        pr_number: PrNumber,
+        draft: bool,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Opens a pull request for the current branch.
+    ///
+    /// # Parameters
+    /// - `options`: Base/title/body overrides and draft/editor switches.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the PR was created (its URL is printed).
+    /// - `Err` if the branch can't be proposed or the API rejected the PR.
+    fn create_pull_request(&self, options: &CreateOptions) -> Result<(), Box<dyn Error>>;
+
+    /// Fast-forwards a branch of the user's fork from upstream and fetches it locally.
+    ///
+    /// # Parameters
+    /// - `branch`: The branch to sync; defaults to the fork's default branch.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the fork's branch matches upstream.
+    /// - `Err` if there is no fork or the branch has diverged from upstream.
+    fn sync_fork(&self, branch: Option<&str>) -> Result<(), Box<dyn Error>>;
+
+    /// Retargets a PR to a different base branch and/or changes its title.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `base`: The new base branch, if retargeting.
+    /// - `title`: The new title, if renaming.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the PR was updated (with a summary of the commit delta).
+    /// - `Err` if the base doesn't exist or the PR would be empty against it.
+    fn move_pull_request(
+        &self,
+        pr_number: PrNumber,
+        base: Option<&str>,
+        title: Option<&str>,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Lists the files changed by a PR with their "Viewed" state and overall progress.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `include_generated`: List generated and vendored files too.
+    ///
+    /// # Returns
+    /// - `Ok(())` after printing the files table and the size of the change.
+    /// - `Err` if the files could not be fetched.
+    fn show_pull_request_files(
+        &self,
+        pr_number: PrNumber,
+        include_generated: bool,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Prints where a PR's changes are: line totals by directory and by file type, as bars.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `options`: Directory depth and whether generated files count.
+    ///
+    /// # Returns
+    /// - `Ok(())` after printing both groupings.
+    /// - `Err` if the changed files could not be fetched.
+    fn diff_stats(
+        &self,
+        pr_number: PrNumber,
+        options: &DiffStatsOptions,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Reports how much of the code a PR adds is covered by tests, per file.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `options`: Where the report is and the minimum coverage.
+    ///
+    /// # Returns
+    /// - `Ok(())` after printing the per-file coverage of the added lines.
+    /// - `Err` if no coverage report was found, or coverage is below `fail_under`.
+    ///
+    /// # Usage
+    /// The report is an LCOV or Cobertura file from the PR's CI (an artifact or a
+    /// configured service); lines it doesn't list don't count as coverable.
+    fn coverage(
+        &self,
+        pr_number: PrNumber,
+        options: &CoverageOptions,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Runs a local command against a PR's code in a throwaway worktree.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `options`: The command, and whether to keep the worktree.
+    ///
+    /// # Returns
+    /// - `Ok(code)` with the command's exit code once it finished.
+    /// - `Err` if the PR couldn't be checked out or the command couldn't be started.
+    ///
+    /// # Usage
+    /// The user's checkout, branch and uncommitted changes are left alone; the command's
+    /// output is streamed as it runs.
+    fn run_command(&self, pr_number: PrNumber, options: &RunOptions)
+        -> Result<i32, Box<dyn Error>>;
+
+    /// Binary-searches a PR's commits for the first one a test command fails on.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `options`: The test command, whether to test the base too, and whether to keep the worktree.
+    ///
+    /// # Returns
+    /// - `Ok(())` after naming the first failing commit (or saying the head passes).
+    /// - `Err` if the PR couldn't be checked out, the command couldn't be run, or it fails
+    ///   on the base already.
+    ///
+    /// # Usage
+    /// Exit codes are read like `git bisect run`: 0 is good, 125 skips the commit, anything
+    /// else is bad. The search happens in a throwaway worktree.
+    fn bisect(&self, pr_number: PrNumber, options: &BisectOptions) -> Result<(), Box<dyn Error>>;
+
+    /// Serves JSON-RPC requests on stdin/stdout for editor plugins.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the client sends `exit` or closes stdin.
+    /// - `Err` if the repository can't be determined or stdio fails.
+    ///
+    /// # Parameters
+    /// - `options`: Whether to also receive webhook deliveries (see [`ServeOptions`]).
+    ///
+    /// # Usage
+    /// Offers list, details, files, comments, comment and review; requests that fail are
+    /// answered with a JSON-RPC error while the server keeps running.
+    fn serve(&self, options: &ServeOptions) -> Result<(), Box<dyn Error>>;
+
+    /// Shows the conversation and inline comments of a PR.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `hide_outdated`: Leave out inline comments whose lines are gone from the diff.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the comments were printed.
+    /// - `Err` if the comments could not be fetched.
+    ///
+    /// # Usage
+    /// Inline comments are grouped by file with their replies; outdated ones are marked and
+    /// shown with the end of the diff hunk they were written on.
+    fn comments(&self, pr_number: PrNumber, hide_outdated: bool) -> Result<(), Box<dyn Error>>;
+
+    /// Shows, declares or ends a merge freeze of a branch.
+    ///
+    /// # Parameters
+    /// - `action`: What to do (see [`FreezeAction`]).
+    ///
+    /// # Returns
+    /// - `Ok(())` once the freeze was shown or changed.
+    /// - `Err` if the marker could not be read or written (e.g. no push access).
+    ///
+    /// # Usage
+    /// While a branch is frozen, `merge` refuses PRs into it unless overridden and `list`
+    /// marks them as waiting for the freeze to end.
+    fn freeze(&self, action: &FreezeAction) -> Result<(), Box<dyn Error>>;
+
+    /// Lists, approves and merges routine PRs from trusted automation (e.g. Dependabot).
+    ///
+    /// # Parameters
+    /// - `options`: Whose PRs to consider and what to do with them (see [`AutopilotOptions`]).
+    ///
+    /// # Returns
+    /// - `Ok(())` once the PRs were listed (and, if asked, approved and merged).
+    /// - `Err` if the PRs could not be checked, or naming those that failed to merge.
+    ///
+    /// # Usage
+    /// A PR qualifies when it touches only dependency manifests and lockfiles and its checks
+    /// are green; without `approve` or `merge` nothing is changed.
+    fn autopilot(&self, options: &AutopilotOptions) -> Result<(), Box<dyn Error>>;
+
+    /// Shows the open PRs of dependency bots, grouped by ecosystem and dependency.
+    ///
+    /// # Parameters
+    /// - `options`: Whose PRs to show and whether to ask for rebases (see [`BotsOptions`]).
+    ///
+    /// # Returns
+    /// - `Ok(())` once the PRs were listed (and, if asked, rebases requested).
+    /// - `Err` if the PRs could not be fetched, or naming those a rebase couldn't be asked for.
+    ///
+    /// # Usage
+    /// Each dependency gets one row with its PRs, the version jump and a changelog link.
+    fn bots(&self, options: &BotsOptions) -> Result<(), Box<dyn Error>>;
+
+    /// Asks for the PR number before a PR of a high-risk repository is changed.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `action`: What is about to happen (`merge`, `close`), shown in the question.
+    /// - `confirm`: The number given with `--confirm`, if any.
+    ///
+    /// # Returns
+    /// - `Ok(())` if the repository isn't listed in `high-risk-repos` or the number matches.
+    /// - `Err` if it doesn't match, or if there is no terminal to ask and no `confirm`.
+    ///
+    /// # Usage
+    /// The PR's title and author are shown first, so a mistyped number is caught before
+    /// anything changes.
+    fn confirm_high_risk(
+        &self,
+        pr_number: PrNumber,
+        action: &str,
+        confirm: Option<PrNumber>,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Marks files of a PR as viewed (or unviewed), like the web UI's checkboxes.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `paths`: Files to toggle; ignored when `all` is set.
+    /// - `all`: Toggle every changed file.
+    /// - `viewed`: `true` to mark as viewed, `false` to unmark.
+    ///
+    /// # Returns
+    /// - `Ok(())` after updating the files and printing the review progress.
+    /// - `Err` if a path isn't part of the PR or a mutation failed.
+    fn mark_files_viewed(
+        &self,
+        pr_number: PrNumber,
+        paths: &[String],
+        all: bool,
+        viewed: bool,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Generates a PR description from the `describe-template` and, with `regenerate`,
+    /// replaces the PR body with it.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `regenerate`: `true` to update the PR, `false` to only print the result.
+    ///
+    /// # Returns
+    /// - `Ok(())` after printing (or applying) the description.
+    /// - `Err` if the PR data could not be fetched or the update failed.
+    fn describe_pull_request(
+        &self,
+        pr_number: PrNumber,
+        regenerate: bool,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Lists locally saved review drafts and the viewer's server-side pending reviews.
+    ///
+    /// # Returns
+    /// - `Ok(())` after printing the pending reviews.
+    /// - `Err` if the server-side reviews could not be fetched.
+    fn list_pending_reviews(&self) -> Result<(), Box<dyn Error>>;
+
+    /// Resumes, submits or discards the pending review on a PR.
+    ///
+    /// A local draft takes precedence over a server-side pending review on the same PR.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `action`: What to do with the pending review.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the action was carried out.
+    /// - `Err` if there is no pending review on the PR or the API call failed.
+    fn pending_review_action(
+        &self,
+        pr_number: PrNumber,
+        action: &PendingReviewAction,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Records per-path verdicts on a pull request and submits them as one review.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `action`: What to do (see [`ScopeAction`]).
+    ///
+    /// # Returns
+    /// - `Ok(())` once the action was carried out.
+    /// - `Err` if a path covers none of the PR's files, there is nothing to submit, or the
+    ///   API call failed.
+    ///
+    /// # Usage
+    /// Verdicts are kept locally until submitted; the review gets one section per path, and
+    /// only approves the PR when every path was approved and every changed file is covered.
+    fn scoped_review(
+        &self,
+        pr_number: PrNumber,
+        action: &ScopeAction,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Checks that the provider can be used from this clone.
+    ///
+    /// # Returns
+    /// - One [`Diagnostic`] per check: remote parseable, API reachable, token valid
+    ///   with scopes, rate limit remaining and default branch detection. Checks that
+    ///   depend on a failed one are skipped.
+    ///
+    /// # Usage
+    /// Backs `git pr doctor`, after the local checks (git, config, remote, token).
+    fn diagnose(&self) -> Vec<Diagnostic>;
+
+    /// Suggests a reviewer for a PR, and optionally requests a review from them.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `request`: Whether to request a review from the suggested reviewer.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the suggestion was printed (and the review requested).
+    /// - `Err` if no qualified reviewer was found or an API call failed.
+    ///
+    /// # Usage
+    /// Combines code ownership, authorship of the changed lines and each candidate's
+    /// current review load to pick the least-loaded qualified reviewer.
+    fn suggest_reviewer(&self, pr_number: PrNumber, request: bool) -> Result<(), Box<dyn Error>>;
+
+    /// Requests a review from the next person of the configured review rotation.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `dry_run`: Only print whose turn it is, without requesting a review.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the review was requested and the rotation advanced.
+    /// - `Err` if no rotation is configured, nobody in it is available, or the request failed.
+    ///
+    /// # Usage
+    /// The rotation comes from `review-rotation`; people in `review-unavailable` and the
+    /// PR's author are skipped.
+    fn assign_next(&self, pr_number: PrNumber, dry_run: bool) -> Result<(), Box<dyn Error>>;
+
+    /// Merges a pull request after checking it against branch protection.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `options`: Merge method, label gate and dry run (see [`MergeOptions`]).
+    ///
+    /// # Returns
+    /// - `Ok(())` once the PR was merged (or, with `dry_run`, nothing blocks it).
+    /// - `Err` naming each unmet requirement, or if the merge itself failed.
+    ///
+    /// # Usage
+    /// Every requirement (approvals, required checks, resolved conversations, allowed
+    /// merge method, conflicts) is reported before the merge API is called.
+    fn merge(&self, pr_number: PrNumber, options: &MergeOptions) -> Result<(), Box<dyn Error>>;
+
+    /// Lists the unresolved review threads of a pull request.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `resolve_all_mine`: First resolve the unresolved threads the user opened.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the threads were listed (and resolved).
+    /// - `Err` if the threads could not be fetched or one could not be resolved.
+    ///
+    /// # Usage
+    /// Helps clear the "all conversations must be resolved" merge requirement: each
+    /// thread is shown with its file, line, author and the start of its first comment.
+    fn unresolved(&self, pr_number: PrNumber, resolve_all_mine: bool)
+        -> Result<(), Box<dyn Error>>;
+
+    /// Posts a comment on the conversation of a pull request.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `options`: The message and the files to attach (see [`CommentOptions`]).
+    ///
+    /// # Returns
+    /// - `Ok(())` once the comment was posted.
+    /// - `Err` if there is nothing to post, an attachment could not be uploaded, or the
+    ///   comment could not be posted.
+    ///
+    /// # Usage
+    /// Attachments are uploaded first (to the repository's attachment branch, or with the
+    /// `attachment-command`) and embedded below the message.
+    fn comment(&self, pr_number: PrNumber, options: &CommentOptions) -> Result<(), Box<dyn Error>>;
+
+    /// Shows one commit of a pull request, or reviews it.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `sha`: The commit, as a full or abbreviated SHA; it must belong to the PR.
+    /// - `options`: The review summary and inline comments (see [`CommitReviewOptions`]).
+    ///
+    /// # Returns
+    /// - `Ok(())` once the commit was shown or the review submitted.
+    /// - `Err` if the commit isn't part of the PR, or the review could not be submitted.
+    ///
+    /// # Usage
+    /// Without a message or comments the commit's message and diff are paged. Otherwise a
+    /// comment review pinned to that commit (`commit_id`) is submitted, so its comments show
+    /// up against the commit rather than the PR's latest head.
+    fn review_commit(
+        &self,
+        pr_number: PrNumber,
+        sha: &str,
+        options: &CommitReviewOptions,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Submits a review whose body carries a GPG-signed receipt.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `message`: The review message.
+    /// - `event`: The review event type, e.g., "APPROVE", "REQUEST_CHANGES", "COMMENT".
+    ///
+    /// # Returns
+    /// - `Ok(())` if the review was signed and submitted.
+    /// - `Err` if signing failed (nothing is submitted then) or the API call failed.
+    ///
+    /// # Usage
+    /// The receipt states the repository, PR, event, head commit and a hash of the message,
+    /// signed with the local GPG key, so the review can later be checked with
+    /// [`SourceControlProvider::verify_review`].
+    fn submit_signed_review(
+        &self,
+        pr_number: PrNumber,
+        message: &str,
+        event: &str,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Verifies the signed receipt of a review.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The PR the review belongs to.
+    /// - `review_id`: The review's id (as in its `#pullrequestreview-<id>` link).
+    ///
+    /// # Returns
+    /// - `Ok(())` if the signature is good and the receipt matches the review as stored.
+    /// - `Err` describing the mismatch, or if the review has no receipt.
+    fn verify_review(&self, pr_number: PrNumber, review_id: u64) -> Result<(), Box<dyn Error>>;
+
+    /// Runs policy checks on a pull request.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `options`: Which checks to run.
+    ///
+    /// # Returns
+    /// - `Ok(())` if every selected check passed.
+    /// - `Err` describing the failed checks, or if an API call failed.
+    ///
+    /// # Usage
+    /// Meant as a pre-merge gate in scripts and CI: problems are printed, and the error
+    /// makes the command exit non-zero.
+    fn lint_pull_request(
+        &self,
+        pr_number: PrNumber,
+        options: &LintOptions,
+    ) -> Result<(), Box<dyn Error>>;
+
+    /// Welcomes a first-time contributor with a comment on their pull request.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The identifier of the PR.
+    /// - `force`: Comment even if the author isn't new or was welcomed already.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the comment was posted.
+    /// - `Err` if the author isn't a first-time contributor (without `force`) or the
+    ///   comment couldn't be posted.
+    ///
+    /// # Usage
+    /// The comment text comes from the `welcome-template` setting.
+    fn welcome(&self, pr_number: PrNumber, force: bool) -> Result<(), Box<dyn Error>>;
+
+    /// Walks through the open pull requests without labels or assignees, one at a time,
+    /// prompting to label, assign, request reviewers, comment or skip.
+    ///
+    /// # Parameters
+    /// - `restart`: Forget the progress of earlier sessions.
+    ///
+    /// # Returns
+    /// - `Ok(())` when every PR was handled or the maintainer quit.
+    /// - `Err` if stdin isn't a terminal or the PR list could not be fetched.
+    ///
+    /// # Usage
+    /// Progress is saved after every PR, so a later session resumes where this one stopped.
+    fn triage(&self, restart: bool) -> Result<(), Box<dyn Error>>;
+
+    /// Lists the open pull requests waiting on a team's review, longest-waiting first.
+    ///
+    /// # Parameters
+    /// - `team`: The team, as `org/slug` or just `slug` within the repository's owner.
+    ///
+    /// # Returns
+    /// - `Ok(())` once the queue was printed.
+    /// - `Err` if the pull requests could not be fetched.
+    ///
+    /// # Usage
+    /// Reviews requested from the team itself and from any of its members both count.
+    fn queue(&self, team: &str) -> Result<(), Box<dyn Error>>;
+
+    /// Returns the login of the user the token belongs to.
+    ///
+    /// # Returns
+    /// - `Ok(login)`, from the local cache when it was looked up recently.
+    /// - `Err` if the lookup failed, e.g. because the token is invalid.
+    ///
+    /// # Usage
+    /// Commands that act on "your" PRs, threads or markers use this instead of asking
+    /// the API themselves, so the login is fetched at most once a day per token.
+    fn authenticated_login(&self) -> Result<String, Box<dyn Error>>;
+}
+
+/// Error type of [`AsyncSourceControlProvider`]; `Send` so it can cross task boundaries.
+pub(crate) type AsyncError = Box<dyn Error + Send + Sync>;
+
+/// The data side of a provider, as futures.
+///
+/// `SourceControlProvider` methods print as they go, which suits one-shot CLI commands.
+/// Callers that need many requests in flight (tables over many PRs, `--watch`, a TUI)
+/// use this trait instead and render the results themselves. Plain CLI code drives it
+/// through [`crate::providers::blocking::Blocking`] without becoming async itself.
+///
+/// # Design notes:
+/// - `PullRequest` is provider-specific so no fields are lost in translation; a shared
+///   model can be introduced once a second provider exists.
+/// - Default methods are sequential; providers override them to run requests concurrently.
+/// - This is deliberately a subset, not an async version of `SourceControlProvider`: it
+///   holds only the fetches the list table and `--watch` need. Every other command goes
+///   through the synchronous trait, and the GitHub implementation runs its blocking
+///   client on `spawn_blocking` tasks rather than an async client.
+#[async_trait]
+pub(crate) trait AsyncSourceControlProvider: Send + Sync {
+    /// The provider's pull request representation.
+    type PullRequest: Send;
+
+    /// Fetches one pull request.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The pull request to fetch
+    ///
+    /// # Returns
+    /// - `Ok(pr)` with the pull request.
+    /// - `Err` if it doesn't exist or the request failed.
+    async fn pull_request(&self, pr_number: PrNumber) -> Result<Self::PullRequest, AsyncError>;
+
+    /// Fetches several pull requests.
+    ///
+    /// # Parameters
+    /// - `numbers`: The pull requests to fetch
+    ///
+    /// # Returns
+    /// - One `(number, result)` per input, in input order, so one failure doesn't hide
+    ///   the rest.
+    async fn pull_requests(
+        &self,
+        numbers: &[PrNumber],
+    ) -> Vec<(PrNumber, Result<Self::PullRequest, AsyncError>)> {
+        let mut results = Vec::with_capacity(numbers.len());
+        for &number in numbers {
+            results.push((number, self.pull_request(number).await));
+        }
+        results
+    }
+
+    /// Fetches the review and CI state of one pull request.
+    ///
+    /// # Parameters
+    /// - `pr_number`: The pull request to inspect
+    ///
+    /// # Returns
+    /// - `Ok(status)` with the current verdict and overall check state.
+    /// - `Err` if the pull request or its checks can't be fetched.
+    async fn pull_request_status(
+        &self,
+        pr_number: PrNumber,
+    ) -> Result<PullRequestStatus, AsyncError>;
+
+    /// Fetches the review and CI state of several pull requests.
+    ///
+    /// # Returns
+    /// - One `(number, result)` per input, in input order.
+    async fn pull_request_statuses(
+        &self,
+        numbers: &[PrNumber],
+    ) -> Vec<(PrNumber, Result<PullRequestStatus, AsyncError>)> {
+        let mut results = Vec::with_capacity(numbers.len());
+        for &number in numbers {
+            results.push((number, self.pull_request_status(number).await));
+        }
+        results
+    }
+
+    /// Lists the paths changed by one pull request.
+    ///
+    /// # Returns
+    /// - `Ok(paths)` with the repo-relative path of every changed file.
+    /// - `Err` if the files can't be fetched.
+    async fn changed_paths(&self, pr_number: PrNumber) -> Result<Vec<String>, AsyncError>;
+
+    /// Lists the paths changed by several pull requests.
+    ///
+    /// # Returns
+    /// - One `(number, result)` per input, in input order.
+    async fn changed_paths_of(
+        &self,
+        numbers: &[PrNumber],
+    ) -> Vec<(PrNumber, Result<Vec<String>, AsyncError>)> {
+        let mut results = Vec::with_capacity(numbers.len());
+        for &number in numbers {
+            results.push((number, self.changed_paths(number).await));
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pr_numbers_take_an_optional_hash() {
+        assert_eq!("42".parse(), Ok(PrNumber(42)));
+        assert_eq!(" #42 ".parse(), Ok(PrNumber(42)));
+        for bad in [
+            "", "#", "0", "##42", "+42", "-1", "4 2", "42a", "#+42", "٤٢",
+        ] {
+            assert!(bad.parse::<PrNumber>().is_err(), "{:?} parsed", bad);
+        }
+    }
+
+    #[test]
+    fn line_ranges_are_one_line_or_ascending() {
+        assert_eq!("12".parse(), Ok(LineRange { start: 12, end: 12 }));
+        assert_eq!("10 - 14".parse(), Ok(LineRange { start: 10, end: 14 }));
+        assert_eq!(LineRange { start: 10, end: 14 }.to_string(), "10-14");
+        for bad in ["", "0", "14-10", "+3", "3-+4", "1-2-3", "x"] {
+            assert!(bad.parse::<LineRange>().is_err(), "{:?} parsed", bad);
+        }
+    }
+
+    #[test]
+    fn inline_comments_keep_colons_in_the_text() {
+        let comment: InlineComment = "src/lib.rs:40-45:Note: needs a test".parse().unwrap();
+        assert_eq!(comment.path, "src/lib.rs");
+        assert_eq!(comment.lines, LineRange { start: 40, end: 45 });
+        assert_eq!(comment.body, "Note: needs a test");
+        for bad in [
+            "src/lib.rs:42",
+            "src/lib.rs:42: ",
+            ":42:text",
+            "src/lib.rs:x:text",
+        ] {
+            assert!(bad.parse::<InlineComment>().is_err(), "{:?} parsed", bad);
+        }
+    }
+}
@@ -1,24 +1,59 @@
 // This module acts as the root module for defining and accessing source control providers.
 // Currently, it only supports GitHub, but is designed to be extensible to support
 // additional providers like GitLab, Bitbucket, etc., in the future.
+//
+// Each provider lives behind a cargo feature of the same name (`github` is on by default),
+// so packagers can build slim binaries. The trait and the types shared by all providers
+// live in `methods` and are always compiled.
 
 // Import the `SourceControlProvider` trait which defines the behavior any source control
 // provider must implement (e.g., listing PRs, submitting reviews, etc.)
-use crate::providers::github::methods::SourceControlProvider;
+use crate::providers::methods::SourceControlProvider;
 
 // Import the concrete implementation of the provider for GitHub.
 // `GitHubProvider` is a struct that implements the `SourceControlProvider` trait.
+#[cfg(feature = "github")]
 use crate::providers::github::models::GitHubProvider;
 
+// Resolved user/repo configuration (preferred remote, API base URL, etc.)
+use crate::config::Config;
+
 // The Error trait from Rust's standard library is required to support flexible error handling
 // in the return types of provider factories and operations.
 use std::error::Error;
 
+// The provider trait and the provider-agnostic types it uses (PR numbers, options, ...).
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+pub mod methods;
+
+// Runs the async provider trait from synchronous CLI code (see `blocking::Blocking`).
+#[cfg_attr(not(feature = "github"), allow(dead_code))]
+pub mod blocking;
+
 // Re-export the GitHub provider module so other parts of the crate can access it.
-// This allows submodules like `github::methods` and `github::models` to be accessed
+// This allows submodules like `github::models` to be accessed
 // through the public `providers` namespace.
+#[cfg(feature = "github")]
 pub mod github;
 
+/// Providers compiled into this binary (one per enabled cargo feature).
+#[allow(unused_mut, clippy::vec_init_then_push)]
+pub fn compiled_providers() -> Vec<&'static str> {
+    let mut providers = Vec::new();
+    #[cfg(feature = "github")]
+    providers.push("github");
+    providers
+}
+
+/// REST API versions the compiled-in providers are pinned to, as `(provider, version)`.
+#[allow(unused_mut, clippy::vec_init_then_push)]
+pub fn api_versions() -> Vec<(&'static str, &'static str)> {
+    let mut versions = Vec::new();
+    #[cfg(feature = "github")]
+    versions.push(("github", github::api::GITHUB_API_VERSION));
+    versions
+}
+
 /// Attempts to select and construct a source control provider based on the provided remote URL.
 ///
 /// This function acts as a basic factory for determining which provider should be used
@@ -27,8 +62,10 @@ pub mod github;
 ///
 /// # Arguments
 ///
-/// * `remote_url` - The Git remote origin URL obtained via `git remote get-url origin`.
+/// * `remote_url` - The Git remote URL obtained via `git remote get-url <remote>`.
 ///   This string is used to determine which source control provider the project is using.
+/// * `config` - The resolved configuration. A configured `base-url` marks the remote as a
+///   GitHub Enterprise host even when the URL doesn't contain "github.com".
 ///
 /// # Returns
 ///
@@ -47,21 +84,39 @@ pub mod github;
 ///
 /// ```rust
 /// let remote_url = "https://github.com/user/repo.git";
-/// let provider = get_provider(remote_url)?;
+/// let provider = get_provider(remote_url, &Config::load())?;
 /// provider.list_pull_requests()?;
 /// ```
-pub fn get_provider(remote_url: &str) -> Result<Box<dyn SourceControlProvider>, Box<dyn Error>> {
+#[cfg_attr(not(feature = "github"), allow(unused_variables))]
+pub fn get_provider(
+    remote_url: &str,
+    config: &Config,
+) -> Result<Box<dyn SourceControlProvider>, Box<dyn Error>> {
     // Simple pattern match on the remote URL.
     // This check assumes that any GitHub remote will include "github.com" in the URL.
     // In the future, more sophisticated matching or parsing logic may be used
     // to support other providers like GitLab or Bitbucket.
-    if remote_url.contains("github.com") {
+    #[cfg(feature = "github")]
+    if remote_url.contains("github.com") || config.base_url.is_some() {
         // Instantiate a new GitHub provider with the given URL.
         // `.new()` may return an error, so the `?` operator is used to propagate it.
-        Ok(Box::new(GitHubProvider::new(remote_url.to_string())?))
-    } else {
-        // If the URL does not match any known provider, return a generic error.
-        // `.into()` converts the &str into a boxed error.
-        Err("Unsupported provider".into())
+        return Ok(Box::new(GitHubProvider::new(
+            remote_url.to_string(),
+            config,
+        )?));
     }
+
+    // If the URL does not match any compiled-in provider, say which ones there are,
+    // since a slim build may simply lack the one this remote needs.
+    let compiled = compiled_providers();
+    Err(format!(
+        "Unsupported provider for {} (compiled-in providers: {})",
+        remote_url,
+        if compiled.is_empty() {
+            "none".to_string()
+        } else {
+            compiled.join(", ")
+        }
+    )
+    .into())
 }
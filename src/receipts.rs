@@ -0,0 +1,362 @@
+// GPG-signed review receipts (`submit-review --sign`, `git pr verify-review`).
+//
+// A signed review carries a receipt appended to its body: a short statement of what was
+// reviewed, detached-signed with the reviewer's GPG key:
+//
+//   <!-- git-pr-receipt -->
+//   ```
+//   git-pr review receipt v1
+//   repo: acme/api
+//   pr: 42
+//   reviewer: octocat
+//   event: APPROVE
+//   commit: 3f2a9c1...
+//   body-sha256: 9f86d0...
+//   -----BEGIN PGP SIGNATURE-----
+//   ...
+//   -----END PGP SIGNATURE-----
+//   ```
+//
+// The first seven lines are the signed payload. The body hash covers the review text above
+// the marker (trimmed, with CRLF normalized, since GitHub may rewrite line endings), so
+// editing the review on GitHub afterwards is detected, as is moving the receipt to another
+// review, PR or commit. Naming the reviewer keeps anyone else from passing a copy of the
+// receipt off as their own review. Signing uses the same program and key as signed git commits
+// (`gpg.program`, `user.signingkey`), so no extra setup is needed where commits are signed.
+use crate::debug_log;
+use crate::utils::run_git;
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Marks the start of the receipt in a review body.
+const MARKER: &str = "<!-- git-pr-receipt -->";
+
+/// First line of the signed payload, naming the receipt format.
+const HEADER: &str = "git-pr review receipt v1";
+
+/// What a receipt states about a review.
+///
+/// Fields:
+/// - `repo`: `owner/repo` of the PR
+/// - `pr_number`: The PR reviewed
+/// - `reviewer`: GitHub login of the reviewer
+/// - `event`: `APPROVE`, `REQUEST_CHANGES` or `COMMENT`
+/// - `commit`: Head commit the review was submitted against
+/// - `body_sha256`: SHA-256 of the review text (see [`body_hash`])
+pub struct Receipt {
+    pub repo: String,
+    pub pr_number: u64,
+    pub reviewer: String,
+    pub event: String,
+    pub commit: String,
+    pub body_sha256: String,
+}
+
+/// A receipt found in a review body.
+///
+/// Fields:
+/// - `body`: The review text above the receipt
+/// - `receipt`: The parsed statement
+/// - `payload`: The statement exactly as signed
+/// - `signature`: The armored detached signature
+pub struct SignedBody {
+    pub body: String,
+    pub receipt: Receipt,
+    pub payload: String,
+    pub signature: String,
+}
+
+/// The signer of a valid signature, as reported by GPG.
+///
+/// Fields:
+/// - `user_id`: Name and email of the key (`Jane Doe <jane@example.com>`)
+/// - `fingerprint`: Fingerprint of the signing (sub)key
+pub struct Signer {
+    pub user_id: String,
+    pub fingerprint: String,
+}
+
+/// SHA-256 of a review text, ignoring surrounding whitespace and line-ending style.
+pub fn body_hash(body: &str) -> String {
+    let normalized = body.replace("\r\n", "\n");
+    format!("{:x}", Sha256::digest(normalized.trim().as_bytes()))
+}
+
+impl Receipt {
+    /// The statement that gets signed, one `key: value` line per field.
+    pub fn payload(&self) -> String {
+        format!(
+            "{}\nrepo: {}\npr: {}\nreviewer: {}\nevent: {}\ncommit: {}\nbody-sha256: {}\n",
+            HEADER,
+            self.repo,
+            self.pr_number,
+            self.reviewer,
+            self.event,
+            self.commit,
+            self.body_sha256
+        )
+    }
+
+    /// Parses a payload written by [`Receipt::payload`].
+    fn parse(payload: &str) -> Option<Receipt> {
+        let mut lines = payload.lines();
+        if lines.next()? != HEADER {
+            return None;
+        }
+        let mut field = |key: &str| {
+            lines
+                .next()?
+                .strip_prefix(key)?
+                .strip_prefix(": ")
+                .map(str::to_string)
+        };
+        Some(Receipt {
+            repo: field("repo")?,
+            pr_number: field("pr")?.parse().ok()?,
+            reviewer: field("reviewer")?,
+            event: field("event")?,
+            commit: field("commit")?,
+            body_sha256: field("body-sha256")?,
+        })
+    }
+}
+
+/// The GPG program git signs with (`gpg.program`), or `gpg`.
+fn gpg_program() -> String {
+    run_git(&["config", "gpg.program"]).unwrap_or_else(|| "gpg".to_string())
+}
+
+/// Signs a receipt with the local GPG key and appends it to the review text.
+///
+/// GPG may ask for the key's passphrase; its prompts go to the terminal.
+///
+/// # Parameters
+/// - `body`: The review text
+/// - `receipt`: The statement to sign; its `body_sha256` must be [`body_hash`] of `body`
+///
+/// # Returns
+/// - The review text with the receipt block appended
+/// - `Err` if GPG is missing or fails (no secret key, wrong passphrase, ...)
+pub fn sign(body: &str, receipt: &Receipt) -> Result<String, Box<dyn Error>> {
+    let program = gpg_program();
+    let mut command = Command::new(&program);
+    command.args(["--detach-sign", "--armor"]);
+    if let Some(key) = run_git(&["config", "user.signingkey"]) {
+        command.args(["--local-user", &key]);
+    }
+    debug_log!("[DEBUG] Signing review receipt with {}", program);
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| format!("Could not run '{}' to sign the review: {}", program, e))?;
+    child
+        .stdin
+        .take()
+        .ok_or("GPG's input is unavailable")?
+        .write_all(receipt.payload().as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(format!(
+            "'{}' failed to sign the review ({})",
+            program, output.status
+        )
+        .into());
+    }
+    let signature = String::from_utf8(output.stdout)?;
+
+    Ok(format!(
+        "{}\n\n{}\n```\n{}{}\n```\n",
+        body.trim_end(),
+        MARKER,
+        receipt.payload(),
+        signature.trim_end()
+    ))
+}
+
+/// Splits a review body into its text and receipt.
+///
+/// # Returns
+/// - `None` if the body has no (well-formed) receipt
+pub fn parse(review_body: &str) -> Option<SignedBody> {
+    let review_body = review_body.replace("\r\n", "\n");
+    let start = review_body.rfind(MARKER)?;
+    let block = review_body[start + MARKER.len()..]
+        .trim()
+        .strip_prefix("```")?
+        .trim_start_matches(|c: char| c != '\n')
+        .trim_start_matches('\n');
+    let block = &block[..block.rfind("```")?];
+    let split = block.find("-----BEGIN PGP SIGNATURE-----")?;
+    let payload = block[..split].to_string();
+    Some(SignedBody {
+        body: review_body[..start].trim().to_string(),
+        receipt: Receipt::parse(&payload)?,
+        payload,
+        signature: block[split..].trim_end().to_string(),
+    })
+}
+
+/// Checks a detached signature over a payload with GPG.
+///
+/// The public key has to be in the local keyring.
+///
+/// # Returns
+/// - The signer if the signature is good
+/// - `Err` naming why not: bad signature, unknown key, GPG missing
+pub fn verify(payload: &str, signature: &str) -> Result<Signer, Box<dyn Error>> {
+    // The signature has to be a file; it gets a fresh, unpredictable name only this user
+    // can open, so nobody can plant a symlink or a signature of their own in its place.
+    // The payload goes to GPG's stdin.
+    let mut signature_file = tempfile::Builder::new()
+        .prefix("git-pr-receipt-")
+        .suffix(".asc")
+        .tempfile()?;
+    writeln!(signature_file, "{}", signature)?;
+    signature_file.flush()?;
+
+    let program = gpg_program();
+    let mut child = Command::new(&program)
+        .args(["--status-fd", "1", "--verify"])
+        .arg(signature_file.path())
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Could not run '{}' to verify the review: {}", program, e))?;
+    // GPG may stop reading early (e.g. a malformed signature); its verdict says why
+    let _ = child
+        .stdin
+        .take()
+        .ok_or("GPG's input is unavailable")?
+        .write_all(payload.as_bytes());
+    let output = child.wait_with_output()?;
+
+    let status = String::from_utf8_lossy(&output.stdout);
+    debug_log!("[DEBUG] GPG status:\n{}", status);
+    let mut user_id = None;
+    let mut fingerprint = None;
+    for line in status.lines() {
+        let Some(line) = line.strip_prefix("[GNUPG:] ") else {
+            continue;
+        };
+        let mut words = line.splitn(3, ' ');
+        match (words.next(), words.next(), words.next()) {
+            (Some("GOODSIG"), _, Some(uid)) => user_id = Some(uid.to_string()),
+            (Some("VALIDSIG"), Some(fpr), _) => fingerprint = Some(fpr.to_string()),
+            (Some("BADSIG"), _, uid) => {
+                return Err(format!(
+                    "BAD signature from {}: the receipt was altered",
+                    uid.unwrap_or("unknown key")
+                )
+                .into())
+            }
+            (Some("ERRSIG"), Some(key), _) | (Some("NO_PUBKEY"), Some(key), _) => {
+                return Err(format!(
+                    "Can't check the signature: public key {} is not in your keyring",
+                    key
+                )
+                .into())
+            }
+            _ => {}
+        }
+    }
+    match (user_id, fingerprint) {
+        (Some(user_id), Some(fingerprint)) if output.status.success() => Ok(Signer {
+            user_id,
+            fingerprint,
+        }),
+        _ => Err(format!("'{}' could not verify the signature", program).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNATURE: &str =
+        "-----BEGIN PGP SIGNATURE-----\n\niQEzBAABCAAdFiEE\n-----END PGP SIGNATURE-----";
+
+    fn receipt(body: &str) -> Receipt {
+        Receipt {
+            repo: "acme/api".to_string(),
+            pr_number: 42,
+            reviewer: "octocat".to_string(),
+            event: "APPROVE".to_string(),
+            commit: "3f2a9c1".to_string(),
+            body_sha256: body_hash(body),
+        }
+    }
+
+    /// A review body as [`sign`] writes it, with a canned signature.
+    fn signed(body: &str) -> String {
+        format!(
+            "{}\n\n{}\n```\n{}{}\n```\n",
+            body,
+            MARKER,
+            receipt(body).payload(),
+            SIGNATURE
+        )
+    }
+
+    #[test]
+    fn signed_bodies_round_trip() {
+        let parsed = parse(&signed("Looks good.\n\nShip it")).unwrap();
+        assert_eq!(parsed.body, "Looks good.\n\nShip it");
+        assert_eq!(parsed.payload, receipt("Looks good.\n\nShip it").payload());
+        assert_eq!(parsed.signature, SIGNATURE);
+
+        let receipt = parsed.receipt;
+        assert_eq!(receipt.repo, "acme/api");
+        assert_eq!(receipt.pr_number, 42);
+        assert_eq!(receipt.reviewer, "octocat");
+        assert_eq!(receipt.event, "APPROVE");
+        assert_eq!(receipt.commit, "3f2a9c1");
+        assert_eq!(receipt.body_sha256, body_hash(&parsed.body));
+    }
+
+    #[test]
+    fn edited_bodies_no_longer_match() {
+        let edited = signed("Looks good.").replace("Looks good.", "Looks great.");
+        let parsed = parse(&edited).unwrap();
+        assert_eq!(parsed.body, "Looks great.");
+        assert_ne!(parsed.receipt.body_sha256, body_hash(&parsed.body));
+    }
+
+    #[test]
+    fn crlf_line_endings_are_normalized() {
+        let body = "Looks good.\n\nShip it";
+        assert_eq!(body_hash("Looks good.\r\n\r\nShip it\r\n"), body_hash(body));
+
+        let parsed = parse(&signed(body).replace('\n', "\r\n")).unwrap();
+        assert_eq!(parsed.body, body);
+        assert_eq!(parsed.payload, receipt(body).payload());
+        assert_eq!(parsed.signature, SIGNATURE);
+    }
+
+    #[test]
+    fn incomplete_receipts_are_not_parsed() {
+        let full = signed("Looks good.");
+        assert!(parse("Looks good.").is_none());
+        assert!(parse(&full.replace(MARKER, "")).is_none());
+        assert!(parse(full.trim_end().trim_end_matches("```")).is_none());
+        assert!(parse(&full.replace("```\n", "")).is_none());
+        assert!(parse(&full.replace(SIGNATURE, "")).is_none());
+    }
+
+    #[test]
+    fn payloads_need_every_field_in_order() {
+        let payload = receipt("x").payload();
+        assert!(Receipt::parse(&payload).is_some());
+        assert!(Receipt::parse(&payload.replace(HEADER, "git-pr review receipt v2")).is_none());
+        assert!(Receipt::parse(&payload.replace("pr: 42", "pr: #42")).is_none());
+        assert!(Receipt::parse(&payload.replace("reviewer: octocat\n", "")).is_none());
+        let swapped = payload.replace("repo: acme/api\npr: 42", "pr: 42\nrepo: acme/api");
+        assert!(Receipt::parse(&swapped).is_none());
+    }
+}
@@ -0,0 +1,202 @@
+// Per-path review verdicts (`git pr scope`), compiled into one review.
+//
+// Large monorepo PRs are reviewed area by area, often by different owners: "src/api looks
+// good, src/db needs a rollback". Each verdict is recorded locally as the reviewer goes,
+// under `.git/git-pr/scopes/<pr>`, one tab-separated line per path:
+//
+//   <event> <path> <note>
+//
+// with the event as for reviews (APPROVE, REQUEST_CHANGES, COMMENT) and newlines in the
+// note escaped as `\n`. Recording a path again replaces its verdict. On submit the verdicts
+// become a single review whose body has one section per scope plus the changed files no
+// scope covers; its event is REQUEST_CHANGES if any scope requests changes, APPROVE only if
+// every scope approves and every file is covered, and COMMENT otherwise, so a partial
+// approval never approves the whole PR on GitHub. Writes hold the repository lock.
+use crate::debug_log;
+use crate::lock;
+use crate::providers::methods::PrNumber;
+use crate::utils::run_git;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+/// The verdict on one path of a PR.
+///
+/// Fields:
+/// - `path`: Directory or file the verdict covers (no trailing `/`; `.` is the whole tree)
+/// - `event`: `APPROVE`, `REQUEST_CHANGES` or `COMMENT`
+/// - `note`: What the reviewer has to say about it (may be empty)
+#[derive(Clone)]
+pub struct ScopeVerdict {
+    pub path: String,
+    pub event: String,
+    pub note: String,
+}
+
+/// Directory holding the verdicts of the current repository.
+fn scopes_dir() -> Result<PathBuf, Box<dyn Error>> {
+    let git_dir = run_git(&["rev-parse", "--git-dir"]).ok_or("Not inside a git repository")?;
+    Ok(PathBuf::from(git_dir).join("git-pr").join("scopes"))
+}
+
+/// Normalizes a scope as typed (`./src/api/`) into the stored form (`src/api`).
+pub fn normalize_path(path: &str) -> String {
+    let trimmed = path.trim().trim_start_matches("./").trim_matches('/');
+    if trimmed.is_empty() || trimmed == "." {
+        ".".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Whether a scope covers a changed file: the file itself, or anything below the directory.
+pub fn covers(scope: &str, file: &str) -> bool {
+    scope == "."
+        || file == scope
+        || file
+            .strip_prefix(scope)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// The most specific scope covering a file (the longest matching path), if any.
+pub fn scope_of<'a>(verdicts: &'a [ScopeVerdict], file: &str) -> Option<&'a ScopeVerdict> {
+    verdicts
+        .iter()
+        .filter(|v| covers(&v.path, file))
+        .max_by_key(|v| if v.path == "." { 0 } else { v.path.len() })
+}
+
+/// Loads the verdicts recorded for a PR, in the order they were first recorded.
+pub fn load(pr_number: PrNumber) -> Vec<ScopeVerdict> {
+    let Ok(text) =
+        scopes_dir().and_then(|dir| Ok(fs::read_to_string(dir.join(pr_number.to_string()))?))
+    else {
+        return Vec::new();
+    };
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let event = fields.next()?.to_string();
+            let path = fields.next()?.to_string();
+            let note = fields.next().unwrap_or("").replace("\\n", "\n");
+            Some(ScopeVerdict { path, event, note })
+        })
+        .collect()
+}
+
+/// Records a verdict, replacing an earlier one on the same path.
+pub fn record(pr_number: PrNumber, verdict: ScopeVerdict) -> Result<(), Box<dyn Error>> {
+    let dir = scopes_dir()?;
+    let _lock = lock::acquire()?;
+    let mut verdicts = load(pr_number);
+    match verdicts.iter_mut().find(|v| v.path == verdict.path) {
+        Some(existing) => *existing = verdict,
+        None => verdicts.push(verdict),
+    }
+
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(pr_number.to_string());
+    debug_log!(
+        "[DEBUG] Saving {} scope verdict(s) to {}",
+        verdicts.len(),
+        path.display()
+    );
+    let text: String = verdicts
+        .iter()
+        .map(|v| {
+            format!(
+                "{}\t{}\t{}\n",
+                v.event,
+                v.path,
+                v.note.replace('\t', " ").replace('\n', "\\n")
+            )
+        })
+        .collect();
+    fs::write(path, text)?;
+    Ok(())
+}
+
+/// Deletes the verdicts of a PR. Returns `false` if there were none.
+pub fn discard(pr_number: PrNumber) -> bool {
+    let Ok(_lock) = lock::acquire() else {
+        return false;
+    };
+    scopes_dir()
+        .map(|dir| fs::remove_file(dir.join(pr_number.to_string())).is_ok())
+        .unwrap_or(false)
+}
+
+/// Heading of a scope's section in the review body.
+pub fn verdict_label(event: &str) -> &'static str {
+    match event {
+        "APPROVE" => "✅ Approved",
+        "REQUEST_CHANGES" => "❌ Changes requested",
+        _ => "💬 Comments",
+    }
+}
+
+/// Compiles the verdicts into the event and body of one review.
+///
+/// # Parameters
+/// - `summary`: Text put above the sections (`-m` on submit)
+/// - `verdicts`: The recorded verdicts
+/// - `files`: The files the PR changes
+///
+/// # Returns
+/// - `(event, body)`; see the module comment for how the event is chosen
+pub fn compile_review(
+    summary: &str,
+    verdicts: &[ScopeVerdict],
+    files: &[String],
+) -> (String, String) {
+    let uncovered: Vec<&String> = files
+        .iter()
+        .filter(|f| scope_of(verdicts, f).is_none())
+        .collect();
+    let event = if verdicts.iter().any(|v| v.event == "REQUEST_CHANGES") {
+        "REQUEST_CHANGES"
+    } else if uncovered.is_empty() && verdicts.iter().all(|v| v.event == "APPROVE") {
+        "APPROVE"
+    } else {
+        "COMMENT"
+    };
+
+    let mut body = String::new();
+    if !summary.trim().is_empty() {
+        body.push_str(summary.trim());
+        body.push_str("\n\n");
+    }
+    body.push_str("### Review by scope\n");
+    for verdict in verdicts {
+        let count = files
+            .iter()
+            .filter(|f| scope_of(verdicts, f).is_some_and(|v| v.path == verdict.path))
+            .count();
+        let path = if verdict.path == "." {
+            "(everything else)"
+        } else {
+            verdict.path.as_str()
+        };
+        body.push_str(&format!(
+            "\n#### {}: `{}` ({} file{})\n",
+            verdict_label(&verdict.event),
+            path,
+            count,
+            if count == 1 { "" } else { "s" }
+        ));
+        if !verdict.note.trim().is_empty() {
+            body.push_str(&format!("\n{}\n", verdict.note.trim()));
+        }
+    }
+    if !uncovered.is_empty() {
+        body.push_str(&format!(
+            "\n#### ⚠️ Not reviewed ({} file{})\n\n",
+            uncovered.len(),
+            if uncovered.len() == 1 { "" } else { "s" }
+        ));
+        for file in uncovered {
+            body.push_str(&format!("- `{}`\n", file));
+        }
+    }
+    (event.to_string(), body)
+}
@@ -0,0 +1,182 @@
+// `git pr selftest`: runs the main flows of this binary against the bundled mock API.
+//
+// Every flow runs the real binary as a child process, in a scratch repository whose remote
+// is `github.com/selftest/demo`, with `GIT_PR_API_BASE` pointing at the in-process mock
+// (see `mock`). Nothing leaves the machine and no real token is needed, so the command is
+// safe for demos and for checking a freshly built binary in CI.
+use crate::mock::{self, MockServer};
+use crate::ui_println;
+use crate::utils::run_git;
+use colored::Colorize;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// One end-to-end flow.
+///
+/// Fields:
+/// - `name`: Shown in the report
+/// - `args`: Arguments passed to the binary
+/// - `expect`: Text the output (stdout or stderr) must contain
+/// - `sends`: A write the mock must have received, as `(method, path suffix, body text)`
+struct Flow {
+    name: &'static str,
+    args: &'static [&'static str],
+    expect: &'static str,
+    sends: Option<(&'static str, &'static str, &'static str)>,
+}
+
+const FLOWS: &[Flow] = &[
+    Flow {
+        name: "list",
+        args: &["list"],
+        expect: mock::PR_TITLE,
+        sends: None,
+    },
+    Flow {
+        name: "list --porcelain",
+        args: &["list", "--porcelain"],
+        expect: "1\toctocat\t",
+        sends: None,
+    },
+    Flow {
+        name: "checks",
+        args: &["checks", "1", "--porcelain"],
+        expect: "success\tcheck\tbuild",
+        sends: None,
+    },
+    Flow {
+        name: "show-diff",
+        args: &["show-diff", "1", "--raw"],
+        expect: "+hello",
+        sends: None,
+    },
+    Flow {
+        name: "submit-review",
+        args: &["submit-review", "1", "--approve", "-m", "Ship it"],
+        expect: "Review submitted",
+        sends: Some(("POST", "/pulls/1/reviews", "\"APPROVE\"")),
+    },
+    Flow {
+        name: "doctor",
+        args: &["doctor"],
+        expect: "All checks passed",
+        sends: None,
+    },
+];
+
+/// Runs every flow and prints one line per result.
+///
+/// # Returns
+/// - `Ok(true)` if all flows passed
+/// - `Err` if the mock or the scratch repository can't be set up
+pub fn run() -> Result<bool, Box<dyn Error>> {
+    let server = MockServer::start()?;
+    let exe = env::current_exe()?;
+    let repo = scratch_repo()?;
+    ui_println!("🧪 Mock API at {} ({})", server.base, repo.display());
+
+    let mut failed = 0;
+    for flow in FLOWS {
+        let seen = server.requests().len();
+        match run_flow(flow, &exe, &repo, &server, seen) {
+            Ok(()) => ui_println!("{} {}", "✅".green(), flow.name),
+            Err(reason) => {
+                failed += 1;
+                ui_println!("{} {}: {}", "❌".red(), flow.name.red(), reason);
+            }
+        }
+    }
+
+    let _ = fs::remove_dir_all(&repo);
+    if failed == 0 {
+        println!("{}", format!("All {} flows passed.", FLOWS.len()).green());
+    } else {
+        println!(
+            "{}",
+            format!("{} of {} flows failed.", failed, FLOWS.len()).red()
+        );
+    }
+    Ok(failed == 0)
+}
+
+/// Runs one flow and checks its exit status, output and (optionally) the request it sent.
+///
+/// # Returns
+/// - `Err(reason)` describing the first expectation that wasn't met
+fn run_flow(
+    flow: &Flow,
+    exe: &Path,
+    repo: &Path,
+    server: &MockServer,
+    seen: usize,
+) -> Result<(), String> {
+    let mut command = Command::new(exe);
+    // Neither may the user's `GIT_PR_<KEY>` overrides
+    for key in crate::config::KNOWN_KEYS {
+        command.env_remove(crate::config::env_name(key));
+    }
+    for (name, _) in std::env::vars() {
+        if crate::config::env_key(&name).is_some() {
+            command.env_remove(name);
+        }
+    }
+    let output = command
+        .args(flow.args)
+        .current_dir(repo)
+        .env(crate::config::API_BASE_ENV, &server.base)
+        .env("GITHUB_TOKEN", mock::TOKEN)
+        // Keep the user's global config (remote, base-url, ...) out of the test
+        .env("XDG_CONFIG_HOME", repo)
+        .env("COLUMNS", "200")
+        .env_remove("GIT_DIR")
+        .env_remove("GIT_WORK_TREE")
+        .output()
+        .map_err(|e| format!("failed to run {}: {}", exe.display(), e))?;
+
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let last_line = text
+        .lines()
+        .rev()
+        .find(|l| !l.trim().is_empty())
+        .unwrap_or("");
+
+    if !output.status.success() {
+        return Err(format!(
+            "exited with {} ({})",
+            output.status,
+            last_line.trim()
+        ));
+    }
+    if !text.contains(flow.expect) {
+        return Err(format!("expected {:?} in the output", flow.expect));
+    }
+    if let Some((method, suffix, body)) = flow.sends {
+        let sent = server.requests()[seen..]
+            .iter()
+            .any(|r| r.method == method && r.path.ends_with(suffix) && r.body.contains(body));
+        if !sent {
+            return Err(format!("no {} …{} with {} was sent", method, suffix, body));
+        }
+    }
+    Ok(())
+}
+
+/// Creates an empty repository whose `origin` is the mock's canned repository.
+fn scratch_repo() -> Result<PathBuf, Box<dyn Error>> {
+    let dir = env::temp_dir().join(format!("git-pr-selftest-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+
+    let path = dir.to_string_lossy().to_string();
+    let remote = format!("https://github.com/{}/{}.git", mock::OWNER, mock::REPO);
+    run_git(&["-C", &path, "init", "-q"]).ok_or("git init failed")?;
+    run_git(&["-C", &path, "remote", "add", "origin", &remote]).ok_or("git remote add failed")?;
+    Ok(dir)
+}
@@ -0,0 +1,60 @@
+// Detection of security-sensitive changes.
+//
+// Some paths deserve a second pair of eyes whatever the change: authentication code,
+// container images, CI workflows (which run with repository secrets) and dependency
+// manifests (supply chain). `show-details` and `list` flag PRs touching any path that
+// matches the `sensitive-paths` setting (comma-separated CODEOWNERS-style patterns),
+// which defaults to `DEFAULT_SENSITIVE_PATHS`. Setting it to an empty string turns the
+// detection off.
+use crate::codeowners::compile_pattern_list;
+use crate::config::Config;
+use regex::Regex;
+
+/// Patterns treated as sensitive when `sensitive-paths` isn't set.
+pub const DEFAULT_SENSITIVE_PATHS: &[&str] = &[
+    "auth/",
+    "security/",
+    "Dockerfile",
+    "*.dockerfile",
+    "docker-compose*.yml",
+    ".github/workflows/",
+    ".gitlab-ci.yml",
+    "CODEOWNERS",
+    "Cargo.toml",
+    "package.json",
+    "go.mod",
+    "requirements*.txt",
+    "pyproject.toml",
+    "Gemfile",
+    "pom.xml",
+    "build.gradle",
+];
+
+/// The compiled `sensitive-paths` ruleset.
+pub struct SensitivePaths {
+    patterns: Vec<Regex>,
+}
+
+impl SensitivePaths {
+    /// Compiles the `sensitive-paths` setting, or the defaults if it isn't set.
+    pub fn new(config: &Config) -> Self {
+        let patterns = match config.sensitive_paths.as_deref() {
+            Some(list) => compile_pattern_list(list),
+            None => compile_pattern_list(&DEFAULT_SENSITIVE_PATHS.join(",")),
+        };
+        SensitivePaths { patterns }
+    }
+
+    /// Whether detection is turned off (`sensitive-paths` set to an empty list).
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// The sensitive paths among `paths`, in input order.
+    pub fn matching<'a>(&self, paths: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+        paths
+            .into_iter()
+            .filter(|path| self.patterns.iter().any(|p| p.is_match(path)))
+            .collect()
+    }
+}
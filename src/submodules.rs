@@ -0,0 +1,168 @@
+// Submodule pointer changes in PR diffs (`show-diff`) and submodule checkout (`pull`).
+//
+// A PR that bumps a submodule only shows `-Subproject commit <old>` / `+Subproject commit <new>`,
+// which says nothing about what changed. `show-diff` puts the submodule's own commit range
+// above the diff, in the format of `git diff --submodule=log`:
+//
+//   Submodule vendor/libfoo 1a2b3c4..5d6e7f8:
+//     > Fix overflow in the parser
+//     > Release 2.1
+//
+// (`<` marks commits the bump drops, when it moves the pointer backwards or sideways.)
+// The log comes from the local checkout of the submodule; commits it doesn't have yet are
+// fetched first when `submodule-fetch` is `true`, which is off by default since it talks to
+// the submodule's remote. A submodule that isn't checked out is only named.
+use crate::debug_log;
+use crate::utils::run_git;
+use std::error::Error;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// A submodule whose pointer a diff moves.
+///
+/// Fields:
+/// - `path`: Where the submodule lives in the repository
+/// - `old`: The commit before (`None` if the diff adds the submodule)
+/// - `new`: The commit after (`None` if the diff removes it)
+pub struct SubmoduleBump {
+    pub path: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Finds the submodule pointer changes in a unified diff.
+pub fn bumps(diff: &str) -> Vec<SubmoduleBump> {
+    let mut found: Vec<SubmoduleBump> = Vec::new();
+    let mut path: Option<&str> = None;
+    for line in diff.lines() {
+        if let Some(header) = line.strip_prefix("diff --git ") {
+            path = header
+                .rsplit_once(" b/")
+                .map(|(_, after)| after)
+                .or(Some(header));
+            continue;
+        }
+        let (old, commit) = match line.strip_prefix("-Subproject commit ") {
+            Some(commit) => (true, commit),
+            None => match line.strip_prefix("+Subproject commit ") {
+                Some(commit) => (false, commit),
+                None => continue,
+            },
+        };
+        let Some(path) = path else {
+            continue;
+        };
+        // `-dirty` only shows up in local diffs, but costs nothing to drop
+        let commit = commit.trim().trim_end_matches("-dirty").to_string();
+        if found.last().is_none_or(|b| b.path != path) {
+            found.push(SubmoduleBump {
+                path: path.to_string(),
+                old: None,
+                new: None,
+            });
+        }
+        let bump = found.last_mut().expect("pushed above");
+        if old {
+            bump.old = Some(commit);
+        } else {
+            bump.new = Some(commit);
+        }
+    }
+    found
+}
+
+/// Abbreviates a commit id the way git does in `--submodule=log` headers.
+fn short(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+/// Whether the submodule checkout has a commit.
+fn has_commit(dir: &str, sha: &str) -> bool {
+    run_git(&["-C", dir, "cat-file", "-e", &format!("{}^{{commit}}", sha)]).is_some()
+}
+
+/// Describes one bump: a header plus the commits in between, if they can be found.
+fn describe(bump: &SubmoduleBump, fetch: bool) -> String {
+    let (old, new) = match (&bump.old, &bump.new) {
+        (Some(old), Some(new)) => (old, new),
+        (None, Some(new)) => return format!("Submodule {} added at {}\n", bump.path, short(new)),
+        (Some(old), None) => {
+            return format!("Submodule {} removed (was {})\n", bump.path, short(old))
+        }
+        (None, None) => return String::new(),
+    };
+    let header = format!("Submodule {} {}..{}", bump.path, short(old), short(new));
+
+    // An uninitialized submodule is an empty directory; git would fall back to the superproject
+    let Some(dir) = run_git(&["rev-parse", "--show-toplevel"])
+        .map(|top| PathBuf::from(top).join(&bump.path))
+        .filter(|dir| dir.join(".git").exists())
+    else {
+        return format!("{} (not checked out)\n", header);
+    };
+    let dir = dir.to_string_lossy();
+
+    let missing = || !has_commit(&dir, old) || !has_commit(&dir, new);
+    if missing() {
+        if !fetch {
+            return format!(
+                "{} (commits not available locally; set submodule-fetch to fetch them)\n",
+                header
+            );
+        }
+        debug_log!("[DEBUG] Fetching submodule {}", bump.path);
+        let _ = run_git(&["-C", &dir, "fetch", "--quiet"]);
+        if missing() {
+            return format!("{} (commits not found, even after fetching)\n", header);
+        }
+    }
+
+    let log = run_git(&[
+        "-C",
+        &dir,
+        "log",
+        "--left-right",
+        "--format=%m %s",
+        &format!("{}...{}", old, new),
+    ])
+    .unwrap_or_default();
+    let commits: String = log.lines().map(|line| format!("  {}\n", line)).collect();
+    format!("{}:\n{}", header, commits)
+}
+
+/// Describes every submodule a diff bumps, ready to be put above it.
+///
+/// # Parameters
+/// - `diff`: The unified diff
+/// - `fetch`: Fetch the submodule's remote when commits are missing (`submodule-fetch`)
+///
+/// # Returns
+/// - One block per submodule followed by a blank line, or an empty string if the diff
+///   touches no submodule
+pub fn describe_bumps(diff: &str, fetch: bool) -> String {
+    let report: String = bumps(diff).iter().map(|b| describe(b, fetch)).collect();
+    if report.is_empty() {
+        report
+    } else {
+        format!("{}\n", report)
+    }
+}
+
+/// Whether git is set up to update submodules on checkout (`submodule.recurse`).
+pub fn recurse_by_default() -> bool {
+    run_git(&["config", "--type=bool", "submodule.recurse"]).as_deref() == Some("true")
+}
+
+/// Checks out the submodules at the commits the current branch records.
+///
+/// # Returns
+/// - `Err` if `git submodule update` fails
+pub fn update() -> Result<(), Box<dyn Error>> {
+    let status = Command::new("git")
+        .args(["submodule", "update", "--init", "--recursive"])
+        .status()?;
+    if !status.success() {
+        return Err(format!("git submodule update failed ({})", status).into());
+    }
+    Ok(())
+}
@@ -0,0 +1,158 @@
+// Shared table rendering that fits the terminal.
+//
+// Cell widths are measured in display columns (so CJK characters and emoji count as two)
+// with ANSI colour codes ignored. When a table is wider than the terminal, the columns
+// the caller marked as flexible give up space in priority order; everything else keeps
+// its natural width. Output that is not going to a terminal is never constrained.
+//
+// Under `--plain` there is no grid at all: every row becomes a block of `Header: value`
+// lines, separated by blank lines, which screen readers and dumb terminals handle well.
+//
+// Headers are translated here (`header-*` messages), so no table has to do it itself.
+use crate::debug_log;
+use crate::i18n;
+use crate::utils::{is_plain, plain_text};
+use std::env;
+use tabled::builder::Builder;
+use tabled::grid::util::string::get_text_width;
+use tabled::settings::object::Columns;
+use tabled::settings::{Style, Width};
+use tabled::Tabled;
+
+/// Narrowest a flexible column is squeezed to before giving up.
+const MIN_COLUMN_WIDTH: usize = 12;
+
+/// How a flexible column gives up space.
+#[derive(Clone, Copy)]
+pub enum Shrink {
+    /// Wrap onto more lines, breaking between words where possible (free text).
+    Wrap,
+    /// Cut off with a trailing `…` (identifiers, file lists).
+    Truncate,
+}
+
+/// Width of the terminal stdout is attached to, if any.
+///
+/// `COLUMNS` wins when set, so users (and scripts) can force a width.
+pub fn terminal_width() -> Option<usize> {
+    if let Some(cols) = env::var("COLUMNS").ok().and_then(|c| c.parse().ok()) {
+        return Some(cols);
+    }
+    terminal_size::terminal_size().map(|(terminal_size::Width(w), _)| w as usize)
+}
+
+/// Renders rows as a rounded table that fits the terminal width.
+///
+/// # Parameters
+/// - `rows`: The rows to render
+/// - `flex`: Columns allowed to shrink, as `(column index, how)`, in the order they
+///   give up space; the first entry is squeezed first
+///
+/// # Returns
+/// - The rendered table
+pub fn render<T: Tabled>(rows: Vec<T>, flex: &[(usize, Shrink)]) -> String {
+    let headers = T::headers().into_iter().map(String::from).collect();
+    let records = rows
+        .iter()
+        .map(|row| row.fields().into_iter().map(String::from).collect())
+        .collect();
+    render_records(headers, records, flex)
+}
+
+/// Renders plain string records as a rounded table that fits the terminal width, for
+/// tables whose columns are only known at runtime.
+///
+/// # Parameters
+/// - `headers`: The column headers
+/// - `records`: The rows, one cell per header
+/// - `flex`: Columns allowed to shrink, as in [`render`]
+///
+/// # Returns
+/// - The rendered table
+pub fn render_records(
+    headers: Vec<String>,
+    records: Vec<Vec<String>>,
+    flex: &[(usize, Shrink)],
+) -> String {
+    let headers: Vec<String> = headers.iter().map(|h| i18n::header(h)).collect();
+    if is_plain() {
+        return render_plain(&headers, &records);
+    }
+
+    // Natural width of every column: the widest header or cell line in it
+    let mut widths: Vec<usize> = headers.iter().map(|h| get_text_width(h)).collect();
+    for record in &records {
+        for (i, cell) in record.iter().enumerate() {
+            widths[i] = widths[i].max(get_text_width(cell));
+        }
+    }
+
+    let mut builder = Builder::default();
+    builder.push_record(headers);
+    for record in records {
+        builder.push_record(record);
+    }
+    let mut table = builder.build();
+    table.with(Style::rounded());
+
+    let Some(available) = terminal_width() else {
+        return table.to_string();
+    };
+
+    // Each column adds two spaces of padding plus one border; one more closes the row
+    let total: usize = widths.iter().map(|w| w + 3).sum::<usize>() + 1;
+    let mut overflow = total.saturating_sub(available);
+    debug_log!(
+        "[DEBUG] Table width {} on a {}-column terminal",
+        total,
+        available
+    );
+
+    for &(column, shrink) in flex {
+        if overflow == 0 {
+            break;
+        }
+        let Some(&width) = widths.get(column) else {
+            continue;
+        };
+        let give = overflow.min(width.saturating_sub(MIN_COLUMN_WIDTH));
+        if give == 0 {
+            continue;
+        }
+        let target = width - give;
+        match shrink {
+            Shrink::Wrap => {
+                table.modify(Columns::one(column), Width::wrap(target).keep_words(true))
+            }
+            Shrink::Truncate => {
+                table.modify(Columns::one(column), Width::truncate(target).suffix("…"))
+            }
+        };
+        overflow -= give;
+    }
+
+    table.to_string()
+}
+
+/// Renders records as blocks of `Header: value` lines (`--plain`). Empty cells are left
+/// out, and so are rows without any content.
+fn render_plain(headers: &[String], records: &[Vec<String>]) -> String {
+    records
+        .iter()
+        .map(|record| {
+            headers
+                .iter()
+                .zip(record)
+                .filter(|(_, cell)| !cell.trim().is_empty())
+                .map(|(header, cell)| {
+                    // Wrapped cells read as one value
+                    let value = cell.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+                    format!("{}: {}", plain_text(header), plain_text(&value))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
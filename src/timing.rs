@@ -0,0 +1,142 @@
+// Per-request instrumentation behind `--timing`.
+//
+// Every API request is recorded (endpoint pattern and duration) and every lookup answered
+// from local data instead of the API counts as a cache hit. Recording is cheap and always
+// on; the report is only printed when `--timing` was given, on stderr so it never mixes
+// with `--porcelain` or `--quiet` output.
+use crate::debug_log;
+use crate::ui_eprintln;
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// How many endpoints the report lists.
+const SLOWEST_SHOWN: usize = 5;
+
+/// One finished API request.
+struct Sample {
+    endpoint: String,
+    elapsed: Duration,
+}
+
+// Requests can finish on worker threads (see `providers::blocking`), hence the mutexes
+static SAMPLES: Mutex<Vec<Sample>> = Mutex::new(Vec::new());
+static CACHE_HITS: Mutex<BTreeMap<&'static str, usize>> = Mutex::new(BTreeMap::new());
+// Set when `--timing` is given; the report's wall-clock time is measured from here
+static STARTED: OnceLock<Instant> = OnceLock::new();
+
+/// Turns on the report printed by [`print_report`].
+pub fn enable() {
+    let _ = STARTED.set(Instant::now());
+}
+
+/// Records a finished API request.
+///
+/// # Parameters
+/// - `method`: HTTP method (`GET`, `POST`, ...)
+/// - `path`: URL path; PR numbers, SHAs, owner and repository are folded into placeholders
+///   so repeated calls to one endpoint are grouped
+/// - `elapsed`: Time until the response headers arrived (or the request failed)
+pub fn record(method: &str, path: &str, elapsed: Duration) {
+    let endpoint = format!("{} {}", method, endpoint_pattern(path));
+    debug_log!("[DEBUG] {} took {} ms", endpoint, elapsed.as_millis());
+    if let Ok(mut samples) = SAMPLES.lock() {
+        samples.push(Sample { endpoint, elapsed });
+    }
+}
+
+/// Records a lookup answered locally instead of through the API.
+///
+/// # Parameters
+/// - `what`: Short description of the lookup, used to group hits in the report
+pub fn record_cache_hit(what: &'static str) {
+    if let Ok(mut hits) = CACHE_HITS.lock() {
+        *hits.entry(what).or_insert(0) += 1;
+    }
+}
+
+/// Replaces the variable parts of an API path with placeholders.
+///
+/// `/repos/kodelint/git-pr/pulls/42/commits` becomes
+/// `/repos/{owner}/{repo}/pulls/{n}/commits`; 40-character hex segments become `{sha}`.
+fn endpoint_pattern(path: &str) -> String {
+    let mut pattern = Vec::new();
+    let mut segments = path.split('/');
+    while let Some(segment) = segments.next() {
+        if segment == "repos" {
+            pattern.push(segment);
+            if segments.next().is_some() {
+                pattern.push("{owner}");
+            }
+            if segments.next().is_some() {
+                pattern.push("{repo}");
+            }
+        } else if !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) {
+            pattern.push("{n}");
+        } else if segment.len() == 40 && segment.chars().all(|c| c.is_ascii_hexdigit()) {
+            pattern.push("{sha}");
+        } else {
+            pattern.push(segment);
+        }
+    }
+    pattern.join("/")
+}
+
+/// Prints the `--timing` summary to stderr; does nothing unless [`enable`] was called.
+///
+/// Shows the number of API calls, wall-clock and cumulative request time (they differ
+/// when requests run concurrently), cache hits and the endpoints that took longest in total.
+pub fn print_report() {
+    let Some(started) = STARTED.get() else {
+        return;
+    };
+    let samples = SAMPLES
+        .lock()
+        .map(|mut s| std::mem::take(&mut *s))
+        .unwrap_or_default();
+    let hits = CACHE_HITS.lock().map(|h| h.clone()).unwrap_or_default();
+
+    let in_requests: Duration = samples.iter().map(|s| s.elapsed).sum();
+    let hit_count: usize = hits.values().sum();
+    ui_eprintln!(
+        "⏱️  {} API call{} in {} ({} in requests), {} cache hit{}",
+        samples.len(),
+        if samples.len() == 1 { "" } else { "s" },
+        seconds(started.elapsed()),
+        seconds(in_requests),
+        hit_count,
+        if hit_count == 1 { "" } else { "s" },
+    );
+    for (what, count) in &hits {
+        ui_eprintln!("   ♻️  {} × {}", count, what);
+    }
+
+    // Group by endpoint: (calls, total, slowest single call)
+    let mut endpoints: BTreeMap<String, (usize, Duration, Duration)> = BTreeMap::new();
+    for sample in samples {
+        let entry = endpoints.entry(sample.endpoint).or_default();
+        entry.0 += 1;
+        entry.1 += sample.elapsed;
+        entry.2 = entry.2.max(sample.elapsed);
+    }
+    let mut endpoints: Vec<_> = endpoints.into_iter().collect();
+    endpoints.sort_by_key(|(_, (_, total, _))| std::cmp::Reverse(*total));
+
+    if !endpoints.is_empty() {
+        eprintln!("   Slowest endpoints:");
+    }
+    for (endpoint, (calls, total, max)) in endpoints.into_iter().take(SLOWEST_SHOWN) {
+        eprintln!(
+            "   {:>7}  {:>3}×  {}  (max {})",
+            seconds(total),
+            calls,
+            endpoint,
+            seconds(max)
+        );
+    }
+}
+
+/// Formats a duration as seconds with two decimals (`1.25s`).
+fn seconds(duration: Duration) -> String {
+    format!("{:.2}s", duration.as_secs_f64())
+}
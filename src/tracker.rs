@@ -0,0 +1,152 @@
+// Issue-tracker tickets (Jira, Linear) referenced by PRs.
+//
+// Teams that track work outside GitHub name branches and titles after tickets:
+// `feature/ABC-123-login`, "ABC-123: Fix the login crash". Keys of that shape (a project
+// key of capital letters and digits, a dash and a number) are picked out of a PR's title
+// and head branch. With `tracker-url` configured they link to the tracker: the `ticket`
+// column of `list` shows them as clickable keys, and `show-details` looks them up to show
+// each ticket's title and status.
+//
+// Lookups need a token in `GIT_PR_TRACKER_TOKEN`, which is kept out of config files:
+//   jira     `email:api-token` for Jira Cloud (basic auth), or a personal access token
+//            for Jira Server / Data Center (bearer auth)
+//   linear   a personal API key
+// Without a token tickets are only linked. Keys the tracker doesn't know (a 404, or no
+// such issue on Linear) are taken for false positives and left out.
+use crate::config::Config;
+use crate::debug_log;
+use regex::Regex;
+use reqwest::blocking::Client;
+use serde_json::{json, Value};
+use std::env;
+use std::error::Error;
+
+/// Accepted values of the `tracker` config key.
+pub const TRACKERS: [&str; 2] = ["jira", "linear"];
+
+/// Environment variable holding the tracker's API token.
+const TOKEN_ENV: &str = "GIT_PR_TRACKER_TOKEN";
+
+/// Linear's API endpoint (its web UI lives under `tracker-url`).
+const LINEAR_API: &str = "https://api.linear.app/graphql";
+
+/// Prefixes that look like ticket keys but are standards and algorithms (`UTF-8`, `SHA-256`).
+const NOT_TICKETS: &[&str] = &[
+    "UTF", "SHA", "ISO", "RFC", "CVE", "GPL", "LGPL", "TLS", "AES",
+];
+
+/// A ticket as the tracker describes it.
+///
+/// Fields:
+/// - `key`: The ticket key, e.g. `ABC-123`
+/// - `title`: The ticket's summary
+/// - `status`: Its workflow state, e.g. `In Progress`
+pub struct Ticket {
+    pub key: String,
+    pub title: String,
+    pub status: String,
+}
+
+/// Ticket keys mentioned in the given texts (a PR's title and head branch), in order of
+/// appearance and without duplicates.
+pub fn ticket_keys(texts: &[&str]) -> Vec<String> {
+    let key = Regex::new(r"\b([A-Z][A-Z0-9]{1,9})-([1-9][0-9]{0,6})\b").expect("valid regex");
+    let mut keys: Vec<String> = Vec::new();
+    for text in texts {
+        for found in key.captures_iter(text) {
+            if NOT_TICKETS.contains(&&found[1]) {
+                continue;
+            }
+            let found = found[0].to_string();
+            if !keys.contains(&found) {
+                keys.push(found);
+            }
+        }
+    }
+    keys
+}
+
+/// The web page of a ticket, if `tracker-url` is configured.
+pub fn ticket_url(config: &Config, key: &str) -> Option<String> {
+    let base = config.tracker_url.as_deref()?.trim_end_matches('/');
+    Some(match config.tracker() {
+        "linear" => format!("{}/issue/{}", base, key),
+        _ => format!("{}/browse/{}", base, key),
+    })
+}
+
+/// Whether a tracker token is set, so tickets can be looked up.
+pub fn has_token() -> bool {
+    env::var(TOKEN_ENV).is_ok_and(|token| !token.is_empty())
+}
+
+/// Looks a ticket up in the tracker.
+///
+/// # Returns
+/// - `Ok(Some(ticket))` with its title and status
+/// - `Ok(None)` without `tracker-url` or a token, or if the tracker has no such ticket
+/// - `Err` if the tracker can't be reached or refuses the token
+pub fn lookup(config: &Config, key: &str) -> Result<Option<Ticket>, Box<dyn Error>> {
+    let (Some(base), Ok(token)) = (config.tracker_url.as_deref(), env::var(TOKEN_ENV)) else {
+        return Ok(None);
+    };
+    let client = Client::new();
+
+    if config.tracker() == "linear" {
+        let query = "query($id: String!) { issue(id: $id) { title state { name } } }";
+        debug_log!("[DEBUG] Looking up {} on Linear", key);
+        let resp = client
+            .post(LINEAR_API)
+            .header("Authorization", &token)
+            .header("User-Agent", "git-pr")
+            .json(&json!({ "query": query, "variables": { "id": key } }))
+            .send()?;
+        if !resp.status().is_success() {
+            return Err(format!("Linear answered {} for {}", resp.status(), key).into());
+        }
+        let body: Value = resp.json()?;
+        let issue = &body["data"]["issue"];
+        if issue.is_null() {
+            return Ok(None);
+        }
+        return Ok(Some(Ticket {
+            key: key.to_string(),
+            title: issue["title"].as_str().unwrap_or_default().to_string(),
+            status: issue["state"]["name"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+        }));
+    }
+
+    let url = format!(
+        "{}/rest/api/2/issue/{}?fields=summary,status",
+        base.trim_end_matches('/'),
+        key
+    );
+    debug_log!("[DEBUG] Looking up {} at {}", key, url);
+    // Jira Cloud wants basic auth with the account's email, Server/Data Center a bearer PAT
+    let request = match token.split_once(':') {
+        Some((email, api_token)) => client.get(&url).basic_auth(email, Some(api_token)),
+        None => client.get(&url).bearer_auth(&token),
+    };
+    let resp = request.header("User-Agent", "git-pr").send()?;
+    if resp.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !resp.status().is_success() {
+        return Err(format!("Jira answered {} for {}", resp.status(), key).into());
+    }
+    let body: Value = resp.json()?;
+    Ok(Some(Ticket {
+        key: key.to_string(),
+        title: body["fields"]["summary"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        status: body["fields"]["status"]["name"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+    }))
+}
@@ -0,0 +1,87 @@
+// Progress of `git pr triage` sessions.
+//
+// Every PR a maintainer finishes or skips during a session is recorded under
+// `.git/git-pr/triage`, one `<number> <outcome>` line each, so quitting halfway and
+// running `git pr triage` again resumes with the next PR instead of starting over.
+// PRs that got a label or an assignee drop out of the untriaged set by themselves; the
+// file matters for the ones that were skipped or only commented on. Writes hold the
+// repository lock (see `lock`).
+use crate::debug_log;
+use crate::lock;
+use crate::utils::run_git;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+/// How a PR left the triage session.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    /// Triaged: labeled, assigned, reviewers requested or commented on
+    Done,
+    /// Passed over; not offered again until the progress is reset
+    Skipped,
+}
+
+impl Outcome {
+    fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Done => "done",
+            Outcome::Skipped => "skipped",
+        }
+    }
+}
+
+/// File holding the progress of the current repository.
+fn progress_path() -> Result<PathBuf, Box<dyn Error>> {
+    let git_dir = run_git(&["rev-parse", "--git-dir"]).ok_or("Not inside a git repository")?;
+    Ok(PathBuf::from(git_dir).join("git-pr").join("triage"))
+}
+
+/// Loads the recorded outcomes, keyed by PR number. Missing or unreadable progress
+/// counts as a fresh session.
+pub fn load_progress() -> BTreeMap<u64, Outcome> {
+    let Ok(text) = progress_path().and_then(|path| Ok(fs::read_to_string(path)?)) else {
+        return BTreeMap::new();
+    };
+    text.lines()
+        .filter_map(|line| {
+            let (number, outcome) = line.split_once(' ')?;
+            let outcome = match outcome.trim() {
+                "done" => Outcome::Done,
+                "skipped" => Outcome::Skipped,
+                _ => return None,
+            };
+            Some((number.parse().ok()?, outcome))
+        })
+        .collect()
+}
+
+/// Records how a PR left the session.
+pub fn record(number: u64, outcome: Outcome) -> Result<(), Box<dyn Error>> {
+    let path = progress_path()?;
+    let _lock = lock::acquire()?;
+    let mut progress = load_progress();
+    progress.insert(number, outcome);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let text: String = progress
+        .iter()
+        .map(|(number, outcome)| format!("{} {}\n", number, outcome.as_str()))
+        .collect();
+    debug_log!("[DEBUG] Recording PR #{} as {:?}", number, outcome);
+    fs::write(path, text)?;
+    Ok(())
+}
+
+/// Forgets the progress, so the next session starts from the first untriaged PR.
+pub fn reset_progress() -> Result<(), Box<dyn Error>> {
+    let path = progress_path()?;
+    let _lock = lock::acquire()?;
+    match fs::remove_file(&path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
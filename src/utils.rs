@@ -3,6 +3,22 @@
 use std::env;
 // `Command` allows us to spawn system processes like invoking `git`.
 use std::process::Command;
+// Process-wide quiet and plain flags, set once from the command line.
+use std::sync::atomic::{AtomicBool, Ordering};
+// Used to match branch names against the configured branch-name template.
+use regex::Regex;
+// Timestamps from the API are UTC; absolute times are shown in the local zone.
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+
+/// Accepted values of `--timestamps` and the `timestamps` config key.
+pub const TIMESTAMP_STYLES: [&str; 3] = ["relative", "absolute", "iso"];
+
+/// strftime format for `absolute` timestamps unless `date-format` says otherwise.
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Key under `branch.<name>` in `.git/config` recording which PR a branch was pulled from.
+const BRANCH_PR_KEY: &str = "git-pr-number";
 
 /// Determines whether debug logging or verbose diagnostics should be enabled,
 /// based on the presence and value of the `DEBUG` environment variable.
@@ -55,22 +71,179 @@ macro_rules! debug_log {
     };
 }
 
-/// Attempts to retrieve the `origin` remote URL from the local Git repository.
+/// Set once at startup from `--quiet`; read by [`status_log!`].
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Turns quiet mode on or off for the rest of the process.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether `--quiet` was given, i.e. only essential values should reach stdout.
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Set once at startup from `--read-only` or the `read-only` setting; checked before every
+/// API write and `git push`.
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Turns read-only mode on or off for the rest of the process.
+pub fn set_read_only(read_only: bool) {
+    READ_ONLY.store(read_only, Ordering::Relaxed);
+}
+
+/// Fails if read-only mode forbids `what` (e.g. "pushing feature-x").
+pub fn ensure_writable(what: &str) -> Result<(), String> {
+    if READ_ONLY.load(Ordering::Relaxed) {
+        return Err(format!(
+            "Read-only mode: refusing {}; drop --read-only or the read-only setting to allow changes",
+            what
+        ));
+    }
+    Ok(())
+}
+
+/// Set once at startup from `--plain`; read by [`plain_text`] and the table renderer.
+static PLAIN: AtomicBool = AtomicBool::new(false);
+
+/// Turns plain output (no emoji, colors or box-drawing tables) on or off for the rest of
+/// the process.
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
+
+/// Whether `--plain` was given, i.e. output should suit screen readers and dumb terminals.
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
+
+/// Words standing in for the emoji that carry meaning, under `--plain`.
+const EMOJI_LABELS: &[(char, &str)] = &[
+    ('✅', "[ok]"),
+    ('❌', "[error]"),
+    ('⚠', "[warning]"),
+    ('❔', "[unknown]"),
+    ('⏳', "[pending]"),
+    ('⛔', "[blocked]"),
+    ('🔐', "[sensitive]"),
+    ('🌱', "[first-time contributor]"),
+    ('🚨', "[alert]"),
+];
+
+/// Whether `c` is an emoji (or a joiner or variation selector of one).
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF // pictographs, emoticons, symbols
+        | 0x2300..=0x23FF // ⏱ ⏳
+        | 0x2600..=0x27BF // ⚠ ✅ ❌ ✍
+        | 0x2B00..=0x2BFF // ⭐
+        | 0x200D | 0xFE0F // joiner, emoji presentation
+    )
+}
+
+/// Returns `text` as shown under `--plain`: emoji that carry meaning become words
+/// (`✅` → `[ok]`), the others are dropped together with the spaces after them.
+/// Without `--plain` the text is returned unchanged.
+pub fn plain_text(text: &str) -> String {
+    if !is_plain() {
+        return text.to_string();
+    }
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if !is_emoji(c) {
+            plain.push(c);
+            continue;
+        }
+        if let Some((_, label)) = EMOJI_LABELS.iter().find(|(emoji, _)| *emoji == c) {
+            plain.push_str(label);
+            // One space between the label and what follows is enough
+            while chars.peek().is_some_and(|&next| is_emoji(next)) {
+                chars.next();
+            }
+            if chars.peek() == Some(&' ') {
+                while chars.peek() == Some(&' ') {
+                    chars.next();
+                }
+                plain.push(' ');
+            }
+            continue;
+        }
+        while chars
+            .peek()
+            .is_some_and(|&next| next == ' ' || is_emoji(next))
+        {
+            chars.next();
+        }
+    }
+    plain
+}
+
+/// Exits the process with `code`, printing the `--timing` report and the request
+/// budget summary first.
+///
+/// `std::process::exit` skips the end of `main`, where the reports are normally printed.
+pub fn exit(code: i32) -> ! {
+    crate::timing::print_report();
+    crate::budget::print_summary();
+    std::process::exit(code)
+}
+
+/// A macro for decorative progress and status lines (emoji, "✅ Done" messages).
+///
+/// Behaves like `println!`, except that nothing is printed under `--quiet`, so the
+/// output of scripted invocations is limited to the values commands print explicitly.
+///
+/// # Usage:
+/// ```rust
+/// status_log!("✅ Created PR #{}", number);
+/// ```
+#[macro_export]
+macro_rules! status_log {
+    ($($arg:tt)*) => {
+        if !$crate::utils::is_quiet() {
+            println!("{}", $crate::utils::plain_text(&format!($($arg)*)));
+        }
+    };
+}
+
+/// `println!` for decorated user-facing lines (emoji markers, headings).
+///
+/// Under `--plain` the emoji are replaced by words or dropped (see [`plain_text`]);
+/// unlike [`status_log!`], the line is printed under `--quiet` too.
+#[macro_export]
+macro_rules! ui_println {
+    ($($arg:tt)*) => {
+        println!("{}", $crate::utils::plain_text(&format!($($arg)*)))
+    };
+}
+
+/// `eprintln!` for decorated warnings and errors; see [`ui_println!`].
+#[macro_export]
+macro_rules! ui_eprintln {
+    ($($arg:tt)*) => {
+        eprintln!("{}", $crate::utils::plain_text(&format!($($arg)*)))
+    };
+}
+
+/// Attempts to retrieve the URL of the given remote from the local Git repository.
 ///
-/// This function invokes the shell command `git remote get-url origin` and parses the output.
+/// This function invokes the shell command `git remote get-url <remote>` and parses the output.
 /// It can be used to determine where the repo was cloned from, useful for identifying provider (e.g., GitHub).
+/// The remote is usually `origin`, but can be overridden via the `remote` config key.
 ///
 /// # Returns:
 /// - `Some(String)` containing the remote URL if successful.
 /// - `None` if Git fails or the command exits with a non-zero code.
-pub fn get_remote_url() -> Option<String> {
+pub fn get_remote_url(remote: &str) -> Option<String> {
     // Emit a debug message before executing the Git command, if debugging is enabled.
-    debug_log!("[DEBUG] Getting remote origin URL...");
+    debug_log!("[DEBUG] Getting remote {} URL...", remote);
 
-    // Use `git remote get-url origin` to retrieve the remote origin.
+    // Use `git remote get-url <remote>` to retrieve the remote URL.
     // This is the canonical way to get the upstream URL in Git.
     let output = Command::new("git")
-        .args(["remote", "get-url", "origin"])
+        .args(["remote", "get-url", remote])
         .output()
         .expect("Failed to get remote URL"); // Panic if the command itself fails to launch
 
@@ -94,3 +267,467 @@ pub fn get_remote_url() -> Option<String> {
         None
     }
 }
+
+/// Runs a `git` command and returns its trimmed stdout on success.
+///
+/// This is a small convenience wrapper for read-only Git queries (config lookups,
+/// branch names, etc.) where the caller only cares about the output.
+///
+/// # Returns:
+/// - `Some(String)` with the trimmed stdout if Git exited successfully.
+/// - `None` if Git could not be launched or exited with a non-zero code.
+pub fn run_git(args: &[&str]) -> Option<String> {
+    debug_log!("[DEBUG] Running: git {}", args.join(" "));
+
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        debug_log!("[DEBUG] git {} failed ({})", args.join(" "), output.status);
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Expands `{name}` placeholders in a template string.
+///
+/// Unknown placeholders are left untouched so typos are visible in the output
+/// rather than silently disappearing.
+///
+/// # Example:
+/// ```rust
+/// let name = render_template("pr/{number}-{author}", &[("number", "42"), ("author", "alice")]);
+/// assert_eq!(name, "pr/42-alice");
+/// ```
+pub fn render_template(template: &str, values: &[(&str, &str)]) -> String {
+    // Single pass over the template, so a value that itself contains `{owner}`
+    // is copied verbatim instead of being expanded by a later key.
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let tail = &rest[start..];
+
+        let value = tail.find('}').and_then(|end| {
+            let key = &tail[1..end];
+            values
+                .iter()
+                .find(|(name, _)| *name == key)
+                .map(|(_, value)| (*value, end + 1))
+        });
+
+        match value {
+            Some((value, consumed)) => {
+                rendered.push_str(value);
+                rest = &tail[consumed..];
+            }
+            None => {
+                rendered.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Returns the name of the currently checked-out branch, or `None` on a detached HEAD.
+pub fn current_branch() -> Option<String> {
+    run_git(&["symbolic-ref", "--quiet", "--short", "HEAD"])
+}
+
+/// Returns the remote branch name the given local branch tracks, if any.
+///
+/// For `feature-x` tracking `origin/feature-x` this returns `feature-x`.
+pub fn upstream_branch(branch: &str) -> Option<String> {
+    let merge_ref = run_git(&["config", &format!("branch.{}.merge", branch)])?;
+    Some(merge_ref.trim_start_matches("refs/heads/").to_string())
+}
+
+/// Records the PR number a local branch was created from in `.git/config`
+/// (`branch.<name>.git-pr-number`), so later commands can omit the PR number.
+///
+/// Failures are only logged: the metadata is a convenience, not a requirement.
+pub fn record_branch_pr_number(branch: &str, pr_number: &str) {
+    let key = format!("branch.{}.{}", branch, BRANCH_PR_KEY);
+    if run_git(&["config", "--local", &key, pr_number]).is_none() {
+        debug_log!("[DEBUG] Could not record {} = {}", key, pr_number);
+    }
+}
+
+/// Resolves the PR number for a local branch without touching the network.
+///
+/// Resolution order:
+/// 1. The `branch.<name>.git-pr-number` metadata written by `git pr pull`
+/// 2. Matching the branch name against the branch-name template
+///    (`branch-template`, or the default fork pattern `{owner}-pr-{number}`)
+///
+/// # Returns:
+/// - `Some(String)` with the PR number if it could be determined locally.
+/// - `None` otherwise (callers may then fall back to an API lookup).
+pub fn local_branch_pr_number(branch: &str, template: Option<&str>) -> Option<String> {
+    if let Some(number) = run_git(&["config", &format!("branch.{}.{}", branch, BRANCH_PR_KEY)]) {
+        debug_log!("[DEBUG] Found PR #{} in branch metadata", number);
+        return Some(number);
+    }
+
+    let number = number_in_branch(template.unwrap_or("{owner}-pr-{number}"), branch)?;
+    debug_log!(
+        "[DEBUG] Extracted PR #{} from branch name {}",
+        number,
+        branch
+    );
+    Some(number)
+}
+
+/// Reads the PR number out of a branch name made from `template`.
+///
+/// A template may use `{number}` more than once; the name only matches if every
+/// occurrence holds the same number.
+///
+/// # Returns:
+/// - `Some(String)` with the number if the name matches the template.
+/// - `None` if it doesn't, or the template has no `{number}`.
+fn number_in_branch(template: &str, branch: &str) -> Option<String> {
+    let captures = template_to_regex(template)?.captures(branch)?;
+    let mut numbers = captures.iter().skip(1).flatten().map(|m| m.as_str());
+    let number = numbers.next()?;
+    numbers
+        .all(|other| other == number)
+        .then(|| number.to_string())
+}
+
+/// Converts a branch-name template into an anchored regex.
+///
+/// Every `{number}` becomes a captured run of digits (the only capture groups, so callers
+/// can compare the occurrences), any other placeholder matches arbitrary text, and
+/// everything else is matched literally. Templates without a `{number}` placeholder can't
+/// be reversed, so `None` is returned for them.
+fn template_to_regex(template: &str) -> Option<Regex> {
+    if !template.contains("{number}") {
+        return None;
+    }
+
+    let placeholder = Regex::new(r"\{[a-z]+\}").ok()?;
+    let mut pattern = String::from("^");
+    let mut last = 0;
+
+    for m in placeholder.find_iter(template) {
+        pattern.push_str(&regex::escape(&template[last..m.start()]));
+        if m.as_str() == "{number}" {
+            pattern.push_str(r"(\d+)");
+        } else {
+            pattern.push_str(".+?");
+        }
+        last = m.end();
+    }
+    pattern.push_str(&regex::escape(&template[last..]));
+    pattern.push('$');
+
+    Regex::new(&pattern).ok()
+}
+
+/// Pushes the current branch to its upstream, setting one up on `remote` if missing.
+///
+/// Git's own output is passed through to the terminal so users see progress and
+/// rejection messages (e.g. non-fast-forward) exactly as with a manual `git push`.
+///
+/// # Returns:
+/// - `Ok(())` if the push succeeded.
+/// - `Err` if HEAD is detached or `git push` failed.
+pub fn push_current_branch(remote: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let branch = current_branch().ok_or("HEAD is not on a branch")?;
+    ensure_writable(&format!("to push {}", branch))?;
+
+    // Only pass `-u <remote> <branch>` if the branch doesn't track anything yet
+    let status = if upstream_branch(&branch).is_some() {
+        debug_log!("[DEBUG] Pushing {} to its upstream", branch);
+        Command::new("git").arg("push").status()?
+    } else {
+        debug_log!(
+            "[DEBUG] Pushing {} to {} and setting upstream",
+            branch,
+            remote
+        );
+        Command::new("git")
+            .args(["push", "-u", remote, &branch])
+            .status()?
+    };
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("git push failed for branch {}", branch).into())
+    }
+}
+
+/// Returns `true` if `branch` is missing on `remote` or has local commits not yet pushed.
+///
+/// Relies on the remote-tracking ref (`refs/remotes/<remote>/<branch>`), so it reflects
+/// the state as of the last fetch/push.
+pub fn branch_needs_push(remote: &str, branch: &str) -> bool {
+    let tracking = format!("refs/remotes/{}/{}", remote, branch);
+    if run_git(&["rev-parse", "--verify", "--quiet", &tracking]).is_none() {
+        return true;
+    }
+    run_git(&["rev-list", "--count", &format!("{}..HEAD", tracking)])
+        .and_then(|count| count.parse::<u64>().ok())
+        .is_some_and(|count| count > 0)
+}
+
+/// Pushes `branch` to `remote` and sets it as the branch's upstream.
+pub fn push_branch(remote: &str, branch: &str) -> Result<(), Box<dyn std::error::Error>> {
+    ensure_writable(&format!("to push {}", branch))?;
+    debug_log!("[DEBUG] Pushing {} to {}", branch, remote);
+    let status = Command::new("git")
+        .args(["push", "-u", remote, branch])
+        .status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("git push to {} failed for branch {}", remote, branch).into())
+    }
+}
+
+/// Formats an API timestamp for display.
+///
+/// # Parameters
+/// - `time`: The timestamp (UTC)
+/// - `style`: One of [`TIMESTAMP_STYLES`]:
+///   - `relative`: Time elapsed, e.g. `3d`, `5h`, `12m` (`now` under a minute)
+///   - `absolute`: Local date and time in `date_format`, e.g. `2024-05-01 14:03`
+///   - `iso`: RFC 3339 in UTC, e.g. `2024-05-01T12:03:00Z`
+/// - `date_format`: strftime-style format used by `absolute`; an invalid format
+///   falls back to [`DEFAULT_DATE_FORMAT`]
+///
+/// Unknown styles fall back to `relative`.
+pub fn format_timestamp(time: DateTime<Utc>, style: &str, date_format: &str) -> String {
+    match style {
+        "absolute" => {
+            let format = if is_valid_date_format(date_format) {
+                date_format
+            } else {
+                DEFAULT_DATE_FORMAT
+            };
+            time.with_timezone(&Local).format(format).to_string()
+        }
+        "iso" => time.to_rfc3339_opts(SecondsFormat::Secs, true),
+        _ => {
+            let elapsed = Utc::now() - time;
+            if elapsed.num_days() > 0 {
+                format!("{}d", elapsed.num_days())
+            } else if elapsed.num_hours() > 0 {
+                format!("{}h", elapsed.num_hours())
+            } else if elapsed.num_minutes() > 0 {
+                format!("{}m", elapsed.num_minutes())
+            } else {
+                "now".to_string()
+            }
+        }
+    }
+}
+
+/// Whether a strftime-style format parses cleanly (chrono panics on bad specifiers
+/// at render time, so formats from config are checked up front).
+pub fn is_valid_date_format(format: &str) -> bool {
+    StrftimeItems::new(format).all(|item| !matches!(item, Item::Error))
+}
+
+/// Parses a byte size such as `500KB`, `1.5 MB`, `2G` or `1048576` (binary units, 1 KB = 1024 B).
+///
+/// # Returns
+/// - `Err(message)` for anything that isn't a number followed by an optional unit.
+pub fn parse_size(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("Invalid size '{}' (expected e.g. 500KB, 2MB)", text))?;
+    let factor: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        other => {
+            return Err(format!(
+                "Unknown size unit '{}' (use B, KB, MB or GB)",
+                other
+            ))
+        }
+    };
+    Ok((number * factor as f64) as u64)
+}
+
+/// Parses a duration such as `25m`, `1h30m`, `90s`, `7d` or `45` (a bare number is minutes).
+///
+/// # Returns
+/// - The duration in seconds
+/// - `Err(message)` for anything else, or for a zero duration
+pub fn parse_duration(text: &str) -> Result<u64, String> {
+    let text = text.trim();
+    let invalid = || format!("Invalid duration '{}' (expected e.g. 25m, 1h30m, 7d)", text);
+    if let Ok(minutes) = text.parse::<u64>() {
+        return (minutes > 0).then_some(minutes * 60).ok_or_else(invalid);
+    }
+
+    let mut seconds = 0;
+    let mut number = String::new();
+    for c in text.chars() {
+        match c.to_ascii_lowercase() {
+            '0'..='9' => number.push(c),
+            unit @ ('d' | 'h' | 'm' | 's') => {
+                let value: u64 = number.parse().map_err(|_| invalid())?;
+                seconds += value
+                    * match unit {
+                        'd' => 86400,
+                        'h' => 3600,
+                        'm' => 60,
+                        _ => 1,
+                    };
+                number.clear();
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    if !number.is_empty() || seconds == 0 {
+        return Err(invalid());
+    }
+    Ok(seconds)
+}
+
+/// Formats a number of seconds compactly: `40s`, `25m`, `1h 05m`.
+pub fn format_duration(seconds: u64) -> String {
+    match seconds {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s => format!("{}h {:02}m", s / 3600, s % 3600 / 60),
+    }
+}
+
+/// Splits a command line into arguments, shell-style: whitespace separates arguments,
+/// single and double quotes group them, and a backslash escapes the next character.
+///
+/// # Example:
+/// ```rust
+/// let args = split_args(r#"--label "needs review" --sort updated"#)?;
+/// assert_eq!(args, ["--label", "needs review", "--sort", "updated"]);
+/// ```
+///
+/// # Returns
+/// - `Err(message)` on an unterminated quote or a trailing backslash.
+pub fn split_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None | Some('"'), '\\') => {
+                let escaped = chars
+                    .next()
+                    .ok_or_else(|| format!("Trailing backslash in '{}'", line))?;
+                current.get_or_insert_with(String::new).push(escaped);
+            }
+            (None, c) if c.is_whitespace() => args.extend(current.take()),
+            (_, c) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(format!("Unterminated quote in '{}'", line));
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+/// Prints `question` and reads one line of answer from stdin.
+///
+/// # Returns
+/// - The answer, trimmed
+/// - `None` at the end of input (Ctrl-D) or if stdin can't be read
+pub fn prompt(question: &str) -> Option<String> {
+    use std::io::{self, Write};
+
+    print!("{}", question);
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    match io::stdin().read_line(&mut answer) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(answer.trim().to_string()),
+    }
+}
+
+/// Opens `url` in the user's web browser.
+///
+/// Honors `BROWSER`, otherwise uses the platform's opener (`open`, `start` or `xdg-open`).
+///
+/// # Returns
+/// - `Err` if no browser could be started
+pub fn open_in_browser(url: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut command = match env::var("BROWSER") {
+        Ok(browser) if !browser.is_empty() => Command::new(browser),
+        _ if cfg!(target_os = "macos") => Command::new("open"),
+        _ if cfg!(windows) => {
+            let mut command = Command::new("cmd");
+            command.args(["/C", "start", ""]);
+            command
+        }
+        _ => Command::new("xdg-open"),
+    };
+    let status = command
+        .arg(url)
+        .status()
+        .map_err(|e| format!("Could not start a browser: {}", e))?;
+    if !status.success() {
+        return Err(format!("The browser exited with {}", status).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_template_substitutes_in_one_pass() {
+        let name = render_template(
+            "pr/{number}-{author}",
+            &[("author", "{number}"), ("number", "42")],
+        );
+        assert_eq!(name, "pr/42-{number}");
+    }
+
+    #[test]
+    fn render_template_keeps_unknown_placeholders() {
+        let name = render_template("pr/{nubmer}/{", &[("number", "42")]);
+        assert_eq!(name, "pr/{nubmer}/{");
+    }
+
+    #[test]
+    fn number_in_branch_follows_the_template() {
+        assert_eq!(
+            number_in_branch("{owner}-pr-{number}", "alice-pr-42"),
+            Some("42".to_string())
+        );
+        assert_eq!(number_in_branch("{owner}-pr-{number}", "alice-pr-x"), None);
+        assert_eq!(number_in_branch("review/{author}", "review/alice"), None);
+    }
+
+    #[test]
+    fn repeated_numbers_must_agree() {
+        assert_eq!(
+            number_in_branch("pr/{number}-{number}", "pr/7-7"),
+            Some("7".to_string())
+        );
+        assert_eq!(number_in_branch("pr/{number}-{number}", "pr/1-2"), None);
+    }
+}
@@ -0,0 +1,85 @@
+// `git pr version`: what exactly is installed.
+//
+// The JSON form is meant for package managers, bug report templates and update checks;
+// its keys are only ever added to, never renamed.
+use crate::providers::{api_versions, compiled_providers};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+/// Build metadata of this binary.
+///
+/// Fields:
+/// - `name` / `version`: Package name and version from Cargo.toml
+/// - `commit`: Short commit hash the binary was built from (`unknown` outside git)
+/// - `build_date`: UTC date of the build (`YYYY-MM-DD`)
+/// - `target`: Operating system and architecture
+/// - `providers`: Providers compiled in
+/// - `api_versions`: API version each provider is pinned to
+#[derive(Serialize)]
+pub struct BuildInfo {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub commit: &'static str,
+    pub build_date: &'static str,
+    pub target: String,
+    pub providers: Vec<&'static str>,
+    pub api_versions: BTreeMap<&'static str, &'static str>,
+}
+
+impl BuildInfo {
+    /// Collects the metadata embedded at compile time (see `build.rs`).
+    pub fn current() -> Self {
+        BuildInfo {
+            name: env!("CARGO_PKG_NAME"),
+            version: env!("CARGO_PKG_VERSION"),
+            commit: env!("GIT_PR_COMMIT"),
+            build_date: env!("GIT_PR_BUILD_DATE"),
+            target: format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH),
+            providers: compiled_providers(),
+            api_versions: api_versions().into_iter().collect(),
+        }
+    }
+}
+
+/// Prints the build metadata, as JSON or as a short human-readable summary.
+pub fn print(json: bool) -> Result<(), serde_json::Error> {
+    let info = BuildInfo::current();
+    if json {
+        println!("{}", serde_json::to_string_pretty(&info)?);
+        return Ok(());
+    }
+
+    println!(
+        "{} {} ({} {})",
+        info.name, info.version, info.commit, info.build_date
+    );
+    println!("target:    {}", info.target);
+    println!("providers: {}", providers_label(&info.providers));
+    for (provider, version) in &info.api_versions {
+        println!("{} API: {}", provider, version);
+    }
+    Ok(())
+}
+
+/// Comma-separated provider list, or `none` for a build without provider features.
+fn providers_label(providers: &[&str]) -> String {
+    if providers.is_empty() {
+        "none".to_string()
+    } else {
+        providers.join(", ")
+    }
+}
+
+/// The `--version` line, e.g. `0.1.0 (1a2b3c4d5e6f, providers: github)`.
+pub fn version_line() -> &'static str {
+    static LINE: OnceLock<String> = OnceLock::new();
+    LINE.get_or_init(|| {
+        format!(
+            "{} ({}, providers: {})",
+            env!("CARGO_PKG_VERSION"),
+            env!("GIT_PR_COMMIT"),
+            providers_label(&compiled_providers())
+        )
+    })
+}
@@ -0,0 +1,162 @@
+// Throwaway worktrees for `git pr run`.
+//
+// The PR's head commit is checked out detached in a fresh directory under the system's
+// temp dir (`git worktree add --detach`), so the command runs against the PR's code while
+// the user's own checkout, branch and uncommitted changes stay untouched. The worktree is
+// removed afterwards unless it is kept for a closer look.
+//
+// Removal hangs off a guard ([`Worktree`]) so that it happens however the command ends.
+// Ctrl-C would still kill git-pr before the guard runs, so while a command runs in the
+// worktree ([`run`]) git-pr only notes the interrupt and leaves stopping to the command,
+// which gets Ctrl-C from the terminal as well.
+use crate::debug_log;
+use crate::status_log;
+use crate::ui_eprintln;
+use crate::utils::run_git;
+use colored::Colorize;
+use std::env;
+use std::error::Error;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set when Ctrl-C arrives while a command runs in a worktree.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// A worktree made by [`add`], removed when the guard is dropped.
+///
+/// Fields:
+/// - `dir`: The worktree's directory
+/// - `keep`: Leave the worktree in place (and say where) instead of removing it
+pub struct Worktree {
+    pub dir: PathBuf,
+    pub keep: bool,
+}
+
+impl Worktree {
+    /// Checks out `commit` detached in a new temporary worktree (see [`add`]).
+    pub fn add(name: &str, commit: &str, keep: bool) -> Result<Worktree, Box<dyn Error>> {
+        Ok(Worktree {
+            dir: add(name, commit)?,
+            keep,
+        })
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        if self.keep {
+            status_log!(
+                "📂 Kept the worktree at {}; remove it with `git worktree remove --force {}`",
+                self.dir.display(),
+                self.dir.display()
+            );
+        } else if let Err(e) = remove(&self.dir) {
+            ui_eprintln!("{} {}", "⚠️ ".yellow(), e);
+        }
+    }
+}
+
+/// Checks out `commit` detached in a new temporary worktree.
+///
+/// # Parameters
+/// - `name`: Used in the directory name, e.g. `pr-42`
+/// - `commit`: The commit to check out (must be present locally)
+///
+/// # Returns
+/// - The worktree's directory
+/// - `Err` if `git worktree add` fails
+fn add(name: &str, commit: &str) -> Result<PathBuf, Box<dyn Error>> {
+    let dir = env::temp_dir().join(format!("git-pr-{}-{}", name, std::process::id()));
+    debug_log!("[DEBUG] Adding worktree {} at {}", dir.display(), commit);
+    let output = Command::new("git")
+        .args(["worktree", "add", "--detach", "--quiet"])
+        .arg(&dir)
+        .arg(commit)
+        .output()?;
+    if !output.status.success() {
+        return Err(format!(
+            "git worktree add failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(dir)
+}
+
+/// Switches a worktree made by [`add`] to another commit, discarding local changes.
+pub fn checkout(dir: &Path, commit: &str) -> Result<(), Box<dyn Error>> {
+    let dir = dir.to_string_lossy();
+    run_git(&[
+        "-C", &dir, "checkout", "--quiet", "--force", "--detach", commit,
+    ])
+    .map(|_| ())
+    .ok_or_else(|| format!("git checkout of {} failed in {}", commit, dir).into())
+}
+
+/// Removes a worktree made by [`add`], with whatever the command left in it.
+fn remove(dir: &Path) -> Result<(), Box<dyn Error>> {
+    let dir = dir.to_string_lossy();
+    debug_log!("[DEBUG] Removing worktree {}", dir);
+    run_git(&["worktree", "remove", "--force", &dir])
+        .map(|_| ())
+        .ok_or_else(|| format!("git worktree remove failed for {}", dir).into())
+}
+
+/// Runs a command (in a worktree) to completion, with Ctrl-C noted rather than fatal.
+///
+/// # Returns
+/// - The command's exit status; [`interrupted`] tells whether Ctrl-C was pressed
+/// - `Err` if the command can't be started
+pub fn run(command: &mut Command) -> io::Result<ExitStatus> {
+    let _held = HeldInterrupts::new();
+    command.status()
+}
+
+/// Whether Ctrl-C was pressed while a command ran in a worktree.
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Records an interrupt instead of dying of it.
+#[cfg(unix)]
+extern "C" fn note_interrupt(_: libc::c_int) {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+/// Catches SIGINT with [`note_interrupt`] until dropped, then restores the previous handler.
+///
+/// A caught signal goes back to its default in the child at `exec`, so the command still
+/// stops on Ctrl-C. Elsewhere than Unix this does nothing.
+struct HeldInterrupts {
+    #[cfg(unix)]
+    previous: libc::sighandler_t,
+}
+
+impl HeldInterrupts {
+    fn new() -> HeldInterrupts {
+        // SAFETY: the handler only stores to an atomic, which is async-signal-safe
+        #[cfg(unix)]
+        let previous = unsafe {
+            libc::signal(
+                libc::SIGINT,
+                note_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t,
+            )
+        };
+        HeldInterrupts {
+            #[cfg(unix)]
+            previous,
+        }
+    }
+}
+
+impl Drop for HeldInterrupts {
+    fn drop(&mut self) {
+        // SAFETY: puts back the handler that was in place before
+        #[cfg(unix)]
+        unsafe {
+            libc::signal(libc::SIGINT, self.previous);
+        }
+    }
+}